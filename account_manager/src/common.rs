@@ -1,7 +1,11 @@
 use clap::ArgMatches;
-use eth2_wallet::PlainText;
+use eth2_wallet::{
+    bip39::{Language, Mnemonic},
+    PlainText,
+};
 use rand::{distributions::Alphanumeric, Rng};
-use std::fs::create_dir_all;
+use std::fs::{self, create_dir_all};
+use std::io::stdin;
 use std::path::{Path, PathBuf};
 
 /// The `Alphanumeric` crate only generates a-z, A-Z, 0-9, therefore it has a range of 62
@@ -38,6 +42,27 @@ pub fn base_wallet_dir(matches: &ArgMatches, arg: &'static str) -> Result<PathBu
     )
 }
 
+/// Reads a BIP-39 mnemonic from `path`, or from stdin if `path` is `None`.
+pub fn read_mnemonic(path: Option<PathBuf>) -> Result<Mnemonic, String> {
+    let phrase = if let Some(path) = path {
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read mnemonic from {:?}: {:?}", path, e))?
+    } else {
+        eprintln!("Enter the mnemonic phrase:");
+        let mut phrase = String::new();
+        stdin()
+            .read_line(&mut phrase)
+            .map_err(|e| format!("Unable to read mnemonic from stdin: {:?}", e))?;
+        phrase
+    };
+
+    // Normalize the whitespace between words so that a phrase split across lines is accepted.
+    let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    Mnemonic::from_phrase(&phrase, Language::English)
+        .map_err(|e| format!("Unable to parse mnemonic: {}", e))
+}
+
 /// Remove any number of newline or carriage returns from the end of a vector of bytes.
 pub fn strip_off_newlines(mut bytes: Vec<u8>) -> Vec<u8> {
     let mut strip_off = 0;
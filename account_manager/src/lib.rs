@@ -25,7 +25,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
 /// Run the account manager, returning an error if the operation did not succeed.
 pub fn run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
     match matches.subcommand() {
-        (wallet::CMD, Some(matches)) => wallet::cli_run(matches)?,
+        (wallet::CMD, Some(matches)) => wallet::cli_run(matches, env)?,
         (validator::CMD, Some(matches)) => validator::cli_run(matches, env)?,
         (upgrade_legacy_keypairs::CMD, Some(matches)) => upgrade_legacy_keypairs::cli_run(matches)?,
         (unknown, _) => {
@@ -0,0 +1,159 @@
+use crate::{common::ensure_dir_exists, VALIDATOR_DIR_FLAG};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+use validator_dir::{
+    launchpad::{deposit_data_filename, write_deposit_data_json},
+    read_eth1_deposit_data, LaunchpadDepositData, Manager as ValidatorManager,
+    ETH1_DEPOSIT_TX_HASH_FILE,
+};
+
+pub const CMD: &str = "export-deposit-data";
+pub const VALIDATOR_FLAG: &str = "validator";
+pub const OUTPUT_DIR_FLAG: &str = "output-dir";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Exports the deposit data of validators to a deposit_data-<timestamp>.json file, \
+            which can be uploaded to the web launchpad to submit the deposits. Validators \
+            which have already been deposited with the deposit command, or which have no \
+            deposit data (e.g., imported validators), are skipped. The \
+            network will be determined by the --testnet-dir flag on the primary Lighthouse \
+            binary. The validator directories are not locked, so the deposit data may be \
+            exported whilst the validator client is running.",
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_FLAG)
+                .long(VALIDATOR_FLAG)
+                .value_name("VALIDATOR_NAME")
+                .help(
+                    "The name of the directory in --validator-dir for which to export the \
+                    deposit data. Set to 'all' to export all validators in the --validator-dir.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_DIR_FLAG)
+                .long(OUTPUT_DIR_FLAG)
+                .value_name("OUTPUT_DIRECTORY")
+                .help(
+                    "The directory in which the deposit data file will be created. Defaults \
+                    to the current directory.",
+                )
+                .takes_value(true),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(
+    matches: &ArgMatches<'_>,
+    mut env: Environment<T>,
+) -> Result<(), String> {
+    let spec = env.core_context().eth2_config.spec;
+
+    let data_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let validator: String = clap_utils::parse_required(matches, VALIDATOR_FLAG)?;
+    let output_dir: PathBuf =
+        clap_utils::parse_optional(matches, OUTPUT_DIR_FLAG)?.unwrap_or_else(|| PathBuf::from("."));
+
+    let manager = ValidatorManager::open(&data_dir)
+        .map_err(|e| format!("Unable to read --{}: {:?}", VALIDATOR_DIR_FLAG, e))?;
+
+    // Read each validator directory without opening it, since opening a `ValidatorDir` takes a
+    // lock which would fail whilst the validator client is running.
+    let paths = match validator.as_ref() {
+        "all" => manager
+            .validator_paths()
+            .map_err(|e| format!("Unable to read all validators: {:?}", e)),
+        name => manager
+            .directory_names()
+            .map_err(|e| {
+                format!(
+                    "Unable to read --{} directory names: {:?}",
+                    VALIDATOR_DIR_FLAG, e
+                )
+            })?
+            .get(name)
+            .ok_or_else(|| format!("Unknown validator:  {}", name))
+            .map(|path| vec![path.clone()]),
+    }?;
+
+    let deposits = paths
+        .into_iter()
+        .filter(|path| {
+            let deposited = path.join(ETH1_DEPOSIT_TX_HASH_FILE).exists();
+            if deposited {
+                eprintln!("Skipping already deposited validator: {:?}", path);
+            }
+            !deposited
+        })
+        .filter_map(|path| match read_eth1_deposit_data(&path) {
+            Ok(Some(data)) => {
+                let deposit = LaunchpadDepositData::from_deposit_data(&data.deposit_data, &spec);
+
+                // Ensure the deposit is valid for the current network before exporting it, the
+                // deposit may have been created with a different `--testnet-dir`.
+                Some(
+                    deposit
+                        .verify(&spec)
+                        .map(|_| deposit)
+                        .map_err(|e| format!("Invalid deposit data for {:?}: {:?}", path, e)),
+                )
+            }
+            Ok(None) => {
+                // Validators which were imported or recovered have no deposit data.
+                eprintln!("Skipping validator without deposit data: {:?}", path);
+                None
+            }
+            Err(e) => Some(Err(format!(
+                "Unable to read deposit data for {:?}: {:?}",
+                path, e
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if deposits.is_empty() {
+        eprintln!("No deposits to export");
+        return Ok(());
+    }
+
+    ensure_dir_exists(&output_dir)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Unable to read system time: {:?}", e))?
+        .as_secs();
+    let path = output_dir.join(deposit_data_filename(timestamp));
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+
+    write_deposit_data_json(&deposits, file)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))?;
+
+    eprintln!("Exported {} deposits", deposits.len());
+    println!("{}", path.display());
+
+    Ok(())
+}
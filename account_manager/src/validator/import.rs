@@ -0,0 +1,262 @@
+use crate::{
+    common::{ensure_dir_exists, strip_off_newlines},
+    validator::slashing_protection::{
+        genesis_validators_root, import_interchange, read_interchange_file,
+    },
+    SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG,
+};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use eth2_keystore::{Keystore, PlainText};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::stdin;
+use std::path::{Path, PathBuf};
+use types::EthSpec;
+use validator_client::SLASHING_PROTECTION_FILENAME;
+use validator_dir::Builder as ValidatorDirBuilder;
+
+pub const CMD: &str = "import";
+pub const KEYSTORE_FLAG: &str = "keystore";
+pub const DIR_FLAG: &str = "directory";
+pub const PASSWORD_FLAG: &str = "password-file";
+pub const SLASHING_PROTECTION_FLAG: &str = "slashing-protection";
+
+/// The prefix of the keystore files produced by the upstream deposit CLI (e.g.,
+/// `keystore-m_12381_3600_0_0_0-1595406747.json`).
+pub const KEYSTORE_PREFIX: &str = "keystore-m_";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Imports one or more EIP-2335 keystores into a Lighthouse validator directory. \
+            Each keystore is decrypted before it is imported to ensure that the password is \
+            correct. The validator client must not be running whilst the import takes place.",
+        )
+        .arg(
+            Arg::with_name(KEYSTORE_FLAG)
+                .long(KEYSTORE_FLAG)
+                .value_name("KEYSTORE_PATH")
+                .help("Path to a single keystore to be imported.")
+                .conflicts_with(DIR_FLAG)
+                .required_unless(DIR_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DIR_FLAG)
+                .long(DIR_FLAG)
+                .value_name("KEYSTORES_DIRECTORY")
+                .help(
+                    "Path to a directory which contains zero or more keystores \
+                    for import. This directory and all sub-directories will be \
+                    searched and any file name which starts with 'keystore-m_' \
+                    and ends with '.json' will be attempted to be imported.",
+                )
+                .conflicts_with(KEYSTORE_FLAG)
+                .required_unless(KEYSTORE_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("KEYSTORE_PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the password which will unlock all of the \
+                    keystores being imported. If not supplied, the password is read from stdin.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SLASHING_PROTECTION_FLAG)
+                .long(SLASHING_PROTECTION_FLAG)
+                .value_name("INTERCHANGE_FILE")
+                .help(
+                    "If present, the EIP-3076 slashing protection interchange file at this path \
+                    will be imported along with the keystores.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path where the validator directories will be created. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The path where the validator keystore passwords will be stored. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<(), String> {
+    let keystore: Option<PathBuf> = clap_utils::parse_optional(matches, KEYSTORE_FLAG)?;
+    let keystores_dir: Option<PathBuf> = clap_utils::parse_optional(matches, DIR_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let interchange_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, SLASHING_PROTECTION_FLAG)?;
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+
+    // Read the slashing protection interchange and determine the genesis validators root up
+    // front, so that an invalid interchange file or an unknown genesis state is reported before
+    // any changes are made to the validator directory.
+    let interchange = interchange_path
+        .as_deref()
+        .map(read_interchange_file)
+        .transpose()?;
+    let genesis_validators_root = if interchange.is_some() {
+        Some(genesis_validators_root(&env)?)
+    } else {
+        None
+    };
+
+    let keystore_paths = match (keystore, keystores_dir) {
+        (Some(keystore), None) => vec![keystore],
+        (None, Some(keystores_dir)) => {
+            let mut paths = vec![];
+            find_keystores(&keystores_dir, &mut paths)?;
+            paths.sort();
+            paths
+        }
+        _ => {
+            return Err(format!(
+                "Must supply either --{} or --{}",
+                KEYSTORE_FLAG, DIR_FLAG
+            ))
+        }
+    };
+
+    if keystore_paths.is_empty() {
+        eprintln!("No keystores found to import.");
+        return Ok(());
+    }
+
+    ensure_dir_exists(&validator_dir)?;
+    ensure_dir_exists(&secrets_dir)?;
+
+    let password = if let Some(path) = password_path {
+        fs::read(&path)
+            .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))
+            .map(|bytes| PlainText::from(strip_off_newlines(bytes)))?
+    } else {
+        eprintln!("Enter the password for the keystores:");
+        let mut password = String::new();
+        stdin()
+            .read_line(&mut password)
+            .map_err(|e| format!("Unable to read password from stdin: {:?}", e))?;
+        PlainText::from(strip_off_newlines(password.into_bytes()))
+    };
+
+    // Validate every keystore before importing any of them, so that a bad keystore or password
+    // does not leave the validator directory partially imported.
+    let mut seen_pubkeys = HashSet::new();
+    let mut keystores = Vec::with_capacity(keystore_paths.len());
+    for path in &keystore_paths {
+        let keystore = File::open(path)
+            .map_err(|e| format!("Unable to open keystore {:?}: {:?}", path, e))
+            .and_then(|file| {
+                Keystore::from_json_reader(file)
+                    .map_err(|e| format!("Unable to parse keystore {:?}: {:?}", path, e))
+            })?;
+
+        // Decryption also verifies that the secret key matches the public key of the keystore.
+        keystore
+            .decrypt_keypair(password.as_bytes())
+            .map_err(|e| format!("Unable to decrypt keystore {:?}: {:?}", path, e))?;
+
+        let voting_pubkey = format!("0x{}", keystore.pubkey());
+
+        if validator_dir.join(&voting_pubkey).exists() {
+            return Err(format!(
+                "Validator {} from {:?} already exists in {:?}",
+                voting_pubkey, path, validator_dir
+            ));
+        }
+
+        if !seen_pubkeys.insert(voting_pubkey.clone()) {
+            return Err(format!(
+                "Validator {} from {:?} is duplicated in the import",
+                voting_pubkey, path
+            ));
+        }
+
+        keystores.push(keystore);
+    }
+
+    // Import the slashing protection data before any keystores, so that a validator is never
+    // imported without its slashing protection. The import is atomic, so an interchange which is
+    // rejected by the database leaves both the database and the validator directory untouched.
+    if let (Some(interchange), Some(genesis_validators_root)) =
+        (interchange, genesis_validators_root)
+    {
+        let num_validators = import_interchange(
+            &interchange,
+            &validator_dir.join(SLASHING_PROTECTION_FILENAME),
+            genesis_validators_root,
+        )?;
+
+        eprintln!(
+            "Imported slashing protection data for {} validators",
+            num_validators
+        );
+    }
+
+    let num_keystores = keystores.len();
+    for (i, keystore) in keystores.into_iter().enumerate() {
+        let voting_pubkey = keystore.pubkey().to_string();
+
+        ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
+            .voting_keystore(keystore, password.as_bytes())
+            .store_withdrawal_keystore(false)
+            .build()
+            .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
+
+        println!("{}/{}\t0x{}", i + 1, num_keystores, voting_pubkey);
+    }
+
+    Ok(())
+}
+
+/// Recursively searches `dir` for files which look like keystores produced by the upstream deposit
+/// CLI, adding them to `paths`.
+fn find_keystores(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Unable to read {:?}: {:?}", dir, e))?;
+
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Unable to read entry in {:?}: {:?}", dir, e))?
+            .path();
+
+        if path.is_dir() {
+            find_keystores(&path, paths)?;
+        } else if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(false, |name| {
+                name.starts_with(KEYSTORE_PREFIX) && name.ends_with(".json")
+            })
+        {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
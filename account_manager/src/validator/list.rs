@@ -0,0 +1,183 @@
+use crate::VALIDATOR_DIR_FLAG;
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use remote_beacon_node::RemoteBeaconNode;
+use rest_types::ValidatorStatus;
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+use types::{ChainSpec, EthSpec, PublicKey, PublicKeyBytes};
+use validator_dir::{
+    read_eth1_deposit_tx_hash, read_voting_pubkey, validator_is_enabled,
+    Manager as ValidatorManager,
+};
+
+pub const CMD: &str = "list";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+pub const JSON_FLAG: &str = "json";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Lists the validators in the --validator-dir, optionally showing their status on the \
+            beacon chain. Prints one validator per line as tab-separated columns: pubkey, enabled, \
+            eth1 deposit tx hash, index, status and balance (in Gwei). Unknown values are shown \
+            as '-'. The validator directories are not locked, so the list may be taken whilst \
+            the validator client is running.",
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "If present, the beacon node HTTP API at this address is queried for the \
+                    index, status and balance of each validator at the head of the chain.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(JSON_FLAG)
+                .long(JSON_FLAG)
+                .help("If present, the validators are printed as a JSON list."),
+        )
+}
+
+/// A validator in the `--validator-dir`, along with its status on the beacon chain (if known).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorInfo {
+    pub voting_pubkey: PublicKey,
+    pub enabled: bool,
+    pub eth1_deposit_tx_hash: Option<String>,
+    pub index: Option<usize>,
+    pub status: Option<ValidatorStatus>,
+    pub balance: Option<u64>,
+}
+
+pub fn cli_run<T: EthSpec>(
+    matches: &ArgMatches<'_>,
+    mut env: Environment<T>,
+) -> Result<(), String> {
+    let data_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let beacon_node: Option<String> = clap_utils::parse_optional(matches, BEACON_NODE_FLAG)?;
+
+    let manager = ValidatorManager::open(&data_dir)
+        .map_err(|e| format!("Unable to read --{}: {:?}", VALIDATOR_DIR_FLAG, e))?;
+
+    // Read each validator directory without opening it, since opening a `ValidatorDir` takes a
+    // lock which would fail (or interfere) whilst the validator client is running.
+    let mut validators = vec![];
+    for path in manager
+        .validator_paths()
+        .map_err(|e| format!("Unable to read all validators: {:?}", e))?
+    {
+        let voting_pubkey =
+            read_voting_pubkey(&path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+        let eth1_deposit_tx_hash = read_eth1_deposit_tx_hash(&path)
+            .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+
+        validators.push(ValidatorInfo {
+            voting_pubkey,
+            enabled: validator_is_enabled(&path),
+            eth1_deposit_tx_hash,
+            index: None,
+            status: None,
+            balance: None,
+        });
+    }
+
+    if let Some(beacon_node) = beacon_node {
+        let remote = RemoteBeaconNode::<T>::new(beacon_node)?;
+        let spec = env.core_context().eth2_config.spec;
+
+        env.runtime()
+            .block_on(add_beacon_chain_info(&remote, &mut validators, &spec))?;
+    }
+
+    validators.sort_by_key(|validator| validator.voting_pubkey.as_hex_string());
+
+    if matches.is_present(JSON_FLAG) {
+        let json = serde_json::to_string_pretty(&validators)
+            .map_err(|e| format!("Unable to serialize validators: {:?}", e))?;
+        println!("{}", json);
+    } else {
+        for validator in &validators {
+            println!(
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                validator.voting_pubkey.as_hex_string(),
+                validator.enabled,
+                or_dash(&validator.eth1_deposit_tx_hash),
+                or_dash(&validator.index),
+                or_dash(&validator.status),
+                or_dash(&validator.balance),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Queries `remote` for each of the `validators`, setting the index, status and balance of each
+/// validator known to the head state.
+async fn add_beacon_chain_info<E: EthSpec>(
+    remote: &RemoteBeaconNode<E>,
+    validators: &mut [ValidatorInfo],
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let head = remote
+        .http
+        .beacon()
+        .get_head()
+        .await
+        .map_err(|e| format!("Unable to query head from beacon node: {:?}", e))?;
+
+    let pubkeys = validators
+        .iter()
+        .map(|validator| validator.voting_pubkey.clone())
+        .collect();
+
+    // Use the head state for both the validators and the epoch, so the status is consistent with
+    // the returned balances.
+    let responses = remote
+        .http
+        .beacon()
+        .get_validators(pubkeys, Some(head.state_root))
+        .await
+        .map_err(|e| format!("Unable to query validators from beacon node: {:?}", e))?;
+
+    let epoch = head.slot.epoch(E::slots_per_epoch());
+
+    for response in responses {
+        if let Some(validator) = validators.iter_mut().find(|validator| {
+            PublicKeyBytes::from(validator.voting_pubkey.clone()) == response.pubkey
+        }) {
+            validator.index = response.validator_index;
+            validator.balance = response.balance;
+            validator.status = response.validator.map(|record| {
+                ValidatorStatus::from_validator(&record, epoch, spec.far_future_epoch)
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Displays `value`, or `-` if it is `None`.
+fn or_dash<T: ToString>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -0,0 +1,167 @@
+use crate::{
+    common::{ensure_dir_exists, random_password, read_mnemonic},
+    SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG,
+};
+use clap::{App, Arg, ArgMatches};
+use eth2_keystore::{keypair_from_secret, Keystore, KeystoreBuilder};
+use eth2_wallet::{bip39::Seed, recover_validator_secret_from_mnemonic, KeyType, PlainText};
+use std::path::PathBuf;
+use validator_dir::Builder as ValidatorDirBuilder;
+
+pub const CMD: &str = "recover";
+pub const FIRST_INDEX_FLAG: &str = "first-index";
+pub const COUNT_FLAG: &str = "count";
+pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+pub const STORE_WITHDRAW_FLAG: &str = "store-withdrawal-keystore";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Recovers validator private keys from a BIP-39 mnemonic using the EIP-2333 HD key \
+            derivation scheme, without requiring an EIP-2386 wallet. No deposit data is \
+            created since the validators are expected to have already been deposited.",
+        )
+        .arg(
+            Arg::with_name(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help("The first of consecutive key indexes you wish to recover.")
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::with_name(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("COUNT")
+                .help(
+                    "The number of validator keys you wish to recover. Counted consecutively \
+                    from the provided --first-index.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help(
+                    "If present, the mnemonic will be read from this file. Otherwise, the \
+                    mnemonic is read from stdin.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path where the validator directories will be created. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The path where the validator keystore passwords will be stored. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STORE_WITHDRAW_FLAG)
+                .long(STORE_WITHDRAW_FLAG)
+                .help(
+                    "If present, the withdrawal keystore will be stored alongside the voting \
+                    keypair. It is generally recommended to *not* store the withdrawal key and \
+                    instead generate them from the mnemonic when required.",
+                ),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+    let first_index: u32 = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
+    let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
+    let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
+
+    let last_index = first_index
+        .checked_add(count)
+        .ok_or_else(|| format!("--{} + --{} is too large", FIRST_INDEX_FLAG, COUNT_FLAG))?;
+
+    ensure_dir_exists(&validator_dir)?;
+    ensure_dir_exists(&secrets_dir)?;
+
+    let mnemonic = read_mnemonic(mnemonic_path)?;
+    let seed = Seed::new(&mnemonic, "");
+
+    for index in first_index..last_index {
+        let voting_password = random_password();
+        let withdrawal_password = random_password();
+
+        let voting = keystore_for_index(seed.as_bytes(), index, KeyType::Voting, &voting_password)?;
+        let withdrawal = keystore_for_index(
+            seed.as_bytes(),
+            index,
+            KeyType::Withdrawal,
+            &withdrawal_password,
+        )?;
+
+        let voting_pubkey = voting.pubkey().to_string();
+
+        if validator_dir.join(format!("0x{}", voting_pubkey)).exists() {
+            eprintln!(
+                "Skipping validator {} as it already exists: 0x{}",
+                index, voting_pubkey
+            );
+            continue;
+        }
+
+        ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
+            .voting_keystore(voting, voting_password.as_bytes())
+            .withdrawal_keystore(withdrawal, withdrawal_password.as_bytes())
+            .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
+            .build()
+            .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
+
+        println!(
+            "{}/{}\tIndex: {}\t0x{}",
+            index - first_index + 1,
+            count,
+            index,
+            voting_pubkey
+        );
+    }
+
+    Ok(())
+}
+
+/// Derives the `key_type` key for the validator at `index` from the mnemonic `seed`, returning
+/// it as a keystore encrypted with `password`.
+fn keystore_for_index(
+    seed: &[u8],
+    index: u32,
+    key_type: KeyType,
+    password: &PlainText,
+) -> Result<Keystore, String> {
+    let (secret, path) = recover_validator_secret_from_mnemonic(seed, index, key_type)
+        .map_err(|e| format!("Unable to recover validator key: {:?}", e))?;
+
+    let keypair = keypair_from_secret(secret.as_bytes())
+        .map_err(|e| format!("Unable to create keypair: {:?}", e))?;
+
+    KeystoreBuilder::new(&keypair, password.as_bytes(), format!("{}", path))
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("Unable to build keystore: {:?}", e))
+}
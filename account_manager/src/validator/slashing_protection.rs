@@ -0,0 +1,212 @@
+use crate::VALIDATOR_DIR_FLAG;
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use slashing_protection::{
+    interchange::{Interchange, InterchangeFormat},
+    SlashingDatabase,
+};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use types::{EthSpec, Hash256};
+use validator_client::SLASHING_PROTECTION_FILENAME;
+
+pub const CMD: &str = "slashing-protection";
+pub const IMPORT_CMD: &str = "import";
+pub const EXPORT_CMD: &str = "export";
+
+pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
+pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
+pub const MINIMAL_FLAG: &str = "minimal";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Import or export slashing protection data to or from another client")
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .subcommand(
+            App::new(IMPORT_CMD)
+                .about(
+                    "Import an EIP-3076 slashing protection interchange file. The validator \
+                    client must not be running whilst the import takes place.",
+                )
+                .arg(
+                    Arg::with_name(IMPORT_FILE_ARG)
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help("The slashing protection interchange file to import (.json)")
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            App::new(EXPORT_CMD)
+                .about(
+                    "Export an EIP-3076 slashing protection interchange file. The validator \
+                    client must not be running whilst the export takes place.",
+                )
+                .arg(
+                    Arg::with_name(EXPORT_FILE_ARG)
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help("The filename to export the interchange file to")
+                        .required(true),
+                )
+                .arg(Arg::with_name(MINIMAL_FLAG).long(MINIMAL_FLAG).help(
+                    "Export only the most recent block and attestation of each \
+                            validator, rather than their full signing history.",
+                )),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
+    let data_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let slashing_protection_db_path = data_dir.join(SLASHING_PROTECTION_FILENAME);
+
+    let genesis_validators_root = genesis_validators_root(&env)?;
+
+    match matches.subcommand() {
+        (IMPORT_CMD, Some(matches)) => {
+            let import_filename: PathBuf = clap_utils::parse_required(matches, IMPORT_FILE_ARG)?;
+
+            let num_validators = import_interchange_file(
+                &import_filename,
+                &slashing_protection_db_path,
+                genesis_validators_root,
+            )?;
+
+            eprintln!(
+                "Import completed successfully for {} validators",
+                num_validators
+            );
+
+            Ok(())
+        }
+        (EXPORT_CMD, Some(matches)) => {
+            let export_filename: PathBuf = clap_utils::parse_required(matches, EXPORT_FILE_ARG)?;
+            let format = if matches.is_present(MINIMAL_FLAG) {
+                InterchangeFormat::Minimal
+            } else {
+                InterchangeFormat::Complete
+            };
+
+            if !slashing_protection_db_path.exists() {
+                return Err(format!(
+                    "No slashing protection database exists at: {}",
+                    slashing_protection_db_path.display()
+                ));
+            }
+
+            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
+                .map_err(|e| {
+                    format!(
+                        "Unable to open database at {}: {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            let interchange = slashing_protection_database
+                .export_interchange_info(genesis_validators_root, format)
+                .map_err(|e| format!("Error during export: {:?}", e))?;
+
+            let output_file = File::create(&export_filename).map_err(|e| {
+                format!(
+                    "Error creating output file {}: {:?}",
+                    export_filename.display(),
+                    e
+                )
+            })?;
+
+            interchange
+                .write_to(&output_file)
+                .map_err(|e| format!("Error writing output file: {:?}", e))?;
+
+            eprintln!(
+                "Export completed successfully for {} validators",
+                interchange.data.len()
+            );
+
+            Ok(())
+        }
+        ("", _) => Err("No subcommand provided, see --help for options".to_string()),
+        (command, _) => Err(format!("No such subcommand `{}`", command)),
+    }
+}
+
+/// Import the EIP-3076 interchange file at `import_filename` into the slashing protection
+/// database at `slashing_protection_db_path`, creating the database if it does not exist.
+///
+/// Returns the number of validators in the interchange file.
+pub fn import_interchange_file(
+    import_filename: &Path,
+    slashing_protection_db_path: &Path,
+    genesis_validators_root: Hash256,
+) -> Result<usize, String> {
+    let interchange = read_interchange_file(import_filename)?;
+    import_interchange(
+        &interchange,
+        slashing_protection_db_path,
+        genesis_validators_root,
+    )
+}
+
+/// Read and parse the EIP-3076 interchange file at `import_filename`.
+pub fn read_interchange_file(import_filename: &Path) -> Result<Interchange, String> {
+    let import_file = File::open(import_filename)
+        .map_err(|e| format!("Unable to open import file {:?}: {:?}", import_filename, e))?;
+
+    Interchange::from_json_reader(&import_file)
+        .map_err(|e| format!("Error parsing file for import: {:?}", e))
+}
+
+/// Import `interchange` into the slashing protection database at `slashing_protection_db_path`,
+/// creating the database if it does not exist.
+///
+/// The import is atomic: if any part of `interchange` is invalid, nothing is imported.
+///
+/// Returns the number of validators in the interchange.
+pub fn import_interchange(
+    interchange: &Interchange,
+    slashing_protection_db_path: &Path,
+    genesis_validators_root: Hash256,
+) -> Result<usize, String> {
+    let slashing_protection_database =
+        SlashingDatabase::open_or_create(slashing_protection_db_path).map_err(|e| {
+            format!(
+                "Unable to open database at {}: {:?}",
+                slashing_protection_db_path.display(),
+                e
+            )
+        })?;
+
+    slashing_protection_database
+        .import_interchange_info(interchange, genesis_validators_root)
+        .map_err(|e| format!("Error during import: {:?}", e))?;
+
+    Ok(interchange.data.len())
+}
+
+/// Read the genesis validators root from the genesis state of the testnet in use, which is
+/// required to ensure that interchange files are not applied to the wrong network.
+pub fn genesis_validators_root<T: EthSpec>(env: &Environment<T>) -> Result<Hash256, String> {
+    env.testnet
+        .as_ref()
+        .and_then(|testnet| testnet.genesis_state.as_ref())
+        .map(|genesis_state| genesis_state.genesis_validators_root)
+        .ok_or_else(|| {
+            "Unable to determine the genesis validators root, the genesis state is unknown. \
+             Use --testnet-dir to specify a testnet with a known genesis state."
+                .to_string()
+        })
+}
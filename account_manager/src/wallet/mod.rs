@@ -1,11 +1,14 @@
 pub mod create;
 pub mod list;
+pub mod recover;
 
 use crate::{
     common::{base_wallet_dir, ensure_dir_exists},
     BASE_DIR_FLAG,
 };
 use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use types::EthSpec;
 
 pub const CMD: &str = "wallet";
 
@@ -21,15 +24,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         )
         .subcommand(create::cli_app())
         .subcommand(list::cli_app())
+        .subcommand(recover::cli_app())
 }
 
-pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<(), String> {
     let base_dir = base_wallet_dir(matches, BASE_DIR_FLAG)?;
     ensure_dir_exists(&base_dir)?;
 
     match matches.subcommand() {
         (create::CMD, Some(matches)) => create::cli_run(matches, base_dir),
         (list::CMD, Some(_)) => list::cli_run(base_dir),
+        (recover::CMD, Some(matches)) => recover::cli_run::<T>(matches, env, base_dir),
         (unknown, _) => {
             return Err(format!(
                 "{} does not have a {} command. See --help",
@@ -0,0 +1,202 @@
+use crate::{
+    common::read_mnemonic,
+    wallet::create::{read_or_create_password, HD_TYPE, NAME_FLAG, PASSPHRASE_FLAG, TYPE_FLAG},
+    BASE_DIR_FLAG,
+};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use eth2_keystore::keypair_from_secret;
+use eth2_wallet::{bip39::Seed, recover_validator_secret_from_mnemonic, KeyType};
+use eth2_wallet_manager::{WalletManager, WalletType};
+use remote_beacon_node::RemoteBeaconNode;
+use std::path::PathBuf;
+use types::{EthSpec, PublicKey, PublicKeyBytes};
+
+pub const CMD: &str = "recover";
+pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+pub const BEACON_NODE_FLAG: &str = "beacon-node";
+
+/// The number of consecutive validator indices queried from the beacon node at a time.
+///
+/// The scan stops at the first batch that does not contain a known validator.
+const SCAN_BATCH_SIZE: u32 = 16;
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Recovers an EIP-2386 wallet from an existing BIP-39 mnemonic.")
+        .arg(
+            Arg::with_name(NAME_FLAG)
+                .long(NAME_FLAG)
+                .value_name("WALLET_NAME")
+                .help(
+                    "The wallet will be created with this name. It is not allowed to \
+                            create two wallets with the same name for the same --base-dir.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(PASSPHRASE_FLAG)
+                .long(PASSPHRASE_FLAG)
+                .value_name("WALLET_PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the password which will unlock the wallet. \
+                    If the file does not exist, a random password will be generated and \
+                    saved at that path. To avoid confusion, if the file does not already \
+                    exist it must include a '.pass' suffix.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(TYPE_FLAG)
+                .long(TYPE_FLAG)
+                .value_name("WALLET_TYPE")
+                .help(
+                    "The type of wallet to create. Only HD (hierarchical-deterministic) \
+                            wallets are supported presently..",
+                )
+                .takes_value(true)
+                .possible_values(&[HD_TYPE])
+                .default_value(HD_TYPE),
+        )
+        .arg(
+            Arg::with_name(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help(
+                    "If present, the mnemonic will be read from this file. Otherwise, the \
+                    mnemonic is read from stdin.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(BEACON_NODE_FLAG)
+                .long(BEACON_NODE_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help(
+                    "If present, the beacon node HTTP API at this address is queried for \
+                    validators derived from the mnemonic and the wallet will only generate \
+                    validators with a higher index than any found. Deposits which have not \
+                    yet been processed by the beacon chain will not be found.",
+                )
+                .takes_value(true),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(
+    matches: &ArgMatches,
+    mut env: Environment<T>,
+    base_dir: PathBuf,
+) -> Result<(), String> {
+    let name: String = clap_utils::parse_required(matches, NAME_FLAG)?;
+    let wallet_password_path: PathBuf = clap_utils::parse_required(matches, PASSPHRASE_FLAG)?;
+    let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
+    let beacon_node: Option<String> = clap_utils::parse_optional(matches, BEACON_NODE_FLAG)?;
+    let type_field: String = clap_utils::parse_required(matches, TYPE_FLAG)?;
+
+    let wallet_type = match type_field.as_ref() {
+        HD_TYPE => WalletType::Hd,
+        unknown => return Err(format!("--{} {} is not supported", TYPE_FLAG, unknown)),
+    };
+
+    let mgr = WalletManager::open(&base_dir)
+        .map_err(|e| format!("Unable to open --{}: {:?}", BASE_DIR_FLAG, e))?;
+
+    let mnemonic = read_mnemonic(mnemonic_path)?;
+
+    // Scan the beacon chain before creating the wallet so that a failed scan does not leave a
+    // wallet behind with an incorrect `nextaccount`.
+    let nextaccount = if let Some(beacon_node) = beacon_node {
+        let remote = RemoteBeaconNode::<T>::new(beacon_node)?;
+        let seed = Seed::new(&mnemonic, "");
+
+        env.runtime()
+            .block_on(find_nextaccount(&remote, seed.as_bytes()))?
+    } else {
+        0
+    };
+
+    let wallet_password = read_or_create_password(&wallet_password_path)?;
+
+    let mut wallet = mgr
+        .create_wallet(name, wallet_type, &mnemonic, wallet_password.as_bytes())
+        .map_err(|e| format!("Unable to create wallet: {:?}", e))?;
+
+    if nextaccount > 0 {
+        wallet
+            .set_nextaccount(nextaccount)
+            .map_err(|e| format!("Unable to update wallet: {:?}", e))?;
+    }
+
+    println!("Your wallet has been recovered.");
+    println!("");
+    println!("Your wallet's UUID is:");
+    println!("");
+    println!("\t{}", wallet.wallet().uuid());
+    println!("");
+    println!(
+        "The next validator created with this wallet will have index {}.",
+        wallet.wallet().nextaccount()
+    );
+
+    Ok(())
+}
+
+/// Queries `remote` for the voting keys derived from the mnemonic `seed`, returning the index after the
+/// highest index known to the beacon chain (or `0` if none are known).
+async fn find_nextaccount<E: EthSpec>(
+    remote: &RemoteBeaconNode<E>,
+    seed: &[u8],
+) -> Result<u32, String> {
+    let mut nextaccount = 0;
+    let mut start = 0_u32;
+
+    loop {
+        let end = start
+            .checked_add(SCAN_BATCH_SIZE)
+            .ok_or_else(|| "Validator index overflow".to_string())?;
+
+        let pubkeys = (start..end)
+            .map(|index| voting_pubkey(seed, index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let responses = remote
+            .http
+            .beacon()
+            .get_validators(pubkeys.clone(), None)
+            .await
+            .map_err(|e| format!("Unable to query validators from beacon node: {:?}", e))?;
+
+        let pubkey_bytes = pubkeys
+            .into_iter()
+            .map(PublicKeyBytes::from)
+            .collect::<Vec<_>>();
+
+        let highest = responses
+            .iter()
+            .filter(|response| response.validator_index.is_some())
+            .filter_map(|response| pubkey_bytes.iter().position(|pk| *pk == response.pubkey))
+            .max();
+
+        match highest {
+            Some(offset) => {
+                nextaccount = start + offset as u32 + 1;
+                start = end;
+            }
+            None => break,
+        }
+    }
+
+    Ok(nextaccount)
+}
+
+/// Derives the voting public key of the validator at `index` from the mnemonic `seed`.
+fn voting_pubkey(seed: &[u8], index: u32) -> Result<PublicKey, String> {
+    let (secret, _) = recover_validator_secret_from_mnemonic(seed, index, KeyType::Voting)
+        .map_err(|e| format!("Unable to derive voting key: {:?}", e))?;
+
+    keypair_from_secret(secret.as_bytes())
+        .map(|keypair| keypair.pk)
+        .map_err(|e| format!("Unable to derive voting keypair: {:?}", e))
+}
@@ -332,6 +332,12 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
             return Err(Error::InvalidSignature);
         }
 
+        // Send the verified attestation to the slasher before any observation checks, so that
+        // it may detect slashable messages which are otherwise rejected as duplicates.
+        if let Some(slasher) = chain.slasher.as_ref() {
+            slasher.accept_attestation(indexed_attestation.clone());
+        }
+
         // Observe the valid attestation so we do not re-process it.
         //
         // It's important to double check that the attestation is not already known, otherwise two
@@ -382,6 +388,11 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
     pub fn attestation(&self) -> &Attestation<T::EthSpec> {
         &self.signed_aggregate.message.aggregate
     }
+
+    /// Returns the wrapped `IndexedAttestation`.
+    pub fn indexed_attestation(&self) -> &IndexedAttestation<T::EthSpec> {
+        &self.indexed_attestation
+    }
 }
 
 impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
@@ -418,11 +429,16 @@ impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
         /*
          * The attestation is the first valid attestation received for the participating validator
          * for the slot, attestation.data.slot.
+         *
+         * This check is skipped when the slasher is enabled, so that the signature of a repeat
+         * attestation is verified and the attestation may be sent to the slasher. Such an
+         * attestation is still rejected by the second check below.
          */
-        if chain
-            .observed_attesters
-            .validator_has_been_observed(&attestation, validator_index as usize)
-            .map_err(|e| BeaconChainError::from(e))?
+        if chain.slasher.is_none()
+            && chain
+                .observed_attesters
+                .validator_has_been_observed(&attestation, validator_index as usize)
+                .map_err(|e| BeaconChainError::from(e))?
         {
             return Err(Error::PriorAttestationKnown {
                 validator_index,
@@ -433,6 +449,10 @@ impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
         // The aggregate signature of the attestation is valid.
         verify_attestation_signature(chain, &indexed_attestation)?;
 
+        if let Some(slasher) = chain.slasher.as_ref() {
+            slasher.accept_attestation(indexed_attestation.clone());
+        }
+
         // Now that the attestation has been fully verified, store that we have received a valid
         // attestation from this validator.
         //
@@ -6,9 +6,9 @@ use crate::block_verification::{
     check_block_relevancy, get_block_root, signature_verify_chain_segment, BlockError,
     FullyVerifiedBlock, GossipVerifiedBlock, IntoFullyVerifiedBlock,
 };
-use crate::errors::{BeaconChainError as Error, BlockProductionError};
+use crate::errors::{BeaconChainError as Error, BlockProductionError, HistoricalBlockError};
 use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
-use crate::events::{EventHandler, EventKind};
+use crate::events::{EventHandler, EventKind, EventTopic};
 use crate::fork_choice::{Error as ForkChoiceError, ForkChoice};
 use crate::head_tracker::HeadTracker;
 use crate::metrics;
@@ -17,20 +17,25 @@ use crate::naive_aggregation_pool::{Error as NaiveAggregationError, NaiveAggrega
 use crate::observed_attestations::{Error as AttestationObservationError, ObservedAttestations};
 use crate::observed_attesters::{ObservedAggregators, ObservedAttesters};
 use crate::observed_block_producers::ObservedBlockProducers;
+use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::shuffling_cache::ShufflingCache;
 use crate::snapshot_cache::SnapshotCache;
 use crate::timeout_rw_lock::TimeoutRwLock;
+use crate::validator_monitor::ValidatorMonitor;
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::BeaconSnapshot;
+use bls::verify_signature_sets;
 use operation_pool::{OperationPool, PersistedOperationPool};
+use parking_lot::RwLock;
+use slasher::Slasher;
 use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
-use state_processing::per_block_processing::errors::{
-    AttestationValidationError, AttesterSlashingValidationError, ExitValidationError,
-    ProposerSlashingValidationError,
+use state_processing::per_block_processing::errors::AttestationValidationError;
+use state_processing::signature_sets::block_proposal_signature_set_from_parts;
+use state_processing::{
+    per_block_processing, per_slot_processing, BlockSignatureStrategy, SigVerifiedOp,
 };
-use state_processing::{per_block_processing, per_slot_processing, BlockSignatureStrategy};
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -42,7 +47,7 @@ use store::iter::{
     BlockRootsIterator, ParentRootBlockIterator, ReverseBlockRootIterator,
     ReverseStateRootIterator, StateRootsIterator,
 };
-use store::{Error as DBError, Store};
+use store::{AnchorInfo, Error as DBError, Store};
 use types::*;
 
 // Text included in blocks.
@@ -186,6 +191,12 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub observed_aggregators: ObservedAggregators<T::EthSpec>,
     /// Maintains a record of which validators have proposed blocks for each slot.
     pub observed_block_producers: ObservedBlockProducers<T::EthSpec>,
+    /// Maintains a record of which validators have submitted voluntary exits.
+    pub observed_voluntary_exits: ObservedOperations<SignedVoluntaryExit, T::EthSpec>,
+    /// Maintains a record of which validators we've seen proposer slashings for.
+    pub observed_proposer_slashings: ObservedOperations<ProposerSlashing, T::EthSpec>,
+    /// Maintains a record of which validators we've seen attester slashings for.
+    pub observed_attester_slashings: ObservedOperations<AttesterSlashing<T::EthSpec>, T::EthSpec>,
     /// Provides information from the Ethereum 1 (PoW) chain.
     pub eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec, T::Store>>,
     /// Stores a "snapshot" of the chain at the time the head-of-the-chain block was received.
@@ -209,6 +220,10 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub(crate) validator_pubkey_cache: TimeoutRwLock<ValidatorPubkeyCache>,
     /// A list of any hard-coded forks that have been disabled.
     pub disabled_forks: Vec<String>,
+    /// Detects slashable messages amongst verified gossip attestations and blocks, if enabled.
+    pub slasher: Option<Arc<Slasher<T::EthSpec>>>,
+    /// Tracks the attestations and blocks of a set of validators of interest.
+    pub validator_monitor: RwLock<ValidatorMonitor<T::EthSpec>>,
     /// Logging to CLI, etc.
     pub(crate) log: Logger,
 }
@@ -388,6 +403,55 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .map(|(ancestor_block_root, _)| ancestor_block_root))
     }
 
+    /// Traverse backwards from `old_head_root` to find the most recent block which is also an
+    /// ancestor of `new_head`.
+    ///
+    /// Returns the root and slot of the common ancestor, along with the roots of the blocks which
+    /// were passed over on the way (i.e., those orphaned by switching to `new_head`), most recent
+    /// first. Returns `None` if no common ancestor is known.
+    pub fn find_common_ancestor(
+        &self,
+        old_head_root: Hash256,
+        new_head: &BeaconSnapshot<T::EthSpec>,
+    ) -> Result<Option<(Hash256, Slot, Vec<Hash256>)>, Error> {
+        let mut orphaned_block_roots: Vec<Hash256> = vec![];
+        let mut iter = self.rev_iter_block_roots_from(old_head_root)?;
+
+        while let Some((block_root, slot)) = iter.next() {
+            // Read from the state of the new head where possible, falling back to the database
+            // for slots older than `SLOTS_PER_HISTORICAL_ROOT`.
+            let new_chain_root = match slot.cmp(&new_head.beacon_block.slot()) {
+                Ordering::Greater => None,
+                Ordering::Equal => Some(new_head.beacon_block_root),
+                Ordering::Less => match new_head.beacon_state.get_block_root(slot) {
+                    Ok(root) => Some(*root),
+                    Err(_) => self.get_ancestor_block_root(new_head.beacon_block_root, slot)?,
+                },
+            };
+
+            if new_chain_root == Some(block_root) {
+                // The ancestor may have been repeated across skipped slots, in which case it was
+                // wrongly counted as orphaned and its own slot is further back.
+                if orphaned_block_roots.last() == Some(&block_root) {
+                    orphaned_block_roots.pop();
+                }
+                let ancestor_slot = iter
+                    .take_while(|(root, _)| *root == block_root)
+                    .last()
+                    .map_or(slot, |(_, ancestor_slot)| ancestor_slot);
+
+                return Ok(Some((block_root, ancestor_slot, orphaned_block_roots)));
+            }
+
+            // Skipped slots repeat the root of the prior block.
+            if orphaned_block_roots.last() != Some(&block_root) {
+                orphaned_block_roots.push(block_root);
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Iterates across all `(state_root, slot)` pairs from the head of the chain (inclusive) to
     /// the earliest reachable ancestor (may or may not be genesis).
     ///
@@ -474,17 +538,31 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     ///
     /// A summarized version of `Self::head` that involves less cloning.
     pub fn head_info(&self) -> Result<HeadInfo, Error> {
+        // Read from fork choice before taking the head lock, to avoid holding both locks at once.
+        let fork_choice_finalized_checkpoint = self.fork_choice.finalized_checkpoint();
+
         let head = self
             .canonical_head
             .try_read_for(HEAD_LOCK_TIMEOUT)
             .ok_or_else(|| Error::CanonicalHeadLockTimeout)?;
 
+        // When the chain was started from a weak subjectivity checkpoint, the head state may
+        // reference a finalized checkpoint prior to that anchor, for which we have no block.
+        // Report the anchor instead, so that peers sync us from a block that we know.
+        let finalized_checkpoint = if head.beacon_state.finalized_checkpoint.epoch
+            < fork_choice_finalized_checkpoint.epoch
+        {
+            fork_choice_finalized_checkpoint
+        } else {
+            head.beacon_state.finalized_checkpoint.clone()
+        };
+
         Ok(HeadInfo {
             slot: head.beacon_block.slot(),
             block_root: head.beacon_block_root,
             state_root: head.beacon_state_root,
             current_justified_checkpoint: head.beacon_state.current_justified_checkpoint.clone(),
-            finalized_checkpoint: head.beacon_state.finalized_checkpoint.clone(),
+            finalized_checkpoint,
             fork: head.beacon_state.fork.clone(),
             genesis_time: head.beacon_state.genesis_time,
             genesis_validators_root: head.beacon_state.genesis_validators_root,
@@ -738,6 +816,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.naive_aggregation_pool.get(data).map_err(Into::into)
     }
 
+    /// Returns an aggregated `Attestation`, if any, at `slot` with an `attestation.data` that has
+    /// the given `attestation_data_root`.
+    ///
+    /// The attestation will be obtained from `self.naive_aggregation_pool`.
+    pub fn get_aggregated_attestation_by_slot_and_root(
+        &self,
+        slot: Slot,
+        attestation_data_root: &Hash256,
+    ) -> Option<Attestation<T::EthSpec>> {
+        self.naive_aggregation_pool
+            .get_by_slot_and_root(slot, attestation_data_root)
+    }
+
     /// Produce an unaggregated `Attestation` that is valid for the given `slot` and `index`.
     ///
     /// The produced `Attestation` will not be valid until it has been signed by exactly one
@@ -869,6 +960,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         VerifiedUnaggregatedAttestation::verify(attestation, self).map(|v| {
             metrics::inc_counter(&metrics::UNAGGREGATED_ATTESTATION_PROCESSING_SUCCESSES);
+            let validator_monitor = self.validator_monitor.read();
+            if !validator_monitor.is_empty() {
+                validator_monitor.register_unaggregated_attestation(v.indexed_attestation());
+            }
             v
         })
     }
@@ -885,6 +980,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         VerifiedAggregatedAttestation::verify(signed_aggregate, self).map(|v| {
             metrics::inc_counter(&metrics::AGGREGATED_ATTESTATION_PROCESSING_SUCCESSES);
+            let validator_monitor = self.validator_monitor.read();
+            if !validator_monitor.is_empty() {
+                validator_monitor.register_aggregated_attestation(v.indexed_attestation());
+            }
             v
         })
     }
@@ -958,6 +1057,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         };
 
+        if self.event_handler.has_subscribers(EventTopic::Attestation) {
+            let _ = self
+                .event_handler
+                .register(EventKind::BeaconAttestationImported {
+                    attestation: Box::new(attestation.clone()),
+                });
+        }
+
         Ok(unaggregated_attestation)
     }
 
@@ -992,6 +1099,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .map_err(Error::from)?;
         }
 
+        if self.event_handler.has_subscribers(EventTopic::Attestation) {
+            let _ = self
+                .event_handler
+                .register(EventKind::BeaconAttestationImported {
+                    attestation: Box::new(signed_aggregate.attestation().clone()),
+                });
+        }
+
         Ok(signed_aggregate)
     }
 
@@ -1065,81 +1180,88 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
-    /// Accept some exit and queue it for inclusion in an appropriate block.
-    pub fn process_voluntary_exit(
+    /// Verify a voluntary exit and check whether it is the first exit seen for its validator.
+    ///
+    /// The exit is verified against the state at the current wall-clock slot, so that exits
+    /// which are not yet valid for inclusion are rejected.
+    pub fn verify_voluntary_exit_for_gossip(
         &self,
         exit: SignedVoluntaryExit,
-    ) -> Result<(), ExitValidationError> {
-        match self.wall_clock_state() {
-            Ok(state) => {
-                if self.eth1_chain.is_some() {
-                    self.op_pool.insert_voluntary_exit(exit, &state, &self.spec)
-                } else {
-                    Ok(())
-                }
-            }
-            Err(e) => {
-                error!(
-                    &self.log,
-                    "Unable to process voluntary exit";
-                    "error" => format!("{:?}", e),
-                    "reason" => "no state"
-                );
-                Ok(())
-            }
+    ) -> Result<ObservationOutcome<SignedVoluntaryExit>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self
+            .observed_voluntary_exits
+            .verify_and_observe(exit, &wall_clock_state, &self.spec)?)
+    }
+
+    /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
+    pub fn process_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit>) {
+        if self
+            .event_handler
+            .has_subscribers(EventTopic::VoluntaryExit)
+        {
+            let _ = self
+                .event_handler
+                .register(EventKind::BeaconVoluntaryExitImported {
+                    exit: exit.as_inner().clone(),
+                });
+        }
+
+        if self.eth1_chain.is_some() {
+            self.op_pool.insert_voluntary_exit(exit)
         }
     }
 
-    /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
-    pub fn process_proposer_slashing(
+    /// Verify a proposer slashing and check whether it is the first seen for its proposer.
+    pub fn verify_proposer_slashing_for_gossip(
         &self,
         proposer_slashing: ProposerSlashing,
-    ) -> Result<(), ProposerSlashingValidationError> {
-        match self.wall_clock_state() {
-            Ok(state) => {
-                if self.eth1_chain.is_some() {
-                    self.op_pool
-                        .insert_proposer_slashing(proposer_slashing, &state, &self.spec)
-                } else {
-                    Ok(())
-                }
-            }
-            Err(e) => {
-                error!(
-                    &self.log,
-                    "Unable to process proposer slashing";
-                    "error" => format!("{:?}", e),
-                    "reason" => "no state"
-                );
-                Ok(())
-            }
+    ) -> Result<ObservationOutcome<ProposerSlashing>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self.observed_proposer_slashings.verify_and_observe(
+            proposer_slashing,
+            &wall_clock_state,
+            &self.spec,
+        )?)
+    }
+
+    /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
+    pub fn process_proposer_slashing(&self, proposer_slashing: SigVerifiedOp<ProposerSlashing>) {
+        if self.eth1_chain.is_some() {
+            self.op_pool.insert_proposer_slashing(proposer_slashing)
         }
     }
 
+    /// Verify an attester slashing and check whether it slashes at least one validator that
+    /// hasn't already been slashed by a previously seen attester slashing.
+    pub fn verify_attester_slashing_for_gossip(
+        &self,
+        attester_slashing: AttesterSlashing<T::EthSpec>,
+    ) -> Result<ObservationOutcome<AttesterSlashing<T::EthSpec>>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self.observed_attester_slashings.verify_and_observe(
+            attester_slashing,
+            &wall_clock_state,
+            &self.spec,
+        )?)
+    }
+
     /// Accept some attester slashing and queue it for inclusion in an appropriate block.
     pub fn process_attester_slashing(
         &self,
-        attester_slashing: AttesterSlashing<T::EthSpec>,
-    ) -> Result<(), AttesterSlashingValidationError> {
-        match self.wall_clock_state() {
-            Ok(state) => {
-                if self.eth1_chain.is_some() {
-                    self.op_pool
-                        .insert_attester_slashing(attester_slashing, &state, &self.spec)
-                } else {
-                    Ok(())
-                }
-            }
-            Err(e) => {
-                error!(
-                    &self.log,
-                    "Unable to process attester slashing";
-                    "error" => format!("{:?}", e),
-                    "reason" => "no state"
-                );
-                Ok(())
-            }
+        attester_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>>,
+    ) -> Result<(), Error> {
+        if self.eth1_chain.is_some() {
+            let head = self
+                .canonical_head
+                .try_read_for(HEAD_LOCK_TIMEOUT)
+                .ok_or_else(|| Error::CanonicalHeadLockTimeout)?;
+
+            self.op_pool
+                .insert_attester_slashing(attester_slashing, &head.beacon_state, &self.spec)
         }
+
+        Ok(())
     }
 
     /// Attempt to verify and import a chain of blocks to `self`.
@@ -1280,6 +1402,122 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         ChainSegmentResult::Successful { imported_blocks }
     }
 
+    /// Verify and store a batch of historic blocks which are older than the oldest block in the
+    /// database (i.e., the blocks prior to the weak subjectivity checkpoint).
+    ///
+    /// The blocks must be sorted in ascending slot order. Starting from the oldest block in the
+    /// database, the blocks are checked to form an unbroken hash chain by following each
+    /// `parent_root` backwards, then all proposer signatures are verified in a single batch.
+    /// Blocks which are not older than the current oldest block are ignored, as is the genesis
+    /// block, which is always stored.
+    ///
+    /// Returns the number of blocks stored.
+    pub fn import_historical_block_batch(
+        &self,
+        blocks: Vec<SignedBeaconBlock<T::EthSpec>>,
+    ) -> Result<usize, HistoricalBlockError> {
+        let anchor_info = self
+            .store
+            .get_anchor_info()
+            .ok_or_else(|| HistoricalBlockError::NoAnchorInfo)?;
+
+        let mut expected_block_root = anchor_info.oldest_block_parent;
+        let mut prev_block_slot = anchor_info.oldest_block_slot;
+        let mut filled_blocks = Vec::with_capacity(blocks.len());
+
+        for block in blocks
+            .into_iter()
+            .rev()
+            .skip_while(|block| block.slot() >= anchor_info.oldest_block_slot)
+        {
+            if expected_block_root == self.genesis_block_root {
+                break;
+            }
+
+            let block_root = block.canonical_root();
+            if block_root != expected_block_root {
+                return Err(HistoricalBlockError::MismatchedBlockRoot {
+                    block_root,
+                    expected_block_root,
+                });
+            }
+            if block.slot() >= prev_block_slot {
+                return Err(HistoricalBlockError::NonLinearSlots);
+            }
+
+            expected_block_root = block.parent_root();
+            prev_block_slot = block.slot();
+            filled_blocks.push((block_root, block));
+        }
+
+        let backfill_complete = expected_block_root == self.genesis_block_root;
+        if filled_blocks.is_empty() && !backfill_complete {
+            return Ok(0);
+        }
+
+        let head_info = self.head_info()?;
+        let signature_sets = {
+            let pubkey_cache = self
+                .validator_pubkey_cache
+                .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+                .ok_or_else(|| Error::ValidatorPubkeyCacheLockTimeout)?;
+
+            filled_blocks
+                .iter()
+                .map(|(block_root, block)| {
+                    let proposer_index = block.message.proposer_index;
+                    let pubkey = pubkey_cache
+                        .get(proposer_index as usize)
+                        .cloned()
+                        .ok_or_else(|| HistoricalBlockError::ValidatorUnknown(proposer_index))?;
+
+                    Ok(block_proposal_signature_set_from_parts(
+                        block,
+                        Some(*block_root),
+                        Cow::Owned(pubkey),
+                        &head_info.fork,
+                        head_info.genesis_validators_root,
+                        &self.spec,
+                    ))
+                })
+                .collect::<Result<Vec<_>, HistoricalBlockError>>()?
+        };
+
+        if !signature_sets.is_empty() && !verify_signature_sets(signature_sets) {
+            return Err(HistoricalBlockError::InvalidSignature);
+        }
+
+        let num_filled = filled_blocks.len();
+
+        // Once the genesis block is reached, include it so that the `block_roots` of all slots
+        // back to genesis are filled.
+        let new_anchor = if backfill_complete {
+            let genesis_block = self
+                .store
+                .get_block(&self.genesis_block_root)?
+                .ok_or_else(|| Error::MissingBeaconBlock(self.genesis_block_root))?;
+            filled_blocks.push((self.genesis_block_root, genesis_block));
+
+            AnchorInfo {
+                anchor_slot: anchor_info.anchor_slot,
+                oldest_block_slot: Slot::new(0),
+                oldest_block_parent: Hash256::zero(),
+            }
+        } else {
+            AnchorInfo {
+                anchor_slot: anchor_info.anchor_slot,
+                oldest_block_slot: prev_block_slot,
+                oldest_block_parent: expected_block_root,
+            }
+        };
+
+        filled_blocks.reverse();
+        self.store
+            .put_historical_blocks(filled_blocks, new_anchor)?;
+
+        Ok(num_filled)
+    }
+
     /// Returns `Ok(GossipVerifiedBlock)` if the supplied `block` should be forwarded onto the
     /// gossip network. The block is not imported into the chain, it is just partially verified.
     ///
@@ -1346,8 +1584,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Increment the Prometheus counter for block processing requests.
         metrics::inc_counter(&metrics::BLOCK_PROCESSING_REQUESTS);
 
-        // Clone the block so we can provide it to the event handler.
-        let block = unverified_block.block().clone();
+        // Clone the block so we can provide it to the event handler, if anyone is listening.
+        let block_slot = unverified_block.block().slot();
+        let event_block = if self.event_handler.has_subscribers(EventTopic::Block) {
+            Some(unverified_block.block().clone())
+        } else {
+            None
+        };
 
         // A small closure to group the verification and import errors.
         let import_block = |unverified_block: B| -> Result<Hash256, BlockError> {
@@ -1363,16 +1606,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     self.log,
                     "Beacon block imported";
                     "block_root" => format!("{:?}", block_root),
-                    "block_slot" => format!("{:?}", block.slot().as_u64()),
+                    "block_slot" => format!("{:?}", block_slot.as_u64()),
                 );
 
                 // Increment the Prometheus counter for block processing successes.
                 metrics::inc_counter(&metrics::BLOCK_PROCESSING_SUCCESSES);
 
-                let _ = self.event_handler.register(EventKind::BeaconBlockImported {
-                    block_root: block_root,
-                    block: Box::new(block),
-                });
+                if let Some(block) = event_block {
+                    let _ = self.event_handler.register(EventKind::BeaconBlockImported {
+                        block_root: block_root,
+                        block: Box::new(block),
+                    });
+                }
 
                 Ok(block_root)
             }
@@ -1385,11 +1630,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     "error" => format!("{:?}", e),
                 );
 
-                let _ = self.event_handler.register(EventKind::BeaconBlockRejected {
-                    reason: format!("Internal error: {:?}", e),
-                    block: Box::new(block),
-                });
-
                 Err(BlockError::BeaconChainError(e))
             }
             // The block failed verification.
@@ -1400,11 +1640,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     "reason" => format!("{:?}", other),
                 );
 
-                let _ = self.event_handler.register(EventKind::BeaconBlockRejected {
-                    reason: format!("Invalid block: {:?}", other),
-                    block: Box::new(block),
-                });
-
                 Err(other)
             }
         };
@@ -1502,6 +1737,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         metrics::stop_timer(fork_choice_register_timer);
 
+        {
+            let validator_monitor = self.validator_monitor.read();
+            if !validator_monitor.is_empty() {
+                validator_monitor.register_block(block, &state);
+            }
+        }
+
         metrics::observe(
             &metrics::OPERATIONS_PER_BLOCK_ATTESTATION,
             block.body.attestations.len() as f64,
@@ -1766,6 +2008,30 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         if is_reorg {
             metrics::inc_counter(&metrics::FORK_CHOICE_REORG_COUNT);
+
+            let common_ancestor = self
+                .find_common_ancestor(current_head.block_root, &new_head)
+                .unwrap_or_else(|e| {
+                    warn!(
+                        self.log,
+                        "Unable to find re-org common ancestor";
+                        "error" => format!("{:?}", e)
+                    );
+                    None
+                });
+            let (depth, orphaned_blocks) = match &common_ancestor {
+                Some((_, common_ancestor_slot, orphaned)) => (
+                    Some(
+                        current_head
+                            .slot
+                            .as_u64()
+                            .saturating_sub(common_ancestor_slot.as_u64()),
+                    ),
+                    Some(orphaned.len()),
+                ),
+                None => (None, None),
+            };
+
             warn!(
                 self.log,
                 "Beacon chain re-org";
@@ -1773,8 +2039,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "previous_slot" => current_head.slot,
                 "new_head_parent" => format!("{}", new_head.beacon_block.parent_root()),
                 "new_head" => format!("{}", beacon_block_root),
-                "new_slot" => new_head.beacon_block.slot()
+                "new_slot" => new_head.beacon_block.slot(),
+                "depth" => depth,
+                "orphaned_blocks" => orphaned_blocks,
             );
+
+            if let (Some(depth), Some((common_ancestor_root, common_ancestor_slot, orphaned))) =
+                (depth, common_ancestor)
+            {
+                metrics::observe(&metrics::FORK_CHOICE_REORG_DEPTH, depth as f64);
+
+                let _ = self.event_handler.register(EventKind::ChainReorg {
+                    depth,
+                    common_ancestor_root,
+                    common_ancestor_slot,
+                    old_head_root: current_head.block_root,
+                    old_head_slot: current_head.slot,
+                    new_head_root: beacon_block_root,
+                    new_head_slot: new_head.beacon_block.slot(),
+                    orphaned_block_roots: orphaned,
+                });
+            }
         } else {
             debug!(
                 self.log,
@@ -1800,16 +2085,22 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             });
         }
 
-        if current_head.slot.epoch(T::EthSpec::slots_per_epoch())
+        let is_epoch_transition = current_head.slot.epoch(T::EthSpec::slots_per_epoch())
             < new_head
                 .beacon_state
                 .slot
-                .epoch(T::EthSpec::slots_per_epoch())
-            || is_reorg
-        {
+                .epoch(T::EthSpec::slots_per_epoch());
+
+        if is_epoch_transition || is_reorg {
             self.persist_head_and_fork_choice()?;
         }
 
+        if is_epoch_transition {
+            self.validator_monitor
+                .write()
+                .process_valid_state(&new_head.beacon_state);
+        }
+
         let update_head_timer = metrics::start_timer(&metrics::UPDATE_HEAD_TIMES);
 
         // Update the snapshot that stores the head of the chain at the time it received the
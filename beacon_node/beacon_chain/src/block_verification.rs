@@ -308,10 +308,15 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
         check_block_against_finalized_slot(&block.message, chain)?;
 
         // Check that we have not already received a block with a valid signature for this slot.
-        if chain
-            .observed_block_producers
-            .proposer_has_been_observed(&block.message)
-            .map_err(|e| BlockError::BeaconChainError(e.into()))?
+        //
+        // This check is skipped when the slasher is enabled, so that the signature of a repeat
+        // proposal is verified and the block may be sent to the slasher. Such a block is still
+        // rejected by the second check below.
+        if chain.slasher.is_none()
+            && chain
+                .observed_block_producers
+                .proposer_has_been_observed(&block.message)
+                .map_err(|e| BlockError::BeaconChainError(e.into()))?
         {
             return Err(BlockError::RepeatProposal {
                 proposer: block.message.proposer_index,
@@ -346,6 +351,10 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
             return Err(BlockError::ProposalSignatureInvalid);
         }
 
+        if let Some(slasher) = chain.slasher.as_ref() {
+            slasher.accept_block_header(block.signed_block_header());
+        }
+
         // Now the signature is valid, store the proposal so we don't accept another from this
         // validator and slot.
         //
@@ -10,6 +10,7 @@ use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::shuffling_cache::ShufflingCache;
 use crate::snapshot_cache::{SnapshotCache, DEFAULT_SNAPSHOT_CACHE_SIZE};
 use crate::timeout_rw_lock::TimeoutRwLock;
+use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::{
     BeaconChain, BeaconChainTypes, BeaconSnapshot, Eth1Chain, Eth1ChainBackend, EventHandler,
@@ -17,14 +18,16 @@ use crate::{
 };
 use eth1::Config as Eth1Config;
 use operation_pool::{OperationPool, PersistedOperationPool};
+use parking_lot::RwLock;
 use proto_array_fork_choice::ProtoArrayForkChoice;
+use slasher::Slasher;
 use slog::{info, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use store::Store;
+use store::{AnchorInfo, Store};
 use types::{
     BeaconBlock, BeaconState, ChainSpec, EthSpec, Hash256, Signature, SignedBeaconBlock, Slot,
 };
@@ -89,6 +92,8 @@ pub struct BeaconChainBuilder<T: BeaconChainTypes> {
     validator_pubkey_cache: Option<ValidatorPubkeyCache>,
     spec: ChainSpec,
     disabled_forks: Vec<String>,
+    slasher: Option<Arc<Slasher<T::EthSpec>>>,
+    validator_monitor_config: ValidatorMonitorConfig,
     log: Option<Logger>,
 }
 
@@ -126,6 +131,8 @@ where
             disabled_forks: Vec::new(),
             validator_pubkey_cache: None,
             spec: TEthSpec::default_spec(),
+            slasher: None,
+            validator_monitor_config: <_>::default(),
             log: None,
         }
     }
@@ -176,6 +183,18 @@ where
         self
     }
 
+    /// Sets the slasher which will be sent verified gossip attestations and blocks.
+    pub fn slasher(mut self, slasher: Arc<Slasher<TEthSpec>>) -> Self {
+        self.slasher = Some(slasher);
+        self
+    }
+
+    /// Sets the validators which will be tracked by the validator monitor.
+    pub fn validator_monitor_config(mut self, config: ValidatorMonitorConfig) -> Self {
+        self.validator_monitor_config = config;
+        self
+    }
+
     /// Attempt to load an existing eth1 cache from the builder's `Store`.
     pub fn get_persisted_eth1_backend(&self) -> Result<Option<SszEth1>, String> {
         let store = self
@@ -262,27 +281,43 @@ where
         let finalized_block_root = head_state.finalized_checkpoint.root;
         let finalized_block = store
             .get::<SignedBeaconBlock<TEthSpec>>(&finalized_block_root)
-            .map_err(|e| format!("DB error when reading finalized block: {:?}", e))?
-            .ok_or_else(|| "Finalized block not found in store".to_string())?;
-        let finalized_state_root = finalized_block.state_root();
-        let finalized_state = store
-            .get_state(&finalized_state_root, Some(finalized_block.slot()))
-            .map_err(|e| format!("DB error when reading finalized state: {:?}", e))?
-            .ok_or_else(|| "Finalized state not found in store".to_string())?;
-
-        self.finalized_snapshot = Some(BeaconSnapshot {
-            beacon_block_root: finalized_block_root,
-            beacon_block: finalized_block,
-            beacon_state_root: finalized_state_root,
-            beacon_state: finalized_state,
-        });
+            .map_err(|e| format!("DB error when reading finalized block: {:?}", e))?;
 
-        self.canonical_head = Some(BeaconSnapshot {
+        let canonical_head = BeaconSnapshot {
             beacon_block_root: head_block_root,
             beacon_block: head_block,
             beacon_state_root: head_state_root,
             beacon_state: head_state,
-        });
+        };
+
+        self.finalized_snapshot = if let Some(finalized_block) = finalized_block {
+            let finalized_state_root = finalized_block.state_root();
+            let finalized_state = store
+                .get_state(&finalized_state_root, Some(finalized_block.slot()))
+                .map_err(|e| format!("DB error when reading finalized state: {:?}", e))?
+                .ok_or_else(|| "Finalized state not found in store".to_string())?;
+
+            Some(BeaconSnapshot {
+                beacon_block_root: finalized_block_root,
+                beacon_block: finalized_block,
+                beacon_state_root: finalized_state_root,
+                beacon_state: finalized_state,
+            })
+        } else if store
+            .get::<SszForkChoice>(&Hash256::from_slice(&FORK_CHOICE_DB_KEY))
+            .map_err(|e| format!("DB error when reading persisted fork choice: {:?}", e))?
+            .is_some()
+        {
+            // A chain started from a weak subjectivity checkpoint does not have the blocks prior
+            // to the checkpoint, which may include the finalized block of the head state until the
+            // chain finalizes beyond the checkpoint. Fork choice is restored from disk in this
+            // case, so the head is a suitable stand-in for the remaining uses of the snapshot.
+            Some(canonical_head.clone())
+        } else {
+            return Err("Finalized block not found in store".to_string());
+        };
+
+        self.canonical_head = Some(canonical_head);
 
         let pubkey_cache = ValidatorPubkeyCache::load_from_file(pubkey_cache_path)
             .map_err(|e| format!("Unable to open persisted pubkey cache: {:?}", e))?;
@@ -338,6 +373,134 @@ where
         Ok(self.empty_op_pool())
     }
 
+    /// Starts a new chain from a finalized weak subjectivity checkpoint, rather than from genesis.
+    ///
+    /// The `weak_subj_block` must be the block at the first slot of an epoch and
+    /// `weak_subj_state` must be its post-state. The `genesis_state` is used to determine the
+    /// genesis block root and to check that the checkpoint belongs to the expected chain.
+    ///
+    /// The database split point, fork choice and head tracker are all anchored at the checkpoint;
+    /// no blocks or states prior to it will be available.
+    pub fn weak_subjectivity_state(
+        mut self,
+        mut weak_subj_state: BeaconState<TEthSpec>,
+        weak_subj_block: SignedBeaconBlock<TEthSpec>,
+        mut genesis_state: BeaconState<TEthSpec>,
+    ) -> Result<Self, String> {
+        let store = self
+            .store
+            .clone()
+            .ok_or_else(|| "weak_subjectivity_state requires a store")?;
+        let log = self
+            .log
+            .as_ref()
+            .ok_or_else(|| "weak_subjectivity_state requires a log".to_string())?;
+
+        let slots_per_epoch = TEthSpec::slots_per_epoch();
+        if weak_subj_state.slot % slots_per_epoch != 0 {
+            return Err(format!(
+                "Checkpoint state at slot {} is not at the start of an epoch. \
+                 The first slot of the epoch may have been skipped, try a later checkpoint.",
+                weak_subj_state.slot
+            ));
+        }
+
+        if weak_subj_block.slot() != weak_subj_state.slot {
+            return Err(format!(
+                "Checkpoint block slot {} does not match checkpoint state slot {}. \
+                 The first slot of the epoch may have been skipped, try a later checkpoint.",
+                weak_subj_block.slot(),
+                weak_subj_state.slot
+            ));
+        }
+
+        if weak_subj_state.genesis_validators_root != genesis_state.genesis_validators_root {
+            return Err(format!(
+                "Checkpoint state is for a different chain; genesis_validators_root is {:?}, \
+                 expected {:?}",
+                weak_subj_state.genesis_validators_root, genesis_state.genesis_validators_root
+            ));
+        }
+
+        let weak_subj_state_root = weak_subj_state
+            .update_tree_hash_cache()
+            .map_err(|e| format!("Error hashing checkpoint state: {:?}", e))?;
+
+        if weak_subj_block.state_root() != weak_subj_state_root {
+            return Err(format!(
+                "Checkpoint block has state root {:?} but the checkpoint state root is {:?}",
+                weak_subj_block.state_root(),
+                weak_subj_state_root
+            ));
+        }
+
+        weak_subj_state
+            .build_all_caches(&self.spec)
+            .map_err(|e| format!("Failed to build checkpoint state caches: {:?}", e))?;
+
+        let genesis_block = genesis_block(&mut genesis_state, &self.spec)?;
+        let genesis_block_root = genesis_block.canonical_root();
+        let weak_subj_block_root = weak_subj_block.canonical_root();
+
+        info!(
+            log,
+            "Starting beacon chain";
+            "method" => "weak subjectivity checkpoint",
+            "slot" => weak_subj_state.slot,
+            "block_root" => format!("{:?}", weak_subj_block_root),
+        );
+
+        // Set the split before storing the checkpoint state so that it is stored in the hot DB.
+        store
+            .initialize_split(weak_subj_state.slot, weak_subj_state_root)
+            .map_err(|e| format!("Failed to initialize database split: {:?}", e))?;
+        store
+            .put_state(&weak_subj_state_root, &weak_subj_state)
+            .map_err(|e| format!("Failed to store checkpoint state: {:?}", e))?;
+        store
+            .put(&weak_subj_block_root, &weak_subj_block)
+            .map_err(|e| format!("Failed to store checkpoint block: {:?}", e))?;
+
+        // Store the genesis block so that it may still be served, although its state is not stored.
+        store
+            .put(&genesis_block_root, &genesis_block)
+            .map_err(|e| format!("Failed to store genesis block: {:?}", e))?;
+        store.put(&Hash256::zero(), &genesis_block).map_err(|e| {
+            format!(
+                "Failed to store genesis block under 0x00..00 alias: {:?}",
+                e
+            )
+        })?;
+
+        // Record the checkpoint block as the oldest block, so that backfill sync can download
+        // the blocks prior to it.
+        store
+            .put_anchor_info(AnchorInfo {
+                anchor_slot: weak_subj_state.slot,
+                oldest_block_slot: weak_subj_block.slot(),
+                oldest_block_parent: weak_subj_block.parent_root(),
+            })
+            .map_err(|e| format!("Failed to store anchor info: {:?}", e))?;
+
+        let head_tracker = HeadTracker::default();
+        head_tracker.register_block(
+            weak_subj_block_root,
+            weak_subj_block.parent_root(),
+            weak_subj_block.slot(),
+        );
+
+        self.genesis_block_root = Some(genesis_block_root);
+        self.head_tracker = Some(head_tracker);
+        self.finalized_snapshot = Some(BeaconSnapshot {
+            beacon_block_root: weak_subj_block_root,
+            beacon_block: weak_subj_block,
+            beacon_state_root: weak_subj_state_root,
+            beacon_state: weak_subj_state,
+        });
+
+        Ok(self.empty_op_pool())
+    }
+
     /// Sets the `BeaconChain` eth1 backend.
     pub fn eth1_backend(mut self, backend: Option<TEth1Backend>) -> Self {
         self.eth1_chain = backend.map(Eth1Chain::new);
@@ -346,7 +509,7 @@ where
 
     /// Sets the `BeaconChain` event handler backend.
     ///
-    /// For example, provide `WebSocketSender` as a `handler`.
+    /// For example, provide `ServerSentEventHandler` as a `handler`.
     pub fn event_handler(mut self, handler: TEventHandler) -> Self {
         self.event_handler = Some(handler);
         self
@@ -439,6 +602,10 @@ where
             observed_aggregators: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_block_producers: <_>::default(),
+            // TODO: allow for persisting and loading the pool from disk.
+            observed_voluntary_exits: <_>::default(),
+            observed_proposer_slashings: <_>::default(),
+            observed_attester_slashings: <_>::default(),
             eth1_chain: self.eth1_chain,
             genesis_validators_root: canonical_head.beacon_state.genesis_validators_root,
             canonical_head: TimeoutRwLock::new(canonical_head.clone()),
@@ -459,6 +626,11 @@ where
             shuffling_cache: TimeoutRwLock::new(ShufflingCache::new()),
             validator_pubkey_cache: TimeoutRwLock::new(validator_pubkey_cache),
             disabled_forks: self.disabled_forks,
+            slasher: self.slasher,
+            validator_monitor: RwLock::new(ValidatorMonitor::new(
+                self.validator_monitor_config,
+                log.clone(),
+            )),
             log: log.clone(),
         };
 
@@ -528,9 +700,11 @@ where
                 finalized_snapshot.beacon_block_root,
             )?;
 
+            // Fork choice is anchored at the finalized snapshot, which is the genesis block unless
+            // the chain was started from a weak subjectivity checkpoint.
             ForkChoice::new(
                 backend,
-                genesis_block_root,
+                finalized_snapshot.beacon_block_root,
                 &finalized_snapshot.beacon_state,
             )
         };
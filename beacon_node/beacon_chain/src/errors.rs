@@ -10,8 +10,12 @@ use ssz::DecodeError;
 use ssz_types::Error as SszTypesError;
 use state_processing::{
     block_signature_verifier::Error as BlockSignatureVerifierError,
-    per_block_processing::errors::AttestationValidationError,
-    signature_sets::Error as SignatureSetError, BlockProcessingError, SlotProcessingError,
+    per_block_processing::errors::{
+        AttestationValidationError, AttesterSlashingValidationError, ExitValidationError,
+        ProposerSlashingValidationError,
+    },
+    signature_sets::Error as SignatureSetError,
+    BlockProcessingError, SlotProcessingError,
 };
 use std::time::Duration;
 use types::*;
@@ -73,6 +77,9 @@ pub enum BeaconChainError {
     ObservedAttestationsError(ObservedAttestationsError),
     ObservedAttestersError(ObservedAttestersError),
     ObservedBlockProducersError(ObservedBlockProducersError),
+    ExitValidationError(ExitValidationError),
+    ProposerSlashingValidationError(ProposerSlashingValidationError),
+    AttesterSlashingValidationError(AttesterSlashingValidationError),
     ArithError(ArithError),
 }
 
@@ -85,6 +92,9 @@ easy_from_to!(ObservedAttestationsError, BeaconChainError);
 easy_from_to!(ObservedAttestersError, BeaconChainError);
 easy_from_to!(ObservedBlockProducersError, BeaconChainError);
 easy_from_to!(BlockSignatureVerifierError, BeaconChainError);
+easy_from_to!(ExitValidationError, BeaconChainError);
+easy_from_to!(ProposerSlashingValidationError, BeaconChainError);
+easy_from_to!(AttesterSlashingValidationError, BeaconChainError);
 easy_from_to!(ArithError, BeaconChainError);
 
 #[derive(Debug)]
@@ -102,6 +112,41 @@ pub enum BlockProductionError {
     NoEth1ChainConnection,
 }
 
+/// An error encountered whilst importing a batch of historic blocks during backfill sync.
+#[derive(Debug)]
+pub enum HistoricalBlockError {
+    /// The database was initialized from genesis, so there are no historic blocks to import.
+    NoAnchorInfo,
+    /// A block in the batch is not the parent of the block that follows it (or of the oldest
+    /// block in the database).
+    ///
+    /// The batch is invalid.
+    MismatchedBlockRoot {
+        block_root: Hash256,
+        expected_block_root: Hash256,
+    },
+    /// The slots of the blocks in the batch are not strictly increasing.
+    ///
+    /// The batch is invalid.
+    NonLinearSlots,
+    /// A block references a proposer index that is not in the validator pubkey cache.
+    ValidatorUnknown(u64),
+    /// At least one of the proposer signatures in the batch is invalid.
+    ///
+    /// The batch is invalid.
+    InvalidSignature,
+    BeaconChainError(BeaconChainError),
+    DBError(store::Error),
+}
+
+easy_from_to!(BeaconChainError, HistoricalBlockError);
+
+impl From<store::Error> for HistoricalBlockError {
+    fn from(e: store::Error) -> Self {
+        HistoricalBlockError::DBError(e)
+    }
+}
+
 easy_from_to!(BlockProcessingError, BlockProductionError);
 easy_from_to!(BeaconStateError, BlockProductionError);
 easy_from_to!(SlotProcessingError, BlockProductionError);
@@ -1,23 +1,105 @@
-use serde_derive::{Deserialize, Serialize};
 use std::marker::PhantomData;
-use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock};
-pub use websocket_server::WebSocketSender;
+use tokio::sync::broadcast;
+use types::EthSpec;
+
+pub use rest_types::{EventKind, EventTopic};
+
+/// The number of events buffered for each subscriber to a low-volume topic (e.g., `head` or
+/// `block`) before the oldest are dropped.
+///
+/// A subscriber which falls further behind than this will miss events, rather than slowing down
+/// the `BeaconChain`.
+pub const DEFAULT_EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// The number of events buffered for each subscriber to the `attestation` topic, which may
+/// receive thousands of events each slot.
+pub const DEFAULT_ATTESTATION_CHANNEL_CAPACITY: usize = 16_384;
 
 pub trait EventHandler<T: EthSpec>: Sized + Send + Sync {
     fn register(&self, kind: EventKind<T>) -> Result<(), String>;
+
+    /// Returns `true` if there is at least one subscriber to `topic`.
+    ///
+    /// Callers should check this before building an event, so that no work is done for events
+    /// which would be discarded.
+    fn has_subscribers(&self, _topic: EventTopic) -> bool {
+        false
+    }
+
+    /// Returns a receiver for all events on `topic` registered after this call, or `None` if this
+    /// handler does not support subscriptions.
+    fn subscribe(&self, _topic: EventTopic) -> Option<broadcast::Receiver<EventKind<T>>> {
+        None
+    }
 }
 
 pub struct NullEventHandler<T: EthSpec>(PhantomData<T>);
 
-impl<T: EthSpec> EventHandler<T> for WebSocketSender<T> {
-    fn register(&self, kind: EventKind<T>) -> Result<(), String> {
-        self.send_string(
-            serde_json::to_string(&kind)
-                .map_err(|e| format!("Unable to serialize event: {:?}", e))?,
+/// Broadcasts events to any number of subscribers, such as server-sent event streams.
+///
+/// Each topic has its own channel, so that a subscriber to a low-volume topic does not miss
+/// events because of a burst of events on another topic.
+pub struct ServerSentEventHandler<T: EthSpec> {
+    head_tx: broadcast::Sender<EventKind<T>>,
+    block_tx: broadcast::Sender<EventKind<T>>,
+    attestation_tx: broadcast::Sender<EventKind<T>>,
+    voluntary_exit_tx: broadcast::Sender<EventKind<T>>,
+    finalized_checkpoint_tx: broadcast::Sender<EventKind<T>>,
+    chain_reorg_tx: broadcast::Sender<EventKind<T>>,
+}
+
+impl<T: EthSpec> ServerSentEventHandler<T> {
+    /// Creates a new handler which buffers up to `attestation_capacity` events for each
+    /// subscriber to the `attestation` topic and up to `capacity` events for each subscriber to
+    /// any other topic.
+    pub fn new(capacity: usize, attestation_capacity: usize) -> Self {
+        Self {
+            head_tx: broadcast::channel(capacity).0,
+            block_tx: broadcast::channel(capacity).0,
+            attestation_tx: broadcast::channel(attestation_capacity).0,
+            voluntary_exit_tx: broadcast::channel(capacity).0,
+            finalized_checkpoint_tx: broadcast::channel(capacity).0,
+            chain_reorg_tx: broadcast::channel(capacity).0,
+        }
+    }
+
+    fn sender(&self, topic: EventTopic) -> &broadcast::Sender<EventKind<T>> {
+        match topic {
+            EventTopic::Head => &self.head_tx,
+            EventTopic::Block => &self.block_tx,
+            EventTopic::Attestation => &self.attestation_tx,
+            EventTopic::VoluntaryExit => &self.voluntary_exit_tx,
+            EventTopic::FinalizedCheckpoint => &self.finalized_checkpoint_tx,
+            EventTopic::ChainReorg => &self.chain_reorg_tx,
+        }
+    }
+}
+
+impl<T: EthSpec> Default for ServerSentEventHandler<T> {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_EVENT_CHANNEL_CAPACITY,
+            DEFAULT_ATTESTATION_CHANNEL_CAPACITY,
         )
     }
 }
 
+impl<T: EthSpec> EventHandler<T> for ServerSentEventHandler<T> {
+    fn register(&self, kind: EventKind<T>) -> Result<(), String> {
+        // An error is only returned when there are no subscribers, which is not a failure.
+        let _ = self.sender(kind.topic()).send(kind);
+        Ok(())
+    }
+
+    fn has_subscribers(&self, topic: EventTopic) -> bool {
+        self.sender(topic).receiver_count() > 0
+    }
+
+    fn subscribe(&self, topic: EventTopic) -> Option<broadcast::Receiver<EventKind<T>>> {
+        Some(self.sender(topic).subscribe())
+    }
+}
+
 impl<T: EthSpec> EventHandler<T> for NullEventHandler<T> {
     fn register(&self, _kind: EventKind<T>) -> Result<(), String> {
         Ok(())
@@ -30,36 +112,28 @@ impl<T: EthSpec> Default for NullEventHandler<T> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(
-    bound = "T: EthSpec",
-    rename_all = "snake_case",
-    tag = "event",
-    content = "data"
-)]
-pub enum EventKind<T: EthSpec> {
-    BeaconHeadChanged {
-        reorg: bool,
-        current_head_beacon_block_root: Hash256,
-        previous_head_beacon_block_root: Hash256,
-    },
-    BeaconFinalization {
-        epoch: Epoch,
-        root: Hash256,
-    },
-    BeaconBlockImported {
-        block_root: Hash256,
-        block: Box<SignedBeaconBlock<T>>,
-    },
-    BeaconBlockRejected {
-        reason: String,
-        block: Box<SignedBeaconBlock<T>>,
-    },
-    BeaconAttestationImported {
-        attestation: Box<Attestation<T>>,
-    },
-    BeaconAttestationRejected {
-        reason: String,
-        attestation: Box<Attestation<T>>,
-    },
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Epoch, Hash256, MainnetEthSpec};
+
+    #[test]
+    fn events_only_sent_to_subscribed_topic() {
+        let handler = ServerSentEventHandler::<MainnetEthSpec>::new(4, 4);
+        assert!(!handler.has_subscribers(EventTopic::FinalizedCheckpoint));
+
+        let mut finalized_rx = handler.subscribe(EventTopic::FinalizedCheckpoint).unwrap();
+        let mut head_rx = handler.subscribe(EventTopic::Head).unwrap();
+        assert!(handler.has_subscribers(EventTopic::FinalizedCheckpoint));
+        assert!(!handler.has_subscribers(EventTopic::Block));
+
+        let event = EventKind::BeaconFinalization {
+            epoch: Epoch::new(1),
+            root: Hash256::from_low_u64_be(1),
+        };
+        handler.register(event.clone()).unwrap();
+
+        assert_eq!(finalized_rx.try_recv().ok(), Some(event));
+        assert!(head_rx.try_recv().is_err());
+    }
 }
@@ -9,7 +9,10 @@ use ssz_derive::{Decode, Encode};
 use state_processing::common::get_indexed_attestation;
 use std::marker::PhantomData;
 use store::{DBColumn, Error as StoreError, SimpleStoreItem};
-use types::{BeaconBlock, BeaconState, BeaconStateError, Epoch, Hash256, IndexedAttestation, Slot};
+use types::{
+    BeaconBlock, BeaconState, BeaconStateError, Checkpoint, Epoch, Hash256, IndexedAttestation,
+    Slot,
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -213,6 +216,13 @@ impl<T: BeaconChainTypes> ForkChoice<T> {
         self.backend.latest_message(validator_index)
     }
 
+    /// Returns the finalized checkpoint currently used by fork choice.
+    ///
+    /// This is never earlier than the checkpoint that fork choice was instantiated from.
+    pub fn finalized_checkpoint(&self) -> Checkpoint {
+        self.checkpoint_manager.read().current.finalized.clone()
+    }
+
     /// Trigger a prune on the underlying fork choice backend.
     pub fn prune(&self) -> Result<()> {
         let finalized_root = self.checkpoint_manager.read().current.finalized.root;
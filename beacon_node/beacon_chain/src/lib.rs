@@ -18,11 +18,13 @@ mod naive_aggregation_pool;
 mod observed_attestations;
 mod observed_attesters;
 mod observed_block_producers;
+pub mod observed_operations;
 mod persisted_beacon_chain;
 mod shuffling_cache;
 mod snapshot_cache;
 pub mod test_utils;
 mod timeout_rw_lock;
+pub mod validator_monitor;
 mod validator_pubkey_cache;
 
 pub use self::beacon_chain::{
@@ -30,7 +32,7 @@ pub use self::beacon_chain::{
     StateSkipConfig,
 };
 pub use self::beacon_snapshot::BeaconSnapshot;
-pub use self::errors::{BeaconChainError, BlockProductionError};
+pub use self::errors::{BeaconChainError, BlockProductionError, HistoricalBlockError};
 pub use attestation_verification::Error as AttestationError;
 pub use block_verification::{BlockError, BlockProcessingOutcome, GossipVerifiedBlock};
 pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
@@ -45,3 +47,4 @@ pub use state_processing::per_block_processing::errors::{
 };
 pub use store;
 pub use types;
+pub use validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
@@ -226,6 +226,11 @@ lazy_static! {
         "beacon_fork_choice_reorg_total",
         "Count of occasions fork choice has switched to a different chain"
     );
+    pub static ref FORK_CHOICE_REORG_DEPTH: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_fork_choice_reorg_depth",
+        "Number of slots between the previous head and the common ancestor of a re-org",
+        vec![1.0, 2.0, 3.0, 4.0, 8.0, 16.0, 32.0, 64.0]
+    );
     pub static ref FORK_CHOICE_TIMES: Result<Histogram> =
         try_create_histogram("beacon_fork_choice_seconds", "Full runtime of fork choice");
     pub static ref FORK_CHOICE_FIND_HEAD_TIMES: Result<Histogram> =
@@ -338,6 +343,85 @@ lazy_static! {
     );
 }
 
+// Third lazy-static block is used to account for macro recursion limit.
+lazy_static! {
+    /*
+     * Validator Monitor
+     */
+    pub static ref VALIDATOR_MONITOR_UNAGGREGATED_ATTESTATION_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "validator_monitor_unaggregated_attestation_total",
+            "Number of unaggregated attestations seen from a monitored validator",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_AGGREGATED_ATTESTATION_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "validator_monitor_aggregated_attestation_total",
+            "Number of aggregated attestations seen including a monitored validator",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_ATTESTATION_IN_BLOCK_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "validator_monitor_attestation_in_block_total",
+            "Number of imported blocks including an attestation from a monitored validator",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "validator_monitor_beacon_block_total",
+            "Number of imported blocks proposed by a monitored validator",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EPOCH_ATTESTATION_INCLUDED: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_epoch_attestation_included",
+            "Set to 1 if an attestation from the last summarised epoch was included in a block",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EPOCH_INCLUSION_DISTANCE: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_epoch_inclusion_distance",
+            "Minimum inclusion distance of the attestation from the last summarised epoch",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EPOCH_CORRECT_SOURCE: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_epoch_correct_source",
+            "Set to 1 if the attestation from the last summarised epoch had the correct source",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EPOCH_CORRECT_TARGET: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_epoch_correct_target",
+            "Set to 1 if the attestation from the last summarised epoch had the correct target",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EPOCH_CORRECT_HEAD: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_epoch_correct_head",
+            "Set to 1 if the attestation from the last summarised epoch had the correct head",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EPOCH_BALANCE_CHANGE_GWEI: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_epoch_balance_change_gwei",
+            "Change in balance due to the rewards and penalties of the last summarised epoch",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_BALANCE_GWEI: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_balance_gwei",
+            "Balance of a monitored validator at the head of the chain",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_EFFECTIVE_BALANCE_GWEI: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "validator_monitor_effective_balance_gwei",
+            "Effective balance of a monitored validator at the head of the chain",
+            &["validator"]
+        );
+}
+
 /// Scrape the `beacon_chain` for metrics that are not constantly updated (e.g., the present slot,
 /// head state info, etc) and update the Prometheus `DEFAULT_REGISTRY`.
 pub fn scrape_for_metrics<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) {
@@ -1,7 +1,8 @@
 use crate::metrics;
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use types::{Attestation, AttestationData, EthSpec, Slot};
+use tree_hash::TreeHash;
+use types::{Attestation, AttestationData, EthSpec, Hash256, Slot};
 
 /// The number of slots that will be stored in the pool.
 ///
@@ -120,6 +121,15 @@ impl<E: EthSpec> AggregatedAttestationMap<E> {
         Ok(self.map.get(data).cloned())
     }
 
+    /// Returns an aggregated `Attestation` with the given `attestation.data.tree_hash_root()`, if
+    /// any.
+    pub fn get_by_root(&self, root: &Hash256) -> Option<Attestation<E>> {
+        self.map
+            .iter()
+            .find(|(data, _attestation)| data.tree_hash_root() == *root)
+            .map(|(_data, attestation)| attestation.clone())
+    }
+
     pub fn len(&self) -> usize {
         self.map.len()
     }
@@ -226,6 +236,16 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
             .unwrap_or_else(|| Ok(None))
     }
 
+    /// Returns an aggregated `Attestation` at `slot` with the given `attestation.data` root, if
+    /// any.
+    pub fn get_by_slot_and_root(&self, slot: Slot, root: &Hash256) -> Option<Attestation<E>> {
+        self.maps
+            .read()
+            .iter()
+            .find(|(map_slot, _map)| **map_slot == slot)
+            .and_then(|(_slot, map)| map.get_by_root(root))
+    }
+
     /// Removes any attestations with a slot lower than `current_slot` and bars any future
     /// attestations with a slot lower than `current_slot - SLOTS_RETAINED`.
     pub fn prune(&self, current_slot: Slot) {
@@ -0,0 +1,207 @@
+//! Provides the `ObservedOperations` struct which allows for rejecting gossip voluntary exits,
+//! proposer slashings and attester slashings that only concern validators which have already been
+//! the subject of an operation of the same kind.
+
+use parking_lot::Mutex;
+use state_processing::{SigVerifiedOp, VerifyOperation};
+use std::collections::HashSet;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use types::{
+    AttesterSlashing, BeaconState, ChainSpec, EthSpec, ProposerSlashing, SignedVoluntaryExit,
+};
+
+/// Maintains a record of the validator indices for which an operation of type `T` has been seen.
+///
+/// The spec requires that only the *first* valid exit or slashing for each validator is
+/// propagated on gossip, this struct tracks the indices required to implement that check.
+///
+/// The cache is never pruned. Each validator can only exit or be slashed once, so the set is
+/// bounded by the size of the validator registry.
+pub struct ObservedOperations<T: ObservableOperation<E>, E: EthSpec> {
+    /// Indices of validators for whom we have already seen an instance of an operation `T`.
+    ///
+    /// For voluntary exits, this is the set of all `signed_voluntary_exit.message.validator_index`.
+    /// For proposer slashings, this is the set of all `proposer_slashing.index`.
+    /// For attester slashings, this is the set of all validators who would be slashed by
+    /// previously seen attester slashings, i.e. those validators in the intersection of
+    /// `attestation_1.attester_indices` and `attestation_2.attester_indices`.
+    observed_validator_indices: Mutex<HashSet<u64>>,
+    _phantom: PhantomData<(T, E)>,
+}
+
+/// The result of `ObservedOperations::verify_and_observe`.
+#[derive(Debug)]
+pub enum ObservationOutcome<T> {
+    /// The operation is new and valid, it should be imported and propagated.
+    New(SigVerifiedOp<T>),
+    /// All the validators concerned by the operation have been seen before, it should be ignored.
+    AlreadyKnown,
+}
+
+/// Trait for exits and slashings which can be observed using `ObservedOperations`.
+pub trait ObservableOperation<E: EthSpec>: VerifyOperation<E> + Sized {
+    /// The set of validator indices involved in this operation.
+    ///
+    /// See the comment on `observed_validator_indices` above for detail.
+    fn observed_validators(&self) -> Vec<u64>;
+}
+
+impl<E: EthSpec> ObservableOperation<E> for SignedVoluntaryExit {
+    fn observed_validators(&self) -> Vec<u64> {
+        vec![self.message.validator_index]
+    }
+}
+
+impl<E: EthSpec> ObservableOperation<E> for ProposerSlashing {
+    fn observed_validators(&self) -> Vec<u64> {
+        vec![self.signed_header_1.message.proposer_index]
+    }
+}
+
+impl<E: EthSpec> ObservableOperation<E> for AttesterSlashing<E> {
+    fn observed_validators(&self) -> Vec<u64> {
+        let attestation_1_indices =
+            HashSet::<u64>::from_iter(self.attestation_1.attesting_indices.iter().copied());
+        let attestation_2_indices =
+            HashSet::<u64>::from_iter(self.attestation_2.attesting_indices.iter().copied());
+        attestation_1_indices
+            .intersection(&attestation_2_indices)
+            .copied()
+            .collect()
+    }
+}
+
+impl<T: ObservableOperation<E>, E: EthSpec> Default for ObservedOperations<T, E> {
+    fn default() -> Self {
+        Self {
+            observed_validator_indices: Mutex::new(HashSet::new()),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: ObservableOperation<E>, E: EthSpec> ObservedOperations<T, E> {
+    /// Verify `op` against `head_state` (including signatures) and record the validators it
+    /// concerns, so that future operations concerning only those validators are ignored.
+    ///
+    /// Returns `Ok(ObservationOutcome::AlreadyKnown)` without performing any verification if all
+    /// the validators concerned by `op` have been observed previously.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the operation-specific validation error if `op` is invalid. In this case the
+    /// validators are *not* recorded, so a later valid operation may still be accepted.
+    pub fn verify_and_observe(
+        &self,
+        op: T,
+        head_state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<ObservationOutcome<T>, T::Error> {
+        // Hold the lock for the duration of the verification so that two copies of the same
+        // operation arriving concurrently can't both be considered new.
+        let mut observed_validator_indices = self.observed_validator_indices.lock();
+
+        let new_validator_indices = op.observed_validators();
+
+        // For attester slashings, the spec only requires that *at least one* of the slashable
+        // indices has not been seen before. The same check covers exits and proposer slashings,
+        // which only ever concern a single validator.
+        if new_validator_indices
+            .iter()
+            .all(|index| observed_validator_indices.contains(index))
+        {
+            return Ok(ObservationOutcome::AlreadyKnown);
+        }
+
+        let verified_op = op.validate(head_state, spec)?;
+
+        observed_validator_indices.extend(new_validator_indices);
+
+        Ok(ObservationOutcome::New(verified_op))
+    }
+
+    /// Returns the number of validators for which an operation has been observed.
+    pub fn len(&self) -> usize {
+        self.observed_validator_indices.lock().len()
+    }
+
+    /// Returns `true` if no operations have been observed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(debug_assertions))]
+mod tests {
+    use super::*;
+    use types::{
+        test_utils::{
+            ProposerSlashingTestTask, TestingBeaconStateBuilder, TestingProposerSlashingBuilder,
+        },
+        MainnetEthSpec,
+    };
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn proposer_slashing_observation() {
+        let spec = E::default_spec();
+        let mut state_builder =
+            TestingBeaconStateBuilder::<E>::from_deterministic_keypairs(16, &spec);
+        state_builder.build_caches(&spec).unwrap();
+        let (state, keypairs) = state_builder.build();
+
+        let observed = ObservedOperations::<ProposerSlashing, E>::default();
+        assert!(observed.is_empty(), "should start empty");
+
+        let slashing = |proposer_index: u64| {
+            TestingProposerSlashingBuilder::double_vote::<E>(
+                ProposerSlashingTestTask::Valid,
+                proposer_index,
+                &keypairs[proposer_index as usize].sk,
+                &state.fork,
+                state.genesis_validators_root,
+                &spec,
+            )
+        };
+
+        let invalid = TestingProposerSlashingBuilder::double_vote::<E>(
+            ProposerSlashingTestTask::ProposalsIdentical,
+            0,
+            &keypairs[0].sk,
+            &state.fork,
+            state.genesis_validators_root,
+            &spec,
+        );
+        assert!(
+            observed.verify_and_observe(invalid, &state, &spec).is_err(),
+            "should reject invalid slashing"
+        );
+        assert!(observed.is_empty(), "should not observe invalid slashing");
+
+        assert!(
+            matches!(
+                observed.verify_and_observe(slashing(0), &state, &spec),
+                Ok(ObservationOutcome::New(_))
+            ),
+            "first slashing should be new"
+        );
+        assert!(
+            matches!(
+                observed.verify_and_observe(slashing(0), &state, &spec),
+                Ok(ObservationOutcome::AlreadyKnown)
+            ),
+            "second slashing for the same proposer should be known"
+        );
+        assert!(
+            matches!(
+                observed.verify_and_observe(slashing(1), &state, &spec),
+                Ok(ObservationOutcome::New(_))
+            ),
+            "slashing for another proposer should be new"
+        );
+        assert_eq!(observed.len(), 2, "should observe two proposers");
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
 };
 use genesis::interop_genesis_state;
 use rayon::prelude::*;
+use slasher::Slasher;
 use sloggers::{null::NullLoggerBuilder, Build};
 use slot_clock::TestingSlotClock;
 use state_processing::per_slot_processing;
@@ -98,6 +99,22 @@ impl<E: EthSpec> BeaconChainHarness<HarnessType<E>> {
         eth_spec_instance: E,
         keypairs: Vec<Keypair>,
         target_aggregators_per_committee: u64,
+    ) -> Self {
+        Self::new_with_target_aggregators_and_slasher(
+            eth_spec_instance,
+            keypairs,
+            target_aggregators_per_committee,
+            None,
+        )
+    }
+
+    /// Instantiate a new harness with `validator_count` initial validators, a custom
+    /// `target_aggregators_per_committee` spec value and an optional slasher.
+    pub fn new_with_target_aggregators_and_slasher(
+        eth_spec_instance: E,
+        keypairs: Vec<Keypair>,
+        target_aggregators_per_committee: u64,
+        slasher: Option<Arc<Slasher<E>>>,
     ) -> Self {
         let data_dir = tempdir().expect("should create temporary data_dir");
         let mut spec = E::default_spec();
@@ -106,7 +123,7 @@ impl<E: EthSpec> BeaconChainHarness<HarnessType<E>> {
 
         let log = NullLoggerBuilder.build().expect("logger should build");
 
-        let chain = BeaconChainBuilder::new(eth_spec_instance)
+        let mut builder = BeaconChainBuilder::new(eth_spec_instance)
             .logger(log.clone())
             .custom_spec(spec.clone())
             .store(Arc::new(MemoryStore::open()))
@@ -123,9 +140,13 @@ impl<E: EthSpec> BeaconChainHarness<HarnessType<E>> {
             .testing_slot_clock(HARNESS_SLOT_TIME)
             .expect("should configure testing slot clock")
             .reduced_tree_fork_choice()
-            .expect("should add fork choice to builder")
-            .build()
-            .expect("should build");
+            .expect("should add fork choice to builder");
+
+        if let Some(slasher) = slasher {
+            builder = builder.slasher(slasher);
+        }
+
+        let chain = builder.build().expect("should build");
 
         Self {
             spec: chain.spec.clone(),
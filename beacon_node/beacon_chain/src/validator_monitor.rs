@@ -0,0 +1,438 @@
+//! Monitors a set of validators, tracking their attestations and blocks as they are seen on the
+//! network and included in the chain.
+//!
+//! Each observation is exported as a Prometheus metric labelled with the validator. Once no more
+//! attestations can be included for an epoch, a summary of that epoch is logged for each validator.
+
+use crate::metrics;
+use parking_lot::RwLock;
+use rest_types::ValidatorId;
+use serde_derive::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use state_processing::common::get_indexed_attestation;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use types::{BeaconBlock, BeaconState, Epoch, EthSpec, IndexedAttestation, PublicKeyBytes};
+
+/// The number of epochs for which summaries are retained for each validator.
+///
+/// Must be large enough to hold the summaries of every epoch which is yet to be logged.
+pub const HISTORIC_EPOCHS: u64 = 4;
+
+/// Configures which validators are monitored by the `ValidatorMonitor`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMonitorConfig {
+    /// Monitor any validator which subscribes to attestation duties via the HTTP API.
+    pub auto_register: bool,
+    /// The public keys of validators to monitor.
+    pub pubkeys: Vec<PublicKeyBytes>,
+    /// The indices of validators to monitor.
+    pub indices: Vec<u64>,
+}
+
+/// The observations of a single monitored validator during a single epoch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EpochSummary {
+    /// The number of unaggregated attestations seen on gossip or the HTTP API.
+    pub unaggregated_attestations: usize,
+    /// The number of aggregated attestations including the validator seen on gossip or the HTTP
+    /// API.
+    pub aggregated_attestations: usize,
+    /// The number of imported blocks including an attestation from the validator.
+    pub attestation_block_inclusions: usize,
+    /// The smallest number of slots between the attestation and a block which included it.
+    pub min_inclusion_distance: Option<u64>,
+    /// True if an included attestation voted for the correct source checkpoint.
+    pub correct_source: bool,
+    /// True if an included attestation voted for the correct target checkpoint.
+    pub correct_target: bool,
+    /// True if an included attestation voted for the correct head block.
+    pub correct_head: bool,
+    /// The number of imported blocks proposed by the validator.
+    pub blocks: usize,
+}
+
+/// A single validator which is being monitored.
+struct MonitoredValidator {
+    /// Identifies the validator in logs and in the `validator` label of metrics.
+    id: String,
+    /// Observations for recent epochs.
+    ///
+    /// Behind a lock so that observations can be registered whilst holding only a read lock on
+    /// the `ValidatorMonitor`.
+    summaries: RwLock<HashMap<Epoch, EpochSummary>>,
+    /// The balance of the validator when the last summary was logged.
+    last_balance: Option<u64>,
+}
+
+impl MonitoredValidator {
+    fn new(id: String) -> Self {
+        Self {
+            id,
+            summaries: RwLock::new(HashMap::new()),
+            last_balance: None,
+        }
+    }
+
+    /// Updates the summary for `epoch`, creating it if required.
+    fn touch_summary<F: FnOnce(&mut EpochSummary)>(&self, epoch: Epoch, func: F) {
+        let mut summaries = self.summaries.write();
+
+        func(summaries.entry(epoch).or_default());
+
+        // Drop any summaries which are too old to be useful.
+        let oldest_epoch = epoch.saturating_sub(HISTORIC_EPOCHS);
+        summaries.retain(|summary_epoch, _| *summary_epoch >= oldest_epoch);
+    }
+}
+
+/// Tracks the attestations and blocks of a set of validators, logging and exporting metrics about
+/// them.
+pub struct ValidatorMonitor<T> {
+    /// Monitored validators, keyed by their index.
+    validators: HashMap<u64, MonitoredValidator>,
+    /// Public keys of monitored validators whose index is not yet known.
+    unknown_pubkeys: HashSet<PublicKeyBytes>,
+    /// If true, validators which subscribe to duties via the HTTP API are monitored.
+    auto_register: bool,
+    /// The most recent epoch for which summaries were logged.
+    last_summarised_epoch: Option<Epoch>,
+    log: Logger,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: EthSpec> ValidatorMonitor<T> {
+    pub fn new(config: ValidatorMonitorConfig, log: Logger) -> Self {
+        let mut monitor = Self {
+            validators: HashMap::new(),
+            unknown_pubkeys: config.pubkeys.into_iter().collect(),
+            auto_register: config.auto_register,
+            last_summarised_epoch: None,
+            log,
+            _phantom: PhantomData,
+        };
+
+        for index in config.indices {
+            monitor.add_validator_index(index);
+        }
+
+        monitor
+    }
+
+    /// Returns `true` if no validators are currently monitored.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty() && self.unknown_pubkeys.is_empty()
+    }
+
+    /// Returns the summary of `epoch` for the validator at `index`, if any.
+    pub fn get_summary(&self, index: u64, epoch: Epoch) -> Option<EpochSummary> {
+        self.validators
+            .get(&index)
+            .and_then(|validator| validator.summaries.read().get(&epoch).cloned())
+    }
+
+    /// Starts monitoring the validator at `index`, if it is not already monitored.
+    fn add_validator(&mut self, index: u64, id: String) {
+        let log = &self.log;
+        self.validators.entry(index).or_insert_with(|| {
+            info!(
+                log,
+                "Started monitoring validator";
+                "validator" => &id,
+                "index" => index,
+            );
+            MonitoredValidator::new(id)
+        });
+    }
+
+    /// Starts monitoring the validator at `index`.
+    pub fn add_validator_index(&mut self, index: u64) {
+        self.add_validator(index, index.to_string());
+    }
+
+    /// Starts monitoring the validator at `index` if automatic registration is enabled.
+    ///
+    /// Called when a validator subscribes to its duties via the HTTP API.
+    pub fn auto_register_local_validator(&mut self, index: u64) {
+        if self.auto_register {
+            self.add_validator_index(index);
+        }
+    }
+
+    /// Registers an unaggregated attestation which was verified for gossip.
+    pub fn register_unaggregated_attestation(&self, indexed_attestation: &IndexedAttestation<T>) {
+        let data = &indexed_attestation.data;
+
+        for index in indexed_attestation.attesting_indices.iter() {
+            if let Some(validator) = self.validators.get(index) {
+                metrics::inc_counter_vec(
+                    &metrics::VALIDATOR_MONITOR_UNAGGREGATED_ATTESTATION_TOTAL,
+                    &[&validator.id],
+                );
+
+                info!(
+                    self.log,
+                    "Unaggregated attestation";
+                    "validator" => &validator.id,
+                    "slot" => data.slot,
+                    "head" => format!("{:?}", data.beacon_block_root),
+                );
+
+                validator.touch_summary(data.target.epoch, |summary| {
+                    summary.unaggregated_attestations += 1
+                });
+            }
+        }
+    }
+
+    /// Registers an aggregated attestation which was verified for gossip.
+    pub fn register_aggregated_attestation(&self, indexed_attestation: &IndexedAttestation<T>) {
+        let data = &indexed_attestation.data;
+
+        for index in indexed_attestation.attesting_indices.iter() {
+            if let Some(validator) = self.validators.get(index) {
+                metrics::inc_counter_vec(
+                    &metrics::VALIDATOR_MONITOR_AGGREGATED_ATTESTATION_TOTAL,
+                    &[&validator.id],
+                );
+
+                info!(
+                    self.log,
+                    "Attestation included in aggregate";
+                    "validator" => &validator.id,
+                    "slot" => data.slot,
+                    "head" => format!("{:?}", data.beacon_block_root),
+                );
+
+                validator.touch_summary(data.target.epoch, |summary| {
+                    summary.aggregated_attestations += 1
+                });
+            }
+        }
+    }
+
+    /// Registers an imported `block` and its post-`state`, recording the proposer and any
+    /// attestations from monitored validators.
+    ///
+    /// The committee caches of `state` must be built for the previous and current epochs.
+    pub fn register_block(&self, block: &BeaconBlock<T>, state: &BeaconState<T>) {
+        if let Some(validator) = self.validators.get(&block.proposer_index) {
+            metrics::inc_counter_vec(
+                &metrics::VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL,
+                &[&validator.id],
+            );
+
+            info!(
+                self.log,
+                "Block from monitored validator";
+                "validator" => &validator.id,
+                "slot" => block.slot,
+            );
+
+            validator.touch_summary(block.slot.epoch(T::slots_per_epoch()), |summary| {
+                summary.blocks += 1
+            });
+        }
+
+        for attestation in block.body.attestations.iter() {
+            let data = &attestation.data;
+            let indexed_attestation = match state
+                .get_beacon_committee(data.slot, data.index)
+                .map_err(|e| format!("{:?}", e))
+                .and_then(|committee| {
+                    get_indexed_attestation(committee.committee, attestation)
+                        .map_err(|e| format!("{:?}", e))
+                }) {
+                Ok(indexed_attestation) => indexed_attestation,
+                Err(e) => {
+                    warn!(
+                        self.log,
+                        "Unable to index attestation for monitoring";
+                        "error" => e,
+                        "block_slot" => block.slot,
+                    );
+                    continue;
+                }
+            };
+
+            let inclusion_distance = block.slot.as_u64().saturating_sub(data.slot.as_u64());
+            let correct_target = state
+                .get_block_root_at_epoch(data.target.epoch)
+                .map_or(false, |root| *root == data.target.root);
+            let correct_head = state
+                .get_block_root(data.slot)
+                .map_or(false, |root| *root == data.beacon_block_root);
+
+            for index in indexed_attestation.attesting_indices.iter() {
+                if let Some(validator) = self.validators.get(index) {
+                    metrics::inc_counter_vec(
+                        &metrics::VALIDATOR_MONITOR_ATTESTATION_IN_BLOCK_TOTAL,
+                        &[&validator.id],
+                    );
+
+                    info!(
+                        self.log,
+                        "Attestation included in block";
+                        "validator" => &validator.id,
+                        "slot" => data.slot,
+                        "inclusion_distance" => inclusion_distance,
+                        "correct_head" => correct_head,
+                        "correct_target" => correct_target,
+                    );
+
+                    validator.touch_summary(data.target.epoch, |summary| {
+                        summary.attestation_block_inclusions += 1;
+                        summary.min_inclusion_distance = Some(
+                            summary
+                                .min_inclusion_distance
+                                .map_or(inclusion_distance, |distance| {
+                                    std::cmp::min(distance, inclusion_distance)
+                                }),
+                        );
+                        // A block may only include attestations with the correct source.
+                        summary.correct_source = true;
+                        summary.correct_target |= correct_target;
+                        summary.correct_head |= correct_head;
+                    });
+                }
+            }
+        }
+    }
+
+    /// Processes a new head `state` which is in a later epoch than the previous head.
+    ///
+    /// Finds the index of any monitored public keys which have been added to the registry, then
+    /// logs the summary of the latest epoch for which attestations may no longer be included,
+    /// along with the change in balance caused by its rewards and penalties.
+    pub fn process_valid_state(&mut self, state: &BeaconState<T>) {
+        if !self.unknown_pubkeys.is_empty() {
+            let found = state
+                .validators
+                .iter()
+                .enumerate()
+                .filter(|(_, validator)| self.unknown_pubkeys.contains(&validator.pubkey))
+                .map(|(index, validator)| (index as u64, validator.pubkey.clone()))
+                .collect::<Vec<_>>();
+
+            for (index, pubkey) in found {
+                self.unknown_pubkeys.remove(&pubkey);
+                self.add_validator(index, ValidatorId::PublicKey(pubkey).to_string());
+            }
+        }
+
+        // Attestations from `epoch` can be included until the end of the following epoch. The
+        // rewards for `epoch` are applied at the start of `epoch + 2`.
+        let epoch = match state.current_epoch().as_u64().checked_sub(2) {
+            Some(epoch) => Epoch::new(epoch),
+            None => return,
+        };
+
+        if self
+            .last_summarised_epoch
+            .map_or(false, |last_epoch| last_epoch >= epoch)
+        {
+            return;
+        }
+        self.last_summarised_epoch = Some(epoch);
+
+        for (index, validator) in self.validators.iter_mut() {
+            let (balance, effective_balance, is_active) = match (
+                state.balances.get(*index as usize),
+                state.validators.get(*index as usize),
+            ) {
+                (Some(balance), Some(v)) => (*balance, v.effective_balance, v.is_active_at(epoch)),
+                _ => continue,
+            };
+
+            let id = validator.id.as_str();
+            let balance_change = validator
+                .last_balance
+                .map(|last_balance| balance as i64 - last_balance as i64);
+            validator.last_balance = Some(balance);
+
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_BALANCE_GWEI,
+                &[id],
+                balance as i64,
+            );
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_EFFECTIVE_BALANCE_GWEI,
+                &[id],
+                effective_balance as i64,
+            );
+
+            if !is_active {
+                continue;
+            }
+
+            let summary = validator
+                .summaries
+                .read()
+                .get(&epoch)
+                .cloned()
+                .unwrap_or_default();
+            let included = summary.min_inclusion_distance.is_some();
+
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_EPOCH_ATTESTATION_INCLUDED,
+                &[id],
+                included as i64,
+            );
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_EPOCH_INCLUSION_DISTANCE,
+                &[id],
+                summary.min_inclusion_distance.unwrap_or(0) as i64,
+            );
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_EPOCH_CORRECT_SOURCE,
+                &[id],
+                summary.correct_source as i64,
+            );
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_EPOCH_CORRECT_TARGET,
+                &[id],
+                summary.correct_target as i64,
+            );
+            metrics::set_gauge_vec(
+                &metrics::VALIDATOR_MONITOR_EPOCH_CORRECT_HEAD,
+                &[id],
+                summary.correct_head as i64,
+            );
+            if let Some(balance_change) = balance_change {
+                metrics::set_gauge_vec(
+                    &metrics::VALIDATOR_MONITOR_EPOCH_BALANCE_CHANGE_GWEI,
+                    &[id],
+                    balance_change,
+                );
+            }
+
+            if included {
+                info!(
+                    self.log,
+                    "Previous epoch attestation success";
+                    "validator" => id,
+                    "epoch" => epoch,
+                    "inclusion_distance" => summary.min_inclusion_distance,
+                    "correct_source" => summary.correct_source,
+                    "correct_target" => summary.correct_target,
+                    "correct_head" => summary.correct_head,
+                    "blocks" => summary.blocks,
+                    "balance" => balance,
+                    "balance_change" => balance_change,
+                );
+            } else {
+                warn!(
+                    self.log,
+                    "Previous epoch attestation missing";
+                    "validator" => id,
+                    "epoch" => epoch,
+                    "unaggregated_attestations" => summary.unaggregated_attestations,
+                    "aggregated_attestations" => summary.aggregated_attestations,
+                    "blocks" => summary.blocks,
+                    "balance" => balance,
+                    "balance_change" => balance_change,
+                );
+            }
+        }
+    }
+}
@@ -8,8 +8,12 @@ use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, HarnessType},
     BeaconChain, BeaconChainTypes,
 };
+use slasher::{Config as SlasherConfig, Slasher};
+use slog::Logger;
 use state_processing::per_slot_processing;
+use std::sync::Arc;
 use store::Store;
+use tempfile::tempdir;
 use tree_hash::TreeHash;
 use types::{
     test_utils::generate_deterministic_keypair, AggregateSignature, Attestation, EthSpec, Hash256,
@@ -1007,3 +1011,79 @@ fn attestation_that_skips_epochs() {
         "should gossip verify attestation that skips slots"
     );
 }
+
+/// Ensures that a conflicting attestation which is rejected as a duplicate on gossip is still
+/// sent to the slasher.
+#[test]
+fn conflicting_unaggregated_attestations_sent_to_slasher() {
+    let slasher_dir = tempdir().expect("should create temporary slasher dir");
+    let slasher = Arc::new(
+        Slasher::open(
+            SlasherConfig::new(slasher_dir.path().join("slasher_db")),
+            Logger::root(slog::Discard, slog::o!()),
+        )
+        .expect("should open slasher"),
+    );
+    let harness = BeaconChainHarness::new_with_target_aggregators_and_slasher(
+        MainnetEthSpec,
+        KEYPAIRS[..].to_vec(),
+        4,
+        Some(slasher.clone()),
+    );
+    let chain = &harness.chain;
+
+    // Produce a block without attestations, so that there are two known blocks to vote for and
+    // no validator has been observed in this epoch.
+    harness.advance_slot();
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(vec![]),
+    );
+    harness.advance_slot();
+
+    let current_slot = chain.slot().expect("should get slot");
+    let current_epoch = chain.epoch().expect("should get epoch");
+    let head = chain.head().expect("should get head");
+
+    let (attestation, validator_index, validator_committee_index, validator_sk) =
+        get_valid_unaggregated_attestation(chain);
+
+    let mut conflicting = chain
+        .produce_unaggregated_attestation(current_slot, attestation.data.index)
+        .expect("should produce attestation");
+    conflicting.data.beacon_block_root = chain.genesis_block_root;
+    conflicting
+        .sign(
+            &validator_sk,
+            validator_committee_index,
+            &head.beacon_state.fork,
+            chain.genesis_validators_root,
+            &chain.spec,
+        )
+        .expect("should sign attestation");
+    assert_ne!(attestation.data, conflicting.data);
+
+    assert!(
+        chain
+            .verify_unaggregated_attestation_for_gossip(attestation.clone())
+            .is_ok(),
+        "first attestation should be verified"
+    );
+    assert!(
+        matches!(
+            chain.verify_unaggregated_attestation_for_gossip(conflicting.clone()),
+            Err(AttnError::PriorAttestationKnown { validator_index: i, .. })
+                if i == validator_index as u64
+        ),
+        "conflicting attestation should be rejected as a duplicate"
+    );
+
+    slasher
+        .process_queued(current_epoch)
+        .expect("should process slasher queue");
+    let slashings = slasher.get_attester_slashings();
+    assert_eq!(slashings.len(), 1, "slasher should find the double vote");
+    assert_eq!(slashings[0].attestation_1.data, attestation.data);
+    assert_eq!(slashings[0].attestation_2.data, conflicting.data);
+}
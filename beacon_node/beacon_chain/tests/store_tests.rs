@@ -4,17 +4,23 @@
 extern crate lazy_static;
 
 use beacon_chain::attestation_verification::Error as AttnError;
+use beacon_chain::builder::BeaconChainBuilder;
+use beacon_chain::migrate::{BlockingMigrator, Migrate};
 use beacon_chain::test_utils::{
-    AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
+    AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType, HARNESS_GENESIS_TIME,
+    HARNESS_SLOT_TIME,
 };
 use beacon_chain::BeaconSnapshot;
 use beacon_chain::StateSkipConfig;
+use genesis::interop_genesis_state;
 use rand::Rng;
 use sloggers::{null::NullLoggerBuilder, Build};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 use store::{
+    chunked_iter::ChunkedVectorIter,
+    chunked_vector::BlockRoots,
     iter::{BlockRootsIterator, StateRootsIterator},
     DiskStore, Store, StoreConfig,
 };
@@ -1277,6 +1283,241 @@ fn prunes_skipped_slots_states() {
     }
 }
 
+#[test]
+fn weak_subjectivity_sync() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Use the finalized checkpoint of the full chain as the weak subjectivity checkpoint.
+    let finalized_checkpoint = harness
+        .chain
+        .head_info()
+        .expect("should get head info")
+        .finalized_checkpoint;
+    let chain_dump = harness.chain.chain_dump().unwrap();
+    let anchor = chain_dump
+        .iter()
+        .find(|snapshot| snapshot.beacon_block_root == finalized_checkpoint.root)
+        .expect("finalized block should be in chain dump")
+        .clone();
+    let anchor_slot = anchor.beacon_block.slot();
+
+    let spec = MinimalEthSpec::default_spec();
+    let genesis_state = interop_genesis_state::<E>(
+        &KEYPAIRS[0..LOW_VALIDATOR_COUNT],
+        HARNESS_GENESIS_TIME,
+        &spec,
+    )
+    .expect("should generate interop state");
+
+    let log = NullLoggerBuilder.build().expect("logger should build");
+    let ws_db_path = tempdir().unwrap();
+    let ws_data_dir = tempdir().unwrap();
+    let ws_store = get_store(&ws_db_path);
+
+    let ws_chain = BeaconChainBuilder::new(MinimalEthSpec)
+        .logger(log.clone())
+        .custom_spec(spec)
+        .store(ws_store.clone())
+        .store_migrator(<BlockingMigrator<_> as Migrate<_, E>>::new(
+            ws_store.clone(),
+            log,
+        ))
+        .data_dir(ws_data_dir.path().to_path_buf())
+        .weak_subjectivity_state(anchor.beacon_state, anchor.beacon_block, genesis_state)
+        .expect("should start from weak subjectivity state")
+        .dummy_eth1_backend()
+        .expect("should build dummy backend")
+        .null_event_handler()
+        .testing_slot_clock(HARNESS_SLOT_TIME)
+        .expect("should configure testing slot clock")
+        .reduced_tree_fork_choice()
+        .expect("should add fork choice to builder")
+        .build()
+        .expect("should build");
+
+    assert_eq!(ws_store.get_split_slot(), anchor_slot);
+    assert_eq!(
+        ws_chain.genesis_block_root,
+        harness.chain.genesis_block_root
+    );
+    assert_eq!(
+        ws_chain.head_info().unwrap().finalized_checkpoint,
+        finalized_checkpoint,
+        "should report the anchor as finalized"
+    );
+
+    // Backfill the blocks prior to the anchor, one epoch per batch, newest batch first.
+    let anchor_info = ws_store.get_anchor_info().expect("should have anchor info");
+    assert_eq!(anchor_info.oldest_block_slot, anchor_slot);
+    assert!(!anchor_info.block_backfill_complete());
+
+    let historical_blocks = chain_dump
+        .iter()
+        .filter(|snapshot| snapshot.beacon_block.slot() < anchor_slot)
+        .map(|snapshot| snapshot.beacon_block.clone())
+        .collect::<Vec<_>>();
+    let batches = historical_blocks
+        .chunks(E::slots_per_epoch() as usize)
+        .rev()
+        .collect::<Vec<_>>();
+
+    assert!(
+        ws_chain
+            .import_historical_block_batch(batches[1].to_vec())
+            .is_err(),
+        "should reject a batch which does not end at the parent of the oldest block"
+    );
+
+    for batch in &batches {
+        ws_chain
+            .import_historical_block_batch(batch.to_vec())
+            .expect("should import historical batch");
+    }
+
+    assert!(ws_store
+        .get_anchor_info()
+        .expect("should have anchor info")
+        .block_backfill_complete());
+
+    for block in &historical_blocks {
+        assert_eq!(
+            ws_store
+                .get_block(&block.canonical_root())
+                .unwrap()
+                .as_ref(),
+            Some(block),
+            "historical block should be stored"
+        );
+    }
+
+    // The frozen block roots prior to the anchor should match the original chain.
+    let backfilled_block_roots = ChunkedVectorIter::<BlockRoots, E>::new(
+        ws_store.clone(),
+        0,
+        anchor_slot,
+        &MinimalEthSpec::default_spec(),
+    )
+    .map(|(_, block_root)| block_root)
+    .collect::<Vec<_>>();
+    let expected_block_roots = harness
+        .chain
+        .rev_iter_block_roots()
+        .unwrap()
+        .filter(|(_, slot)| *slot < anchor_slot)
+        .map(|(block_root, _)| block_root)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect::<Vec<_>>();
+    assert_eq!(backfilled_block_roots, expected_block_roots);
+
+    // Import the blocks after the anchor and check that the new chain reaches the same head.
+    ws_chain
+        .slot_clock
+        .set_slot(harness.chain.slot().unwrap().as_u64());
+
+    for snapshot in chain_dump
+        .into_iter()
+        .filter(|snapshot| snapshot.beacon_block.slot() > anchor_slot)
+    {
+        ws_chain
+            .process_block(snapshot.beacon_block)
+            .expect("should import block after anchor");
+    }
+    ws_chain.fork_choice().expect("should run fork choice");
+
+    assert_eq!(
+        ws_chain.head().unwrap().beacon_block_root,
+        harness.chain.head().unwrap().beacon_block_root
+    );
+}
+
+#[test]
+fn weak_subjectivity_sync_skipped_boundary_slot() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    // Produce blocks up to the slot prior to an epoch boundary, then skip the boundary slot.
+    let boundary_slot = Epoch::new(2).start_slot(E::slots_per_epoch());
+    harness.extend_chain(
+        boundary_slot.as_usize() - 1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness.advance_slot();
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize * 4,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // The checkpoint block for the boundary epoch is the last block prior to the skipped slot.
+    let chain_dump = harness.chain.chain_dump().unwrap();
+    let anchor = chain_dump
+        .iter()
+        .find(|snapshot| snapshot.beacon_block.slot() == boundary_slot - 1)
+        .expect("should have block prior to boundary")
+        .clone();
+    assert_eq!(
+        *harness
+            .chain
+            .head()
+            .unwrap()
+            .beacon_state
+            .get_block_root(boundary_slot)
+            .unwrap(),
+        anchor.beacon_block_root,
+        "boundary slot should be skipped"
+    );
+    assert_ne!(anchor.beacon_state.slot % E::slots_per_epoch(), 0);
+
+    let spec = MinimalEthSpec::default_spec();
+    let genesis_state = interop_genesis_state::<E>(
+        &KEYPAIRS[0..LOW_VALIDATOR_COUNT],
+        HARNESS_GENESIS_TIME,
+        &spec,
+    )
+    .expect("should generate interop state");
+
+    let log = NullLoggerBuilder.build().expect("logger should build");
+    let ws_db_path = tempdir().unwrap();
+    let ws_data_dir = tempdir().unwrap();
+    let ws_store = get_store(&ws_db_path);
+
+    // Hot state summaries require the split to lie on an epoch boundary, so the checkpoint must
+    // be rejected rather than anchoring the database at an unaligned slot.
+    let error = BeaconChainBuilder::new(MinimalEthSpec)
+        .logger(log.clone())
+        .custom_spec(spec)
+        .store(ws_store.clone())
+        .store_migrator(<BlockingMigrator<_> as Migrate<_, E>>::new(
+            ws_store.clone(),
+            log,
+        ))
+        .data_dir(ws_data_dir.path().to_path_buf())
+        .weak_subjectivity_state(anchor.beacon_state, anchor.beacon_block, genesis_state)
+        .err()
+        .expect("should reject checkpoint prior to skipped boundary slot");
+
+    assert!(
+        error.contains("not at the start of an epoch"),
+        "unexpected error: {}",
+        error
+    );
+    assert_eq!(ws_store.get_split_slot(), 0);
+}
+
 /// Check that the head state's slot matches `expected_slot`.
 fn check_slot(harness: &TestHarness, expected_slot: u64) {
     let state = &harness.chain.head().expect("should get head").beacon_state;
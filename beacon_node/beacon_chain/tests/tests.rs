@@ -14,7 +14,9 @@ use state_processing::{
     per_slot_processing, per_slot_processing::Error as SlotProcessingError, EpochProcessingError,
 };
 use store::Store;
-use types::{BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot};
+use types::{
+    BeaconStateError, Epoch, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot,
+};
 
 // Should ideally be divisible by 3.
 pub const VALIDATOR_COUNT: usize = 24;
@@ -180,6 +182,87 @@ fn chooses_fork() {
     );
 }
 
+#[test]
+fn finds_common_ancestor_of_fork() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let delay = MinimalEthSpec::default_spec().min_attestation_inclusion_delay as usize;
+
+    let honest_validators: Vec<usize> = (0..two_thirds).collect();
+    let faulty_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+
+    let initial_blocks = delay + 1;
+    let honest_fork_blocks = delay + 1;
+    let faulty_fork_blocks = delay + 2;
+
+    let common_head = harness.extend_chain(
+        initial_blocks,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let (honest_head, faulty_head) = harness.generate_two_forks_by_skipping_a_block(
+        &honest_validators,
+        &faulty_validators,
+        honest_fork_blocks,
+        faulty_fork_blocks,
+    );
+
+    let head = harness.chain.head().expect("should get head");
+    assert_eq!(head.beacon_block_root, honest_head);
+
+    let (ancestor_root, ancestor_slot, orphaned) = harness
+        .chain
+        .find_common_ancestor(faulty_head, &head)
+        .expect("should search for ancestor")
+        .expect("should find ancestor");
+
+    assert_eq!(
+        ancestor_root, common_head,
+        "should find the last shared block"
+    );
+    assert_eq!(ancestor_slot, Slot::from(initial_blocks));
+    assert_eq!(
+        orphaned.len(),
+        faulty_fork_blocks,
+        "every block on the faulty fork should be orphaned"
+    );
+    assert_eq!(orphaned.first(), Some(&faulty_head));
+}
+
+#[test]
+fn validator_monitor_records_included_attestations() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let validator_index = 0;
+
+    harness
+        .chain
+        .validator_monitor
+        .write()
+        .add_validator_index(validator_index);
+
+    harness.extend_chain(
+        MinimalEthSpec::slots_per_epoch() as usize * 3,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let validator_monitor = harness.chain.validator_monitor.read();
+    let summary = validator_monitor
+        .get_summary(validator_index, Epoch::new(1))
+        .expect("should have a summary for the validator");
+
+    assert_eq!(
+        summary.min_inclusion_distance,
+        Some(MinimalEthSpec::default_spec().min_attestation_inclusion_delay),
+        "attestation should be included in the next block"
+    );
+    assert!(summary.correct_source);
+    assert!(summary.correct_target);
+    assert!(summary.correct_head);
+}
+
 #[test]
 fn finalizes_with_full_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
@@ -1,9 +1,11 @@
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
+use crate::slasher_service::spawn_slasher_service;
 use crate::Client;
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::{CachingEth1Backend, Eth1Chain},
+    events::ServerSentEventHandler,
     migrate::{BackgroundMigrator, Migrate, NullMigrator},
     slot_clock::{SlotClock, SystemTimeSlotClock},
     store::{DiskStore, MemoryStore, SimpleDiskStore, Store, StoreConfig},
@@ -15,18 +17,24 @@ use eth2_config::Eth2Config;
 use eth2_libp2p::NetworkGlobals;
 use genesis::{interop_genesis_state, Eth1GenesisService};
 use network::{NetworkConfig, NetworkMessage, NetworkService};
-use slog::info;
+use remote_beacon_node::RemoteBeaconNode;
+use slasher::Slasher;
+use slog::{info, o};
 use ssz::Decode;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::UnboundedSender;
-use types::{test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec};
-use websocket_server::{Config as WebSocketConfig, WebSocketSender};
+use types::{
+    test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec, SignedBeaconBlock,
+};
 
 /// Interval between polling the eth1 node for genesis information.
 pub const ETH1_GENESIS_UPDATE_INTERVAL_MILLIS: u64 = 7_000;
+/// Timeout for requests to a remote beacon node when downloading a checkpoint. This is generous
+/// since the finalized state may be large.
+pub const CHECKPOINT_SYNC_HTTP_TIMEOUT_SECS: u64 = 180;
 
 /// Builds a `Client` instance.
 ///
@@ -55,7 +63,6 @@ pub struct ClientBuilder<T: BeaconChainTypes> {
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     network_send: Option<UnboundedSender<NetworkMessage<T::EthSpec>>>,
     http_listen_addr: Option<SocketAddr>,
-    websocket_listen_addr: Option<SocketAddr>,
     eth_spec_instance: T::EthSpec,
 }
 
@@ -89,7 +96,6 @@ where
             network_globals: None,
             network_send: None,
             http_listen_addr: None,
-            websocket_listen_addr: None,
             eth_spec_instance,
         }
     }
@@ -120,6 +126,8 @@ where
         let eth_spec_instance = self.eth_spec_instance.clone();
         let data_dir = config.data_dir.clone();
         let disabled_forks = config.disabled_forks.clone();
+        let slasher_config = config.slasher.clone();
+        let validator_monitor_config = config.validator_monitor.clone();
 
         let store =
             store.ok_or_else(|| "beacon_chain_start_method requires a store".to_string())?;
@@ -137,7 +145,17 @@ where
             .store_migrator(store_migrator)
             .data_dir(data_dir)
             .custom_spec(spec.clone())
-            .disabled_forks(disabled_forks);
+            .disabled_forks(disabled_forks)
+            .validator_monitor_config(validator_monitor_config);
+
+        let builder = if let Some(slasher_config) = slasher_config {
+            let slasher =
+                Slasher::open(slasher_config, context.log.new(o!("service" => "slasher")))
+                    .map_err(|e| format!("Unable to open slasher: {:?}", e))?;
+            builder.slasher(Arc::new(slasher))
+        } else {
+            builder
+        };
 
         let chain_exists = builder
             .store_contains_beacon_chain()
@@ -181,11 +199,80 @@ where
 
                 builder.genesis_state(genesis_state).map(|v| (v, None))?
             }
+            ClientGenesis::WeakSubjSszBytes {
+                anchor_state_bytes,
+                anchor_block_bytes,
+                genesis_state_bytes,
+            } => {
+                info!(
+                    context.log,
+                    "Starting from known checkpoint state";
+                );
+
+                let anchor_state = BeaconState::from_ssz_bytes(&anchor_state_bytes)
+                    .map_err(|e| format!("Unable to parse checkpoint state SSZ: {:?}", e))?;
+                let anchor_block = SignedBeaconBlock::from_ssz_bytes(&anchor_block_bytes)
+                    .map_err(|e| format!("Unable to parse checkpoint block SSZ: {:?}", e))?;
+                let genesis_state = BeaconState::from_ssz_bytes(&genesis_state_bytes)
+                    .map_err(|e| format!("Unable to parse genesis state SSZ: {:?}", e))?;
+
+                builder
+                    .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
+                    .map(|v| (v, None))?
+            }
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                genesis_state_bytes,
+            } => {
+                info!(
+                    context.log,
+                    "Downloading finalized checkpoint";
+                    "remote_url" => &url,
+                );
+
+                let remote = RemoteBeaconNode::<TEthSpec>::new_with_timeout(
+                    url,
+                    Duration::from_secs(CHECKPOINT_SYNC_HTTP_TIMEOUT_SECS),
+                )
+                .map_err(|e| format!("Unable to parse checkpoint sync URL: {:?}", e))?;
+
+                let head = remote
+                    .http
+                    .beacon()
+                    .get_head()
+                    .await
+                    .map_err(|e| format!("Unable to get head from remote node: {:?}", e))?;
+                let (anchor_block, _) = remote
+                    .http
+                    .beacon()
+                    .get_block_by_root(head.finalized_block_root)
+                    .await
+                    .map_err(|e| format!("Unable to get finalized block: {:?}", e))?;
+                let (anchor_state, _) = remote
+                    .http
+                    .beacon()
+                    .get_state_by_root(anchor_block.state_root())
+                    .await
+                    .map_err(|e| format!("Unable to get finalized state: {:?}", e))?;
+                let genesis_state = BeaconState::from_ssz_bytes(&genesis_state_bytes)
+                    .map_err(|e| format!("Unable to parse genesis state SSZ: {:?}", e))?;
+
+                info!(
+                    context.log,
+                    "Downloaded finalized checkpoint";
+                    "block_root" => format!("{:?}", head.finalized_block_root),
+                    "slot" => anchor_block.slot(),
+                );
+
+                builder
+                    .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
+                    .map(|v| (v, None))?
+            }
             ClientGenesis::DepositContract => {
                 info!(
                     context.log,
                     "Waiting for eth2 genesis from eth1";
-                    "eth1_endpoint" => &config.eth1.endpoint,
+                    "eth1_endpoints" => format!("{:?}", &config.eth1.endpoints),
                     "contract_deploy_block" => config.eth1.deposit_contract_deploy_block,
                     "deposit_contract" => &config.eth1.deposit_contract_address
                 );
@@ -290,12 +377,18 @@ where
             network_chan: network_send,
         };
 
+        let deposit_contract = rest_api::DepositContractInfo {
+            chain_id: client_config.eth1.chain_id,
+            address: client_config.eth1.deposit_contract_address.clone(),
+        };
+
         let log = context.log.clone();
         let (exit_channel, listening_addr) = context.runtime_handle.enter(|| {
             rest_api::start_server(
                 &client_config.rest_api,
                 beacon_chain,
                 network_info,
+                deposit_contract,
                 client_config
                     .create_db_path()
                     .map_err(|_| "unable to read data dir")?,
@@ -352,6 +445,34 @@ where
         Ok(self)
     }
 
+    /// Immediately starts the service that periodically runs the slasher, if the beacon chain
+    /// has one.
+    pub fn slasher_service(mut self) -> Result<Self, String> {
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or_else(|| "slasher_service requires a runtime_context")?
+            .service_context("slasher_service".into());
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or_else(|| "slasher_service requires a beacon chain")?;
+
+        if beacon_chain.slasher.is_none() {
+            return Ok(self);
+        }
+
+        let runtime_handle = context.runtime_handle.clone();
+        let exit_channel = context
+            .runtime_handle
+            .enter(|| spawn_slasher_service(beacon_chain, runtime_handle, context.log.clone()))
+            .map_err(|e| format!("Unable to start slasher service: {}", e))?;
+
+        self.exit_channels.push(exit_channel);
+
+        Ok(self)
+    }
+
     /// Consumers the builder, returning a `Client` if all necessary components have been
     /// specified.
     ///
@@ -364,7 +485,6 @@ where
             beacon_chain: self.beacon_chain,
             network_globals: self.network_globals,
             http_listen_addr: self.http_listen_addr,
-            websocket_listen_addr: self.websocket_listen_addr,
             _exit_channels: self.exit_channels,
         }
     }
@@ -418,7 +538,7 @@ impl<TStore, TStoreMigrator, TSlotClock, TEth1Backend, TEthSpec>
             TSlotClock,
             TEth1Backend,
             TEthSpec,
-            WebSocketSender<TEthSpec>,
+            ServerSentEventHandler<TEthSpec>,
         >,
     >
 where
@@ -428,34 +548,11 @@ where
     TEth1Backend: Eth1ChainBackend<TEthSpec, TStore> + 'static,
     TEthSpec: EthSpec + 'static,
 {
-    /// Specifies that the `BeaconChain` should publish events using the WebSocket server.
-    pub fn websocket_event_handler(mut self, config: WebSocketConfig) -> Result<Self, String> {
-        let context = self
-            .runtime_context
-            .as_ref()
-            .ok_or_else(|| "websocket_event_handler requires a runtime_context")?
-            .service_context("ws".into());
-
-        let (sender, exit_channel, listening_addr): (
-            WebSocketSender<TEthSpec>,
-            Option<_>,
-            Option<_>,
-        ) = if config.enabled {
-            let (sender, exit, listening_addr) = context
-                .runtime_handle
-                .enter(|| websocket_server::start_server(&config, &context.log))?;
-            (sender, Some(exit), Some(listening_addr))
-        } else {
-            (WebSocketSender::dummy(), None, None)
-        };
-
-        if let Some(channel) = exit_channel {
-            self.exit_channels.push(channel);
-        }
-        self.event_handler = Some(sender);
-        self.websocket_listen_addr = listening_addr;
-
-        Ok(self)
+    /// Specifies that the `BeaconChain` should publish events to subscribers of the HTTP API
+    /// event stream.
+    pub fn server_sent_event_handler(mut self) -> Self {
+        self.event_handler = Some(ServerSentEventHandler::default());
+        self
     }
 }
 
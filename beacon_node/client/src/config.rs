@@ -1,3 +1,4 @@
+use beacon_chain::ValidatorMonitorConfig;
 use network::NetworkConfig;
 use serde_derive::{Deserialize, Serialize};
 use std::fs;
@@ -29,6 +30,21 @@ pub enum ClientGenesis {
     /// We include the bytes instead of the `BeaconState<E>` because the `EthSpec` type
     /// parameter would be very annoying.
     SszBytes { genesis_state_bytes: Vec<u8> },
+    /// Starts from a finalized weak subjectivity checkpoint, loaded from SSZ-encoded
+    /// `BeaconState` and `SignedBeaconBlock` bytes.
+    ///
+    /// The genesis state is required to determine the genesis block root.
+    WeakSubjSszBytes {
+        anchor_state_bytes: Vec<u8>,
+        anchor_block_bytes: Vec<u8>,
+        genesis_state_bytes: Vec<u8>,
+    },
+    /// Starts from the finalized checkpoint of a trusted beacon node, downloaded from its HTTP
+    /// API at `url`.
+    CheckpointSyncUrl {
+        url: String,
+        genesis_state_bytes: Vec<u8>,
+    },
 }
 
 impl Default for ClientGenesis {
@@ -62,8 +78,11 @@ pub struct Config {
     pub store: store::StoreConfig,
     pub network: network::NetworkConfig,
     pub rest_api: rest_api::Config,
-    pub websocket_server: websocket_server::Config,
     pub eth1: eth1::Config,
+    /// Configuration for the slasher, which is disabled if `None`.
+    pub slasher: Option<slasher::Config>,
+    /// The validators to track with the validator monitor.
+    pub validator_monitor: ValidatorMonitorConfig,
 }
 
 impl Default for Config {
@@ -78,12 +97,13 @@ impl Default for Config {
             store: <_>::default(),
             network: NetworkConfig::default(),
             rest_api: <_>::default(),
-            websocket_server: <_>::default(),
             spec_constants: TESTNET_SPEC_CONSTANTS.into(),
             dummy_eth1_backend: false,
             sync_eth1_chain: false,
             eth1: <_>::default(),
             disabled_forks: Vec::new(),
+            slasher: None,
+            validator_monitor: <_>::default(),
         }
     }
 }
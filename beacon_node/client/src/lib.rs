@@ -3,6 +3,7 @@ extern crate slog;
 pub mod config;
 mod metrics;
 mod notifier;
+mod slasher_service;
 
 pub mod builder;
 pub mod error;
@@ -24,7 +25,6 @@ pub struct Client<T: BeaconChainTypes> {
     beacon_chain: Option<Arc<BeaconChain<T>>>,
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     http_listen_addr: Option<SocketAddr>,
-    websocket_listen_addr: Option<SocketAddr>,
     /// Exit channels will complete/error when dropped, causing each service to exit gracefully.
     _exit_channels: Vec<tokio::sync::oneshot::Sender<()>>,
 }
@@ -40,11 +40,6 @@ impl<T: BeaconChainTypes> Client<T> {
         self.http_listen_addr
     }
 
-    /// Returns the address of the client's WebSocket API server, if it was started.
-    pub fn websocket_listen_addr(&self) -> Option<SocketAddr> {
-        self.websocket_listen_addr
-    }
-
     /// Returns the port of the client's libp2p stack, if it was started.
     pub fn libp2p_listen_port(&self) -> Option<u16> {
         self.network_globals.as_ref().map(|n| n.listen_port_tcp())
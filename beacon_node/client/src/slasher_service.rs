@@ -0,0 +1,140 @@
+use beacon_chain::{observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes};
+use futures::prelude::*;
+use slasher::Slasher;
+use slog::{debug, error, info, Logger};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+
+/// Spawns a service which periodically processes the attestations and blocks queued in the
+/// beacon chain's slasher, importing any slashings it finds into the op pool.
+pub fn spawn_slasher_service<T: BeaconChainTypes>(
+    beacon_chain: Arc<BeaconChain<T>>,
+    runtime_handle: Handle,
+    log: Logger,
+) -> Result<tokio::sync::oneshot::Sender<()>, String> {
+    let slasher = beacon_chain
+        .slasher
+        .clone()
+        .ok_or_else(|| "slasher service requires a slasher")?;
+    let update_period = Duration::from_secs(slasher.config().update_period);
+
+    info!(
+        log,
+        "Slasher enabled";
+        "database_path" => format!("{:?}", slasher.config().database_path),
+        "history_length" => slasher.config().history_length,
+    );
+
+    let mut interval = tokio::time::interval(update_period);
+
+    let interval_future = async move {
+        while let Some(_) = interval.next().await {
+            let beacon_chain = beacon_chain.clone();
+            let slasher = slasher.clone();
+            let task_log = log.clone();
+
+            // Processing may read and write large amounts of data, avoid blocking the executor.
+            let result = runtime_handle
+                .spawn_blocking(move || process_queued(&beacon_chain, &slasher, &task_log))
+                .await;
+
+            if let Err(e) = result {
+                error!(
+                    log,
+                    "Slasher task failed";
+                    "error" => format!("{:?}", e)
+                );
+            }
+        }
+    };
+
+    let (exit_signal, exit) = tokio::sync::oneshot::channel();
+
+    tokio::spawn(futures::future::select(Box::pin(interval_future), exit));
+
+    Ok(exit_signal)
+}
+
+/// Run the slasher on its queued messages, then verify and import the slashings it finds.
+fn process_queued<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    slasher: &Slasher<T::EthSpec>,
+    log: &Logger,
+) {
+    let current_epoch = match beacon_chain.epoch() {
+        Ok(epoch) => epoch,
+        Err(e) => {
+            error!(
+                log,
+                "Slasher unable to read current epoch";
+                "error" => format!("{:?}", e)
+            );
+            return;
+        }
+    };
+
+    if let Err(e) = slasher.process_queued(current_epoch) {
+        error!(
+            log,
+            "Error during slasher processing";
+            "epoch" => current_epoch,
+            "error" => format!("{:?}", e)
+        );
+        return;
+    }
+
+    for slashing in slasher.get_attester_slashings() {
+        match beacon_chain.verify_attester_slashing_for_gossip(slashing) {
+            Ok(ObservationOutcome::New(verified_slashing)) => {
+                if let Err(e) = beacon_chain.process_attester_slashing(verified_slashing) {
+                    debug!(
+                        log,
+                        "Error importing attester slashing from slasher";
+                        "error" => format!("{:?}", e)
+                    );
+                }
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    log,
+                    "Dropping attester slashing from slasher";
+                    "reason" => "Slashings already known for all slashed validators",
+                );
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Slasher produced an invalid attester slashing";
+                    "error" => format!("{:?}", e)
+                );
+            }
+        }
+    }
+
+    for slashing in slasher.get_proposer_slashings() {
+        let validator_index = slashing.signed_header_1.message.proposer_index;
+
+        match beacon_chain.verify_proposer_slashing_for_gossip(slashing) {
+            Ok(ObservationOutcome::New(verified_slashing)) => {
+                beacon_chain.process_proposer_slashing(verified_slashing);
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    log,
+                    "Dropping proposer slashing from slasher";
+                    "reason" => "Already seen a proposer slashing for that validator",
+                    "validator_index" => validator_index,
+                );
+            }
+            Err(e) => {
+                error!(
+                    log,
+                    "Slasher produced an invalid proposer slashing";
+                    "validator_index" => validator_index,
+                    "error" => format!("{:?}", e)
+                );
+            }
+        }
+    }
+}
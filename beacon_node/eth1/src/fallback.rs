@@ -0,0 +1,250 @@
+//! Allows the `Service` to treat an ordered list of eth1 endpoints as a single node, trying each
+//! healthy endpoint in turn until a request succeeds.
+//!
+//! An endpoint is considered healthy if it is online, reports the expected network and chain IDs
+//! (when configured) and is not syncing.
+
+use crate::http::{get_chain_id, get_network_id, is_syncing};
+use crate::metrics;
+use crate::service::Error;
+use crate::Config;
+use futures::future::join_all;
+use parking_lot::RwLock;
+use slog::{debug, warn, Logger};
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+/// Timeout for each of the requests made when checking the health of an endpoint.
+const HEALTH_CHECK_TIMEOUT_MILLIS: u64 = 15_000;
+
+/// Reasons why an endpoint is not considered healthy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EndpointHealthError {
+    /// The health of the endpoint has not yet been checked.
+    Uninitialized,
+    /// The endpoint did not respond, or it responded with an error.
+    Offline,
+    /// The endpoint reported a network ID other than `Config::network_id`.
+    WrongNetworkId,
+    /// The endpoint reported a chain ID other than `Config::chain_id`.
+    WrongChainId,
+    /// The endpoint reported that it is still syncing.
+    NotSynced,
+}
+
+impl EndpointHealthError {
+    /// Returns a short, static description of `self`, suitable for use as a metric label.
+    fn as_str(self) -> &'static str {
+        match self {
+            EndpointHealthError::Uninitialized => "uninitialized",
+            EndpointHealthError::Offline => "offline",
+            EndpointHealthError::WrongNetworkId => "wrong_network_id",
+            EndpointHealthError::WrongChainId => "wrong_chain_id",
+            EndpointHealthError::NotSynced => "not_synced",
+        }
+    }
+}
+
+/// The reason why a request was not served by a particular endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EndpointError {
+    /// The endpoint was unhealthy and we didn't attempt to contact it.
+    Unavailable(EndpointHealthError),
+    /// We attempted to contact the endpoint but the request failed.
+    RequestFailed(String),
+}
+
+/// The health of an endpoint and the most recent error it produced (if any).
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointStatus {
+    pub health: Result<(), EndpointHealthError>,
+    pub last_error: Option<String>,
+}
+
+impl Default for EndpointStatus {
+    fn default() -> Self {
+        Self {
+            health: Err(EndpointHealthError::Uninitialized),
+            last_error: None,
+        }
+    }
+}
+
+/// Tracks the status of each of the endpoints in `Config::endpoints`.
+///
+/// Statuses are keyed by URL so that the list of endpoints may be changed via
+/// `Service::update_config` without invalidating the state of the endpoints that remain.
+#[derive(Default)]
+pub struct Endpoints {
+    statuses: RwLock<HashMap<String, EndpointStatus>>,
+}
+
+impl Endpoints {
+    /// Returns the status of `endpoint`.
+    pub fn status(&self, endpoint: &str) -> EndpointStatus {
+        self.statuses
+            .read()
+            .get(endpoint)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Check the health of all endpoints concurrently, updating their statuses.
+    pub async fn refresh_all(&self, config: &Config, log: &Logger) {
+        join_all(
+            config
+                .endpoints
+                .iter()
+                .map(|endpoint| self.refresh_status(endpoint, config, log)),
+        )
+        .await;
+    }
+
+    /// Check the health of `endpoint`, updating its status and returning the result.
+    pub async fn refresh_status(
+        &self,
+        endpoint: &str,
+        config: &Config,
+        log: &Logger,
+    ) -> Result<(), EndpointHealthError> {
+        let result = check_health(endpoint, config).await;
+
+        let health = match result {
+            Ok(()) => Ok(()),
+            Err((e, message)) => {
+                warn!(
+                    log,
+                    "Eth1 endpoint is unhealthy";
+                    "endpoint" => endpoint,
+                    "reason" => e.as_str(),
+                    "error" => &message,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::ENDPOINT_HEALTH_CHECK_FAILURES,
+                    &[endpoint, e.as_str()],
+                );
+                self.set_last_error(endpoint, message);
+                Err(e)
+            }
+        };
+
+        self.statuses
+            .write()
+            .entry(endpoint.to_string())
+            .or_default()
+            .health = health;
+
+        health
+    }
+
+    /// Run `func` against each healthy endpoint in `config.endpoints`, in order, returning the
+    /// first successful result.
+    ///
+    /// Endpoints which have never been checked are checked before they are used. An endpoint
+    /// whose request fails is marked as `Offline` until it is next checked.
+    pub async fn first_success<'a, F, O, R>(
+        &'a self,
+        config: &'a Config,
+        log: &Logger,
+        func: F,
+    ) -> Result<O, Error>
+    where
+        F: Fn(&'a str) -> R,
+        R: Future<Output = Result<O, Error>>,
+    {
+        let mut errors = vec![];
+
+        for endpoint in &config.endpoints {
+            let health = match self.status(endpoint).health {
+                Err(EndpointHealthError::Uninitialized) => {
+                    self.refresh_status(endpoint, config, log).await
+                }
+                other => other,
+            };
+
+            if let Err(e) = health {
+                errors.push((endpoint.clone(), EndpointError::Unavailable(e)));
+                continue;
+            }
+
+            metrics::inc_counter_vec(&metrics::ENDPOINT_REQUESTS, &[endpoint.as_str()]);
+
+            match func(endpoint).await {
+                Ok(output) => return Ok(output),
+                Err(e) => {
+                    let message = format!("{:?}", e);
+                    debug!(
+                        log,
+                        "Eth1 endpoint request failed";
+                        "endpoint" => endpoint,
+                        "error" => &message,
+                    );
+                    metrics::inc_counter_vec(&metrics::ENDPOINT_ERRORS, &[endpoint.as_str()]);
+
+                    let mut statuses = self.statuses.write();
+                    let status = statuses.entry(endpoint.clone()).or_default();
+                    status.health = Err(EndpointHealthError::Offline);
+                    status.last_error = Some(message.clone());
+                    drop(statuses);
+
+                    errors.push((endpoint.clone(), EndpointError::RequestFailed(message)));
+                }
+            }
+        }
+
+        Err(Error::AllEndpointsFailed(errors))
+    }
+
+    fn set_last_error(&self, endpoint: &str, error: String) {
+        self.statuses
+            .write()
+            .entry(endpoint.to_string())
+            .or_default()
+            .last_error = Some(error);
+    }
+}
+
+/// Checks that `endpoint` is online, on the expected network and chain and is not syncing.
+async fn check_health(
+    endpoint: &str,
+    config: &Config,
+) -> Result<(), (EndpointHealthError, String)> {
+    let timeout = Duration::from_millis(HEALTH_CHECK_TIMEOUT_MILLIS);
+
+    if let Some(expected) = config.network_id {
+        let network_id = get_network_id(endpoint, timeout)
+            .await
+            .map_err(|e| (EndpointHealthError::Offline, e))?;
+        if network_id != expected {
+            return Err((
+                EndpointHealthError::WrongNetworkId,
+                format!("Expected network id {}, got {}", expected, network_id),
+            ));
+        }
+    }
+
+    if let Some(expected) = config.chain_id {
+        let chain_id = get_chain_id(endpoint, timeout)
+            .await
+            .map_err(|e| (EndpointHealthError::Offline, e))?;
+        if chain_id != expected {
+            return Err((
+                EndpointHealthError::WrongChainId,
+                format!("Expected chain id {}, got {}", expected, chain_id),
+            ));
+        }
+    }
+
+    if is_syncing(endpoint, timeout)
+        .await
+        .map_err(|e| (EndpointHealthError::Offline, e))?
+    {
+        return Err((
+            EndpointHealthError::NotSynced,
+            "Endpoint is syncing".to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -51,6 +51,48 @@ pub async fn get_block_number(endpoint: &str, timeout: Duration) -> Result<u64,
     .map_err(|e| format!("Failed to get block number: {}", e))
 }
 
+/// Returns the network ID reported by `net_version`.
+///
+/// Uses HTTP JSON RPC at `endpoint`. E.g., `http://localhost:8545`.
+pub async fn get_network_id(endpoint: &str, timeout: Duration) -> Result<u64, String> {
+    let response_body = send_rpc_request(endpoint, "net_version", json!([]), timeout).await?;
+    response_result(&response_body)?
+        .ok_or_else(|| "No result field was returned for network id".to_string())?
+        .as_str()
+        .ok_or_else(|| "Data was not string")?
+        .parse::<u64>()
+        .map_err(|e| format!("Failed to get network id: {:?}", e))
+}
+
+/// Returns the chain ID reported by `eth_chainId` (EIP-695).
+///
+/// Uses HTTP JSON RPC at `endpoint`. E.g., `http://localhost:8545`.
+pub async fn get_chain_id(endpoint: &str, timeout: Duration) -> Result<u64, String> {
+    let response_body = send_rpc_request(endpoint, "eth_chainId", json!([]), timeout).await?;
+    hex_to_u64_be(
+        response_result(&response_body)?
+            .ok_or_else(|| "No result field was returned for chain id".to_string())?
+            .as_str()
+            .ok_or_else(|| "Data was not string")?,
+    )
+    .map_err(|e| format!("Failed to get chain id: {}", e))
+}
+
+/// Returns `true` if the node reports that it is still syncing via `eth_syncing`.
+///
+/// Uses HTTP JSON RPC at `endpoint`. E.g., `http://localhost:8545`.
+pub async fn is_syncing(endpoint: &str, timeout: Duration) -> Result<bool, String> {
+    let response_body = send_rpc_request(endpoint, "eth_syncing", json!([]), timeout).await?;
+    // The node returns `false` when it is not syncing, otherwise an object describing progress.
+    match response_result(&response_body)?
+        .ok_or_else(|| "No result field was returned for syncing status".to_string())?
+    {
+        Value::Bool(syncing) => Ok(syncing),
+        Value::Object(_) => Ok(true),
+        other => Err(format!("Unexpected syncing status: {:?}", other)),
+    }
+}
+
 /// Gets a block hash by block number.
 ///
 /// Uses HTTP JSON RPC at `endpoint`. E.g., `http://localhost:8545`.
@@ -2,6 +2,7 @@ use crate::Config;
 use crate::{
     block_cache::BlockCache,
     deposit_cache::{DepositCache, SszDepositCache},
+    fallback::Endpoints,
 };
 use parking_lot::RwLock;
 use ssz::{Decode, Encode};
@@ -28,6 +29,7 @@ pub struct Inner {
     pub block_cache: RwLock<BlockCache>,
     pub deposit_cache: RwLock<DepositUpdater>,
     pub config: RwLock<Config>,
+    pub endpoints: Endpoints,
 }
 
 impl Inner {
@@ -80,6 +82,7 @@ impl SszEth1Cache {
                 last_processed_block: self.last_processed_block,
             }),
             config: RwLock::new(config),
+            endpoints: Endpoints::default(),
         })
     }
 }
@@ -16,4 +16,23 @@ lazy_static! {
         try_create_int_gauge("eth1_deposit_cache_len", "Number of deposits in the eth1 cache");
     pub static ref HIGHEST_PROCESSED_DEPOSIT_BLOCK: Result<IntGauge> =
         try_create_int_gauge("eth1_highest_processed_deposit_block", "Number of the last block checked for deposits");
+
+    /*
+     * Eth1 endpoints
+     */
+    pub static ref ENDPOINT_REQUESTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "eth1_endpoint_requests_total",
+        "The number of requests made to each eth1 endpoint",
+        &["endpoint"]
+    );
+    pub static ref ENDPOINT_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "eth1_endpoint_errors_total",
+        "The number of requests to each eth1 endpoint which returned an error",
+        &["endpoint"]
+    );
+    pub static ref ENDPOINT_HEALTH_CHECK_FAILURES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "eth1_endpoint_health_check_failures_total",
+        "The number of failed health checks for each eth1 endpoint, by reason",
+        &["endpoint", "reason"]
+    );
 }
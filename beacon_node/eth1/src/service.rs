@@ -2,6 +2,7 @@ use crate::metrics;
 use crate::{
     block_cache::{BlockCache, Error as BlockCacheError, Eth1Block},
     deposit_cache::Error as DepositCacheError,
+    fallback::{EndpointError, EndpointStatus},
     http::{get_block, get_block_number, get_deposit_logs_in_range, Log},
     inner::{DepositUpdater, Inner},
     DepositLog,
@@ -54,6 +55,9 @@ pub enum Error {
     },
     /// There was an unexpected internal error.
     Internal(String),
+    /// None of the eth1 endpoints were able to serve a request. Contains the reason that each
+    /// endpoint did not succeed, in the order they were tried.
+    AllEndpointsFailed(Vec<(String, EndpointError)>),
 }
 
 /// The success message for an Eth1Data cache update.
@@ -71,8 +75,15 @@ pub struct DepositCacheUpdateOutcome {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// An Eth1 node (e.g., Geth) running a HTTP JSON-RPC endpoint.
-    pub endpoint: String,
+    /// An ordered list of Eth1 nodes (e.g., Geth) running a HTTP JSON-RPC endpoint.
+    ///
+    /// Requests are sent to the first healthy endpoint, falling back to the next endpoint in the
+    /// list if a request fails.
+    pub endpoints: Vec<String>,
+    /// If `Some`, endpoints which report a different `net_version` are considered unhealthy.
+    pub network_id: Option<u64>,
+    /// If `Some`, endpoints which report a different `eth_chainId` are considered unhealthy.
+    pub chain_id: Option<u64>,
     /// The address the `BlockCache` and `DepositCache` should assume is the canonical deposit contract.
     pub deposit_contract_address: String,
     /// Defines the first block that the `DepositCache` will start searching for deposit logs.
@@ -102,7 +113,9 @@ pub struct Config {
 impl Default for Config {
     fn default() -> Self {
         Self {
-            endpoint: "http://localhost:8545".into(),
+            endpoints: vec!["http://localhost:8545".into()],
+            network_id: None,
+            chain_id: None,
             deposit_contract_address: "0x0000000000000000000000000000000000000000".into(),
             deposit_contract_deploy_block: 1,
             lowest_cached_block_number: 1,
@@ -204,6 +217,15 @@ impl Service {
         self.deposits().read().cache.len()
     }
 
+    /// Returns the status of each of the configured eth1 endpoints, in order.
+    pub fn endpoint_statuses(&self) -> Vec<(String, EndpointStatus)> {
+        self.config()
+            .endpoints
+            .iter()
+            .map(|endpoint| (endpoint.clone(), self.inner.endpoints.status(endpoint)))
+            .collect()
+    }
+
     /// Read the service's configuration.
     pub fn config(&self) -> RwLockReadGuard<Config> {
         self.inner.config.read()
@@ -248,6 +270,15 @@ impl Service {
     pub async fn update(
         service: Self,
     ) -> Result<(DepositCacheUpdateOutcome, BlockCacheUpdateOutcome), String> {
+        // Check the health of each endpoint so that endpoints which have failed (or are syncing)
+        // may be used again once they recover.
+        let config = service.config().clone();
+        service
+            .inner
+            .endpoints
+            .refresh_all(&config, &service.log)
+            .await;
+
         let update_deposit_cache = async {
             let outcome = Service::update_deposit_cache(service.clone())
                 .await
@@ -341,13 +372,13 @@ impl Service {
     ///
     /// Emits logs for debugging and errors.
     pub async fn update_deposit_cache(service: Self) -> Result<DepositCacheUpdateOutcome, Error> {
-        let endpoint = service.config().endpoint.clone();
-        let follow_distance = service.config().follow_distance;
-        let deposit_contract_address = service.config().deposit_contract_address.clone();
+        let config = service.config().clone();
+        let endpoints = &service.inner.endpoints;
+        let follow_distance = config.follow_distance;
+        let deposit_contract_address = config.deposit_contract_address.as_str();
 
-        let blocks_per_log_query = service.config().blocks_per_log_query;
-        let max_log_requests_per_update = service
-            .config()
+        let blocks_per_log_query = config.blocks_per_log_query;
+        let max_log_requests_per_update = config
             .max_log_requests_per_update
             .unwrap_or_else(usize::max_value);
 
@@ -356,9 +387,13 @@ impl Service {
             .read()
             .last_processed_block
             .map(|n| n + 1)
-            .unwrap_or_else(|| service.config().deposit_contract_deploy_block);
+            .unwrap_or_else(|| config.deposit_contract_deploy_block);
 
-        let range = get_new_block_numbers(&endpoint, next_required_block, follow_distance).await?;
+        let range = endpoints
+            .first_success(&config, &service.log, |endpoint| {
+                get_new_block_numbers(endpoint, next_required_block, follow_distance)
+            })
+            .await?;
 
         let block_number_chunks = if let Some(range) = range {
             range
@@ -379,18 +414,18 @@ impl Service {
             stream::try_unfold(block_number_chunks.into_iter(), |mut chunks| async {
                 match chunks.next() {
                     Some(chunk) => {
-                        let chunk_1 = chunk.clone();
-                        match get_deposit_logs_in_range(
-                            &endpoint,
-                            &deposit_contract_address,
-                            chunk,
-                            Duration::from_millis(GET_DEPOSIT_LOG_TIMEOUT_MILLIS),
-                        )
-                        .await
-                        {
-                            Ok(logs) => Ok(Some(((chunk_1, logs), chunks))),
-                            Err(e) => Err(Error::GetDepositLogsFailed(e)),
-                        }
+                        let logs = endpoints
+                            .first_success(&config, &service.log, |endpoint| {
+                                get_deposit_logs_in_range(
+                                    endpoint,
+                                    deposit_contract_address,
+                                    chunk.clone(),
+                                    Duration::from_millis(GET_DEPOSIT_LOG_TIMEOUT_MILLIS),
+                                )
+                                .map_err(Error::GetDepositLogsFailed)
+                            })
+                            .await?;
+                        Ok(Some(((chunk, logs), chunks)))
                     }
                     None => Ok(None),
                 }
@@ -474,9 +509,10 @@ impl Service {
     ///
     /// Emits logs for debugging and errors.
     pub async fn update_block_cache(service: Self) -> Result<BlockCacheUpdateOutcome, Error> {
-        let block_cache_truncation = service.config().block_cache_truncation;
-        let max_blocks_per_update = service
-            .config()
+        let config = service.config().clone();
+        let endpoints = &service.inner.endpoints;
+        let block_cache_truncation = config.block_cache_truncation;
+        let max_blocks_per_update = config
             .max_blocks_per_update
             .unwrap_or_else(usize::max_value);
 
@@ -486,12 +522,15 @@ impl Service {
             .read()
             .highest_block_number()
             .map(|n| n + 1)
-            .unwrap_or_else(|| service.config().lowest_cached_block_number);
+            .unwrap_or_else(|| config.lowest_cached_block_number);
 
-        let endpoint = service.config().endpoint.clone();
-        let follow_distance = service.config().follow_distance;
+        let follow_distance = config.follow_distance;
 
-        let range = get_new_block_numbers(&endpoint, next_required_block, follow_distance).await?;
+        let range = endpoints
+            .first_success(&config, &service.log, |endpoint| {
+                get_new_block_numbers(endpoint, next_required_block, follow_distance)
+            })
+            .await?;
         // Map the range of required blocks into a Vec.
         //
         // If the required range is larger than the size of the cache, drop the exiting cache
@@ -542,10 +581,12 @@ impl Service {
             |mut block_numbers| async {
                 match block_numbers.next() {
                     Some(block_number) => {
-                        match download_eth1_block(service.inner.clone(), block_number).await {
-                            Ok(eth1_block) => Ok(Some((eth1_block, block_numbers))),
-                            Err(e) => Err(e),
-                        }
+                        let eth1_block = endpoints
+                            .first_success(&config, &service.log, |endpoint| {
+                                download_eth1_block(&service.inner, endpoint, block_number)
+                            })
+                            .await?;
+                        Ok(Some((eth1_block, block_numbers)))
                     }
                     None => Ok(None),
                 }
@@ -661,9 +702,11 @@ async fn get_new_block_numbers<'a>(
 /// `block_number`.
 ///
 /// Performs three async calls to an Eth1 HTTP JSON RPC endpoint.
-async fn download_eth1_block(cache: Arc<Inner>, block_number: u64) -> Result<Eth1Block, Error> {
-    let endpoint = cache.config.read().endpoint.clone();
-
+async fn download_eth1_block(
+    cache: &Inner,
+    endpoint: &str,
+    block_number: u64,
+) -> Result<Eth1Block, Error> {
     let deposit_root = cache
         .deposit_cache
         .read()
@@ -678,7 +721,7 @@ async fn download_eth1_block(cache: Arc<Inner>, block_number: u64) -> Result<Eth
 
     // Performs a `get_blockByNumber` call to an eth1 node.
     let http_block = get_block(
-        &endpoint,
+        endpoint,
         block_number,
         Duration::from_millis(GET_BLOCK_TIMEOUT_MILLIS),
     )
@@ -115,7 +115,7 @@ mod eth1_cache {
 
             let service = Service::new(
                 Config {
-                    endpoint: eth1.endpoint(),
+                    endpoints: vec![eth1.endpoint()],
                     deposit_contract_address: deposit_contract.address(),
                     lowest_cached_block_number: initial_block_number,
                     follow_distance,
@@ -186,7 +186,7 @@ mod eth1_cache {
 
         let service = Service::new(
             Config {
-                endpoint: eth1.endpoint(),
+                endpoints: vec![eth1.endpoint()],
                 deposit_contract_address: deposit_contract.address(),
                 lowest_cached_block_number: get_block_number(&web3).await,
                 follow_distance: 0,
@@ -232,7 +232,7 @@ mod eth1_cache {
 
         let service = Service::new(
             Config {
-                endpoint: eth1.endpoint(),
+                endpoints: vec![eth1.endpoint()],
                 deposit_contract_address: deposit_contract.address(),
                 lowest_cached_block_number: get_block_number(&web3).await,
                 follow_distance: 0,
@@ -275,7 +275,7 @@ mod eth1_cache {
 
         let service = Service::new(
             Config {
-                endpoint: eth1.endpoint(),
+                endpoints: vec![eth1.endpoint()],
                 deposit_contract_address: deposit_contract.address(),
                 lowest_cached_block_number: get_block_number(&web3).await,
                 follow_distance: 0,
@@ -321,7 +321,7 @@ mod deposit_tree {
 
         let service = Service::new(
             Config {
-                endpoint: eth1.endpoint(),
+                endpoints: vec![eth1.endpoint()],
                 deposit_contract_address: deposit_contract.address(),
                 deposit_contract_deploy_block: start_block,
                 follow_distance: 0,
@@ -393,7 +393,7 @@ mod deposit_tree {
 
         let service = Service::new(
             Config {
-                endpoint: eth1.endpoint(),
+                endpoints: vec![eth1.endpoint()],
                 deposit_contract_address: deposit_contract.address(),
                 deposit_contract_deploy_block: start_block,
                 lowest_cached_block_number: start_block,
@@ -630,7 +630,7 @@ mod fast {
         let now = get_block_number(&web3).await;
         let service = Service::new(
             Config {
-                endpoint: eth1.endpoint(),
+                endpoints: vec![eth1.endpoint()],
                 deposit_contract_address: deposit_contract.address(),
                 deposit_contract_deploy_block: now,
                 lowest_cached_block_number: now,
@@ -700,7 +700,7 @@ mod persist {
 
         let now = get_block_number(&web3).await;
         let config = Config {
-            endpoint: eth1.endpoint(),
+            endpoints: vec![eth1.endpoint()],
             deposit_contract_address: deposit_contract.address(),
             deposit_contract_deploy_block: now,
             lowest_cached_block_number: now,
@@ -758,3 +758,72 @@ mod persist {
         );
     }
 }
+
+mod fallback {
+    use super::*;
+    use eth1::{EndpointHealthError, Error};
+
+    /// An address that nothing should be listening on.
+    const OFFLINE_ENDPOINT: &str = "http://127.0.0.1:1";
+
+    #[tokio::test]
+    async fn falls_back_to_healthy_endpoint() {
+        let log = null_logger();
+
+        let eth1 = GanacheEth1Instance::new()
+            .await
+            .expect("should start eth1 environment");
+        let deposit_contract = &eth1.deposit_contract;
+        let web3 = eth1.web3();
+
+        let service = Service::new(
+            Config {
+                endpoints: vec![OFFLINE_ENDPOINT.to_string(), eth1.endpoint()],
+                deposit_contract_address: deposit_contract.address(),
+                lowest_cached_block_number: get_block_number(&web3).await,
+                follow_distance: 0,
+                ..Config::default()
+            },
+            log,
+        );
+
+        for _ in 0..4 {
+            eth1.ganache.evm_mine().await.expect("should mine block")
+        }
+
+        Service::update_deposit_cache(service.clone())
+            .await
+            .expect("should update deposit cache using the second endpoint");
+        Service::update_block_cache(service.clone())
+            .await
+            .expect("should update block cache using the second endpoint");
+
+        assert!(service.block_cache_len() > 0, "should have imported blocks");
+
+        let statuses = service.endpoint_statuses();
+        assert_eq!(statuses[0].0, OFFLINE_ENDPOINT);
+        assert_eq!(statuses[0].1.health, Err(EndpointHealthError::Offline));
+        assert!(statuses[0].1.last_error.is_some());
+        assert_eq!(statuses[1].0, eth1.endpoint());
+        assert_eq!(statuses[1].1.health, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn all_endpoints_offline() {
+        let service = Service::new(
+            Config {
+                endpoints: vec![OFFLINE_ENDPOINT.to_string()],
+                ..Config::default()
+            },
+            null_logger(),
+        );
+
+        match Service::update_block_cache(service.clone()).await {
+            Err(Error::AllEndpointsFailed(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].0, OFFLINE_ENDPOINT);
+            }
+            other => panic!("expected all endpoints to fail, got {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use crate::discovery::{enr::Eth2Enr, Discovery};
 use crate::peer_manager::{PeerManager, PeerManagerEvent};
-use crate::rpc::*;
+use crate::rpc::{methods::GoodbyeReason, *};
 use crate::types::{GossipEncoding, GossipKind, GossipTopic};
-use crate::{error, Enr, NetworkConfig, NetworkGlobals, PubsubMessage, TopicHash};
+use crate::{error, metrics, Enr, NetworkConfig, NetworkGlobals, PubsubMessage, TopicHash};
 use discv5::Discv5Event;
 use futures::prelude::*;
 use handler::{BehaviourHandler, BehaviourHandlerIn, BehaviourHandlerOut, DelegateIn, DelegateOut};
@@ -25,6 +25,7 @@ use std::{
     marker::PhantomData,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use types::{EnrForkId, EthSpec, SubnetId};
 
@@ -282,7 +283,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             gossipsub: Gossipsub::new(local_peer_id, net_conf.gs_config.clone()),
             discovery: Discovery::new(local_key, net_conf, network_globals.clone(), log)?,
             identify,
-            peer_manager: PeerManager::new(network_globals.clone(), log),
+            peer_manager: PeerManager::new(network_globals.clone(), net_conf.max_peers, log),
             events: Vec::new(),
             seen_gossip_messages: LruCache::new(100_000),
             meta_data,
@@ -395,6 +396,12 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
 
     /// Sends an RPC Request/Response via the RPC protocol.
     pub fn send_rpc(&mut self, peer_id: PeerId, rpc_event: RPCEvent<TSpec>) {
+        if let RPCEvent::Request(_, request) = &rpc_event {
+            metrics::inc_counter_vec(
+                &metrics::RPC_REQUESTS_TX,
+                &[&request.protocol().to_string()],
+            );
+        }
         self.eth2_rpc.send_rpc(peer_id, rpc_event);
     }
 
@@ -589,6 +596,16 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
 
     fn on_rpc_event(&mut self, message: RPCMessage<TSpec>) {
         let peer_id = message.peer_id;
+        match &message.event {
+            RPCEvent::Request(_, request) => metrics::inc_counter_vec(
+                &metrics::RPC_REQUESTS_RX,
+                &[&request.protocol().to_string()],
+            ),
+            RPCEvent::Error(_, protocol, _) => {
+                metrics::inc_counter_vec(&metrics::RPC_ERRORS, &[&protocol.to_string()])
+            }
+            RPCEvent::Response(_, _) => {}
+        }
         // The METADATA and PING RPC responses are handled within the behaviour and not
         // propagated
         // TODO: Improve the RPC types to better handle this logic discrepancy
@@ -653,11 +670,17 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
                     PeerManagerEvent::MetaData(peer_id) => {
                         self.send_meta_data_request(peer_id);
                     }
-                    PeerManagerEvent::_DisconnectPeer(_peer_id) => {
-                        //TODO: Implement
+                    PeerManagerEvent::DisconnectPeer(peer_id, reason) => {
+                        // the swarm is managed by the network, so the network is informed of
+                        // the disconnect
+                        return Poll::Ready(NBAction::GenerateEvent(
+                            BehaviourEvent::DisconnectPeer(peer_id, reason),
+                        ));
                     }
-                    PeerManagerEvent::_BanPeer(_peer_id) => {
-                        //TODO: Implement
+                    PeerManagerEvent::BanPeer(peer_id, timeout) => {
+                        return Poll::Ready(NBAction::GenerateEvent(BehaviourEvent::BanPeer(
+                            peer_id, timeout,
+                        )));
                     }
                 },
                 Poll::Pending => break,
@@ -726,4 +749,8 @@ pub enum BehaviourEvent<TSpec: EthSpec> {
     PeerSubscribed(PeerId, TopicHash),
     /// Inform the network to send a Status to this peer.
     StatusPeer(PeerId),
+    /// Inform the network to say Goodbye to this peer and disconnect it.
+    DisconnectPeer(PeerId, GoodbyeReason),
+    /// Inform the network to disconnect this peer and ban it for the given duration.
+    BanPeer(PeerId, Duration),
 }
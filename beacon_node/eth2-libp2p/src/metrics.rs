@@ -17,4 +17,23 @@ lazy_static! {
         "libp2p_peer_disconnect_event_total",
         "Count of libp2p peer disconnect events"
     );
+
+    /*
+     * RPC
+     */
+    pub static ref RPC_REQUESTS_RX: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_requests_rx_total",
+        "Count of RPC requests received, per protocol",
+        &["protocol"]
+    );
+    pub static ref RPC_REQUESTS_TX: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_requests_tx_total",
+        "Count of RPC requests sent, per protocol",
+        &["protocol"]
+    );
+    pub static ref RPC_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_rpc_errors_total",
+        "Count of RPC errors, per protocol",
+        &["protocol"]
+    );
 }
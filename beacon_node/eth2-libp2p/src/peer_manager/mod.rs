@@ -2,7 +2,7 @@
 
 pub use self::peerdb::*;
 use crate::metrics;
-use crate::rpc::{MetaData, Protocol, RPCError, RPCResponseErrorCode};
+use crate::rpc::{methods::GoodbyeReason, MetaData, Protocol, RPCError, RPCResponseErrorCode};
 use crate::{NetworkGlobals, PeerId};
 use futures::prelude::*;
 use futures::Stream;
@@ -15,6 +15,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
+use tokio::time::Interval;
 use types::EthSpec;
 
 pub mod client;
@@ -22,11 +23,18 @@ mod peer_info;
 mod peer_sync_status;
 mod peerdb;
 
-pub use peer_info::{PeerConnectionStatus::*, PeerInfo};
+pub use peer_info::{PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo};
 pub use peer_sync_status::{PeerSyncStatus, SyncInfo};
 /// The minimum reputation before a peer is disconnected.
 // Most likely this needs tweaking.
-const _MIN_REP_BEFORE_BAN: Rep = 10;
+pub const MIN_REP_BEFORE_BAN: Rep = 10;
+/// The time in seconds that a peer is banned for once its reputation falls below
+/// `MIN_REP_BEFORE_BAN`.
+pub const REPUTATION_BAN_TIMEOUT: u64 = 3600;
+/// The time in seconds between updates of the reputations of all peers.
+const HEARTBEAT_INTERVAL: u64 = 30;
+/// The time in seconds between each 1rep decay of the reputations of all peers.
+const DECAY_INTERVAL: u64 = 3600;
 /// The time in seconds between re-status's peers.
 const STATUS_INTERVAL: u64 = 300;
 /// The time in seconds between PING events. We do not send a ping if the other peer as PING'd us within
@@ -43,8 +51,13 @@ pub struct PeerManager<TSpec: EthSpec> {
     ping_peers: HashSetDelay<PeerId>,
     /// A collection of peers awaiting to be Status'd.
     status_peers: HashSetDelay<PeerId>,
-    /// Last updated moment.
-    _last_updated: Instant,
+    /// The interval at which reputations are updated.
+    heartbeat: Interval,
+    /// The last moment at which reputations were decayed.
+    last_decayed: Instant,
+    /// The target number of connected peers. The lowest scoring peers are disconnected when
+    /// this is exceeded.
+    max_peers: usize,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
@@ -76,12 +89,18 @@ pub enum PeerAction {
 }
 
 impl PeerAction {
+    /// The reputation change for each action.
+    ///
+    /// Reputations are acted upon, so these are sized against `DEFAULT_REPUTATION` and
+    /// `MIN_REP_BEFORE_BAN`: a peer starting at the default is banned after 5 low, 9 mid or 14 high
+    /// tolerance errors, matching the notes on each variant. Larger penalties would ban a peer
+    /// after only one to three errors.
     fn rep_change(&self) -> RepChange {
         match self {
             PeerAction::Fatal => RepChange::worst(),
-            PeerAction::LowToleranceError => RepChange::bad(60),
-            PeerAction::MidToleranceError => RepChange::bad(25),
-            PeerAction::HighToleranceError => RepChange::bad(15),
+            PeerAction::LowToleranceError => RepChange::bad(10),
+            PeerAction::MidToleranceError => RepChange::bad(5),
+            PeerAction::HighToleranceError => RepChange::bad(3),
             PeerAction::_ValidMessage => RepChange::good(20),
         }
     }
@@ -95,20 +114,26 @@ pub enum PeerManagerEvent {
     Ping(PeerId),
     /// Request METADATA from a peer.
     MetaData(PeerId),
-    /// The peer should be disconnected.
-    _DisconnectPeer(PeerId),
-    /// The peer should be disconnected and banned.
-    _BanPeer(PeerId),
+    /// The peer should be disconnected with the given reason.
+    DisconnectPeer(PeerId, GoodbyeReason),
+    /// The peer should be disconnected and banned for the given duration.
+    BanPeer(PeerId, Duration),
 }
 
 impl<TSpec: EthSpec> PeerManager<TSpec> {
-    pub fn new(network_globals: Arc<NetworkGlobals<TSpec>>, log: &slog::Logger) -> Self {
+    pub fn new(
+        network_globals: Arc<NetworkGlobals<TSpec>>,
+        max_peers: usize,
+        log: &slog::Logger,
+    ) -> Self {
         PeerManager {
             network_globals,
             events: SmallVec::new(),
-            _last_updated: Instant::now(),
             ping_peers: HashSetDelay::new(Duration::from_secs(PING_INTERVAL)),
             status_peers: HashSetDelay::new(Duration::from_secs(STATUS_INTERVAL)),
+            heartbeat: tokio::time::interval(Duration::from_secs(HEARTBEAT_INTERVAL)),
+            last_decayed: Instant::now(),
+            max_peers,
             log: log.clone(),
         }
     }
@@ -204,7 +229,6 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
 
     /// Updates the state of the peer as disconnected.
     pub fn notify_disconnect(&mut self, peer_id: &PeerId) {
-        //self.update_reputations();
         self.network_globals.peers.write().disconnect(peer_id);
 
         // remove the ping and status timer for the peer
@@ -238,13 +262,42 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     ///
     /// If the peer doesn't exist, log a warning and insert defaults.
     pub fn report_peer(&mut self, peer_id: &PeerId, action: PeerAction) {
-        //TODO: Check these. There are double disconnects for example
-        // self.update_reputations();
-        self.network_globals
-            .peers
-            .write()
-            .add_reputation(peer_id, action.rep_change());
-        // self.update_reputations();
+        let should_ban = {
+            let mut pdb = self.network_globals.peers.write();
+            pdb.add_reputation(peer_id, action.rep_change());
+            pdb.reputation(peer_id) < MIN_REP_BEFORE_BAN && !pdb.is_banned(peer_id)
+        };
+
+        if should_ban {
+            self.ban_for_reputation(peer_id);
+        }
+    }
+
+    /// Bans a peer in the `PeerDB`, optionally for a limited `duration`.
+    ///
+    /// This only updates the state of the peer; the caller is responsible for disconnecting the
+    /// peer at the swarm level.
+    pub fn ban_peer(&mut self, peer_id: &PeerId, duration: Option<Duration>) {
+        self.network_globals.peers.write().ban(peer_id, duration);
+
+        // remove the ping and status timer for the peer
+        self.ping_peers.remove(peer_id);
+        self.status_peers.remove(peer_id);
+    }
+
+    /// Lifts a ban on a peer in the `PeerDB`.
+    ///
+    /// The reputation of the peer is raised to `MIN_REP_BEFORE_BAN` so that it is not banned
+    /// again immediately. Returns `false` if the peer was not banned.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) -> bool {
+        let mut pdb = self.network_globals.peers.write();
+        if !pdb.unban(peer_id) {
+            return false;
+        }
+        if let Some(info) = pdb.peer_info_mut(peer_id) {
+            info.reputation = std::cmp::max(info.reputation, MIN_REP_BEFORE_BAN);
+        }
+        true
     }
 
     /// Updates `PeerInfo` with `identify` information.
@@ -326,9 +379,6 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     /// This informs if the peer was accepted in to the db or not.
     // TODO: Drop peers if over max_peer limit
     fn connect_peer(&mut self, peer_id: &PeerId, connection: ConnectingType) -> bool {
-        // TODO: remove after timed updates
-        //self.update_reputations();
-
         {
             let mut peerdb = self.network_globals.peers.write();
             if peerdb.connection_status(peer_id).map(|c| c.is_banned()) == Some(true) {
@@ -364,94 +414,105 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         self.network_globals.peers.write().dialing_peer(peer_id);
     }
 
+    /// Bans a peer whose reputation has fallen below `MIN_REP_BEFORE_BAN` for
+    /// `REPUTATION_BAN_TIMEOUT`, requesting that it is disconnected.
+    fn ban_for_reputation(&mut self, peer_id: &PeerId) {
+        let timeout = Duration::from_secs(REPUTATION_BAN_TIMEOUT);
+        debug!(self.log, "Banning peer for low reputation"; "peer_id" => peer_id.to_string(), "timeout" => format!("{:?}", timeout));
+        self.ban_peer(peer_id, Some(timeout));
+        self.events
+            .push(PeerManagerEvent::BanPeer(peer_id.clone(), timeout));
+    }
+
     /// Updates the reputation of known peers according to their connection
     /// status and the time that has passed.
     ///
-    /// **Disconnected peers** get a 1rep hit every hour they stay disconnected.
-    /// **Banned peers** get a 1rep gain for every hour to slowly allow them back again.
+    /// **Connected peers** move 1rep towards `DEFAULT_REPUTATION` every hour, so that past
+    /// errors are slowly forgiven.
+    /// **Disconnected peers** get a 1rep hit every hour to slowly forget them.
+    /// **Banned peers** are left untouched; their ban expires after a fixed timeout.
     ///
-    /// A banned(disconnected) peer that gets its rep above(below) MIN_REP_BEFORE_BAN is
-    /// now considered a disconnected(banned) peer.
-    fn _update_reputations(&mut self) {
-        // avoid locking the peerdb too often
-        // TODO: call this on a timer
-        if self._last_updated.elapsed().as_secs() < 30 {
-            return;
+    /// A peer whose rep falls below MIN_REP_BEFORE_BAN is banned, and the lowest scoring peers
+    /// are disconnected if we are connected to more than `max_peers`.
+    fn update_reputations(&mut self, now: Instant) {
+        // the number of hours that have passed since the last decay, usually 0 or 1
+        let decay_hours = now
+            .checked_duration_since(self.last_decayed)
+            .unwrap_or_else(|| Duration::from_secs(0))
+            .as_secs()
+            / DECAY_INTERVAL;
+        if decay_hours > 0 {
+            self.last_decayed += Duration::from_secs(decay_hours * DECAY_INTERVAL);
         }
+        let rep_dif: Rep = decay_hours.try_into().unwrap_or(Rep::max_value());
 
-        let now = Instant::now();
-
-        // Check for peers that get banned, unbanned and that should be disconnected
         let mut ban_queue = Vec::new();
-        let mut unban_queue = Vec::new();
-
-        /* Check how long have peers been in this state and update their reputations if needed */
-        let mut pdb = self.network_globals.peers.write();
+        let mut disconnect_queue = Vec::new();
 
-        for (id, info) in pdb._peers_mut() {
-            // Update reputations
-            match info.connection_status {
-                Connected { .. } => {
-                    // Connected peers gain reputation by sending useful messages
-                }
-                Disconnected { since } | Banned { since } => {
-                    // For disconnected peers, lower their reputation by 1 for every hour they
-                    // stay disconnected. This helps us slowly forget disconnected peers.
-                    // In the same way, slowly allow banned peers back again.
-                    let dc_hours = now
-                        .checked_duration_since(since)
-                        .unwrap_or_else(|| Duration::from_secs(0))
-                        .as_secs()
-                        / 3600;
-                    let last_dc_hours = self
-                        ._last_updated
-                        .checked_duration_since(since)
-                        .unwrap_or_else(|| Duration::from_secs(0))
-                        .as_secs()
-                        / 3600;
-                    if dc_hours > last_dc_hours {
-                        // this should be 1 most of the time
-                        let rep_dif = (dc_hours - last_dc_hours)
-                            .try_into()
-                            .unwrap_or(Rep::max_value());
-
-                        info.reputation = if info.connection_status.is_banned() {
-                            info.reputation.saturating_add(rep_dif)
+        {
+            let mut pdb = self.network_globals.peers.write();
+
+            for (id, info) in pdb.peers_mut() {
+                // Update reputations
+                match info.connection_status {
+                    Connected { .. } => {
+                        info.reputation = if info.reputation < DEFAULT_REPUTATION {
+                            std::cmp::min(
+                                info.reputation.saturating_add(rep_dif),
+                                DEFAULT_REPUTATION,
+                            )
                         } else {
-                            info.reputation.saturating_sub(rep_dif)
+                            std::cmp::max(
+                                info.reputation.saturating_sub(rep_dif),
+                                DEFAULT_REPUTATION,
+                            )
                         };
                     }
-                }
-                Dialing { since } => {
-                    // A peer shouldn't be dialing for more than 2 minutes
-                    if since.elapsed().as_secs() > 120 {
-                        warn!(self.log,"Peer has been dialing for too long"; "peer_id" => id.to_string());
-                        // TODO: decide how to handle this
+                    Disconnected { .. } => {
+                        info.reputation = info.reputation.saturating_sub(rep_dif);
+                    }
+                    Banned { .. } => {}
+                    Dialing { since } => {
+                        // A peer shouldn't be dialing for more than 2 minutes
+                        if since.elapsed().as_secs() > 120 {
+                            warn!(self.log,"Peer has been dialing for too long"; "peer_id" => id.to_string());
+                            // TODO: decide how to handle this
+                        }
                     }
+                    Unknown => {} //TODO: Handle this case
+                }
+
+                // Check if the peer gets banned. Disconnected peers are only banned when they
+                // misbehave, not as they are slowly forgotten.
+                if info.reputation < MIN_REP_BEFORE_BAN && info.connection_status.is_connected() {
+                    ban_queue.push(id.clone());
                 }
-                Unknown => {} //TODO: Handle this case
             }
-            // Check if the peer gets banned or unbanned and if it should be disconnected
-            if info.reputation < _MIN_REP_BEFORE_BAN && !info.connection_status.is_banned() {
-                // This peer gets banned. Check if we should request disconnection
-                ban_queue.push(id.clone());
-            } else if info.reputation >= _MIN_REP_BEFORE_BAN && info.connection_status.is_banned() {
-                // This peer gets unbanned
-                unban_queue.push(id.clone());
+
+            // Disconnect the lowest scoring peers if we have too many
+            let connected = pdb.best_peers_by_status(|status| status.is_connected());
+            if connected.len() > self.max_peers {
+                disconnect_queue.extend(
+                    connected
+                        .into_iter()
+                        .skip(self.max_peers)
+                        .map(|(id, _)| id.clone())
+                        .filter(|id| !ban_queue.contains(id)),
+                );
             }
         }
 
         for id in ban_queue {
-            pdb.ban(&id);
-
-            self.events.push(PeerManagerEvent::_BanPeer(id.clone()));
+            self.ban_for_reputation(&id);
         }
 
-        for id in unban_queue {
-            pdb.disconnect(&id);
+        for id in disconnect_queue {
+            debug!(self.log, "Disconnecting peer as we have too many peers"; "peer_id" => id.to_string(), "max_peers" => self.max_peers);
+            self.events.push(PeerManagerEvent::DisconnectPeer(
+                id,
+                GoodbyeReason::TooManyPeers,
+            ));
         }
-
-        self._last_updated = Instant::now();
     }
 }
 
@@ -459,6 +520,11 @@ impl<TSpec: EthSpec> Stream for PeerManager<TSpec> {
     type Item = PeerManagerEvent;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // update the reputations of all peers on each heartbeat
+        while let Poll::Ready(_) = self.heartbeat.poll_tick(cx) {
+            self.update_reputations(Instant::now());
+        }
+
         // poll the timeouts for pings and status'
         loop {
             match self.ping_peers.poll_next_unpin(cx) {
@@ -504,3 +570,161 @@ enum ConnectingType {
     /// We have successfully dialed a peer.
     OutgoingConnected,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::{build_enr, CombinedKey, CombinedKeyExt};
+    use crate::NetworkConfig;
+    use libp2p::core::identity::Keypair;
+    use slog::{o, Drain};
+    use types::{EnrForkId, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn build_peer_manager(max_peers: usize) -> PeerManager<E> {
+        let log = slog::Logger::root(slog::Discard.fuse(), o!());
+        let config = NetworkConfig::default();
+        let enr_key = CombinedKey::from_libp2p(&Keypair::generate_secp256k1()).unwrap();
+        let enr = build_enr::<E>(&enr_key, &config, EnrForkId::default()).unwrap();
+        let network_globals = Arc::new(NetworkGlobals::new(enr, 0, 0, &log));
+
+        PeerManager::new(network_globals, max_peers, &log)
+    }
+
+    /// Connects a new peer with the given reputation.
+    fn connect_peer(pm: &mut PeerManager<E>, reputation: Rep) -> PeerId {
+        let peer_id = PeerId::random();
+        pm.connect_ingoing(&peer_id);
+        pm.network_globals
+            .peers
+            .write()
+            .set_reputation(&peer_id, reputation);
+        peer_id
+    }
+
+    fn reputation(pm: &PeerManager<E>, peer_id: &PeerId) -> Rep {
+        pm.network_globals.peers.read().reputation(peer_id)
+    }
+
+    fn is_ban(event: &PeerManagerEvent, peer_id: &PeerId) -> bool {
+        match event {
+            PeerManagerEvent::BanPeer(id, duration) => {
+                id == peer_id && *duration == Duration::from_secs(REPUTATION_BAN_TIMEOUT)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_too_many_peers_disconnect(event: &PeerManagerEvent, peer_id: &PeerId) -> bool {
+        match event {
+            PeerManagerEvent::DisconnectPeer(id, reason) => {
+                id == peer_id && *reason == GoodbyeReason::TooManyPeers
+            }
+            _ => false,
+        }
+    }
+
+    #[tokio::test]
+    async fn report_peer_bans_below_min_rep() {
+        let mut pm = build_peer_manager(50);
+        let peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION);
+
+        for _ in 0..4 {
+            pm.report_peer(&peer_id, PeerAction::LowToleranceError);
+        }
+        assert_eq!(reputation(&pm, &peer_id), MIN_REP_BEFORE_BAN);
+        assert!(pm.events.is_empty());
+
+        pm.report_peer(&peer_id, PeerAction::LowToleranceError);
+        assert_eq!(pm.events.len(), 1);
+        assert!(is_ban(&pm.events[0], &peer_id));
+        assert!(pm.network_globals.peers.read().is_banned(&peer_id));
+
+        // A peer which is already banned is not banned again.
+        pm.report_peer(&peer_id, PeerAction::LowToleranceError);
+        assert_eq!(pm.events.len(), 1);
+
+        let fatal_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION);
+        pm.report_peer(&fatal_peer_id, PeerAction::Fatal);
+        assert_eq!(pm.events.len(), 2);
+        assert!(is_ban(&pm.events[1], &fatal_peer_id));
+    }
+
+    #[tokio::test]
+    async fn update_reputations_decays_towards_default() {
+        let mut pm = build_peer_manager(50);
+        let low_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION - 20);
+        let high_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION + 20);
+        let nearly_default_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION - 1);
+        let disconnected_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION);
+        pm.notify_disconnect(&disconnected_peer_id);
+
+        // No time has passed, so there is no decay.
+        let last_decayed = pm.last_decayed;
+        pm.update_reputations(last_decayed);
+        assert_eq!(reputation(&pm, &low_peer_id), DEFAULT_REPUTATION - 20);
+
+        pm.update_reputations(last_decayed + Duration::from_secs(2 * DECAY_INTERVAL));
+        assert_eq!(reputation(&pm, &low_peer_id), DEFAULT_REPUTATION - 18);
+        assert_eq!(reputation(&pm, &high_peer_id), DEFAULT_REPUTATION + 18);
+        assert_eq!(reputation(&pm, &nearly_default_peer_id), DEFAULT_REPUTATION);
+        assert_eq!(
+            reputation(&pm, &disconnected_peer_id),
+            DEFAULT_REPUTATION - 2
+        );
+        assert!(pm.events.is_empty());
+
+        // The decay is only applied once for each interval.
+        assert_eq!(
+            pm.last_decayed,
+            last_decayed + Duration::from_secs(2 * DECAY_INTERVAL)
+        );
+        pm.update_reputations(last_decayed + Duration::from_secs(2 * DECAY_INTERVAL));
+        assert_eq!(reputation(&pm, &low_peer_id), DEFAULT_REPUTATION - 18);
+    }
+
+    #[tokio::test]
+    async fn update_reputations_bans_connected_peers_below_min_rep() {
+        let mut pm = build_peer_manager(50);
+        let bad_peer_id = connect_peer(&mut pm, MIN_REP_BEFORE_BAN - 5);
+        let good_peer_id = connect_peer(&mut pm, MIN_REP_BEFORE_BAN);
+
+        let last_decayed = pm.last_decayed;
+        pm.update_reputations(last_decayed + Duration::from_secs(DECAY_INTERVAL));
+
+        assert_eq!(reputation(&pm, &bad_peer_id), MIN_REP_BEFORE_BAN - 4);
+        assert_eq!(pm.events.len(), 1);
+        assert!(is_ban(&pm.events[0], &bad_peer_id));
+        assert!(!pm.network_globals.peers.read().is_banned(&good_peer_id));
+    }
+
+    #[tokio::test]
+    async fn update_reputations_disconnects_lowest_scoring_peers() {
+        let mut pm = build_peer_manager(2);
+        let best_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION + 20);
+        let good_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION + 10);
+        let worse_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION - 10);
+        let worst_peer_id = connect_peer(&mut pm, DEFAULT_REPUTATION - 20);
+
+        let last_decayed = pm.last_decayed;
+        pm.update_reputations(last_decayed);
+
+        assert_eq!(pm.events.len(), 2);
+        for peer_id in &[worse_peer_id, worst_peer_id] {
+            assert!(pm
+                .events
+                .iter()
+                .any(|event| is_too_many_peers_disconnect(event, peer_id)));
+        }
+        for peer_id in &[best_peer_id, good_peer_id] {
+            assert!(!pm
+                .events
+                .iter()
+                .any(|event| is_too_many_peers_disconnect(event, peer_id)));
+        }
+
+        // Excess peers are disconnected without being banned.
+        assert_eq!(pm.network_globals.peers.read().banned_peers().count(), 0);
+    }
+}
@@ -7,7 +7,7 @@ use serde::{
     ser::{SerializeStructVariant, Serializer},
     Serialize,
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use types::{EthSpec, SubnetId};
 use PeerConnectionStatus::*;
 
@@ -94,6 +94,8 @@ pub enum PeerConnectionStatus {
     Banned {
         /// moment when the peer was banned.
         since: Instant,
+        /// moment when the ban expires, if it is temporary.
+        until: Option<Instant>,
     },
     /// We are currently dialing this peer.
     Dialing {
@@ -119,7 +121,7 @@ impl Serialize for PeerConnectionStatus {
                 s.serialize_field("since", &since.elapsed().as_secs())?;
                 s.end()
             }
-            Banned { since } => {
+            Banned { since, .. } => {
                 let mut s = serializer.serialize_struct_variant("", 2, "Banned", 1)?;
                 s.serialize_field("since", &since.elapsed().as_secs())?;
                 s.end()
@@ -202,10 +204,12 @@ impl PeerConnectionStatus {
         };
     }
 
-    /// Modifies the status to Banned
-    pub fn ban(&mut self) {
+    /// Modifies the status to Banned, for `duration` if the ban is temporary
+    pub fn ban(&mut self, duration: Option<Duration>) {
+        let since = Instant::now();
         *self = Banned {
-            since: Instant::now(),
+            since,
+            until: duration.and_then(|duration| since.checked_add(duration)),
         };
     }
 
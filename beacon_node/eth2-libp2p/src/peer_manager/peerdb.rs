@@ -4,7 +4,7 @@ use crate::rpc::methods::MetaData;
 use crate::PeerId;
 use slog::{crit, debug, warn};
 use std::collections::{hash_map::Entry, HashMap};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use types::{EthSpec, SubnetId};
 
 /// A peer's reputation (perceived potential usefulness)
@@ -77,7 +77,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
     }
 
     /// Returns an iterator over all peers in the db.
-    pub(super) fn _peers_mut(&mut self) -> impl Iterator<Item = (&PeerId, &mut PeerInfo<TSpec>)> {
+    pub(super) fn peers_mut(&mut self) -> impl Iterator<Item = (&PeerId, &mut PeerInfo<TSpec>)> {
         self.peers.iter_mut()
     }
 
@@ -221,8 +221,31 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Returns if the peer is currently banned.
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        if let Some(PeerConnectionStatus::Banned { .. }) = self.connection_status(peer_id) {
+            true
+        } else {
+            false
+        }
+    }
+
     /* Setters */
 
+    /// Inserts a previously known peer as disconnected with the given reputation, unless the peer
+    /// is already known.
+    pub fn restore_peer(&mut self, peer_id: &PeerId, reputation: Rep) {
+        if let Entry::Vacant(entry) = self.peers.entry(peer_id.clone()) {
+            let mut info = PeerInfo::default();
+            info.reputation = reputation;
+            info.connection_status.disconnect();
+            entry.insert(info);
+            self.n_dc += 1;
+            debug!(self.log, "Peer restored in db"; "peer_id" => peer_id.to_string(), "reputation" => reputation, "n_dc" => self.n_dc);
+            self.shrink_to_fit();
+        }
+    }
+
     /// A peer is being dialed.
     pub fn dialing_peer(&mut self, peer_id: &PeerId) {
         let info = self.peers.entry(peer_id.clone()).or_default();
@@ -291,8 +314,8 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
-    /// Sets a peer as banned
-    pub fn ban(&mut self, peer_id: &PeerId) {
+    /// Sets a peer as banned, for `duration` if the ban is temporary
+    pub fn ban(&mut self, peer_id: &PeerId, duration: Option<Duration>) {
         let log_ref = &self.log;
         let info = self.peers.entry(peer_id.clone()).or_insert_with(|| {
             warn!(log_ref, "Banning unknown peer";
@@ -303,7 +326,20 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             self.n_dc = self.n_dc.saturating_sub(1);
         }
         debug!(self.log, "Peer banned"; "peer_id" => peer_id.to_string(), "n_dc" => self.n_dc);
-        info.connection_status.ban();
+        info.connection_status.ban(duration);
+    }
+
+    /// Sets a banned peer as disconnected. Returns `false` if the peer was not banned.
+    pub fn unban(&mut self, peer_id: &PeerId) -> bool {
+        let info = match self.peers.get_mut(peer_id) {
+            Some(info) if info.connection_status.is_banned() => info,
+            _ => return false,
+        };
+        info.connection_status.disconnect();
+        self.n_dc += 1;
+        debug!(self.log, "Peer unbanned"; "peer_id" => peer_id.to_string(), "n_dc" => self.n_dc);
+        self.shrink_to_fit();
+        true
     }
 
     /// Add the meta data of a peer.
@@ -529,7 +565,7 @@ mod tests {
         pdb.disconnect(&random_peer);
         assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
 
-        pdb.ban(&random_peer);
+        pdb.ban(&random_peer, None);
         assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
         pdb.disconnect(&random_peer);
         assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
@@ -539,4 +575,45 @@ mod tests {
         pdb.disconnect(&random_peer);
         assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
     }
+
+    #[test]
+    fn test_unban() {
+        let mut pdb = get_db();
+
+        let random_peer = PeerId::random();
+
+        pdb.connect_ingoing(&random_peer);
+        pdb.ban(&random_peer, Some(Duration::from_secs(60)));
+        assert!(pdb.banned_peers().any(|peer_id| *peer_id == random_peer));
+        assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
+
+        assert!(pdb.unban(&random_peer));
+        assert_eq!(pdb.banned_peers().count(), 0);
+        assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
+
+        // A peer which is not banned cannot be unbanned.
+        assert!(!pdb.unban(&random_peer));
+        assert!(!pdb.unban(&PeerId::random()));
+    }
+
+    #[test]
+    fn test_restore_peer() {
+        let mut pdb = get_db();
+
+        let restored_peer = PeerId::random();
+        pdb.restore_peer(&restored_peer, 20);
+        assert_eq!(pdb.reputation(&restored_peer), 20);
+        assert!(pdb
+            .disconnected_peers()
+            .any(|peer_id| *peer_id == restored_peer));
+        assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
+
+        // A known peer is left untouched.
+        let connected_peer = PeerId::random();
+        pdb.connect_ingoing(&connected_peer);
+        pdb.restore_peer(&connected_peer, 20);
+        assert_eq!(pdb.reputation(&connected_peer), DEFAULT_REPUTATION);
+        assert!(pdb.is_connected(&connected_peer));
+        assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
+    }
 }
@@ -63,6 +63,9 @@ pub enum GoodbyeReason {
     /// Error/fault in the RPC.
     Fault = 3,
 
+    /// This node is connected to too many peers.
+    TooManyPeers = 129,
+
     /// Unknown reason.
     Unknown = 0,
 }
@@ -73,6 +76,7 @@ impl From<u64> for GoodbyeReason {
             1 => GoodbyeReason::ClientShutdown,
             2 => GoodbyeReason::IrrelevantNetwork,
             3 => GoodbyeReason::Fault,
+            129 => GoodbyeReason::TooManyPeers,
             _ => GoodbyeReason::Unknown,
         }
     }
@@ -319,6 +323,7 @@ impl std::fmt::Display for GoodbyeReason {
             GoodbyeReason::ClientShutdown => write!(f, "Client Shutdown"),
             GoodbyeReason::IrrelevantNetwork => write!(f, "Irrelevant Network"),
             GoodbyeReason::Fault => write!(f, "Fault"),
+            GoodbyeReason::TooManyPeers => write!(f, "Too Many Peers"),
             GoodbyeReason::Unknown => write!(f, "Unknown Reason"),
         }
     }
@@ -1,7 +1,8 @@
 use crate::behaviour::{Behaviour, BehaviourEvent};
 use crate::discovery::enr;
 use crate::multiaddr::Protocol;
-use crate::types::{error, GossipKind};
+use crate::rpc::{methods::GoodbyeReason, RPCEvent, RPCRequest};
+use crate::types::{error, Enr, GossipKind};
 use crate::EnrExt;
 use crate::{NetworkConfig, NetworkGlobals};
 use futures::prelude::*;
@@ -19,13 +20,14 @@ use libp2p::{
     PeerId, Swarm, Transport,
 };
 use slog::{crit, debug, info, o, trace, warn};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{Error, ErrorKind};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::DelayQueue;
+use tokio::time::delay_queue::{DelayQueue, Key};
 use types::{EnrForkId, EthSpec};
 
 pub const NETWORK_KEY_FILENAME: &str = "key";
@@ -75,9 +77,16 @@ pub struct Service<TSpec: EthSpec> {
     /// A current list of peers to ban after a given timeout.
     peers_to_ban: DelayQueue<PeerId>,
 
+    /// A current list of peers to disconnect, without banning, after a given timeout.
+    peers_to_disconnect: DelayQueue<PeerId>,
+
     /// A list of timeouts after which peers become unbanned.
     peer_ban_timeout: DelayQueue<PeerId>,
 
+    /// The keys into `peer_ban_timeout` of all currently banned peers, used to extend or lift a
+    /// ban before it expires.
+    peer_ban_keys: HashMap<PeerId, Key>,
+
     /// The libp2p logger handle.
     pub log: slog::Logger,
 }
@@ -211,7 +220,9 @@ impl<TSpec: EthSpec> Service<TSpec> {
             swarm,
             network_globals: network_globals.clone(),
             peers_to_ban: DelayQueue::new(),
+            peers_to_disconnect: DelayQueue::new(),
             peer_ban_timeout: DelayQueue::new(),
+            peer_ban_keys: HashMap::new(),
             log,
         };
 
@@ -221,11 +232,107 @@ impl<TSpec: EthSpec> Service<TSpec> {
     /// Adds a peer to be banned for a period of time, specified by a timeout.
     pub fn disconnect_and_ban_peer(&mut self, peer_id: PeerId, timeout: Duration) {
         warn!(self.log, "Disconnecting and banning peer"; "peer_id" => peer_id.to_string(), "timeout" => format!("{:?}", timeout));
+        self.swarm.peer_manager().ban_peer(&peer_id, Some(timeout));
+
+        if let Some(key) = self.peer_ban_keys.get(&peer_id) {
+            // the peer is already banned, extend (or shorten) the existing ban
+            self.peer_ban_timeout.reset(key, timeout);
+            return;
+        }
+
         self.peers_to_ban.insert(
             peer_id.clone(),
             Duration::from_millis(BAN_PEER_WAIT_TIMEOUT),
         );
-        self.peer_ban_timeout.insert(peer_id, timeout);
+        let key = self.peer_ban_timeout.insert(peer_id.clone(), timeout);
+        self.peer_ban_keys.insert(peer_id, key);
+    }
+
+    /// Sends a Goodbye with the given reason to a peer, then disconnects and bans it for
+    /// `timeout`.
+    pub fn goodbye_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason, timeout: Duration) {
+        self.swarm.send_rpc(
+            peer_id.clone(),
+            RPCEvent::Request(0, RPCRequest::Goodbye(reason)),
+        );
+        self.disconnect_and_ban_peer(peer_id, timeout);
+    }
+
+    /// Sends a Goodbye with the given reason to a peer, then disconnects it without banning it.
+    ///
+    /// The peer is free to reconnect once the disconnect has taken effect.
+    pub fn disconnect_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
+        debug!(self.log, "Disconnecting peer"; "peer_id" => peer_id.to_string(), "reason" => reason.to_string());
+        self.swarm.send_rpc(
+            peer_id.clone(),
+            RPCEvent::Request(0, RPCRequest::Goodbye(reason)),
+        );
+        // give the Goodbye time to be sent before closing the connection
+        self.peers_to_disconnect
+            .insert(peer_id, Duration::from_millis(BAN_PEER_WAIT_TIMEOUT));
+    }
+
+    /// Lifts the ban on a peer before its timeout expires.
+    ///
+    /// Returns `false` if the peer was not banned.
+    pub fn unban_peer(&mut self, peer_id: &PeerId) -> bool {
+        let was_banned = match self.peer_ban_keys.remove(peer_id) {
+            Some(key) => {
+                self.peer_ban_timeout.remove(&key);
+                true
+            }
+            None => false,
+        };
+
+        // the `PeerDB` may also contain bans which were not issued through this service
+        if self.swarm.peer_manager().unban_peer(peer_id) || was_banned {
+            self.swarm.peer_unbanned(peer_id);
+            Swarm::unban_peer_id(&mut self.swarm, peer_id.clone());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Attempts to dial a peer at the given address.
+    pub fn dial(&mut self, multiaddr: Multiaddr) {
+        match Swarm::dial_addr(&mut self.swarm, multiaddr.clone()) {
+            Ok(()) => {
+                debug!(self.log, "Dialing libp2p peer"; "address" => format!("{}", multiaddr))
+            }
+            Err(err) => debug!(
+                self.log,
+                "Could not connect to peer";
+                "address" => format!("{}", multiaddr),
+                "error" => format!("{:?}", err)
+            ),
+        };
+    }
+
+    /// Adds an ENR to the discovery routing table and dials each of its TCP addresses, unless
+    /// the peer is already connected or being dialed.
+    pub fn dial_enr(&mut self, enr: Enr) {
+        let peer_id = enr.peer_id();
+        let multiaddrs = enr.multiaddr();
+        self.swarm.add_enr(enr);
+
+        if self
+            .network_globals
+            .peers
+            .read()
+            .is_connected_or_dialing(&peer_id)
+        {
+            return;
+        }
+
+        for multiaddr in multiaddrs {
+            // ignore udp multiaddr if it exists
+            let components = multiaddr.iter().collect::<Vec<_>>();
+            if let Protocol::Udp(_) = components[1] {
+                continue;
+            }
+            self.dial(multiaddr);
+        }
     }
 
     pub async fn next_event(&mut self) -> Libp2pEvent<TSpec> {
@@ -328,16 +435,37 @@ impl<TSpec: EthSpec> Service<TSpec> {
             }
             Some(Ok(peer_to_ban)) = self.peers_to_ban.next() => {
                 let peer_id = peer_to_ban.into_inner();
-                Swarm::ban_peer_id(&mut self.swarm, peer_id.clone());
-                // TODO: Correctly notify protocols of the disconnect
-                // TODO: Also remove peer from the DHT: https://github.com/sigp/lighthouse/issues/629
-                self.swarm.inject_disconnected(&peer_id);
-                // inform the behaviour that the peer has been banned
-                self.swarm.peer_banned(peer_id);
+                // the ban may have been lifted before it took effect
+                if self.peer_ban_keys.contains_key(&peer_id) {
+                    Swarm::ban_peer_id(&mut self.swarm, peer_id.clone());
+                    // TODO: Correctly notify protocols of the disconnect
+                    // TODO: Also remove peer from the DHT: https://github.com/sigp/lighthouse/issues/629
+                    self.swarm.inject_disconnected(&peer_id);
+                    // inform the behaviour that the peer has been banned
+                    self.swarm.peer_banned(peer_id);
+                }
+            }
+            Some(Ok(peer_to_disconnect)) = self.peers_to_disconnect.next() => {
+                let peer_id = peer_to_disconnect.into_inner();
+                // the peer may have already disconnected, or been banned, which disconnects it
+                if !self.peer_ban_keys.contains_key(&peer_id)
+                    && self.network_globals.peers.read().is_connected(&peer_id)
+                {
+                    // libp2p only closes all connections to a peer when it is banned, so the peer
+                    // is banned and then immediately unbanned
+                    Swarm::ban_peer_id(&mut self.swarm, peer_id.clone());
+                    Swarm::unban_peer_id(&mut self.swarm, peer_id.clone());
+                    // no events are emitted for the closed connections, so notify the behaviours
+                    // and the peer manager directly
+                    self.swarm.inject_disconnected(&peer_id);
+                    self.swarm.peer_manager().notify_disconnect(&peer_id);
+                }
             }
             Some(Ok(peer_to_unban)) = self.peer_ban_timeout.next() => {
                 debug!(self.log, "Peer has been unbanned"; "peer" => format!("{:?}", peer_to_unban));
                 let unban_peer = peer_to_unban.into_inner();
+                self.peer_ban_keys.remove(&unban_peer);
+                self.swarm.peer_manager().unban_peer(&unban_peer);
                 self.swarm.peer_unbanned(&unban_peer);
                 Swarm::unban_peer_id(&mut self.swarm, unban_peer);
             }
@@ -8,6 +8,7 @@ pub mod service;
 mod attestation_service;
 mod metrics;
 mod persisted_dht;
+mod persisted_peers;
 mod router;
 mod sync;
 
@@ -20,6 +20,11 @@ lazy_static! {
         "network_gossip_aggregated_attestations_rx_total",
         "Count of gossip aggregated attestations received"
     );
+    pub static ref GOSSIP_MESSAGES_RX: Result<IntCounterVec> = try_create_int_counter_vec(
+        "network_gossip_messages_rx_total",
+        "Count of gossip messages received, per topic kind",
+        &["kind"]
+    );
 
     /*
      * Gossip Tx
@@ -36,4 +41,18 @@ lazy_static! {
         "network_gossip_aggregated_attestations_tx_total",
         "Count of gossip aggregated attestations transmitted"
     );
+    pub static ref GOSSIP_MESSAGES_TX: Result<IntCounterVec> = try_create_int_counter_vec(
+        "network_gossip_messages_tx_total",
+        "Count of gossip messages transmitted, per topic kind",
+        &["kind"]
+    );
+
+    /*
+     * RPC
+     */
+    pub static ref RPC_REQUEST_PROCESSING_TIME: Result<HistogramVec> = try_create_histogram_vec(
+        "network_rpc_request_processing_seconds",
+        "Time taken to process an RPC request received from a peer, per protocol",
+        &["protocol"]
+    );
 }
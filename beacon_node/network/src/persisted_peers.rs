@@ -0,0 +1,160 @@
+use eth2_libp2p::{PeerConnectionStatus, PeerDB, PeerId, DEFAULT_REPUTATION};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use store::{DBColumn, Error as StoreError, SimpleStoreItem, Store};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PersistedPeers`.
+pub const PEERS_DB_KEY: &str = "PERSISTEDPEERSPERSISTEDPEERSPERS";
+
+pub fn load_peers<T: Store<E>, E: EthSpec>(store: Arc<T>) -> Vec<PersistedPeer> {
+    // Load peers from store
+    let key = Hash256::from_slice(&PEERS_DB_KEY.as_bytes());
+    match store.get(&key) {
+        Ok(Some(p)) => {
+            let p: PersistedPeers = p;
+            p.peers
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attempt to persist the reputations and bans of peers to `self.store`.
+pub fn persist_peers<T: Store<E>, E: EthSpec>(
+    store: Arc<T>,
+    peers: PersistedPeers,
+) -> Result<(), store::Error> {
+    let key = Hash256::from_slice(&PEERS_DB_KEY.as_bytes());
+    store.put(&key, &peers)?;
+    Ok(())
+}
+
+/// The reputation of a peer and the remainder of its ban, if it is banned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistedPeer {
+    pub peer_id: PeerId,
+    pub reputation: u8,
+    pub ban_timeout: Option<Duration>,
+}
+
+impl Encodable for PersistedPeer {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(3);
+        s.append(&self.peer_id.clone().into_bytes());
+        s.append(&self.reputation);
+        // a ban of zero seconds is equivalent to no ban
+        s.append(
+            &self
+                .ban_timeout
+                .map_or(0, |ban_timeout| ban_timeout.as_secs()),
+        );
+    }
+}
+
+impl Decodable for PersistedPeer {
+    fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+        let peer_id = PeerId::from_bytes(rlp.val_at(0)?)
+            .map_err(|_| DecoderError::Custom("Invalid peer id"))?;
+        let ban_secs: u64 = rlp.val_at(2)?;
+
+        Ok(PersistedPeer {
+            peer_id,
+            reputation: rlp.val_at(1)?,
+            ban_timeout: if ban_secs > 0 {
+                Some(Duration::from_secs(ban_secs))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+/// Wrapper around the peers with a non-default reputation or a temporary ban for persistence to
+/// disk.
+pub struct PersistedPeers {
+    pub peers: Vec<PersistedPeer>,
+}
+
+impl PersistedPeers {
+    /// Collects the peers in `peer_db` worth remembering across restarts.
+    ///
+    /// Permanently banned peers are not persisted, since a ban must have a timeout to be
+    /// restored.
+    pub fn from_peer_db<E: EthSpec>(peer_db: &PeerDB<E>) -> Self {
+        let now = Instant::now();
+        let peers = peer_db
+            .peers()
+            .filter_map(|(peer_id, info)| {
+                let ban_timeout = match info.connection_status {
+                    PeerConnectionStatus::Banned { until, .. } => {
+                        Some(until?.checked_duration_since(now)?)
+                    }
+                    _ if info.reputation != DEFAULT_REPUTATION => None,
+                    _ => return None,
+                };
+
+                Some(PersistedPeer {
+                    peer_id: peer_id.clone(),
+                    reputation: info.reputation,
+                    ban_timeout,
+                })
+            })
+            .collect();
+
+        PersistedPeers { peers }
+    }
+}
+
+impl SimpleStoreItem for PersistedPeers {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerReputations
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        rlp::encode_list(&self.peers)
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let rlp = rlp::Rlp::new(bytes);
+        let peers: Vec<PersistedPeer> = rlp
+            .as_list()
+            .map_err(|e| StoreError::RlpError(format!("{}", e)))?;
+        Ok(PersistedPeers { peers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use store::MemoryStore;
+    use types::MinimalEthSpec;
+
+    #[test]
+    fn test_persisted_peers() {
+        let store = Arc::new(MemoryStore::<MinimalEthSpec>::open());
+        let peers = vec![
+            PersistedPeer {
+                peer_id: PeerId::random(),
+                reputation: 30,
+                ban_timeout: None,
+            },
+            PersistedPeer {
+                peer_id: PeerId::random(),
+                reputation: 0,
+                ban_timeout: Some(Duration::from_secs(600)),
+            },
+        ];
+        let key = Hash256::from_slice(&PEERS_DB_KEY.as_bytes());
+        store
+            .put(
+                &key,
+                &PersistedPeers {
+                    peers: peers.clone(),
+                },
+            )
+            .unwrap();
+        let persisted: PersistedPeers = store.get(&key).unwrap().unwrap();
+        assert_eq!(persisted.peers, peers);
+    }
+}
@@ -6,8 +6,8 @@
 
 pub mod processor;
 
-use crate::error;
 use crate::service::NetworkMessage;
+use crate::{error, metrics};
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockError};
 use eth2_libp2p::{
     rpc::{RPCCodedResponse, RPCRequest, RPCResponse, RequestId, ResponseTermination},
@@ -139,6 +139,10 @@ impl<T: BeaconChainTypes> Router<T> {
         request_id: RequestId,
         request: RPCRequest<T::EthSpec>,
     ) {
+        let _timer = metrics::start_timer_vec(
+            &metrics::RPC_REQUEST_PROCESSING_TIME,
+            &[&request.protocol().to_string()],
+        );
         match request {
             RPCRequest::Status(status_message) => {
                 self.processor
@@ -287,23 +291,45 @@ impl<T: BeaconChainTypes> Router<T> {
                     }
                 }
             }
-            PubsubMessage::VoluntaryExit(_exit) => {
-                // TODO: Apply more sophisticated validation
-                self.propagate_message(id, peer_id.clone());
-                // TODO: Handle exits
-                debug!(self.log, "Received a voluntary exit"; "peer_id" => format!("{}", peer_id) );
+            PubsubMessage::VoluntaryExit(exit) => {
+                debug!(self.log, "Received a voluntary exit"; "peer_id" => format!("{}", peer_id));
+                if let Some(verified_exit) = self
+                    .processor
+                    .verify_voluntary_exit_for_gossip(&peer_id, *exit)
+                {
+                    self.propagate_message(id, peer_id);
+                    self.processor.import_verified_voluntary_exit(verified_exit);
+                }
             }
-            PubsubMessage::ProposerSlashing(_proposer_slashing) => {
-                // TODO: Apply more sophisticated validation
-                self.propagate_message(id, peer_id.clone());
-                // TODO: Handle proposer slashings
-                debug!(self.log, "Received a proposer slashing"; "peer_id" => format!("{}", peer_id) );
+            PubsubMessage::ProposerSlashing(proposer_slashing) => {
+                debug!(
+                    self.log,
+                    "Received a proposer slashing";
+                    "peer_id" => format!("{}", peer_id)
+                );
+                if let Some(verified_proposer_slashing) = self
+                    .processor
+                    .verify_proposer_slashing_for_gossip(&peer_id, *proposer_slashing)
+                {
+                    self.propagate_message(id, peer_id);
+                    self.processor
+                        .import_verified_proposer_slashing(verified_proposer_slashing);
+                }
             }
-            PubsubMessage::AttesterSlashing(_attester_slashing) => {
-                // TODO: Apply more sophisticated validation
-                self.propagate_message(id, peer_id.clone());
-                // TODO: Handle attester slashings
-                debug!(self.log, "Received an attester slashing"; "peer_id" => format!("{}", peer_id) );
+            PubsubMessage::AttesterSlashing(attester_slashing) => {
+                debug!(
+                    self.log,
+                    "Received an attester slashing";
+                    "peer_id" => format!("{}", peer_id)
+                );
+                if let Some(verified_attester_slashing) = self
+                    .processor
+                    .verify_attester_slashing_for_gossip(&peer_id, *attester_slashing)
+                {
+                    self.propagate_message(id, peer_id);
+                    self.processor
+                        .import_verified_attester_slashing(verified_attester_slashing);
+                }
             }
         }
     }
@@ -5,6 +5,7 @@ use beacon_chain::{
         Error as AttnError, IntoForkChoiceVerifiedAttestation, VerifiedAggregatedAttestation,
         VerifiedUnaggregatedAttestation,
     },
+    observed_operations::ObservationOutcome,
     BeaconChain, BeaconChainTypes, BlockError, BlockProcessingOutcome, GossipVerifiedBlock,
 };
 use eth2_libp2p::rpc::methods::*;
@@ -12,12 +13,13 @@ use eth2_libp2p::rpc::{RPCCodedResponse, RPCEvent, RPCRequest, RPCResponse, Requ
 use eth2_libp2p::{NetworkGlobals, PeerId};
 use slog::{debug, error, o, trace, warn};
 use ssz::Encode;
+use state_processing::SigVerifiedOp;
 use std::sync::Arc;
 use store::Store;
 use tokio::sync::{mpsc, oneshot};
 use types::{
-    Attestation, ChainSpec, Epoch, EthSpec, Hash256, SignedAggregateAndProof, SignedBeaconBlock,
-    Slot,
+    Attestation, AttesterSlashing, ChainSpec, Epoch, EthSpec, Hash256, ProposerSlashing,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
 };
 
 //TODO: Rate limit requests
@@ -345,20 +347,34 @@ impl<T: BeaconChainTypes> Processor<T> {
             return;
         }
 
-        let forwards_block_root_iter = match self
+        // Blocks older than the oldest block in the database (i.e., prior to a weak subjectivity
+        // checkpoint which has not yet been back-filled) cannot be served. Start from the first
+        // requested slot which is not older than the oldest block, respecting the step.
+        let oldest_block_slot = self
             .chain
-            .forwards_iter_block_roots(Slot::from(req.start_slot))
-        {
-            Ok(iter) => iter,
-            Err(e) => {
-                return error!(
-                    self.log,
-                    "Unable to obtain root iter";
-                    "error" => format!("{:?}", e)
-                )
-            }
+            .store
+            .get_anchor_info()
+            .map(|anchor| anchor.oldest_block_slot.as_u64())
+            .unwrap_or(0);
+        let start_slot = if req.start_slot < oldest_block_slot {
+            let offset = (oldest_block_slot - req.start_slot) % req.step;
+            oldest_block_slot + (req.step - offset) % req.step
+        } else {
+            req.start_slot
         };
 
+        let forwards_block_root_iter =
+            match self.chain.forwards_iter_block_roots(Slot::from(start_slot)) {
+                Ok(iter) => iter,
+                Err(e) => {
+                    return error!(
+                        self.log,
+                        "Unable to obtain root iter";
+                        "error" => format!("{:?}", e)
+                    )
+                }
+            };
+
         // pick out the required blocks, ignoring skip-slots and stepping by the step parameter;
         let mut last_block_root = None;
         let block_roots = forwards_block_root_iter
@@ -857,6 +873,146 @@ impl<T: BeaconChainTypes> Processor<T> {
         }
     }
 
+    /// Verify a voluntary exit before gossiping or processing it.
+    ///
+    /// Errors are logged at debug level.
+    pub fn verify_voluntary_exit_for_gossip(
+        &self,
+        peer_id: &PeerId,
+        voluntary_exit: SignedVoluntaryExit,
+    ) -> Option<SigVerifiedOp<SignedVoluntaryExit>> {
+        let validator_index = voluntary_exit.message.validator_index;
+
+        match self.chain.verify_voluntary_exit_for_gossip(voluntary_exit) {
+            Ok(ObservationOutcome::New(sig_verified_exit)) => Some(sig_verified_exit),
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Dropping exit for already exiting validator";
+                    "validator_index" => validator_index,
+                    "peer" => format!("{:?}", peer_id)
+                );
+                None
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Dropping invalid exit";
+                    "validator_index" => validator_index,
+                    "peer" => format!("{:?}", peer_id),
+                    "error" => format!("{:?}", e)
+                );
+                None
+            }
+        }
+    }
+
+    /// Import a verified exit into the op pool.
+    pub fn import_verified_voluntary_exit(
+        &self,
+        verified_voluntary_exit: SigVerifiedOp<SignedVoluntaryExit>,
+    ) {
+        self.chain.process_voluntary_exit(verified_voluntary_exit);
+        debug!(self.log, "Successfully imported voluntary exit");
+    }
+
+    /// Verify a proposer slashing before gossiping or processing it.
+    ///
+    /// Errors are logged at debug level.
+    pub fn verify_proposer_slashing_for_gossip(
+        &self,
+        peer_id: &PeerId,
+        proposer_slashing: ProposerSlashing,
+    ) -> Option<SigVerifiedOp<ProposerSlashing>> {
+        let validator_index = proposer_slashing.signed_header_1.message.proposer_index;
+
+        match self
+            .chain
+            .verify_proposer_slashing_for_gossip(proposer_slashing)
+        {
+            Ok(ObservationOutcome::New(verified_slashing)) => Some(verified_slashing),
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Dropping proposer slashing";
+                    "reason" => "Already seen a proposer slashing for that validator",
+                    "validator_index" => validator_index,
+                    "peer" => format!("{:?}", peer_id)
+                );
+                None
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Dropping invalid proposer slashing";
+                    "validator_index" => validator_index,
+                    "peer" => format!("{:?}", peer_id),
+                    "error" => format!("{:?}", e)
+                );
+                None
+            }
+        }
+    }
+
+    /// Import a verified proposer slashing into the op pool.
+    pub fn import_verified_proposer_slashing(
+        &self,
+        proposer_slashing: SigVerifiedOp<ProposerSlashing>,
+    ) {
+        self.chain.process_proposer_slashing(proposer_slashing);
+        debug!(self.log, "Successfully imported proposer slashing");
+    }
+
+    /// Verify an attester slashing before gossiping or processing it.
+    ///
+    /// Errors are logged at debug level.
+    pub fn verify_attester_slashing_for_gossip(
+        &self,
+        peer_id: &PeerId,
+        attester_slashing: AttesterSlashing<T::EthSpec>,
+    ) -> Option<SigVerifiedOp<AttesterSlashing<T::EthSpec>>> {
+        match self
+            .chain
+            .verify_attester_slashing_for_gossip(attester_slashing)
+        {
+            Ok(ObservationOutcome::New(verified_slashing)) => Some(verified_slashing),
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Dropping attester slashing";
+                    "reason" => "Slashings already known for all slashed validators",
+                    "peer" => format!("{:?}", peer_id)
+                );
+                None
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Dropping invalid attester slashing";
+                    "peer" => format!("{:?}", peer_id),
+                    "error" => format!("{:?}", e)
+                );
+                None
+            }
+        }
+    }
+
+    /// Import a verified attester slashing into the op pool.
+    pub fn import_verified_attester_slashing(
+        &self,
+        attester_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>>,
+    ) {
+        if let Err(e) = self.chain.process_attester_slashing(attester_slashing) {
+            debug!(
+                self.log,
+                "Error importing attester slashing";
+                "error" => format!("{:?}", e)
+            );
+        } else {
+            debug!(self.log, "Successfully imported attester slashing");
+        }
+    }
+
     /// Apply the attestation to fork choice, suppressing errors.
     ///
     /// We suppress the errors when adding an attestation to fork choice since the spec
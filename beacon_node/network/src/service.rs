@@ -1,4 +1,5 @@
 use crate::persisted_dht::{load_dht, persist_dht};
+use crate::persisted_peers::{load_peers, persist_peers, PersistedPeers};
 use crate::router::{Router, RouterMessage};
 use crate::{
     attestation_service::{AttServiceMessage, AttestationService},
@@ -7,8 +8,9 @@ use crate::{
 use crate::{error, metrics};
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2_libp2p::Service as LibP2PService;
-use eth2_libp2p::{rpc::RPCRequest, BehaviourEvent, Enr, MessageId, NetworkGlobals, PeerId};
-use eth2_libp2p::{Libp2pEvent, PubsubMessage, RPCEvent};
+use eth2_libp2p::rpc::{methods::GoodbyeReason, RPCRequest};
+use eth2_libp2p::{BehaviourEvent, Enr, MessageId, NetworkGlobals, PeerId};
+use eth2_libp2p::{Libp2pEvent, Multiaddr, PubsubMessage, RPCEvent};
 use futures::prelude::*;
 use rest_types::ValidatorSubscription;
 use slog::{debug, error, info, o, trace};
@@ -81,6 +83,17 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             libp2p.swarm.add_enr(enr);
         }
 
+        // restore the reputations of known peers, keeping bad peers banned across restarts
+        for peer in load_peers::<T::Store, T::EthSpec>(store.clone()) {
+            network_globals
+                .peers
+                .write()
+                .restore_peer(&peer.peer_id, peer.reputation);
+            if let Some(ban_timeout) = peer.ban_timeout {
+                libp2p.disconnect_and_ban_peer(peer.peer_id, ban_timeout);
+            }
+        }
+
         // launch derived network services
 
         // router task
@@ -149,6 +162,26 @@ fn spawn_service<T: BeaconChainTypes>(
                         ),
                     }
 
+                    let peers =
+                        PersistedPeers::from_peer_db(&service.network_globals.peers.read());
+                    debug!(
+                        service.log,
+                        "Persisting peer reputations to store";
+                        "Number of peers" => format!("{}", peers.peers.len()),
+                    );
+
+                    match persist_peers::<T::Store, T::EthSpec>(service.store.clone(), peers) {
+                        Err(e) => error!(
+                            service.log,
+                            "Failed to persist peer reputations on drop";
+                            "error" => format!("{:?}", e)
+                        ),
+                        Ok(_) => info!(
+                            service.log,
+                            "Saved peer reputations";
+                        ),
+                    }
+
                     info!(service.log, "Network service shutdown");
                     return;
             }
@@ -224,6 +257,23 @@ fn spawn_service<T: BeaconChainTypes>(
                                 std::time::Duration::from_secs(BAN_PEER_TIMEOUT),
                             );
                         }
+                        NetworkMessage::GoodbyePeer { peer_id, reason } => {
+                            service.libp2p.disconnect_peer(peer_id, reason);
+                        }
+                        NetworkMessage::BanPeer { peer_id, duration } => {
+                            service.libp2p.disconnect_and_ban_peer(peer_id, duration);
+                        }
+                        NetworkMessage::UnbanPeer { peer_id } => {
+                            if !service.libp2p.unban_peer(&peer_id) {
+                                debug!(service.log, "Attempted to unban a peer which is not banned"; "peer_id" => peer_id.to_string());
+                            }
+                        }
+                        NetworkMessage::Dial { multiaddr } => {
+                            service.libp2p.dial(multiaddr);
+                        }
+                        NetworkMessage::DialEnr { enr } => {
+                            service.libp2p.dial_enr(enr);
+                        }
                         NetworkMessage::Subscribe { subscriptions } => {
                             // the result is dropped as it used solely for ergonomics
                             let _ = service
@@ -281,6 +331,14 @@ fn spawn_service<T: BeaconChainTypes>(
                                     debug!(service.log, "Failed to send re-status  peer to router");
                                 });
                         }
+                        BehaviourEvent::DisconnectPeer(peer_id, reason) => {
+                            // the peer is only disconnected, so that it may reconnect once we
+                            // have room for it
+                            service.libp2p.disconnect_peer(peer_id, reason);
+                        }
+                        BehaviourEvent::BanPeer(peer_id, timeout) => {
+                            service.libp2p.goodbye_peer(peer_id, GoodbyeReason::Fault, timeout);
+                        }
                         BehaviourEvent::PubsubMessage {
                             id,
                             source,
@@ -396,11 +454,25 @@ pub enum NetworkMessage<T: EthSpec> {
     },
     /// Disconnect and bans a peer id.
     Disconnect { peer_id: PeerId },
+    /// Sends a Goodbye to a peer with the given reason, then disconnects it without banning it.
+    GoodbyePeer {
+        peer_id: PeerId,
+        reason: GoodbyeReason,
+    },
+    /// Disconnects a peer and bans it for the given duration.
+    BanPeer { peer_id: PeerId, duration: Duration },
+    /// Lifts the ban on a peer.
+    UnbanPeer { peer_id: PeerId },
+    /// Dials a peer at the given address.
+    Dial { multiaddr: Multiaddr },
+    /// Adds an ENR to the discovery routing table and dials the peer.
+    DialEnr { enr: Enr },
 }
 
 /// Inspects the `messages` that were being sent to the network and updates Prometheus metrics.
 fn expose_publish_metrics<T: EthSpec>(messages: &[PubsubMessage<T>]) {
     for message in messages {
+        metrics::inc_counter_vec(&metrics::GOSSIP_MESSAGES_TX, &[&message.kind().to_string()]);
         match message {
             PubsubMessage::BeaconBlock(_) => metrics::inc_counter(&metrics::GOSSIP_BLOCKS_TX),
             PubsubMessage::Attestation(_) => {
@@ -416,6 +488,7 @@ fn expose_publish_metrics<T: EthSpec>(messages: &[PubsubMessage<T>]) {
 
 /// Inspects a `message` received from the network and updates Prometheus metrics.
 fn expose_receive_metrics<T: EthSpec>(message: &PubsubMessage<T>) {
+    metrics::inc_counter_vec(&metrics::GOSSIP_MESSAGES_RX, &[&message.kind().to_string()]);
     match message {
         PubsubMessage::BeaconBlock(_) => metrics::inc_counter(&metrics::GOSSIP_BLOCKS_RX),
         PubsubMessage::Attestation(_) => {
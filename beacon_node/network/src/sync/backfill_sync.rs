@@ -0,0 +1,592 @@
+//! Backfill sync downloads the blocks prior to the weak subjectivity checkpoint that the beacon
+//! chain was started from, working backwards from the oldest block in the database towards
+//! genesis.
+//!
+//! Batches of blocks are requested from fully synced peers using `BlocksByRange`. Batches are
+//! numbered from the oldest block in the database, such that batch `1` contains the slots
+//! immediately prior to the oldest block, batch `2` the slots prior to those, and so on. Several
+//! batches are downloaded in parallel, however they are processed strictly in order since each
+//! batch must end with the parent of the oldest block stored by the previous batch.
+//!
+//! Processing a batch verifies the hash chain and the proposer signatures of its blocks and then
+//! stores them in the freezer database (see `BeaconChain::import_historical_block_batch`).
+//!
+//! Backfill sync only requests new batches whilst the node is synced, so that it does not
+//! compete with range sync for peers.
+
+use super::block_processor::{spawn_block_processor, BatchProcessResult, ProcessId};
+use super::network_context::SyncNetworkContext;
+use super::range_sync::{Batch, BatchId, PendingBatches};
+use crate::sync::SyncMessage;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::rpc::RequestId;
+use eth2_libp2p::{NetworkGlobals, PeerId};
+use rand::prelude::*;
+use slog::{debug, info, warn};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use store::Store;
+use tokio::sync::mpsc;
+use types::{EthSpec, SignedBeaconBlock, Slot};
+
+/// The number of epochs worth of blocks requested in each batch.
+const BACKFILL_EPOCHS_PER_BATCH: u64 = 2;
+
+/// The maximum number of batches to download ahead of the batch currently being processed.
+const BACKFILL_BATCH_BUFFER_SIZE: usize = 5;
+
+/// The number of times a batch may fail to download or process before backfill sync is restarted
+/// from the oldest block in the database.
+///
+/// A batch may fail to process through no fault of its own, if an earlier peer falsely claimed
+/// that a range of slots was empty. Restarting re-downloads any such ranges.
+const MAX_BATCH_ATTEMPTS: u8 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackFillState {
+    /// The node is not synced, no new batches will be requested.
+    Paused,
+    /// Batches are being downloaded and processed.
+    Syncing,
+    /// All blocks back to genesis are stored, or the chain was started from genesis.
+    Completed,
+}
+
+/// Downloads and processes the blocks prior to the oldest block in the database.
+pub struct BackFillSync<T: BeaconChainTypes> {
+    /// A reference to the underlying beacon chain.
+    chain: Arc<BeaconChain<T>>,
+
+    /// A reference to the network globals and peer-db.
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+
+    /// The slot of the oldest block when backfill sync was last (re)started. Batches end at
+    /// consecutive multiples of the batch size before this slot.
+    start_slot: Slot,
+
+    /// The batches that are currently awaiting a response from a peer.
+    pending_batches: PendingBatches<T::EthSpec>,
+
+    /// Batches which failed to download and are waiting for an idle peer.
+    queued_batches: VecDeque<Batch<T::EthSpec>>,
+
+    /// The batches that have been downloaded and are awaiting processing.
+    completed_batches: Vec<Batch<T::EthSpec>>,
+
+    /// The batch currently being processed, if any.
+    current_processing_batch: Option<Batch<T::EthSpec>>,
+
+    /// The next batch id that needs to be downloaded.
+    to_be_downloaded_id: BatchId,
+
+    /// The next batch id that needs to be processed.
+    to_be_processed_id: BatchId,
+
+    /// The current state of backfill sync.
+    state: BackFillState,
+
+    /// A send channel to the sync manager, given to the block processor thread.
+    sync_send: mpsc::UnboundedSender<SyncMessage<T::EthSpec>>,
+
+    /// A reference to the sync logger.
+    log: slog::Logger,
+}
+
+impl<T: BeaconChainTypes> BackFillSync<T> {
+    pub fn new(
+        chain: Arc<BeaconChain<T>>,
+        network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+        sync_send: mpsc::UnboundedSender<SyncMessage<T::EthSpec>>,
+        log: slog::Logger,
+    ) -> Self {
+        let (start_slot, state) = match chain.store.get_anchor_info() {
+            Some(anchor) if !anchor.block_backfill_complete() => {
+                (anchor.oldest_block_slot, BackFillState::Paused)
+            }
+            _ => (Slot::new(0), BackFillState::Completed),
+        };
+
+        BackFillSync {
+            chain,
+            network_globals,
+            start_slot,
+            pending_batches: PendingBatches::new(),
+            queued_batches: VecDeque::new(),
+            completed_batches: Vec::new(),
+            current_processing_batch: None,
+            to_be_downloaded_id: BatchId(1),
+            to_be_processed_id: BatchId(1),
+            state,
+            sync_send,
+            log,
+        }
+    }
+
+    /// Start (or continue) requesting batches. Called whenever the node is synced, including
+    /// when a new synced peer is found.
+    pub fn resume(&mut self, network: &mut SyncNetworkContext<T::EthSpec>) {
+        match self.state {
+            BackFillState::Completed => return,
+            BackFillState::Paused => {
+                info!(
+                    self.log,
+                    "Resuming backfill sync";
+                    "oldest_block_slot" => self.oldest_block_slot(),
+                );
+                self.state = BackFillState::Syncing;
+            }
+            BackFillState::Syncing => {}
+        }
+
+        self.request_batches(network);
+        self.process_completed_batches();
+    }
+
+    /// Stop requesting new batches. Batches which are already in-flight are still processed.
+    pub fn pause(&mut self) {
+        if self.state == BackFillState::Syncing {
+            debug!(self.log, "Pausing backfill sync");
+            self.state = BackFillState::Paused;
+        }
+    }
+
+    /// Returns `true` if `request_id` corresponds to a pending backfill batch request.
+    pub fn is_pending_request(&self, request_id: RequestId) -> bool {
+        self.pending_batches.contains(request_id)
+    }
+
+    /// A block (or stream termination) has been received for a pending backfill batch.
+    pub fn on_block_response(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        request_id: RequestId,
+        beacon_block: Option<SignedBeaconBlock<T::EthSpec>>,
+    ) {
+        if let Some(block) = beacon_block {
+            self.pending_batches.add_block(request_id, block);
+            return;
+        }
+
+        // A stream termination has been sent, the batch is complete.
+        let batch = match self.pending_batches.remove(request_id) {
+            Some(batch) => batch,
+            None => return,
+        };
+
+        debug!(
+            self.log, "Backfill batch received";
+            "id" => *batch.id,
+            "blocks" => batch.downloaded_blocks.len(),
+            "start_slot" => batch.start_slot,
+            "end_slot" => batch.end_slot,
+        );
+
+        let out_of_range = batch
+            .downloaded_blocks
+            .iter()
+            .any(|block| block.slot() < batch.start_slot || block.slot() >= batch.end_slot);
+        if out_of_range {
+            warn!(
+                self.log, "BlocksByRange response returned out of range blocks";
+                "id" => *batch.id,
+                "peer" => format!("{:?}", batch.current_peer),
+            );
+            network.downvote_peer(batch.current_peer.clone());
+            self.retry_batch(network, batch);
+            return;
+        }
+
+        let insert_index = self
+            .completed_batches
+            .binary_search(&batch)
+            .unwrap_or_else(|index| index);
+        self.completed_batches.insert(insert_index, batch);
+
+        self.request_batches(network);
+        self.process_completed_batches();
+    }
+
+    /// The block processor has finished processing a backfill batch.
+    pub fn on_batch_process_result(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        batch_id: BatchId,
+        result: &BatchProcessResult,
+    ) {
+        let mut batch = match self.current_processing_batch.take() {
+            Some(batch) if batch.id == batch_id => batch,
+            other => {
+                debug!(self.log, "Unexpected backfill batch result"; "batch_id" => *batch_id);
+                self.current_processing_batch = other;
+                return;
+            }
+        };
+
+        match result {
+            BatchProcessResult::Success => {
+                *self.to_be_processed_id += 1;
+
+                let complete = self
+                    .chain
+                    .store
+                    .get_anchor_info()
+                    .map_or(true, |anchor| anchor.block_backfill_complete());
+
+                if complete {
+                    info!(self.log, "Backfill sync completed");
+                    self.state = BackFillState::Completed;
+                    self.pending_batches = PendingBatches::new();
+                    self.queued_batches.clear();
+                    self.completed_batches.clear();
+                    return;
+                }
+
+                if self.batch_range(self.to_be_processed_id).is_none() {
+                    // Every batch back to genesis has been processed but the chain of blocks did
+                    // not reach the genesis block; some blocks were withheld.
+                    warn!(
+                        self.log, "Backfill sync did not reach genesis";
+                        "oldest_block_slot" => self.oldest_block_slot(),
+                    );
+                    self.restart(network);
+                    return;
+                }
+            }
+            BatchProcessResult::Failed | BatchProcessResult::Partial => {
+                batch.reprocess_retries += 1;
+                warn!(
+                    self.log, "Backfill batch processing failed";
+                    "id" => *batch.id,
+                    "peer" => format!("{:?}", batch.current_peer),
+                    "attempts" => batch.reprocess_retries,
+                );
+
+                if batch.reprocess_retries >= MAX_BATCH_ATTEMPTS {
+                    self.restart(network);
+                    return;
+                }
+
+                self.retry_batch(network, batch);
+            }
+        }
+
+        self.request_batches(network);
+        self.process_completed_batches();
+    }
+
+    /// An RPC error has occurred for a pending backfill batch request.
+    pub fn inject_error(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        peer_id: &PeerId,
+        request_id: RequestId,
+    ) {
+        if let Some(mut batch) = self.pending_batches.remove(request_id) {
+            batch.retries += 1;
+            debug!(
+                self.log, "Backfill batch request failed";
+                "id" => *batch.id,
+                "peer" => format!("{:?}", peer_id),
+                "retries" => batch.retries,
+            );
+
+            if batch.retries >= MAX_BATCH_ATTEMPTS {
+                self.restart(network);
+            } else {
+                self.retry_batch(network, batch);
+            }
+        }
+    }
+
+    /// A peer has disconnected, re-request any batches it was downloading.
+    pub fn peer_disconnect(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        peer_id: &PeerId,
+    ) {
+        let mut batches = vec![];
+        while let Some(batch) = self.pending_batches.remove_batch_by_peer(peer_id) {
+            batches.push(batch);
+        }
+
+        for batch in batches {
+            self.retry_batch(network, batch);
+        }
+    }
+
+    /// Discard all batches and start again from the oldest block in the database.
+    fn restart(&mut self, network: &mut SyncNetworkContext<T::EthSpec>) {
+        self.start_slot = self.oldest_block_slot();
+        self.pending_batches = PendingBatches::new();
+        self.queued_batches.clear();
+        self.completed_batches.clear();
+        self.current_processing_batch = None;
+        self.to_be_downloaded_id = BatchId(1);
+        self.to_be_processed_id = BatchId(1);
+
+        info!(
+            self.log,
+            "Restarting backfill sync";
+            "oldest_block_slot" => self.start_slot,
+        );
+
+        self.request_batches(network);
+    }
+
+    /// Returns the slot of the oldest block in the database.
+    fn oldest_block_slot(&self) -> Slot {
+        self.chain
+            .store
+            .get_anchor_info()
+            .map_or_else(|| Slot::new(0), |anchor| anchor.oldest_block_slot)
+    }
+
+    /// Returns the `(start_slot, end_slot)` of the batch with the given id, or `None` if the
+    /// batch would lie entirely before genesis.
+    fn batch_range(&self, batch_id: BatchId) -> Option<(Slot, Slot)> {
+        let slots_per_batch = T::EthSpec::slots_per_epoch() * BACKFILL_EPOCHS_PER_BATCH;
+        let end_slot = self
+            .start_slot
+            .saturating_sub(batch_id.saturating_sub(1) * slots_per_batch);
+
+        if end_slot == 0 {
+            None
+        } else {
+            Some((end_slot.saturating_sub(slots_per_batch), end_slot))
+        }
+    }
+
+    /// Sends the next batch to the block processor, if it has been downloaded and no other batch
+    /// is being processed.
+    fn process_completed_batches(&mut self) {
+        if self.current_processing_batch.is_some() {
+            return;
+        }
+
+        if !self.completed_batches.is_empty()
+            && self.completed_batches[0].id == self.to_be_processed_id
+        {
+            let mut batch = self.completed_batches.remove(0);
+            let downloaded_blocks = std::mem::replace(&mut batch.downloaded_blocks, Vec::new());
+            let process_id = ProcessId::BackFillBatchId(batch.id);
+            self.current_processing_batch = Some(batch);
+            spawn_block_processor(
+                Arc::downgrade(&self.chain),
+                process_id,
+                downloaded_blocks,
+                self.sync_send.clone(),
+                self.log.clone(),
+            );
+        }
+    }
+
+    /// Re-request a batch, preferably from a different peer.
+    fn retry_batch(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        mut batch: Batch<T::EthSpec>,
+    ) {
+        batch.downloaded_blocks.clear();
+
+        let excluded_peer = batch.current_peer.clone();
+        match self
+            .get_next_peer(Some(&excluded_peer))
+            .or_else(|| self.get_next_peer(None))
+        {
+            Some(peer_id) if self.state == BackFillState::Syncing => {
+                batch.current_peer = peer_id;
+                // A batch which fails to send is queued for the next idle peer.
+                let _ = self.send_batch(network, batch);
+            }
+            _ => self.queued_batches.push_back(batch),
+        }
+    }
+
+    /// Request as many batches as there are idle peers, up to the buffer size.
+    fn request_batches(&mut self, network: &mut SyncNetworkContext<T::EthSpec>) {
+        if self.state != BackFillState::Syncing {
+            return;
+        }
+
+        // A failed send leaves the peer idle, so stop requesting batches rather than selecting the
+        // same peer again. The failed batch is queued and retried on the next call.
+        while let Some(peer_id) = self.get_next_peer(None) {
+            if let Some(mut batch) = self.queued_batches.pop_front() {
+                batch.current_peer = peer_id;
+                if self.send_batch(network, batch).is_err() {
+                    return;
+                }
+                continue;
+            }
+
+            if self.completed_batches.len() + self.pending_batches.len()
+                >= BACKFILL_BATCH_BUFFER_SIZE
+            {
+                return;
+            }
+
+            let (start_slot, end_slot) = match self.batch_range(self.to_be_downloaded_id) {
+                Some(range) => range,
+                None => return,
+            };
+
+            let batch = Batch::new(self.to_be_downloaded_id, start_slot, end_slot, peer_id);
+            *self.to_be_downloaded_id += 1;
+            if self.send_batch(network, batch).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Returns a random synced peer which is not currently downloading a batch.
+    fn get_next_peer(&self, excluded_peer: Option<&PeerId>) -> Option<PeerId> {
+        let mut rng = rand::thread_rng();
+        let peers = self.network_globals.peers.read();
+        let mut synced_peers = peers
+            .synced_peers()
+            .filter(|peer_id| Some(*peer_id) != excluded_peer)
+            .filter(|peer_id| self.pending_batches.peer_is_idle(peer_id))
+            .collect::<Vec<_>>();
+        synced_peers.shuffle(&mut rng);
+        synced_peers.first().map(|peer_id| (*peer_id).clone())
+    }
+
+    /// Requests the provided batch from its current peer.
+    ///
+    /// If the request cannot be sent to the network service the batch is queued and an error is
+    /// returned.
+    fn send_batch(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        batch: Batch<T::EthSpec>,
+    ) -> Result<(), &'static str> {
+        let request = batch.to_blocks_by_range_request();
+        match network.blocks_by_range_request(batch.current_peer.clone(), request) {
+            Ok(request_id) => {
+                self.pending_batches.insert(request_id, batch);
+                Ok(())
+            }
+            Err(e) => {
+                debug!(
+                    self.log, "Failed to send backfill batch request";
+                    "id" => *batch.id,
+                    "peer" => format!("{:?}", batch.current_peer),
+                    "error" => e,
+                );
+                self.queued_batches.push_back(batch);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::{
+        builder::{BeaconChainBuilder, Witness},
+        eth1_chain::CachingEth1Backend,
+        events::NullEventHandler,
+        migrate::NullMigrator,
+    };
+    use eth2_libp2p::discovery::{build_enr, Keypair};
+    use eth2_libp2p::{
+        discovery::CombinedKey, CombinedKeyExt, NetworkConfig, PeerSyncStatus, SyncInfo,
+    };
+    use genesis::{generate_deterministic_keypairs, interop_genesis_state};
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use slot_clock::TestingSlotClock;
+    use std::time::Duration;
+    use store::MemoryStore;
+    use tempfile::tempdir;
+    use types::{EnrForkId, Epoch, Hash256, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    type TestBeaconChainType = Witness<
+        MemoryStore<E>,
+        NullMigrator,
+        TestingSlotClock,
+        CachingEth1Backend<E, MemoryStore<E>>,
+        E,
+        NullEventHandler<E>,
+    >;
+
+    #[test]
+    fn failed_send_does_not_loop() {
+        let log = NullLoggerBuilder.build().expect("logger should build");
+        let spec = E::default_spec();
+        let data_dir = tempdir().expect("should create temporary data_dir");
+        let keypairs = generate_deterministic_keypairs(1);
+
+        let chain: Arc<BeaconChain<TestBeaconChainType>> = Arc::new(
+            BeaconChainBuilder::new(MinimalEthSpec)
+                .logger(log.clone())
+                .custom_spec(spec.clone())
+                .store(Arc::new(MemoryStore::open()))
+                .store_migrator(NullMigrator)
+                .data_dir(data_dir.path().to_path_buf())
+                .genesis_state(
+                    interop_genesis_state::<E>(&keypairs, 0, &spec)
+                        .expect("should generate interop state"),
+                )
+                .expect("should build state using recent genesis")
+                .dummy_eth1_backend()
+                .expect("should build dummy backend")
+                .null_event_handler()
+                .testing_slot_clock(Duration::from_secs(1))
+                .expect("should configure testing slot clock")
+                .reduced_tree_fork_choice()
+                .expect("should add fork choice to builder")
+                .build()
+                .expect("should build"),
+        );
+
+        let config = NetworkConfig::default();
+        let enr_key = CombinedKey::from_libp2p(&Keypair::generate_secp256k1()).unwrap();
+        let enr = build_enr::<E>(&enr_key, &config, EnrForkId::default()).unwrap();
+        let network_globals = Arc::new(NetworkGlobals::new(enr, 0, 0, &log));
+
+        let peer_id = PeerId::random();
+        {
+            let mut peers = network_globals.peers.write();
+            peers.connect_ingoing(&peer_id);
+            peers.set_sync_status(
+                &peer_id,
+                PeerSyncStatus::Synced {
+                    info: SyncInfo {
+                        status_head_slot: Slot::new(0),
+                        status_head_root: Hash256::zero(),
+                        status_finalized_epoch: Epoch::new(0),
+                        status_finalized_root: Hash256::zero(),
+                    },
+                },
+            );
+        }
+
+        // Drop the receiver so that every request to the network service fails to send.
+        let (network_send, network_recv) = mpsc::unbounded_channel();
+        drop(network_recv);
+        let mut network =
+            SyncNetworkContext::new(network_send, network_globals.clone(), log.clone());
+        let (sync_send, _sync_recv) = mpsc::unbounded_channel();
+
+        // The chain was started from genesis, so set the oldest block as if it was not.
+        let mut backfill = BackFillSync::new(chain, network_globals, sync_send, log);
+        backfill.start_slot = Slot::new(E::slots_per_epoch() * 8);
+        backfill.state = BackFillState::Paused;
+
+        backfill.resume(&mut network);
+
+        assert_eq!(backfill.state, BackFillState::Syncing);
+        assert_eq!(backfill.pending_batches.len(), 0);
+        assert_eq!(backfill.queued_batches.len(), 1);
+
+        // Resuming again retries the queued batch without requesting any others.
+        backfill.resume(&mut network);
+
+        assert_eq!(backfill.pending_batches.len(), 0);
+        assert_eq!(backfill.queued_batches.len(), 1);
+        assert_eq!(backfill.to_be_downloaded_id, BatchId(2));
+    }
+}
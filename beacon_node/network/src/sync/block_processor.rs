@@ -15,6 +15,8 @@ pub enum ProcessId {
     RangeBatchId(ChainId, BatchId),
     /// Processing Id of the parent lookup of a block
     ParentLookup(PeerId),
+    /// Processing Id of a backfill syncing batch.
+    BackFillBatchId(BatchId),
 }
 
 /// The result of a block processing request.
@@ -110,10 +112,48 @@ pub fn spawn_block_processor<T: BeaconChainTypes>(
                     }
                 }
             }
+            // this a request from the backfill sync
+            ProcessId::BackFillBatchId(batch_id) => {
+                debug!(log, "Processing backfill batch"; "id" => *batch_id, "blocks" => downloaded_blocks.len());
+                let result = match import_historical_blocks(chain, downloaded_blocks) {
+                    Ok(imported_blocks) => {
+                        debug!(log, "Backfill batch processed"; "id" => *batch_id, "imported_blocks" => imported_blocks);
+                        BatchProcessResult::Success
+                    }
+                    Err(e) => {
+                        warn!(log, "Backfill batch processing failed"; "id" => *batch_id, "error" => e);
+                        BatchProcessResult::Failed
+                    }
+                };
+
+                sync_send
+                    .send(SyncMessage::BackFillBatchProcessed { batch_id, result })
+                    .unwrap_or_else(|_| {
+                        debug!(
+                            log,
+                            "Block processor could not inform backfill sync result. Likely shutting down."
+                        );
+                    });
+            }
         }
     });
 }
 
+/// Helper function to verify and store a batch of blocks older than the oldest block in the
+/// database.
+fn import_historical_blocks<T: BeaconChainTypes>(
+    chain: Weak<BeaconChain<T>>,
+    downloaded_blocks: Vec<SignedBeaconBlock<T::EthSpec>>,
+) -> Result<usize, String> {
+    if let Some(chain) = chain.upgrade() {
+        chain
+            .import_historical_block_batch(downloaded_blocks)
+            .map_err(|e| format!("{:?}", e))
+    } else {
+        Ok(0)
+    }
+}
+
 /// Helper function to process blocks batches which only consumes the chain and blocks to process.
 fn process_blocks<
     'a,
@@ -20,6 +20,11 @@
 //!
 //! See `RangeSync` for further details.
 //!
+//! ## Backfill Sync
+//!
+//! If the chain was started from a weak subjectivity checkpoint, the blocks prior to the
+//! checkpoint are downloaded whilst the node is synced. See `BackFillSync` for further details.
+//!
 //! ## Parent Lookup
 //!
 //! When a block with an unknown parent is received and we are in `Regular` sync mode, the block is
@@ -33,6 +38,7 @@
 //! if an attestation references an unknown block) this manager can search for the block and
 //! subsequently search for parents if needed.
 
+use super::backfill_sync::BackFillSync;
 use super::block_processor::{spawn_block_processor, BatchProcessResult, ProcessId};
 use super::network_context::SyncNetworkContext;
 use super::peer_sync_info::{PeerSyncInfo, PeerSyncType};
@@ -103,6 +109,12 @@ pub enum SyncMessage<T: EthSpec> {
         result: BatchProcessResult,
     },
 
+    /// A backfill batch has been processed by the block processor thread.
+    BackFillBatchProcessed {
+        batch_id: BatchId,
+        result: BatchProcessResult,
+    },
+
     /// A parent lookup has failed for a block given by this `peer_id`.
     ParentLookupFailed(PeerId),
 }
@@ -144,6 +156,9 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// The object handling long-range batch load-balanced syncing.
     range_sync: RangeSync<T>,
 
+    /// The object handling the download of blocks prior to a weak subjectivity checkpoint.
+    backfill_sync: BackFillSync<T>,
+
     /// A collection of parent block lookups.
     parent_queue: SmallVec<[ParentRequests<T::EthSpec>; 3]>,
 
@@ -203,6 +218,12 @@ pub fn spawn<T: BeaconChainTypes>(
             sync_send.clone(),
             log.clone(),
         ),
+        backfill_sync: BackFillSync::new(
+            beacon_chain.clone(),
+            network_globals.clone(),
+            sync_send.clone(),
+            log.clone(),
+        ),
         network: SyncNetworkContext::new(network_send, network_globals.clone(), log.clone()),
         chain: beacon_chain,
         network_globals,
@@ -526,6 +547,11 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             return;
         }
 
+        if self.backfill_sync.is_pending_request(request_id) {
+            self.backfill_sync.inject_error(&mut self.network, &peer_id, request_id);
+            return;
+        }
+
         // otherwise, this is a range sync issue, notify the range sync
         self.range_sync
             .inject_error(&mut self.network, peer_id.clone(), request_id);
@@ -533,6 +559,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
     fn peer_disconnect(&mut self, peer_id: &PeerId) {
         self.range_sync.peer_disconnect(&mut self.network, peer_id);
+        self.backfill_sync.peer_disconnect(&mut self.network, peer_id);
         self.update_sync_state();
     }
 
@@ -584,6 +611,13 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         if let Some((old_state, new_state)) = self.network_globals.update_sync_state() {
             info!(self.log, "Sync state updated"; "old_state" => format!("{}", old_state), "new_state" => format!("{}",new_state));
         }
+
+        // Only download historic blocks once we have caught up with our peers.
+        if self.network_globals.sync_state.read().is_synced() {
+            self.backfill_sync.resume(&mut self.network);
+        } else {
+            self.backfill_sync.pause();
+        }
     }
     /* Processing State Functions */
     // These functions are called in the main poll function to transition the state of the sync
@@ -742,12 +776,20 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         request_id,
                         beacon_block,
                     } => {
-                        self.range_sync.blocks_by_range_response(
-                            &mut self.network,
-                            peer_id,
-                            request_id,
-                            beacon_block.map(|b| *b),
-                        );
+                        if self.backfill_sync.is_pending_request(request_id) {
+                            self.backfill_sync.on_block_response(
+                                &mut self.network,
+                                request_id,
+                                beacon_block.map(|b| *b),
+                            );
+                        } else {
+                            self.range_sync.blocks_by_range_response(
+                                &mut self.network,
+                                peer_id,
+                                request_id,
+                                beacon_block.map(|b| *b),
+                            );
+                        }
                     }
                     SyncMessage::BlocksByRootResponse {
                         peer_id,
@@ -782,6 +824,13 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                             result,
                         );
                     }
+                    SyncMessage::BackFillBatchProcessed { batch_id, result } => {
+                        self.backfill_sync.on_batch_process_result(
+                            &mut self.network,
+                            batch_id,
+                            &result,
+                        );
+                    }
                     SyncMessage::ParentLookupFailed(peer_id) => {
                         self.network.downvote_peer(peer_id);
                     }
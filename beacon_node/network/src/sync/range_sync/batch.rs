@@ -150,6 +150,11 @@ impl<T: EthSpec> PendingBatches<T> {
         }
     }
 
+    /// Returns `true` if there is a pending batch for `request_id`.
+    pub fn contains(&self, request_id: RequestId) -> bool {
+        self.batches.contains_key(&request_id)
+    }
+
     /// The number of current pending batch requests.
     pub fn len(&self) -> usize {
         self.batches.len()
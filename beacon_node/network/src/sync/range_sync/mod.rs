@@ -9,5 +9,6 @@ mod sync_type;
 
 pub use batch::Batch;
 pub use batch::BatchId;
+pub use batch::PendingBatches;
 pub use chain::ChainId;
 pub use range::RangeSync;
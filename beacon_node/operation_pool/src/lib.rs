@@ -9,15 +9,12 @@ use attestation::AttMaxCover;
 use attestation_id::AttestationId;
 use max_cover::maximum_cover;
 use parking_lot::RwLock;
-use state_processing::per_block_processing::errors::{
-    AttestationValidationError, AttesterSlashingValidationError, ExitValidationError,
-    ProposerSlashingValidationError,
-};
+use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
-    get_slashable_indices_modular, verify_attestation_for_block_inclusion,
-    verify_attester_slashing, verify_exit, verify_exit_time_independent_only,
-    verify_proposer_slashing, VerifySignatures,
+    get_slashable_indices_modular, verify_attestation_for_block_inclusion, verify_exit,
+    VerifySignatures,
 };
+use state_processing::SigVerifiedOp;
 use std::collections::{hash_map, HashMap, HashSet};
 use std::marker::PhantomData;
 use types::{
@@ -96,6 +93,15 @@ impl<T: EthSpec> OperationPool<T> {
         self.attestations.read().values().map(Vec::len).sum()
     }
 
+    /// Returns all known attestations, without any filtering.
+    pub fn get_all_attestations(&self) -> Vec<Attestation<T>> {
+        self.attestations
+            .read()
+            .values()
+            .flat_map(|attestations| attestations.iter().cloned())
+            .collect()
+    }
+
     /// Get a list of attestations for inclusion in a block.
     ///
     /// The `validity_filter` is a closure that provides extra filtering of the attestations
@@ -174,17 +180,12 @@ impl<T: EthSpec> OperationPool<T> {
     /// Insert a proposer slashing into the pool.
     pub fn insert_proposer_slashing(
         &self,
-        slashing: ProposerSlashing,
-        state: &BeaconState<T>,
-        spec: &ChainSpec,
-    ) -> Result<(), ProposerSlashingValidationError> {
-        // TODO: should maybe insert anyway if the proposer is unknown in the validator index,
-        // because they could *become* known later
-        verify_proposer_slashing(&slashing, state, VerifySignatures::True, spec)?;
+        verified_proposer_slashing: SigVerifiedOp<ProposerSlashing>,
+    ) {
+        let slashing = verified_proposer_slashing.into_inner();
         self.proposer_slashings
             .write()
             .insert(slashing.signed_header_1.message.proposer_index, slashing);
-        Ok(())
     }
 
     /// Compute the tuple ID that is used to identify an attester slashing.
@@ -212,16 +213,17 @@ impl<T: EthSpec> OperationPool<T> {
     }
 
     /// Insert an attester slashing into the pool.
+    ///
+    /// The `state` is only used to compute the fork-dependent ID of the slashing.
     pub fn insert_attester_slashing(
         &self,
-        slashing: AttesterSlashing<T>,
+        verified_slashing: SigVerifiedOp<AttesterSlashing<T>>,
         state: &BeaconState<T>,
         spec: &ChainSpec,
-    ) -> Result<(), AttesterSlashingValidationError> {
-        verify_attester_slashing(state, &slashing, VerifySignatures::True, spec)?;
+    ) {
+        let slashing = verified_slashing.into_inner();
         let id = Self::attester_slashing_id(&slashing, state, spec);
         self.attester_slashings.write().insert(id, slashing);
-        Ok(())
     }
 
     /// Get proposer and attester slashings for inclusion in a block.
@@ -320,18 +322,22 @@ impl<T: EthSpec> OperationPool<T> {
         self.proposer_slashings.read().len()
     }
 
-    /// Insert a voluntary exit, validating it almost-entirely (future exits are permitted).
-    pub fn insert_voluntary_exit(
-        &self,
-        exit: SignedVoluntaryExit,
-        state: &BeaconState<T>,
-        spec: &ChainSpec,
-    ) -> Result<(), ExitValidationError> {
-        verify_exit_time_independent_only(state, &exit, VerifySignatures::True, spec)?;
+    /// Returns all known attester slashings, without any filtering.
+    pub fn get_all_attester_slashings(&self) -> Vec<AttesterSlashing<T>> {
+        self.attester_slashings.read().values().cloned().collect()
+    }
+
+    /// Returns all known proposer slashings, without any filtering.
+    pub fn get_all_proposer_slashings(&self) -> Vec<ProposerSlashing> {
+        self.proposer_slashings.read().values().cloned().collect()
+    }
+
+    /// Insert a voluntary exit that has previously been checked elsewhere.
+    pub fn insert_voluntary_exit(&self, verified_exit: SigVerifiedOp<SignedVoluntaryExit>) {
+        let exit = verified_exit.into_inner();
         self.voluntary_exits
             .write()
             .insert(exit.message.validator_index, exit);
-        Ok(())
     }
 
     /// Get a list of voluntary exits for inclusion in a block.
@@ -368,6 +374,11 @@ impl<T: EthSpec> OperationPool<T> {
     pub fn num_voluntary_exits(&self) -> usize {
         self.voluntary_exits.read().len()
     }
+
+    /// Returns all known voluntary exits, without any filtering.
+    pub fn get_all_voluntary_exits(&self) -> Vec<SignedVoluntaryExit> {
+        self.voluntary_exits.read().values().cloned().collect()
+    }
 }
 
 /// Filter up to a maximum number of operations out of an iterator.
@@ -421,6 +432,7 @@ mod release_tests {
     use super::attestation::earliest_attestation_validators;
     use super::*;
     use state_processing::common::{get_attesting_indices, get_base_reward};
+    use state_processing::VerifyOperation;
     use std::collections::BTreeSet;
     use std::iter::FromIterator;
     use types::test_utils::*;
@@ -920,12 +932,8 @@ mod release_tests {
         };
 
         // Both slashings should be accepted by the pool.
-        op_pool
-            .insert_proposer_slashing(slashing1.clone(), &state, &spec)
-            .unwrap();
-        op_pool
-            .insert_proposer_slashing(slashing2.clone(), &state, &spec)
-            .unwrap();
+        op_pool.insert_proposer_slashing(slashing1.clone().validate(&state, &spec).unwrap());
+        op_pool.insert_proposer_slashing(slashing2.clone().validate(&state, &spec).unwrap());
 
         // Should only get the second slashing back.
         assert_eq!(op_pool.get_slashings(&state, &spec).0, vec![slashing2]);
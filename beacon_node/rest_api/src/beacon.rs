@@ -8,6 +8,7 @@ use rest_types::{
     BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
     ValidatorRequest, ValidatorResponse,
 };
+use state_processing::VerifyOperation;
 use std::sync::Arc;
 use store::Store;
 use types::{
@@ -464,22 +465,22 @@ pub async fn proposer_slashing<T: BeaconChainTypes>(
             ))
         })
         .and_then(move |proposer_slashing| {
-            let spec = &beacon_chain.spec;
-            let state = &beacon_chain.head().unwrap().beacon_state;
             if beacon_chain.eth1_chain.is_some() {
-                beacon_chain
-                    .op_pool
-                    .insert_proposer_slashing(proposer_slashing, state, spec)
+                let head = beacon_chain.head()?;
+                let verified_proposer_slashing = proposer_slashing
+                    .validate(&head.beacon_state, &beacon_chain.spec)
                     .map_err(|e| {
                         ApiError::BadRequest(format!(
-                            "Error while inserting proposer slashing: {:?}",
+                            "Error while verifying proposer slashing: {:?}",
                             e
                         ))
-                    })
+                    })?;
+                beacon_chain.process_proposer_slashing(verified_proposer_slashing);
+                Ok(())
             } else {
-                return Err(ApiError::BadRequest(
+                Err(ApiError::BadRequest(
                     "Cannot insert proposer slashing on node without Eth1 connection.".to_string(),
-                ));
+                ))
             }
         })
         .and_then(|_| response_builder?.body(&true))
@@ -504,14 +505,20 @@ pub async fn attester_slashing<T: BeaconChainTypes>(
             ))
         })
         .and_then(move |attester_slashing| {
-            let spec = &beacon_chain.spec;
-            let state = &beacon_chain.head().unwrap().beacon_state;
             if beacon_chain.eth1_chain.is_some() {
-                beacon_chain
-                    .op_pool
-                    .insert_attester_slashing(attester_slashing, state, spec)
+                let head = beacon_chain.head()?;
+                let verified_attester_slashing = attester_slashing
+                    .validate(&head.beacon_state, &beacon_chain.spec)
                     .map_err(|e| {
                         ApiError::BadRequest(format!(
+                            "Error while verifying attester slashing: {:?}",
+                            e
+                        ))
+                    })?;
+                beacon_chain
+                    .process_attester_slashing(verified_attester_slashing)
+                    .map_err(|e| {
+                        ApiError::ServerError(format!(
                             "Error while inserting attester slashing: {:?}",
                             e
                         ))
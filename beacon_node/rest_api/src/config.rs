@@ -38,6 +38,11 @@ pub struct Config {
     pub listen_address: Ipv4Addr,
     /// The port the REST API HTTP server will listen on.
     pub port: u16,
+    /// Enable the endpoints which dial, disconnect, ban and unban peers.
+    ///
+    /// These endpoints allow anyone who can reach the server to control the peers of the node,
+    /// so they are disabled by default.
+    pub allow_peer_admin: bool,
 }
 
 impl Default for Config {
@@ -46,6 +51,7 @@ impl Default for Config {
             enabled: false,
             listen_address: Ipv4Addr::new(127, 0, 0, 1),
             port: 5052,
+            allow_peer_admin: false,
         }
     }
 }
@@ -1,4 +1,5 @@
 use hyper::{Body, Response, StatusCode};
+use rest_types::ErrorMessage;
 use std::error::Error as StdError;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -8,6 +9,7 @@ pub enum ApiError {
     NotImplemented(String),
     BadRequest(String),
     NotFound(String),
+    Forbidden(String),
     UnsupportedType(String),
     ImATeapot(String),       // Just in case.
     ProcessingError(String), // A 202 error, for when a block/attestation cannot be processed, but still transmitted.
@@ -23,11 +25,31 @@ impl ApiError {
             ApiError::NotImplemented(desc) => (StatusCode::NOT_IMPLEMENTED, desc),
             ApiError::BadRequest(desc) => (StatusCode::BAD_REQUEST, desc),
             ApiError::NotFound(desc) => (StatusCode::NOT_FOUND, desc),
+            ApiError::Forbidden(desc) => (StatusCode::FORBIDDEN, desc),
             ApiError::UnsupportedType(desc) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, desc),
             ApiError::ImATeapot(desc) => (StatusCode::IM_A_TEAPOT, desc),
             ApiError::ProcessingError(desc) => (StatusCode::ACCEPTED, desc),
         }
     }
+
+    /// Converts `self` into a response with a JSON `ErrorMessage` body, as used by the standard
+    /// `/eth/v1` endpoints.
+    pub fn into_standard_response(self) -> Response<Body> {
+        let (status_code, message) = self.status_code();
+        let error_message = ErrorMessage {
+            code: status_code.as_u16(),
+            message,
+            stacktraces: vec![],
+        };
+        let body =
+            serde_json::to_string(&error_message).expect("ErrorMessage should always serialize.");
+
+        Response::builder()
+            .status(status_code)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .expect("Response should always be created.")
+    }
 }
 
 impl Into<Response<Body>> for ApiError {
@@ -41,6 +41,15 @@ pub fn parse_committee_index(string: &str) -> Result<CommitteeIndex, ApiError> {
         .map_err(|e| ApiError::BadRequest(format!("Unable to parse committee index: {:?}", e)))
 }
 
+/// Parse a validator index.
+///
+/// E.g., `"42"`
+pub fn parse_validator_index(string: &str) -> Result<usize, ApiError> {
+    string
+        .parse::<usize>()
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse validator index: {:?}", e)))
+}
+
 /// Checks the provided request to ensure that the `content-type` header.
 ///
 /// The content-type header should either be omitted, in which case JSON is assumed, or it should
@@ -246,6 +255,17 @@ pub fn publish_beacon_block_to_network<T: BeaconChainTypes + 'static>(
     Ok(())
 }
 
+/// Publish a single gossip message (e.g., a voluntary exit or slashing) to the p2p network.
+pub fn publish_pubsub_message_to_network<T: EthSpec>(
+    chan: &NetworkChannel<T>,
+    message: PubsubMessage<T>,
+) -> Result<(), ApiError> {
+    chan.send(NetworkMessage::Publish {
+        messages: vec![message],
+    })
+    .map_err(|e| ApiError::ServerError(format!("Unable to publish to network: {:?}", e)))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
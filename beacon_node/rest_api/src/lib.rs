@@ -17,6 +17,7 @@ mod node;
 mod response_builder;
 mod router;
 mod spec;
+mod standard;
 mod url_query;
 mod validator;
 
@@ -48,12 +49,20 @@ pub struct NetworkInfo<T: BeaconChainTypes> {
     pub network_chan: NetworkChannel<T::EthSpec>,
 }
 
+/// The eth1 deposit contract followed by the beacon node.
+pub struct DepositContractInfo {
+    /// The chain ID of the eth1 chain, if it was configured.
+    pub chain_id: Option<u64>,
+    pub address: String,
+}
+
 // Allowing more than 7 arguments.
 #[allow(clippy::too_many_arguments)]
 pub fn start_server<T: BeaconChainTypes>(
     config: &Config,
     beacon_chain: Arc<BeaconChain<T>>,
     network_info: NetworkInfo<T>,
+    deposit_contract: DepositContractInfo,
     db_path: PathBuf,
     freezer_db_path: PathBuf,
     eth2_config: Eth2Config,
@@ -61,6 +70,8 @@ pub fn start_server<T: BeaconChainTypes>(
 ) -> Result<(oneshot::Sender<()>, SocketAddr), hyper::Error> {
     let inner_log = log.clone();
     let eth2_config = Arc::new(eth2_config);
+    let deposit_contract = Arc::new(deposit_contract);
+    let allow_peer_admin = config.allow_peer_admin;
 
     // Define the function that will build the request handler.
     let make_service = make_service_fn(move |_socket: &AddrStream| {
@@ -69,6 +80,7 @@ pub fn start_server<T: BeaconChainTypes>(
         let eth2_config = eth2_config.clone();
         let network_globals = network_info.network_globals.clone();
         let network_channel = network_info.network_chan.clone();
+        let deposit_contract = deposit_contract.clone();
         let db_path = db_path.clone();
         let freezer_db_path = freezer_db_path.clone();
 
@@ -79,10 +91,12 @@ pub fn start_server<T: BeaconChainTypes>(
                     beacon_chain.clone(),
                     network_globals.clone(),
                     network_channel.clone(),
+                    deposit_contract.clone(),
                     eth2_config.clone(),
                     log.clone(),
                     db_path.clone(),
                     freezer_db_path.clone(),
+                    allow_peer_admin,
                 )
             }))
         }
@@ -1,13 +1,28 @@
 //! This contains a collection of lighthouse specific HTTP endpoints.
 
+use crate::helpers::{check_content_type_for_json, parse_root, state_at_slot};
 use crate::response_builder::ResponseBuilder;
-use crate::ApiResult;
-use eth2_libp2p::{NetworkGlobals, PeerInfo};
+use crate::{ApiError, ApiResult, NetworkChannel, UrlQuery};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::rpc::methods::GoodbyeReason;
+use eth2_libp2p::{Enr, Multiaddr, NetworkGlobals, PeerConnectionStatus, PeerId, PeerInfo};
 use hyper::{Body, Request};
+use network::NetworkMessage;
+use rest_types::{
+    BanPeerRequest, BannedPeer, BlockRewardResponse, DialPeerRequest, DisconnectPeerRequest,
+    UnbanPeerRequest, ValidatorRewardsResponse,
+};
+use serde::de::DeserializeOwned;
 use serde::Serialize;
+use state_processing::compute_block_reward;
+use state_processing::per_epoch_processing::get_epoch_delta_breakdowns;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use types::EthSpec;
 
+/// The longest ban which may be issued via `/lighthouse/peers/ban`, in seconds (one year).
+const MAX_BAN_DURATION_SECS: u64 = 365 * 24 * 60 * 60;
+
 /// The syncing state of the beacon node.
 pub fn syncing<T: EthSpec>(
     req: Request<Body>,
@@ -47,6 +62,232 @@ pub fn connected_peers<T: EthSpec>(
     ResponseBuilder::new(&req)?.body_no_ssz(&peers)
 }
 
+/// Returns the rewards and penalties given to every validator for their duties during the `epoch`
+/// query parameter.
+///
+/// These are applied to balances at the start of `epoch + 2`, so the state at the last slot of
+/// `epoch + 1` is replayed to compute them.
+pub fn validator_rewards<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let epoch = UrlQuery::from_request(&req)?.epoch()?;
+    let target_slot = (epoch + 2).start_slot(T::EthSpec::slots_per_epoch()) - 1;
+
+    let (_root, mut state) = state_at_slot(&beacon_chain, target_slot)?;
+
+    let rewards = get_epoch_delta_breakdowns(&mut state, &beacon_chain.spec)
+        .map_err(|e| ApiError::ServerError(format!("Unable to compute rewards: {:?}", e)))?
+        .into_iter()
+        .enumerate()
+        .map(|(validator_index, breakdown)| {
+            ValidatorRewardsResponse::from_breakdown(validator_index, breakdown)
+        })
+        .collect::<Vec<_>>();
+
+    ResponseBuilder::new(&req)?.body_no_ssz(&rewards)
+}
+
+/// Returns the rewards earned by the proposer of the block with the `block_root` query parameter,
+/// attributed to each operation included in the block.
+///
+/// The operations are re-applied to the post-state of the block's parent.
+pub fn block_rewards<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let block_root = parse_root(&UrlQuery::from_request(&req)?.only_one("block_root")?)?;
+
+    let block = beacon_chain.get_block(&block_root)?.ok_or_else(|| {
+        ApiError::NotFound(format!("Unable to find block with root {}", block_root))
+    })?;
+    let parent_block = beacon_chain
+        .get_block(&block.parent_root())?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Unable to find parent of block with root {}",
+                block_root
+            ))
+        })?;
+    let parent_state = beacon_chain
+        .get_state(&parent_block.state_root(), Some(parent_block.slot()))?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Unable to find parent state of block with root {}",
+                block_root
+            ))
+        })?;
+
+    let reward = compute_block_reward(&block.message, parent_state, &beacon_chain.spec)
+        .map_err(|e| ApiError::ServerError(format!("Unable to compute block reward: {:?}", e)))?;
+
+    ResponseBuilder::new(&req)?.body_no_ssz(&BlockRewardResponse::from_block_reward(
+        block_root,
+        block.slot(),
+        reward,
+    ))
+}
+
+/// Dials the ENR or multiaddr given in the body of the request.
+///
+/// An ENR is also added to the discovery routing table.
+pub async fn dial_peer<T: EthSpec>(
+    req: Request<Body>,
+    network_chan: NetworkChannel<T>,
+) -> ApiResult {
+    try_future!(check_content_type_for_json(&req));
+    let response_builder = ResponseBuilder::new(&req);
+    let request: DialPeerRequest = parse_json_body(req).await?;
+
+    let message = if request.address.starts_with("enr:") {
+        let enr = request
+            .address
+            .parse::<Enr>()
+            .map_err(|e| ApiError::BadRequest(format!("Unable to parse ENR: {:?}", e)))?;
+        NetworkMessage::DialEnr { enr }
+    } else {
+        let multiaddr = request
+            .address
+            .parse::<Multiaddr>()
+            .map_err(|e| ApiError::BadRequest(format!("Unable to parse multiaddr: {:?}", e)))?;
+        NetworkMessage::Dial { multiaddr }
+    };
+
+    send_to_network(&network_chan, message)?;
+    response_builder?.body_no_ssz(&())
+}
+
+/// Sends a Goodbye with the given reason to a connected peer, then disconnects it.
+///
+/// The peer is not banned and may reconnect; use `ban_peer` to keep it away.
+pub async fn disconnect_peer<T: EthSpec>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T>>,
+    network_chan: NetworkChannel<T>,
+) -> ApiResult {
+    try_future!(check_content_type_for_json(&req));
+    let response_builder = ResponseBuilder::new(&req);
+    let request: DisconnectPeerRequest = parse_json_body(req).await?;
+    let peer_id = parse_peer_id(&request.peer_id)?;
+
+    if !network_globals.peers.read().is_connected(&peer_id) {
+        return Err(ApiError::NotFound(format!(
+            "Peer {} is not connected",
+            peer_id
+        )));
+    }
+
+    send_to_network(
+        &network_chan,
+        NetworkMessage::GoodbyePeer {
+            peer_id,
+            reason: GoodbyeReason::from(request.reason),
+        },
+    )?;
+    response_builder?.body_no_ssz(&())
+}
+
+/// Disconnects a peer and bans it for the duration given in the body of the request.
+///
+/// Banning a peer which is already banned replaces the duration of the existing ban.
+pub async fn ban_peer<T: EthSpec>(
+    req: Request<Body>,
+    network_chan: NetworkChannel<T>,
+) -> ApiResult {
+    try_future!(check_content_type_for_json(&req));
+    let response_builder = ResponseBuilder::new(&req);
+    let request: BanPeerRequest = parse_json_body(req).await?;
+    let peer_id = parse_peer_id(&request.peer_id)?;
+
+    if request.duration_secs == 0 || request.duration_secs > MAX_BAN_DURATION_SECS {
+        return Err(ApiError::BadRequest(format!(
+            "Ban duration must be between 1 and {} seconds",
+            MAX_BAN_DURATION_SECS
+        )));
+    }
+
+    send_to_network(
+        &network_chan,
+        NetworkMessage::BanPeer {
+            peer_id,
+            duration: Duration::from_secs(request.duration_secs),
+        },
+    )?;
+    response_builder?.body_no_ssz(&())
+}
+
+/// Lifts the ban on the peer given in the body of the request.
+pub async fn unban_peer<T: EthSpec>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T>>,
+    network_chan: NetworkChannel<T>,
+) -> ApiResult {
+    try_future!(check_content_type_for_json(&req));
+    let response_builder = ResponseBuilder::new(&req);
+    let request: UnbanPeerRequest = parse_json_body(req).await?;
+    let peer_id = parse_peer_id(&request.peer_id)?;
+
+    if !network_globals.peers.read().is_banned(&peer_id) {
+        return Err(ApiError::NotFound(format!(
+            "Peer {} is not banned",
+            peer_id
+        )));
+    }
+
+    send_to_network(&network_chan, NetworkMessage::UnbanPeer { peer_id })?;
+    response_builder?.body_no_ssz(&())
+}
+
+/// Returns all banned peers and the remaining duration of their bans.
+pub fn banned_peers<T: EthSpec>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T>>,
+) -> ApiResult {
+    let now = Instant::now();
+    let banned: Vec<BannedPeer> = network_globals
+        .peers
+        .read()
+        .peers()
+        .filter_map(|(peer_id, peer_info)| match peer_info.connection_status {
+            PeerConnectionStatus::Banned { since, until } => Some(BannedPeer {
+                peer_id: peer_id.to_string(),
+                banned_secs: now.saturating_duration_since(since).as_secs(),
+                unban_in_secs: until.map(|until| until.saturating_duration_since(now).as_secs()),
+            }),
+            _ => None,
+        })
+        .collect();
+    ResponseBuilder::new(&req)?.body_no_ssz(&banned)
+}
+
+/// Reads the body of `req` and deserializes it from JSON.
+async fn parse_json_body<B: DeserializeOwned>(req: Request<Body>) -> Result<B, ApiError> {
+    let chunks = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+
+    serde_json::from_slice(&chunks)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse JSON body: {:?}", e)))
+}
+
+fn parse_peer_id(string: &str) -> Result<PeerId, ApiError> {
+    string
+        .parse::<PeerId>()
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse peer id: {:?}", e)))
+}
+
+fn send_to_network<T: EthSpec>(
+    network_chan: &NetworkChannel<T>,
+    message: NetworkMessage<T>,
+) -> Result<(), ApiError> {
+    network_chan.send(message).map_err(|e| {
+        ApiError::ServerError(format!(
+            "Unable to send message to the network service: {:?}",
+            e
+        ))
+    })
+}
+
 /// Information returned by `peers` and `connected_peers`.
 #[derive(Clone, Debug, Serialize)]
 #[serde(bound = "T: EthSpec")]
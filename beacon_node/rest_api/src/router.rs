@@ -1,6 +1,6 @@
 use crate::{
     advanced, beacon, consensus, error::ApiError, helpers, lighthouse, metrics, network, node,
-    spec, validator, NetworkChannel,
+    spec, standard, validator, DepositContractInfo, NetworkChannel,
 };
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2_config::Eth2Config;
@@ -19,16 +19,19 @@ pub async fn route<T: BeaconChainTypes>(
     beacon_chain: Arc<BeaconChain<T>>,
     network_globals: Arc<NetworkGlobals<T::EthSpec>>,
     network_channel: NetworkChannel<T::EthSpec>,
+    deposit_contract: Arc<DepositContractInfo>,
     eth2_config: Arc<Eth2Config>,
     local_log: slog::Logger,
     db_path: PathBuf,
     freezer_db_path: PathBuf,
+    allow_peer_admin: bool,
 ) -> Result<Response<Body>, Error> {
     metrics::inc_counter(&metrics::REQUEST_COUNT);
     let timer = metrics::start_timer(&metrics::REQUEST_RESPONSE_TIME);
     let received_instant = Instant::now();
 
     let path = req.uri().path().to_string();
+    let is_standard_api = path.starts_with(standard::PATH_PREFIX);
 
     let log = local_log.clone();
     let request_result = match (req.method(), path.as_ref()) {
@@ -98,7 +101,7 @@ pub async fn route<T: BeaconChainTypes>(
             response.await
         }
         (&Method::POST, "/validator/subscribe") => {
-            validator::post_validator_subscriptions::<T>(req, network_channel).await
+            validator::post_validator_subscriptions::<T>(req, beacon_chain, network_channel).await
         }
         (&Method::GET, "/validator/duties/all") => {
             validator::get_all_validator_duties::<T>(req, beacon_chain)
@@ -171,6 +174,59 @@ pub async fn route<T: BeaconChainTypes>(
         (&Method::GET, "/lighthouse/connected_peers") => {
             lighthouse::connected_peers::<T::EthSpec>(req, network_globals)
         }
+
+        (&Method::GET, "/lighthouse/peers/banned") => {
+            lighthouse::banned_peers::<T::EthSpec>(req, network_globals)
+        }
+
+        (&Method::POST, "/lighthouse/peers/dial")
+        | (&Method::POST, "/lighthouse/peers/disconnect")
+        | (&Method::POST, "/lighthouse/peers/ban")
+        | (&Method::POST, "/lighthouse/peers/unban")
+            if !allow_peer_admin =>
+        {
+            Err(ApiError::Forbidden(
+                "Peer administration is disabled, enable it with --http-allow-peer-admin"
+                    .to_string(),
+            ))
+        }
+
+        (&Method::POST, "/lighthouse/peers/dial") => {
+            lighthouse::dial_peer::<T::EthSpec>(req, network_channel).await
+        }
+
+        (&Method::POST, "/lighthouse/peers/disconnect") => {
+            lighthouse::disconnect_peer::<T::EthSpec>(req, network_globals, network_channel).await
+        }
+
+        (&Method::POST, "/lighthouse/peers/ban") => {
+            lighthouse::ban_peer::<T::EthSpec>(req, network_channel).await
+        }
+
+        (&Method::POST, "/lighthouse/peers/unban") => {
+            lighthouse::unban_peer::<T::EthSpec>(req, network_globals, network_channel).await
+        }
+
+        (&Method::GET, "/lighthouse/validator_rewards") => {
+            lighthouse::validator_rewards::<T>(req, beacon_chain)
+        }
+
+        (&Method::GET, "/lighthouse/block_rewards") => {
+            lighthouse::block_rewards::<T>(req, beacon_chain)
+        }
+
+        // Standard Eth2 Beacon Node API
+        _ if is_standard_api => {
+            standard::route::<T>(
+                req,
+                beacon_chain,
+                network_globals,
+                network_channel,
+                deposit_contract,
+                log,
+            )
+            .await
+        }
         _ => Err(ApiError::NotFound(
             "Request path and/or method not found.".to_owned(),
         )),
@@ -194,7 +250,11 @@ pub async fn route<T: BeaconChainTypes>(
             Ok(response)
         }
         Err(e) => {
-            let error_response = e.into();
+            let error_response = if is_standard_api {
+                e.into_standard_response()
+            } else {
+                e.into()
+            };
 
             debug!(
                 local_log,
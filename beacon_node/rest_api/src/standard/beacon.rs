@@ -0,0 +1,497 @@
+use super::helpers::{block, block_root, respond, state, state_root};
+use crate::helpers::{
+    block_root_at_slot, parse_committee_index, parse_slot, publish_pubsub_message_to_network,
+};
+use crate::response_builder::ResponseBuilder;
+use crate::{ApiError, ApiResult, NetworkChannel, UrlQuery};
+use beacon_chain::{observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes};
+use eth2_libp2p::PubsubMessage;
+use hyper::{Body, Request};
+use rest_types::{
+    BlockHeaderData, BlockId, CommitteeData, FinalityCheckpointsData, GenesisData, RootData,
+    StateId, ValidatorData, ValidatorId, ValidatorStatus,
+};
+use std::sync::Arc;
+use types::{
+    AttesterSlashing, BeaconState, Epoch, EthSpec, ProposerSlashing, RelativeEpoch,
+    SignedVoluntaryExit,
+};
+
+/// HTTP handler to return the genesis time, validators root and fork version.
+pub fn get_genesis<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let head_info = beacon_chain.head_info()?;
+
+    respond(
+        &req,
+        GenesisData {
+            genesis_time: head_info.genesis_time,
+            genesis_validators_root: head_info.genesis_validators_root,
+            genesis_fork_version: beacon_chain.spec.genesis_fork_version,
+        },
+    )
+}
+
+/// HTTP handler to return the root of the state identified by `state_id`.
+pub fn get_state_root<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+) -> ApiResult {
+    let root = state_root(&beacon_chain, state_id)?;
+
+    respond(&req, RootData::from(root))
+}
+
+/// HTTP handler to return the `Fork` of the state identified by `state_id`.
+pub fn get_state_fork<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+) -> ApiResult {
+    let (_root, state) = state(&beacon_chain, state_id)?;
+
+    respond(&req, state.fork)
+}
+
+/// HTTP handler to return the justified and finalized checkpoints of the state identified by
+/// `state_id`.
+pub fn get_state_finality_checkpoints<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+) -> ApiResult {
+    let (_root, state) = state(&beacon_chain, state_id)?;
+
+    respond(
+        &req,
+        FinalityCheckpointsData {
+            previous_justified: state.previous_justified_checkpoint,
+            current_justified: state.current_justified_checkpoint,
+            finalized: state.finalized_checkpoint,
+        },
+    )
+}
+
+/// HTTP handler to return every validator in the state identified by `state_id`.
+pub fn get_state_validators<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+) -> ApiResult {
+    let (_root, state) = state(&beacon_chain, state_id)?;
+
+    let validators = (0..state.validators.len())
+        .map(|index| validator_data(&state, index, beacon_chain.spec.far_future_epoch))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    respond(&req, validators)
+}
+
+/// HTTP handler to return a single validator from the state identified by `state_id`.
+pub fn get_state_validator<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+    validator_id: ValidatorId,
+) -> ApiResult {
+    let (_root, mut state) = state(&beacon_chain, state_id)?;
+
+    let index_opt = match &validator_id {
+        ValidatorId::Index(index) => Some(*index as usize),
+        ValidatorId::PublicKey(pubkey) => {
+            state.update_pubkey_cache()?;
+            state.get_validator_index(pubkey)?
+        }
+    };
+
+    let index = index_opt
+        .filter(|index| *index < state.validators.len())
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown validator: {}", validator_id)))?;
+
+    respond(
+        &req,
+        validator_data(&state, index, beacon_chain.spec.far_future_epoch)?,
+    )
+}
+
+/// Build the `ValidatorData` for the validator at `index` in `state`.
+fn validator_data<E: EthSpec>(
+    state: &BeaconState<E>,
+    index: usize,
+    far_future_epoch: Epoch,
+) -> Result<ValidatorData, ApiError> {
+    let validator = state
+        .validators
+        .get(index)
+        .ok_or_else(|| ApiError::ServerError(format!("Invalid validator index: {}", index)))?;
+    let balance = state
+        .balances
+        .get(index)
+        .ok_or_else(|| ApiError::ServerError(format!("Invalid balances index: {}", index)))?;
+
+    Ok(ValidatorData {
+        index: index as u64,
+        balance: *balance,
+        status: ValidatorStatus::from_validator(validator, state.current_epoch(), far_future_epoch),
+        validator: validator.clone(),
+    })
+}
+
+/// HTTP handler to return the committees at `epoch` for the state identified by `state_id`.
+///
+/// The results may be filtered using the optional `index` and `slot` query parameters.
+pub fn get_state_committees<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+    epoch: Epoch,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+    let index_opt = query
+        .first_of_opt(&["index"])
+        .map(|(_key, value)| parse_committee_index(&value))
+        .transpose()?;
+    let slot_opt = query
+        .first_of_opt(&["slot"])
+        .map(|(_key, value)| parse_slot(&value))
+        .transpose()?;
+
+    let (_root, mut state) = state(&beacon_chain, state_id)?;
+
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch).map_err(|_| {
+        ApiError::BadRequest(format!(
+            "Epoch {} is not within one epoch of the requested state (epoch {})",
+            epoch,
+            state.current_epoch()
+        ))
+    })?;
+
+    state
+        .build_committee_cache(relative_epoch, &beacon_chain.spec)
+        .map_err(|e| ApiError::ServerError(format!("Unable to build committee cache: {:?}", e)))?;
+
+    let committees = state
+        .get_beacon_committees_at_epoch(relative_epoch)
+        .map_err(|e| ApiError::ServerError(format!("Unable to get all committees: {:?}", e)))?
+        .into_iter()
+        .filter(|c| index_opt.map_or(true, |index| c.index == index))
+        .filter(|c| slot_opt.map_or(true, |slot| c.slot == slot))
+        .map(|c| CommitteeData {
+            index: c.index,
+            slot: c.slot,
+            validators: c.committee.iter().map(|i| *i as u64).collect(),
+        })
+        .collect::<Vec<_>>();
+
+    respond(&req, committees)
+}
+
+/// HTTP handler to return the headers of the canonical blocks at the `slot` query parameter, or
+/// the head block if no slot is given.
+pub fn get_headers<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let block_id = match UrlQuery::from_request(&req)?.first_of_opt(&["slot"]) {
+        Some((_key, value)) => BlockId::Slot(parse_slot(&value)?),
+        None => BlockId::Head,
+    };
+
+    let headers = match block_header_data(&beacon_chain, block_id) {
+        Ok(header) => vec![header],
+        // A skipped slot has no headers.
+        Err(ApiError::NotFound(_)) => vec![],
+        Err(e) => return Err(e),
+    };
+
+    respond(&req, headers)
+}
+
+/// HTTP handler to return the header of the block identified by `block_id`.
+pub fn get_header<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+) -> ApiResult {
+    respond(&req, block_header_data(&beacon_chain, block_id)?)
+}
+
+fn block_header_data<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<BlockHeaderData, ApiError> {
+    let (root, block) = block(beacon_chain, block_id)?;
+    let canonical = block_root_at_slot(beacon_chain, block.slot())? == Some(root);
+
+    Ok(BlockHeaderData {
+        root,
+        canonical,
+        header: block.signed_block_header(),
+    })
+}
+
+/// HTTP handler to return the block identified by `block_id`.
+pub fn get_block<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+) -> ApiResult {
+    let (_root, block) = block(&beacon_chain, block_id)?;
+
+    respond(&req, block)
+}
+
+/// HTTP handler to return the root of the block identified by `block_id`.
+pub fn get_block_root<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+) -> ApiResult {
+    let root = block_root(&beacon_chain, block_id)?;
+
+    respond(&req, RootData::from(root))
+}
+
+/// HTTP handler to return the attestations included in the block identified by `block_id`.
+pub fn get_block_attestations<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+) -> ApiResult {
+    let (_root, block) = block(&beacon_chain, block_id)?;
+
+    respond(&req, block.message.body.attestations)
+}
+
+/// HTTP handler to return all attestations in the operation pool.
+pub fn get_pool_attestations<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_attestations())
+}
+
+/// HTTP handler to return all attester slashings in the operation pool.
+pub fn get_pool_attester_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_attester_slashings())
+}
+
+/// HTTP handler to return all proposer slashings in the operation pool.
+pub fn get_pool_proposer_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_proposer_slashings())
+}
+
+/// HTTP handler to return all voluntary exits in the operation pool.
+pub fn get_pool_voluntary_exits<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_voluntary_exits())
+}
+
+/// HTTP handler to verify a `SignedVoluntaryExit`, insert it into the operation pool and publish
+/// it to the network.
+pub async fn post_pool_voluntary_exits<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_channel: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+
+    let exit = serde_json::from_slice::<SignedVoluntaryExit>(&body).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into SignedVoluntaryExit: {:?}",
+            e
+        ))
+    })?;
+
+    match beacon_chain.verify_voluntary_exit_for_gossip(exit) {
+        Ok(ObservationOutcome::New(verified_exit)) => {
+            publish_pubsub_message_to_network(
+                &network_channel,
+                PubsubMessage::VoluntaryExit(Box::new(verified_exit.as_inner().clone())),
+            )?;
+            beacon_chain.process_voluntary_exit(verified_exit)
+        }
+        // Already known exits are not an error, the outcome is the same.
+        Ok(ObservationOutcome::AlreadyKnown) => (),
+        Err(e) => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid voluntary exit: {:?}",
+                e
+            )))
+        }
+    }
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to verify a `ProposerSlashing`, insert it into the operation pool and publish it
+/// to the network.
+pub async fn post_pool_proposer_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_channel: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+
+    let slashing = serde_json::from_slice::<ProposerSlashing>(&body).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into ProposerSlashing: {:?}",
+            e
+        ))
+    })?;
+
+    match beacon_chain.verify_proposer_slashing_for_gossip(slashing) {
+        Ok(ObservationOutcome::New(verified_slashing)) => {
+            publish_pubsub_message_to_network(
+                &network_channel,
+                PubsubMessage::ProposerSlashing(Box::new(verified_slashing.as_inner().clone())),
+            )?;
+            beacon_chain.process_proposer_slashing(verified_slashing)
+        }
+        // Already known slashings are not an error, the outcome is the same.
+        Ok(ObservationOutcome::AlreadyKnown) => (),
+        Err(e) => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid proposer slashing: {:?}",
+                e
+            )))
+        }
+    }
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to verify an `AttesterSlashing`, insert it into the operation pool and publish it
+/// to the network.
+pub async fn post_pool_attester_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_channel: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+
+    let slashing = serde_json::from_slice::<AttesterSlashing<T::EthSpec>>(&body).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into AttesterSlashing: {:?}",
+            e
+        ))
+    })?;
+
+    match beacon_chain.verify_attester_slashing_for_gossip(slashing) {
+        Ok(ObservationOutcome::New(verified_slashing)) => {
+            publish_pubsub_message_to_network(
+                &network_channel,
+                PubsubMessage::AttesterSlashing(Box::new(verified_slashing.as_inner().clone())),
+            )?;
+            beacon_chain
+                .process_attester_slashing(verified_slashing)
+                .map_err(|e| {
+                    ApiError::ServerError(format!(
+                        "Error while inserting attester slashing: {:?}",
+                        e
+                    ))
+                })?
+        }
+        // Already known slashings are not an error, the outcome is the same.
+        Ok(ObservationOutcome::AlreadyKnown) => (),
+        Err(e) => {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid attester slashing: {:?}",
+                e
+            )))
+        }
+    }
+
+    response_builder?.body_no_ssz(&())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::BeaconChainHarness;
+    use futures::executor::block_on;
+    use network::NetworkMessage;
+    use tokio::sync::mpsc;
+    use types::{
+        test_utils::{
+            build_proposer_slashing, generate_deterministic_keypair,
+            generate_deterministic_keypairs, ProposerSlashingTestTask,
+        },
+        MinimalEthSpec,
+    };
+
+    type E = MinimalEthSpec;
+
+    fn post_request<S: serde::Serialize>(path: &str, item: &S) -> Request<Body> {
+        Request::post(path)
+            .body(Body::from(serde_json::to_string(item).unwrap()))
+            .unwrap()
+    }
+
+    #[test]
+    fn post_pool_proposer_slashings_publishes_to_network() {
+        let harness = BeaconChainHarness::new(MinimalEthSpec, generate_deterministic_keypairs(8));
+        let beacon_chain = Arc::new(harness.chain);
+        let (network_channel, mut network_recv) = mpsc::unbounded_channel();
+
+        let state = beacon_chain.head().unwrap().beacon_state;
+        let proposer_index = 0;
+        let slashing = build_proposer_slashing::<E>(
+            ProposerSlashingTestTask::Valid,
+            proposer_index,
+            &generate_deterministic_keypair(proposer_index as usize).sk,
+            &state.fork,
+            state.genesis_validators_root,
+            &beacon_chain.spec,
+        );
+
+        let req = post_request("/eth/v1/beacon/pool/proposer_slashings", &slashing);
+        block_on(post_pool_proposer_slashings(
+            req,
+            beacon_chain.clone(),
+            network_channel.clone(),
+        ))
+        .expect("should accept valid proposer slashing");
+
+        match network_recv.try_recv() {
+            Ok(NetworkMessage::Publish { messages }) => assert_eq!(
+                messages,
+                vec![PubsubMessage::ProposerSlashing(Box::new(slashing.clone()))]
+            ),
+            _ => panic!("proposer slashing should be published"),
+        }
+
+        // A slashing which has already been seen is accepted but not published again.
+        let req = post_request("/eth/v1/beacon/pool/proposer_slashings", &slashing);
+        block_on(post_pool_proposer_slashings(
+            req,
+            beacon_chain,
+            network_channel,
+        ))
+        .expect("should accept known proposer slashing");
+        assert!(network_recv.try_recv().is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use super::helpers::respond;
+use crate::{ApiError, ApiResult, DepositContractInfo};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use hyper::{Body, Request};
+use rest_types::DepositContractData;
+use std::sync::Arc;
+use types::YamlConfig;
+
+/// HTTP handler to return the chain specification in the format of the Eth2 spec configs.
+pub fn get_spec<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(
+        &req,
+        YamlConfig::from_spec::<T::EthSpec>(&beacon_chain.spec),
+    )
+}
+
+/// HTTP handler to return the known forks.
+///
+/// No future forks are scheduled, so this is only the fork of the head state.
+pub fn get_fork_schedule<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, vec![beacon_chain.head_info()?.fork])
+}
+
+/// HTTP handler to return the eth1 deposit contract followed by the beacon node.
+pub fn get_deposit_contract(
+    req: Request<Body>,
+    deposit_contract: Arc<DepositContractInfo>,
+) -> ApiResult {
+    let chain_id = deposit_contract.chain_id.ok_or_else(|| {
+        ApiError::NotFound(
+            "The eth1 chain ID is unknown, it may be set with --eth1-chain-id".to_string(),
+        )
+    })?;
+
+    respond(
+        &req,
+        DepositContractData {
+            chain_id,
+            address: deposit_contract.address.clone(),
+        },
+    )
+}
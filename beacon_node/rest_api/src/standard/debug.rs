@@ -0,0 +1,31 @@
+use super::helpers::{respond, state};
+use crate::ApiResult;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use hyper::{Body, Request};
+use rest_types::{ChainHeadData, StateId};
+use std::sync::Arc;
+
+/// HTTP handler to return the full `BeaconState` identified by `state_id`.
+pub fn get_state<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: StateId,
+) -> ApiResult {
+    let (_root, state) = state(&beacon_chain, state_id)?;
+
+    respond(&req, state)
+}
+
+/// HTTP handler to return the head of every chain known to fork choice.
+pub fn get_heads<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let heads = beacon_chain
+        .heads()
+        .into_iter()
+        .map(|(root, slot)| ChainHeadData { root, slot })
+        .collect::<Vec<_>>();
+
+    respond(&req, heads)
+}
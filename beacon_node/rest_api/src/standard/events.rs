@@ -0,0 +1,102 @@
+use crate::{ApiError, ApiResult, UrlQuery};
+use beacon_chain::events::{EventHandler, EventKind, EventTopic};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use futures::stream;
+use hyper::{Body, Request, Response, StatusCode};
+use slog::{debug, warn, Logger};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::broadcast::RecvError;
+use types::EthSpec;
+
+/// HTTP handler to stream the events for the topics given by the `topics` query parameter as
+/// server-sent events.
+///
+/// Topics may be given as repeated parameters or as a single comma-separated list.
+///
+/// The stream never blocks the `BeaconChain`. If a client reads too slowly the oldest events are
+/// dropped and the client is sent a comment stating how many events were missed.
+pub fn get_events<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) -> ApiResult {
+    let mut topics = vec![];
+    for value in UrlQuery::from_request(&req)?.all_of("topics")? {
+        for topic in value.split(',') {
+            let topic = topic.parse::<EventTopic>().map_err(ApiError::BadRequest)?;
+            if !topics.contains(&topic) {
+                topics.push(topic);
+            }
+        }
+    }
+
+    if topics.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one topic must be specified".to_string(),
+        ));
+    }
+
+    let receivers = topics
+        .iter()
+        .map(|topic| beacon_chain.event_handler.subscribe(*topic))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            ApiError::ServerError("The event stream is not enabled on this node".to_string())
+        })?;
+
+    debug!(
+        log,
+        "Event stream subscribed";
+        "topics" => format!("{:?}", topics)
+    );
+
+    // Each topic has its own channel, merge them into a single stream.
+    let events = stream::select_all(receivers.into_iter().map(|receiver| {
+        Box::pin(stream::unfold(
+            (receiver, log.clone()),
+            |(mut receiver, log)| async move {
+                loop {
+                    let message = match receiver.recv().await {
+                        Ok(event) => match sse_message(&event) {
+                            Ok(message) => message,
+                            Err(e) => {
+                                warn!(
+                                    log,
+                                    "Unable to serialize event";
+                                    "error" => e
+                                );
+                                continue;
+                            }
+                        },
+                        Err(RecvError::Lagged(missed)) => {
+                            debug!(
+                                log,
+                                "Event stream client lagging";
+                                "missed_events" => missed
+                            );
+                            format!(": missed {} events\n\n", missed)
+                        }
+                        Err(RecvError::Closed) => return None,
+                    };
+
+                    return Some((Ok::<_, Infallible>(message), (receiver, log)));
+                }
+            },
+        ))
+    }));
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "text/event-stream")
+        .header("cache-control", "no-cache")
+        .body(Body::wrap_stream(events))
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+}
+
+/// Encode `event` as a single server-sent event message.
+fn sse_message<E: EthSpec>(event: &EventKind<E>) -> Result<String, String> {
+    serde_json::to_string(event)
+        .map(|json| format!("event: {}\ndata: {}\n\n", event.topic(), json))
+        .map_err(|e| format!("{:?}", e))
+}
@@ -0,0 +1,143 @@
+use crate::helpers::{block_root_at_slot, state_at_slot, state_root_at_slot};
+use crate::response_builder::ResponseBuilder;
+use crate::{ApiError, ApiResult};
+use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use hyper::{Body, Request};
+use rest_types::{BlockId, GenericResponse, StateId, ValidatorId};
+use serde::Serialize;
+use types::{BeaconState, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// Parse a `StateId` from a path segment.
+///
+/// E.g., `"head"`, `"1234"` or `"0x0000000000000000000000000000000000000000000000000000000000000000"`
+pub fn parse_state_id(string: &str) -> Result<StateId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Parse a `BlockId` from a path segment.
+///
+/// E.g., `"finalized"`, `"1234"` or `"0x0000000000000000000000000000000000000000000000000000000000000000"`
+pub fn parse_block_id(string: &str) -> Result<BlockId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Parse a `ValidatorId` from a path segment.
+///
+/// E.g., `"42"` or a `0x` prefixed public key.
+pub fn parse_validator_id(string: &str) -> Result<ValidatorId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Build a successful response with `data` wrapped in a `GenericResponse`.
+pub fn respond<T: Serialize>(req: &Request<Body>, data: T) -> ApiResult {
+    ResponseBuilder::new(req)?.body_no_ssz(&GenericResponse::from(data))
+}
+
+/// Returns the slot of the state identified by `state_id`.
+///
+/// States identified by their root have no known slot, so an error is returned.
+fn state_id_slot<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_id: StateId,
+) -> Result<Slot, ApiError> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+
+    match state_id {
+        StateId::Head => Ok(beacon_chain.head_info()?.slot),
+        StateId::Genesis => Ok(beacon_chain.spec.genesis_slot),
+        StateId::Finalized => Ok(beacon_chain
+            .head_info()?
+            .finalized_checkpoint
+            .epoch
+            .start_slot(slots_per_epoch)),
+        StateId::Justified => Ok(beacon_chain
+            .head_info()?
+            .current_justified_checkpoint
+            .epoch
+            .start_slot(slots_per_epoch)),
+        StateId::Slot(slot) => Ok(slot),
+        StateId::Root(root) => Err(ApiError::ServerError(format!(
+            "Unable to determine slot for state root {}",
+            root
+        ))),
+    }
+}
+
+/// Returns the root of the `BeaconState` identified by `state_id`.
+pub fn state_root<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_id: StateId,
+) -> Result<Hash256, ApiError> {
+    match state_id {
+        StateId::Head => Ok(beacon_chain.head_info()?.state_root),
+        StateId::Root(root) => Ok(root),
+        other => state_root_at_slot(
+            beacon_chain,
+            state_id_slot(beacon_chain, other)?,
+            StateSkipConfig::WithStateRoots,
+        ),
+    }
+}
+
+/// Returns the `BeaconState` identified by `state_id`, along with its root.
+pub fn state<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_id: StateId,
+) -> Result<(Hash256, BeaconState<T::EthSpec>), ApiError> {
+    match state_id {
+        StateId::Head => {
+            let head = beacon_chain.head()?;
+            Ok((head.beacon_state_root, head.beacon_state))
+        }
+        StateId::Root(root) => {
+            let state = beacon_chain.get_state(&root, None)?.ok_or_else(|| {
+                ApiError::NotFound(format!("No state exists with root: {}", root))
+            })?;
+            Ok((root, state))
+        }
+        other => state_at_slot(beacon_chain, state_id_slot(beacon_chain, other)?),
+    }
+}
+
+/// Returns the root of the `SignedBeaconBlock` identified by `block_id`.
+pub fn block_root<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<Hash256, ApiError> {
+    let root = match block_id {
+        BlockId::Head => beacon_chain.head_info()?.block_root,
+        BlockId::Genesis => beacon_chain.genesis_block_root,
+        BlockId::Finalized => beacon_chain.head_info()?.finalized_checkpoint.root,
+        BlockId::Justified => beacon_chain.head_info()?.current_justified_checkpoint.root,
+        BlockId::Slot(slot) => block_root_at_slot(beacon_chain, slot)?.ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Unable to find SignedBeaconBlock for slot {}",
+                slot
+            ))
+        })?,
+        BlockId::Root(root) => root,
+    };
+
+    // Prior to the first justification, checkpoints use the zero root to refer to genesis.
+    if root == Hash256::zero() {
+        Ok(beacon_chain.genesis_block_root)
+    } else {
+        Ok(root)
+    }
+}
+
+/// Returns the `SignedBeaconBlock` identified by `block_id`, along with its root.
+pub fn block<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<(Hash256, SignedBeaconBlock<T::EthSpec>), ApiError> {
+    let root = block_root(beacon_chain, block_id)?;
+    let block = beacon_chain.get_block(&root)?.ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "Unable to find SignedBeaconBlock for root {}",
+            root
+        ))
+    })?;
+
+    Ok((root, block))
+}
@@ -0,0 +1,174 @@
+//! Implements the standard Eth2 Beacon Node API, served under `/eth/v1`.
+//!
+//! Unlike the Lighthouse-specific routes, every successful response is wrapped in a
+//! `GenericResponse` and every error is returned as a JSON `ErrorMessage`.
+
+mod beacon;
+mod config;
+mod debug;
+mod events;
+mod helpers;
+mod node;
+mod validator;
+
+use crate::helpers::{parse_epoch, parse_slot};
+use crate::{ApiError, ApiResult, DepositContractInfo, NetworkChannel};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::NetworkGlobals;
+use helpers::{parse_block_id, parse_state_id, parse_validator_id};
+use hyper::{Body, Method, Request};
+use std::sync::Arc;
+
+/// The path prefix shared by all standard API endpoints.
+pub const PATH_PREFIX: &str = "/eth/v1/";
+
+/// Dispatch a request with a path starting with `PATH_PREFIX` to the relevant handler.
+pub async fn route<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+    network_channel: NetworkChannel<T::EthSpec>,
+    deposit_contract: Arc<DepositContractInfo>,
+    log: slog::Logger,
+) -> ApiResult {
+    let path = req.uri().path().to_string();
+    let segments = path
+        .trim_start_matches(PATH_PREFIX)
+        .trim_end_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+
+    match (req.method(), segments.as_slice()) {
+        // Methods for Beacon Node
+        (&Method::GET, ["beacon", "genesis"]) => beacon::get_genesis::<T>(req, beacon_chain),
+        (&Method::GET, ["beacon", "states", state_id, "root"]) => {
+            beacon::get_state_root::<T>(req, beacon_chain, parse_state_id(state_id)?)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "fork"]) => {
+            beacon::get_state_fork::<T>(req, beacon_chain, parse_state_id(state_id)?)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "finality_checkpoints"]) => {
+            beacon::get_state_finality_checkpoints::<T>(
+                req,
+                beacon_chain,
+                parse_state_id(state_id)?,
+            )
+        }
+        (&Method::GET, ["beacon", "states", state_id, "validators"]) => {
+            beacon::get_state_validators::<T>(req, beacon_chain, parse_state_id(state_id)?)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "validators", validator_id]) => {
+            beacon::get_state_validator::<T>(
+                req,
+                beacon_chain,
+                parse_state_id(state_id)?,
+                parse_validator_id(validator_id)?,
+            )
+        }
+        (&Method::GET, ["beacon", "states", state_id, "committees", epoch]) => {
+            beacon::get_state_committees::<T>(
+                req,
+                beacon_chain,
+                parse_state_id(state_id)?,
+                parse_epoch(epoch)?,
+            )
+        }
+        (&Method::GET, ["beacon", "headers"]) => beacon::get_headers::<T>(req, beacon_chain),
+        (&Method::GET, ["beacon", "headers", block_id]) => {
+            beacon::get_header::<T>(req, beacon_chain, parse_block_id(block_id)?)
+        }
+        (&Method::POST, ["beacon", "blocks"]) => {
+            crate::validator::publish_beacon_block::<T>(req, beacon_chain, network_channel, log)
+                .await
+        }
+        (&Method::GET, ["beacon", "blocks", block_id]) => {
+            beacon::get_block::<T>(req, beacon_chain, parse_block_id(block_id)?)
+        }
+        (&Method::GET, ["beacon", "blocks", block_id, "root"]) => {
+            beacon::get_block_root::<T>(req, beacon_chain, parse_block_id(block_id)?)
+        }
+        (&Method::GET, ["beacon", "blocks", block_id, "attestations"]) => {
+            beacon::get_block_attestations::<T>(req, beacon_chain, parse_block_id(block_id)?)
+        }
+        (&Method::GET, ["beacon", "pool", "attestations"]) => {
+            beacon::get_pool_attestations::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "attestations"]) => {
+            crate::validator::publish_attestations::<T>(req, beacon_chain, network_channel, log)
+                .await
+        }
+        (&Method::GET, ["beacon", "pool", "attester_slashings"]) => {
+            beacon::get_pool_attester_slashings::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "attester_slashings"]) => {
+            beacon::post_pool_attester_slashings::<T>(req, beacon_chain, network_channel).await
+        }
+        (&Method::GET, ["beacon", "pool", "proposer_slashings"]) => {
+            beacon::get_pool_proposer_slashings::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "proposer_slashings"]) => {
+            beacon::post_pool_proposer_slashings::<T>(req, beacon_chain, network_channel).await
+        }
+        (&Method::GET, ["beacon", "pool", "voluntary_exits"]) => {
+            beacon::get_pool_voluntary_exits::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "voluntary_exits"]) => {
+            beacon::post_pool_voluntary_exits::<T>(req, beacon_chain, network_channel).await
+        }
+
+        // Methods for Validator
+        (&Method::GET, ["validator", "duties", "attester", epoch]) => {
+            validator::get_attester_duties::<T>(req, beacon_chain, parse_epoch(epoch)?)
+        }
+        (&Method::GET, ["validator", "duties", "proposer", epoch]) => {
+            validator::get_proposer_duties::<T>(req, beacon_chain, parse_epoch(epoch)?)
+        }
+        (&Method::GET, ["validator", "blocks", slot]) => {
+            validator::get_new_block::<T>(req, beacon_chain, parse_slot(slot)?, log)
+        }
+        (&Method::GET, ["validator", "attestation_data"]) => {
+            validator::get_attestation_data::<T>(req, beacon_chain)
+        }
+        (&Method::GET, ["validator", "aggregate_attestation"]) => {
+            validator::get_aggregate_attestation::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["validator", "aggregate_and_proofs"]) => {
+            crate::validator::publish_aggregate_and_proofs::<T>(
+                req,
+                beacon_chain,
+                network_channel,
+                log,
+            )
+            .await
+        }
+
+        // Methods for Node
+        (&Method::GET, ["node", "identity"]) => node::get_identity::<T>(req, network_globals),
+        (&Method::GET, ["node", "peers"]) => node::get_peers::<T>(req, network_globals),
+        (&Method::GET, ["node", "version"]) => node::get_version(req),
+        (&Method::GET, ["node", "syncing"]) => node::get_syncing::<T>(req, beacon_chain),
+        (&Method::GET, ["node", "health"]) => node::get_health::<T>(req, network_globals),
+
+        // Methods for checking configuration
+        (&Method::GET, ["config", "spec"]) => config::get_spec::<T>(req, beacon_chain),
+        (&Method::GET, ["config", "fork_schedule"]) => {
+            config::get_fork_schedule::<T>(req, beacon_chain)
+        }
+        (&Method::GET, ["config", "deposit_contract"]) => {
+            config::get_deposit_contract(req, deposit_contract)
+        }
+
+        // Methods for debugging
+        (&Method::GET, ["debug", "beacon", "states", state_id]) => {
+            debug::get_state::<T>(req, beacon_chain, parse_state_id(state_id)?)
+        }
+        (&Method::GET, ["debug", "beacon", "heads"]) => debug::get_heads::<T>(req, beacon_chain),
+
+        // Methods for events
+        (&Method::GET, ["events"]) => events::get_events::<T>(req, beacon_chain, log),
+
+        _ => Err(ApiError::NotFound(
+            "Request path and/or method not found.".to_owned(),
+        )),
+    }
+}
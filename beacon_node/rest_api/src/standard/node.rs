@@ -0,0 +1,115 @@
+use super::helpers::respond;
+use crate::{ApiError, ApiResult};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::{NetworkGlobals, PeerConnectionStatus};
+use hyper::{Body, Request, Response, StatusCode};
+use rest_types::{IdentityData, PeerData, PeerDirection, PeerState, SyncingData, VersionData};
+use std::sync::Arc;
+
+/// HTTP handler to return the node's peer id, ENR and listening addresses.
+pub fn get_identity<T: BeaconChainTypes>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+) -> ApiResult {
+    respond(
+        &req,
+        IdentityData {
+            peer_id: network_globals.local_peer_id().to_base58(),
+            enr: network_globals.local_enr().to_base64(),
+            p2p_addresses: network_globals
+                .listen_multiaddrs()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        },
+    )
+}
+
+/// HTTP handler to return all known peers.
+pub fn get_peers<T: BeaconChainTypes>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+) -> ApiResult {
+    let peers = network_globals
+        .peers
+        .read()
+        .peers()
+        .map(|(peer_id, peer_info)| {
+            let (state, direction) = match peer_info.connection_status {
+                PeerConnectionStatus::Connected { n_in, .. } => {
+                    let direction = if n_in > 0 {
+                        PeerDirection::Inbound
+                    } else {
+                        PeerDirection::Outbound
+                    };
+                    (PeerState::Connected, Some(direction))
+                }
+                PeerConnectionStatus::Dialing { .. } => {
+                    (PeerState::Connecting, Some(PeerDirection::Outbound))
+                }
+                PeerConnectionStatus::Disconnected { .. }
+                | PeerConnectionStatus::Banned { .. }
+                | PeerConnectionStatus::Unknown => (PeerState::Disconnected, None),
+            };
+
+            PeerData {
+                peer_id: peer_id.to_string(),
+                address: peer_info
+                    .listening_addresses
+                    .last()
+                    .map(ToString::to_string),
+                state,
+                direction,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    respond(&req, peers)
+}
+
+/// HTTP handler to return the version string of the current Lighthouse build.
+pub fn get_version(req: Request<Body>) -> ApiResult {
+    respond(
+        &req,
+        VersionData {
+            version: version::version(),
+        },
+    )
+}
+
+/// HTTP handler to return the head slot and its distance from the current slot.
+pub fn get_syncing<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let head_slot = beacon_chain.head_info()?.slot;
+    let current_slot = beacon_chain
+        .slot()
+        .map_err(|_| ApiError::ServerError("Unable to read slot clock".to_string()))?;
+
+    respond(
+        &req,
+        SyncingData {
+            head_slot,
+            sync_distance: current_slot.saturating_sub(head_slot),
+        },
+    )
+}
+
+/// HTTP handler which responds with an empty body and a status code indicating the node's sync
+/// status: `200` when synced and `206` when syncing.
+pub fn get_health<T: BeaconChainTypes>(
+    _req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+) -> ApiResult {
+    let status = if network_globals.is_syncing() {
+        StatusCode::PARTIAL_CONTENT
+    } else {
+        StatusCode::OK
+    };
+
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+}
@@ -0,0 +1,170 @@
+use super::helpers::respond;
+use crate::helpers::{parse_root, parse_validator_index};
+use crate::validator::get_state_for_epoch;
+use crate::{ApiError, ApiResult, UrlQuery};
+use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use hyper::{Body, Request};
+use rest_types::{AttesterData, ProposerData};
+use slog::{error, Logger};
+use std::sync::Arc;
+use types::{Epoch, EthSpec, RelativeEpoch, Slot};
+
+/// HTTP handler to return the attestation duties at `epoch` for the validators given by the
+/// `index` query parameter.
+///
+/// Indices may be given as repeated parameters or as a single comma-separated list. Validators
+/// which are unknown or have no duties are omitted.
+pub fn get_attester_duties<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    epoch: Epoch,
+) -> ApiResult {
+    let indices = UrlQuery::from_request(&req)?
+        .all_of("index")?
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(parse_validator_index)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut state = get_state_for_epoch(&beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
+
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
+        .map_err(|_| ApiError::ServerError(String::from("Loaded state is in the wrong epoch")))?;
+
+    state
+        .build_committee_cache(relative_epoch, &beacon_chain.spec)
+        .map_err(|e| ApiError::ServerError(format!("Unable to build committee cache: {:?}", e)))?;
+
+    let duties = indices
+        .into_iter()
+        .filter_map(|index| {
+            let validator = state.validators.get(index)?;
+            state
+                .get_attestation_duties(index, relative_epoch)
+                .map_err(|e| {
+                    ApiError::ServerError(format!("Unable to obtain attestation duties: {:?}", e))
+                })
+                .transpose()
+                .map(|duty_result| {
+                    duty_result.map(|duty| AttesterData {
+                        pubkey: validator.pubkey.clone(),
+                        validator_index: index as u64,
+                        committee_index: duty.index,
+                        committee_length: duty.committee_len as u64,
+                        validator_committee_index: duty.committee_position as u64,
+                        slot: duty.slot,
+                    })
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    respond(&req, duties)
+}
+
+/// HTTP handler to return the block proposers for every slot in `epoch`.
+pub fn get_proposer_duties<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    epoch: Epoch,
+) -> ApiResult {
+    let mut state = get_state_for_epoch(&beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
+
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
+        .map_err(|_| ApiError::ServerError(String::from("Loaded state is in the wrong epoch")))?;
+
+    state
+        .build_committee_cache(relative_epoch, &beacon_chain.spec)
+        .map_err(|e| ApiError::ServerError(format!("Unable to build committee cache: {:?}", e)))?;
+
+    let duties = epoch
+        .slot_iter(T::EthSpec::slots_per_epoch())
+        .map(|slot| {
+            let index = state
+                .get_beacon_proposer_index(slot, &beacon_chain.spec)
+                .map_err(|e| {
+                    ApiError::ServerError(format!("Unable to get proposer index: {:?}", e))
+                })?;
+            let validator = state.validators.get(index).ok_or_else(|| {
+                ApiError::ServerError(format!("Invalid validator index: {}", index))
+            })?;
+
+            Ok(ProposerData {
+                pubkey: validator.pubkey.clone(),
+                validator_index: index as u64,
+                slot,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    respond(&req, duties)
+}
+
+/// HTTP handler to produce a new, unsigned `BeaconBlock` at `slot` using the `randao_reveal`
+/// query parameter.
+pub fn get_new_block<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    slot: Slot,
+    log: Logger,
+) -> ApiResult {
+    let randao_reveal = UrlQuery::from_request(&req)?.randao_reveal()?;
+
+    let (block, _state) = beacon_chain
+        .produce_block(randao_reveal, slot)
+        .map_err(|e| {
+            error!(
+                log,
+                "Error whilst producing block";
+                "error" => format!("{:?}", e)
+            );
+
+            ApiError::ServerError(format!(
+                "Beacon node is not able to produce a block: {:?}",
+                e
+            ))
+        })?;
+
+    respond(&req, block)
+}
+
+/// HTTP handler to produce the `AttestationData` for the `slot` and `committee_index` query
+/// parameters.
+pub fn get_attestation_data<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+
+    let slot = query.slot()?;
+    let index = query.committee_index()?;
+
+    let attestation = beacon_chain
+        .produce_unaggregated_attestation(slot, index)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to produce attestation: {:?}", e)))?;
+
+    respond(&req, attestation.data)
+}
+
+/// HTTP handler to return the aggregate attestation for the `slot` and `attestation_data_root`
+/// query parameters.
+pub fn get_aggregate_attestation<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+
+    let slot = query.slot()?;
+    let (_key, root_string) = query.first_of(&["attestation_data_root"])?;
+    let attestation_data_root = parse_root(&root_string)?;
+
+    let attestation = beacon_chain
+        .get_aggregated_attestation_by_slot_and_root(slot, &attestation_data_root)
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No matching aggregate attestation for root {} at slot {}",
+                attestation_data_root, slot
+            ))
+        })?;
+
+    respond(&req, attestation)
+}
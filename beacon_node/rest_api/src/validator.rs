@@ -54,6 +54,7 @@ pub async fn post_validator_duties<T: BeaconChainTypes>(
 /// organise peer discovery and topic subscription for known validators.
 pub async fn post_validator_subscriptions<T: BeaconChainTypes>(
     req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
     network_chan: NetworkChannel<T::EthSpec>,
 ) -> ApiResult {
     try_future!(check_content_type_for_json(&req));
@@ -72,6 +73,13 @@ pub async fn post_validator_subscriptions<T: BeaconChainTypes>(
             ))
         })
         .and_then(move |subscriptions: Vec<ValidatorSubscription>| {
+            {
+                let mut validator_monitor = beacon_chain.validator_monitor.write();
+                for subscription in &subscriptions {
+                    validator_monitor.auto_register_local_validator(subscription.validator_index);
+                }
+            }
+
             network_chan
                 .send(NetworkMessage::Subscribe { subscriptions })
                 .map_err(|e| {
@@ -4,6 +4,7 @@
 extern crate assert_matches;
 
 use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use futures::StreamExt;
 use node_test_rig::{
     environment::{Environment, EnvironmentBuilder},
     testing_client_config, ClientConfig, ClientGenesis, LocalBeaconNode,
@@ -11,7 +12,10 @@ use node_test_rig::{
 use remote_beacon_node::{
     Committee, HeadBeaconBlock, PersistedOperationPool, PublishStatus, ValidatorResponse,
 };
-use rest_types::ValidatorDutyBytes;
+use rest_types::{
+    BlockId, DepositContractData, ErrorMessage, EventKind, EventTopic, StateId, ValidatorDutyBytes,
+    ValidatorId, ValidatorStatus,
+};
 use std::convert::TryInto;
 use std::sync::Arc;
 use types::{
@@ -19,9 +23,9 @@ use types::{
         build_double_vote_attester_slashing, build_proposer_slashing,
         generate_deterministic_keypair, AttesterSlashingTestTask, ProposerSlashingTestTask,
     },
-    BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, EthSpec, MinimalEthSpec, PublicKey,
-    RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot, Slot,
-    Validator,
+    BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, EthSpec, Hash256, MinimalEthSpec,
+    PublicKey, RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot,
+    Slot, Validator,
 };
 use version;
 
@@ -1264,3 +1268,269 @@ fn get_health() {
         .block_on(remote_node.http.node().get_health())
         .unwrap();
 }
+
+#[test]
+fn standard_genesis() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let genesis = env
+        .runtime()
+        .block_on(remote_node.http.standard().get_beacon_genesis())
+        .expect("should fetch from http api");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain");
+    let head_state = beacon_chain.head().expect("should get head").beacon_state;
+
+    assert_eq!(genesis.genesis_time, head_state.genesis_time);
+    assert_eq!(
+        genesis.genesis_validators_root,
+        head_state.genesis_validators_root
+    );
+    assert_eq!(
+        genesis.genesis_fork_version,
+        beacon_chain.spec.genesis_fork_version
+    );
+}
+
+#[test]
+fn standard_deposit_contract() {
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.eth1.chain_id = Some(5);
+    let node = build_node(&mut env, config.clone());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let deposit_contract = env
+        .runtime()
+        .block_on(remote_node.http.standard().get_config_deposit_contract())
+        .expect("should fetch from http api");
+
+    assert_eq!(
+        deposit_contract,
+        DepositContractData {
+            chain_id: 5,
+            address: config.eth1.deposit_contract_address,
+        }
+    );
+}
+
+#[test]
+fn standard_state_root() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain");
+    let head = beacon_chain.head().expect("should get head");
+
+    for state_id in &[
+        StateId::Head,
+        StateId::Genesis,
+        StateId::Slot(head.beacon_state.slot),
+        StateId::Root(head.beacon_state_root),
+    ] {
+        let root = env
+            .runtime()
+            .block_on(remote_node.http.standard().get_beacon_state_root(*state_id))
+            .expect("should fetch from http api");
+
+        assert_eq!(root, head.beacon_state_root, "state id {}", state_id);
+    }
+}
+
+#[test]
+fn standard_validator() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let state = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .head()
+        .expect("should get head")
+        .beacon_state;
+
+    let validator_ids = vec![
+        ValidatorId::Index(1),
+        ValidatorId::PublicKey(state.validators[1].pubkey.clone()),
+    ];
+
+    for validator_id in &validator_ids {
+        let data = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .standard()
+                    .get_beacon_state_validator(StateId::Head, validator_id),
+            )
+            .expect("should fetch from http api");
+
+        assert_eq!(data.index, 1);
+        assert_eq!(data.balance, state.balances[1]);
+        assert_eq!(data.status, ValidatorStatus::ActiveOngoing);
+        assert_eq!(data.validator, state.validators[1]);
+    }
+}
+
+#[test]
+fn standard_error_message() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .standard()
+            .get_beacon_block(BlockId::Root(Hash256::from_low_u64_be(42))),
+    );
+
+    assert_matches!(
+        result.expect_err("should not succeed"),
+        remote_beacon_node::Error::DidNotSucceed { status, body } => {
+            assert_eq!(status, http::StatusCode::NOT_FOUND);
+
+            let error: ErrorMessage =
+                serde_json::from_str(&body).expect("body should be an error message");
+            assert_eq!(error.code, 404);
+        }
+    );
+}
+
+#[test]
+fn standard_events() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: 13_371_337,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let events = env
+        .runtime()
+        .block_on(remote_node.http.standard().get_events(&[EventTopic::Block]))
+        .expect("should subscribe to events");
+    let mut events = Box::pin(events);
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
+
+    env.runtime()
+        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .expect("should publish block");
+
+    let event = env
+        .runtime()
+        .block_on(events.next())
+        .expect("stream should not end")
+        .expect("event should decode");
+
+    assert_matches!(
+        event,
+        EventKind::BeaconBlockImported { block_root: root, .. } => {
+            assert_eq!(root, block_root);
+        }
+    );
+}
+
+#[test]
+fn standard_events_invalid_topic() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let url = format!(
+        "{}eth/v1/events?topics=head,cats",
+        node.client
+            .http_listen_addr()
+            .map(|addr| format!("http://{}/", addr))
+            .expect("should have http server")
+    );
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .json_get::<()>(url.parse().expect("should parse url"), vec![]),
+    );
+
+    assert_matches!(
+        result.expect_err("should not succeed"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        }
+    );
+}
+
+#[test]
+fn peer_admin_disabled_by_default() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .lighthouse()
+            .ban_peer(eth2_libp2p::PeerId::random().to_string(), 60),
+    );
+
+    assert_matches!(
+        result.expect_err("should not succeed"),
+        remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+            assert_eq!(status, http::StatusCode::FORBIDDEN);
+        }
+    );
+
+    let mut config = testing_client_config();
+    config.rest_api.allow_peer_admin = true;
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    env.runtime()
+        .block_on(
+            remote_node
+                .http
+                .lighthouse()
+                .ban_peer(eth2_libp2p::PeerId::random().to_string(), 60),
+        )
+        .expect("should ban peer when peer administration is enabled");
+}
@@ -166,27 +166,37 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("5052")
                 .takes_value(true),
         )
-        /* Websocket related arguments */
+        .arg(
+            Arg::with_name("http-allow-peer-admin")
+                .long("http-allow-peer-admin")
+                .help("Enable the HTTP API endpoints which dial, disconnect, ban and unban peers. \
+                       These endpoints give control of the node's peers to anyone who can reach \
+                       the HTTP API server, so it must not be exposed publicly when this flag is \
+                       set.")
+                .takes_value(false),
+        )
+        /* Deprecated websocket arguments, retained so that existing configurations still start */
         .arg(
             Arg::with_name("ws")
                 .long("ws")
-                .help("Enable the websocket server. Disabled by default.")
+                .help("DEPRECATED: the websocket server has been removed and this flag has no \
+                       effect. Subscribe to the /eth/v1/events stream of the HTTP API instead.")
                 .takes_value(false),
         )
         .arg(
             Arg::with_name("ws-address")
                 .long("ws-address")
                 .value_name("ADDRESS")
-                .help("Set the listen address for the websocket server.")
-                .default_value("127.0.0.1")
+                .help("DEPRECATED: the websocket server has been removed and this flag has no \
+                       effect.")
                 .takes_value(true),
         )
         .arg(
             Arg::with_name("ws-port")
                 .long("ws-port")
                 .value_name("PORT")
-                .help("Set the listen TCP port for the websocket server.")
-                .default_value("5053")
+                .help("DEPRECATED: the websocket server has been removed and this flag has no \
+                       effect.")
                 .takes_value(true),
         )
 
@@ -214,6 +224,59 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Specifies the server for a web3 connection to the Eth1 chain. Also enables the --eth1 flag. Defaults to http://127.0.0.1:8545.")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("eth1-endpoints")
+                .long("eth1-endpoints")
+                .value_name("HTTP-ENDPOINTS")
+                .conflicts_with("eth1-endpoint")
+                .help("One or more comma-delimited servers for web3 connections to the Eth1 chain. \
+                       Requests are sent to the first healthy server, falling back to the next \
+                       server if a request fails. Also enables the --eth1 flag.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("eth1-network-id")
+                .long("eth1-network-id")
+                .value_name("NETWORK_ID")
+                .help("If present, eth1 servers which report a different network ID (net_version) \
+                       are not used.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("eth1-chain-id")
+                .long("eth1-chain-id")
+                .value_name("CHAIN_ID")
+                .help("If present, eth1 servers which report a different chain ID (eth_chainId) \
+                       are not used.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("checkpoint-state")
+                .long("checkpoint-state")
+                .value_name("STATE_SSZ")
+                .help("Start a new database from this SSZ-encoded finalized BeaconState, rather \
+                       than from genesis. Must be used with --checkpoint-block.")
+                .requires("checkpoint-block")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("checkpoint-block")
+                .long("checkpoint-block")
+                .value_name("BLOCK_SSZ")
+                .help("The SSZ-encoded SignedBeaconBlock for the state given by \
+                       --checkpoint-state.")
+                .requires("checkpoint-state")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("checkpoint-sync-url")
+                .long("checkpoint-sync-url")
+                .value_name("BEACON_NODE")
+                .help("Start a new database from the latest finalized checkpoint of the trusted \
+                       beacon node at this HTTP API URL, rather than from genesis.")
+                .conflicts_with("checkpoint-state")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("slots-per-restore-point")
                 .long("slots-per-restore-point")
@@ -230,6 +293,70 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
         )
 
+        /*
+         * Slasher.
+         */
+        .arg(
+            Arg::with_name("slasher")
+                .long("slasher")
+                .help("Run a slasher alongside the beacon node, which detects slashable \
+                       attestations and blocks on the network and includes slashings for them \
+                       in the operation pool. Requires significant additional disk space.")
+        )
+        .arg(
+            Arg::with_name("slasher-dir")
+                .long("slasher-dir")
+                .value_name("DIR")
+                .help("Data directory for the slasher database. [default: slasher_db in the \
+                       data directory]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("slasher-history-length")
+                .long("slasher-history-length")
+                .value_name("EPOCHS")
+                .help("Number of epochs of attestations and blocks for the slasher to retain. \
+                       [default: 4096]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("slasher-update-period")
+                .long("slasher-update-period")
+                .value_name("SECONDS")
+                .help("Number of seconds between each batch of slasher processing. [default: 12]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("slasher-max-queue-length")
+                .long("slasher-max-queue-length")
+                .value_name("COUNT")
+                .help("Maximum number of attestations (and separately, blocks) queued for the \
+                       slasher between updates. Further messages are dropped. [default: 262144]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+
+        /*
+         * Validator monitor.
+         */
+        .arg(
+            Arg::with_name("validator-monitor-auto")
+                .long("validator-monitor-auto")
+                .help("Monitor the attestations and blocks of any validator which subscribes to \
+                       its duties via the HTTP API.")
+        )
+        .arg(
+            Arg::with_name("validator-monitor")
+                .long("validator-monitor")
+                .value_name("VALIDATORS")
+                .help("A comma-separated list of validator indices or 0x-prefixed public keys \
+                       whose attestations and blocks will be monitored.")
+                .takes_value(true)
+        )
+
         /*
          * Purge.
          */
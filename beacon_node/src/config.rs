@@ -4,7 +4,8 @@ use clap_utils::BAD_TESTNET_DIR_MESSAGE;
 use client::{config::DEFAULT_DATADIR, ClientConfig, ClientGenesis};
 use eth2_libp2p::{Enr, Multiaddr};
 use eth2_testnet_config::Eth2TestnetConfig;
-use slog::{crit, info, Logger};
+use rest_types::ValidatorId;
+use slog::{crit, info, warn, Logger};
 use ssz::Encode;
 use std::fs;
 use std::fs::File;
@@ -13,10 +14,12 @@ use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
 use std::net::{TcpListener, UdpSocket};
 use std::path::PathBuf;
 use types::{ChainSpec, EthSpec};
+use url::Url;
 
 pub const CLIENT_CONFIG_FILENAME: &str = "beacon-node.toml";
 pub const BEACON_NODE_DIR: &str = "beacon";
 pub const NETWORK_DIR: &str = "network";
+pub const DEFAULT_SLASHER_DIR: &str = "slasher_db";
 
 /// Gets the fully-initialized global client.
 ///
@@ -246,24 +249,24 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "http-port is not a valid u16.")?;
     }
 
-    /*
-     * Websocket server
-     */
-
-    if cli_args.is_present("ws") {
-        client_config.websocket_server.enabled = true;
+    if cli_args.is_present("http-allow-peer-admin") {
+        client_config.rest_api.allow_peer_admin = true;
     }
 
-    if let Some(address) = cli_args.value_of("ws-address") {
-        client_config.websocket_server.listen_address = address
-            .parse::<Ipv4Addr>()
-            .map_err(|_| "ws-address is not a valid IPv4 address.")?;
-    }
+    /*
+     * Websocket server (deprecated)
+     */
 
-    if let Some(port) = cli_args.value_of("ws-port") {
-        client_config.websocket_server.port = port
-            .parse::<u16>()
-            .map_err(|_| "ws-port is not a valid u16.")?;
+    if ["ws", "ws-address", "ws-port"]
+        .iter()
+        .any(|flag| cli_args.is_present(flag))
+    {
+        warn!(
+            log,
+            "The websocket server has been removed";
+            "msg" => "the --ws, --ws-address and --ws-port flags are ignored, subscribe to the \
+                      /eth/v1/events stream of the HTTP API instead",
+        );
     }
 
     /*
@@ -287,7 +290,29 @@ pub fn get_config<E: EthSpec>(
     // Defines the URL to reach the eth1 node.
     if let Some(val) = cli_args.value_of("eth1-endpoint") {
         client_config.sync_eth1_chain = true;
-        client_config.eth1.endpoint = val.to_string();
+        client_config.eth1.endpoints = parse_eth1_endpoints(val)?;
+    }
+
+    // Defines an ordered list of URLs to reach the eth1 nodes, used for fallback.
+    if let Some(val) = cli_args.value_of("eth1-endpoints") {
+        client_config.sync_eth1_chain = true;
+        client_config.eth1.endpoints = parse_eth1_endpoints(val)?;
+    }
+
+    if let Some(network_id) = cli_args.value_of("eth1-network-id") {
+        client_config.eth1.network_id = Some(
+            network_id
+                .parse::<u64>()
+                .map_err(|_| "eth1-network-id is not a valid integer".to_string())?,
+        );
+    }
+
+    if let Some(chain_id) = cli_args.value_of("eth1-chain-id") {
+        client_config.eth1.chain_id = Some(
+            chain_id
+                .parse::<u64>()
+                .map_err(|_| "eth1-chain-id is not a valid integer".to_string())?,
+        );
     }
 
     if let Some(freezer_dir) = cli_args.value_of("freezer-dir") {
@@ -311,6 +336,54 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "block-cache-size is not a valid integer".to_string())?;
     }
 
+    /*
+     * Slasher.
+     */
+    if cli_args.is_present("slasher") {
+        let slasher_dir = if let Some(slasher_dir) = cli_args.value_of("slasher-dir") {
+            PathBuf::from(slasher_dir)
+        } else {
+            client_config.data_dir.join(DEFAULT_SLASHER_DIR)
+        };
+        let mut slasher_config = slasher::Config::new(slasher_dir);
+
+        if let Some(history_length) = cli_args.value_of("slasher-history-length") {
+            slasher_config.history_length = history_length
+                .parse()
+                .map_err(|_| "slasher-history-length is not a valid integer".to_string())?;
+        }
+
+        if let Some(update_period) = cli_args.value_of("slasher-update-period") {
+            slasher_config.update_period = update_period
+                .parse()
+                .map_err(|_| "slasher-update-period is not a valid integer".to_string())?;
+        }
+
+        if let Some(max_queue_length) = cli_args.value_of("slasher-max-queue-length") {
+            slasher_config.max_queue_length = max_queue_length
+                .parse()
+                .map_err(|_| "slasher-max-queue-length is not a valid integer".to_string())?;
+        }
+
+        client_config.slasher = Some(slasher_config);
+    }
+
+    /*
+     * Validator monitor.
+     */
+    client_config.validator_monitor.auto_register = cli_args.is_present("validator-monitor-auto");
+
+    if let Some(validators) = cli_args.value_of("validator-monitor") {
+        for validator in validators.split(',') {
+            match validator.parse::<ValidatorId>()? {
+                ValidatorId::PublicKey(pubkey) => {
+                    client_config.validator_monitor.pubkeys.push(pubkey)
+                }
+                ValidatorId::Index(index) => client_config.validator_monitor.indices.push(index),
+            }
+        }
+    }
+
     if spec_constants != client_config.spec_constants {
         crit!(log, "Specification constants do not match.";
               "client_config" => client_config.spec_constants.to_string(),
@@ -337,7 +410,6 @@ pub fn get_config<E: EthSpec>(
         client_config.network.discovery_port =
             unused_port("udp").map_err(|e| format!("Failed to get port for discovery: {}", e))?;
         client_config.rest_api.port = 0;
-        client_config.websocket_server.port = 0;
     }
 
     /*
@@ -369,6 +441,28 @@ pub fn get_config<E: EthSpec>(
         client_config.genesis = ClientGenesis::DepositContract;
     }
 
+    // Start from a weak subjectivity checkpoint, if specified. This only has an effect when the
+    // database is empty.
+    if let Some(anchor_state_path) = cli_args.value_of("checkpoint-state") {
+        let genesis_state_bytes = checkpoint_genesis_state_bytes(&client_config.genesis)?;
+        let anchor_block_path = cli_args
+            .value_of("checkpoint-block")
+            .ok_or_else(|| "--checkpoint-block must be supplied with --checkpoint-state")?;
+
+        client_config.genesis = ClientGenesis::WeakSubjSszBytes {
+            anchor_state_bytes: read_bytes(anchor_state_path)?,
+            anchor_block_bytes: read_bytes(anchor_block_path)?,
+            genesis_state_bytes,
+        };
+    } else if let Some(url) = cli_args.value_of("checkpoint-sync-url") {
+        let genesis_state_bytes = checkpoint_genesis_state_bytes(&client_config.genesis)?;
+
+        client_config.genesis = ClientGenesis::CheckpointSyncUrl {
+            url: url.to_string(),
+            genesis_state_bytes,
+        };
+    }
+
     if !config_file_existed {
         write_to_file(config_file_path, &client_config)?;
     }
@@ -389,6 +483,56 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
+/// Parses a comma-delimited list of eth1 endpoints, ignoring surrounding whitespace and empty
+/// entries.
+///
+/// Returns an error if any endpoint is not a valid HTTP(S) URL or if no endpoints are supplied.
+fn parse_eth1_endpoints(endpoints: &str) -> Result<Vec<String>, String> {
+    let endpoints = endpoints
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|endpoint| {
+            let url = Url::parse(endpoint)
+                .map_err(|e| format!("Invalid eth1 endpoint {}: {:?}", endpoint, e))?;
+            if url.scheme() == "http" || url.scheme() == "https" {
+                Ok(endpoint.to_string())
+            } else {
+                Err(format!(
+                    "Invalid eth1 endpoint {}: must be an http or https URL",
+                    endpoint
+                ))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if endpoints.is_empty() {
+        return Err("At least one eth1 endpoint must be supplied (--eth1-endpoints)".to_string());
+    }
+
+    Ok(endpoints)
+}
+
+/// Returns the genesis state bytes from `genesis`, which are required to start from a weak
+/// subjectivity checkpoint.
+fn checkpoint_genesis_state_bytes(genesis: &ClientGenesis) -> Result<Vec<u8>, String> {
+    match genesis {
+        ClientGenesis::SszBytes {
+            genesis_state_bytes,
+        } => Ok(genesis_state_bytes.clone()),
+        _ => Err(
+            "Starting from a checkpoint requires a testnet with a known genesis state. \
+             Use --testnet-dir to specify one."
+                .to_string(),
+        ),
+    }
+}
+
+/// Reads the entire contents of the file at `path`.
+fn read_bytes(path: &str) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Unable to read {}: {:?}", path, e))
+}
+
 /// Gets the testnet dir which should be used.
 pub fn get_testnet_dir(cli_args: &ArgMatches) -> Option<PathBuf> {
     // Read the `--testnet-dir` flag.
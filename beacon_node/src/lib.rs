@@ -12,7 +12,7 @@ pub use eth2_config::Eth2Config;
 
 use beacon_chain::migrate::{BackgroundMigrator, DiskStore};
 use beacon_chain::{
-    builder::Witness, eth1_chain::CachingEth1Backend, events::WebSocketSender,
+    builder::Witness, eth1_chain::CachingEth1Backend, events::ServerSentEventHandler,
     slot_clock::SystemTimeSlotClock,
 };
 use clap::ArgMatches;
@@ -30,7 +30,7 @@ pub type ProductionClient<E> = Client<
         SystemTimeSlotClock,
         CachingEth1Backend<E, DiskStore<E>>,
         E,
-        WebSocketSender<E>,
+        ServerSentEventHandler<E>,
     >,
 >;
 
@@ -93,7 +93,7 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             info!(
                 log,
                 "Block production enabled";
-                "endpoint" => &client_config.eth1.endpoint,
+                "endpoints" => format!("{:?}", &client_config.eth1.endpoints),
                 "method" => "json rpc via http"
             );
             builder.caching_eth1_backend(client_config.eth1.clone())?
@@ -115,10 +115,11 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
 
         let builder = builder
             .system_time_slot_clock()?
-            .websocket_event_handler(client_config.websocket_server.clone())?
+            .server_sent_event_handler()
             .build_beacon_chain()?
             .network(&mut client_config.network)?
-            .notifier()?;
+            .notifier()?
+            .slasher_service()?;
 
         let builder = if client_config.rest_api.enabled {
             builder.http_server(&client_config, &http_eth2_config)?
@@ -1,5 +1,6 @@
 use crate::chunked_vector::{
-    store_updated_vector, BlockRoots, HistoricalRoots, RandaoMixes, StateRoots,
+    chunk_key, store_updated_vector, BlockRoots, Chunk, Field, HistoricalRoots, RandaoMixes,
+    StateRoots,
 };
 use crate::config::StoreConfig;
 use crate::forwards_iter::HybridForwardsBlockRootsIterator;
@@ -26,6 +27,8 @@ use types::*;
 
 /// 32-byte key for accessing the `split` of the freezer DB.
 pub const SPLIT_DB_KEY: &str = "FREEZERDBSPLITFREEZERDBSPLITFREE";
+/// 32-byte key for accessing the `anchor_info` of a database initialized from a checkpoint.
+pub const ANCHOR_INFO_DB_KEY: &str = "ANCHORINFOANCHORINFOANCHORINFOAN";
 
 /// On-disk database that stores finalized states efficiently.
 ///
@@ -37,12 +40,18 @@ pub struct HotColdDB<E: EthSpec> {
     /// States with slots less than `split.slot` are in the cold DB, while states with slots
     /// greater than or equal are in the hot DB.
     split: RwLock<Split>,
+    /// Describes the oldest block in the database, if the database was initialized from a weak
+    /// subjectivity checkpoint rather than from genesis.
+    ///
+    /// Blocks older than the anchor are filled-in by backfill sync and stored in the cold DB.
+    anchor_info: RwLock<Option<AnchorInfo>>,
     config: StoreConfig,
     /// Cold database containing compact historical data.
     pub(crate) cold_db: LevelDB<E>,
     /// Hot database containing duplicated but quick-to-access recent data.
     ///
-    /// The hot database also contains all blocks.
+    /// The hot database also contains all blocks, except for historic blocks older than the
+    /// anchor which are stored in the cold database.
     pub(crate) hot_db: LevelDB<E>,
     /// LRU cache of deserialized blocks. Updated whenever a block is loaded.
     block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
@@ -81,6 +90,11 @@ pub enum HotColdDBError {
         slots_per_epoch: u64,
     },
     RestorePointBlockHashError(BeaconStateError),
+    MissingAnchorInfo,
+    HistoricalBlockSlotError {
+        oldest_block_slot: Slot,
+        block_slot: Slot,
+    },
 }
 
 impl<E: EthSpec> Store<E> for HotColdDB<E> {
@@ -124,8 +138,14 @@ impl<E: EthSpec> Store<E> for HotColdDB<E> {
             return Ok(Some(block.clone()));
         }
 
-        // Fetch from database.
-        match self.get::<SignedBeaconBlock<E>>(block_root)? {
+        // Fetch from the hot database, falling back to the cold database for historic blocks.
+        let block = match self.get::<SignedBeaconBlock<E>>(block_root)? {
+            Some(block) => Some(block),
+            None if self.anchor_info.read().is_some() => self.cold_db.get(block_root)?,
+            None => None,
+        };
+
+        match block {
             Some(block) => {
                 // Add to cache.
                 self.block_cache.lock().put(*block_root, block.clone());
@@ -293,6 +313,74 @@ impl<E: EthSpec> Store<E> for HotColdDB<E> {
         Ok(())
     }
 
+    /// Set the split point to the anchor state, so that the anchor and all later states are stored
+    /// in the hot database.
+    ///
+    /// The anchor must lie on an epoch boundary (in order for the hot state summary scheme to
+    /// work), and the database must not contain any existing states.
+    fn initialize_split(&self, anchor_slot: Slot, anchor_state_root: Hash256) -> Result<(), Error> {
+        if anchor_slot % E::slots_per_epoch() != 0 {
+            return Err(HotColdDBError::FreezeSlotUnaligned(anchor_slot).into());
+        }
+
+        *self.split.write() = Split {
+            slot: anchor_slot,
+            state_root: anchor_state_root,
+        };
+        self.store_split()
+    }
+
+    fn get_anchor_info(&self) -> Option<AnchorInfo> {
+        *self.anchor_info.read()
+    }
+
+    fn put_anchor_info(&self, anchor_info: AnchorInfo) -> Result<(), Error> {
+        let key = Hash256::from_slice(ANCHOR_INFO_DB_KEY.as_bytes());
+        self.hot_db.put(&key, &anchor_info)?;
+        *self.anchor_info.write() = Some(anchor_info);
+        Ok(())
+    }
+
+    /// Store a batch of historic blocks in the cold database, filling in the frozen `block_roots`
+    /// vector for the slots they cover and then advancing the anchor.
+    ///
+    /// The `block_roots` vector is filled for all slots from `anchor_info.oldest_block_slot` up to
+    /// (but excluding) the previous oldest block slot, allowing the forwards block roots iterator
+    /// to serve these slots.
+    fn put_historical_blocks(
+        &self,
+        blocks: Vec<(Hash256, SignedBeaconBlock<E>)>,
+        anchor_info: AnchorInfo,
+    ) -> Result<(), Error> {
+        let prev_anchor = self
+            .get_anchor_info()
+            .ok_or_else(|| HotColdDBError::MissingAnchorInfo)?;
+
+        for (_, block) in &blocks {
+            if block.slot() >= prev_anchor.oldest_block_slot
+                || block.slot() < anchor_info.oldest_block_slot
+            {
+                return Err(HotColdDBError::HistoricalBlockSlotError {
+                    oldest_block_slot: prev_anchor.oldest_block_slot,
+                    block_slot: block.slot(),
+                }
+                .into());
+            }
+        }
+
+        for (block_root, block) in &blocks {
+            self.cold_db.put(block_root, block)?;
+        }
+
+        self.store_historical_block_roots(
+            &blocks,
+            anchor_info.oldest_block_slot,
+            prev_anchor.oldest_block_slot,
+        )?;
+
+        self.put_anchor_info(anchor_info)
+    }
+
     fn forwards_block_roots_iterator(
         store: Arc<Self>,
         start_slot: Slot,
@@ -351,6 +439,7 @@ impl<E: EthSpec> HotColdDB<E> {
 
         let db = HotColdDB {
             split: RwLock::new(Split::default()),
+            anchor_info: RwLock::new(None),
             cold_db: LevelDB::open(cold_path)?,
             hot_db: LevelDB::open(hot_path)?,
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
@@ -365,6 +454,7 @@ impl<E: EthSpec> HotColdDB<E> {
         if let Some(split) = db.load_split()? {
             *db.split.write() = split;
         }
+        *db.anchor_info.write() = db.load_anchor_info()?;
         Ok(db)
     }
 
@@ -678,6 +768,66 @@ impl<E: EthSpec> HotColdDB<E> {
         Ok(())
     }
 
+    /// Load the anchor info from disk.
+    fn load_anchor_info(&self) -> Result<Option<AnchorInfo>, Error> {
+        let key = Hash256::from_slice(ANCHOR_INFO_DB_KEY.as_bytes());
+        self.hot_db.get(&key)
+    }
+
+    /// Write the frozen `block_roots` vector for the slots `start_slot..end_slot`.
+    ///
+    /// The `blocks` must be sorted in ascending slot order, and the first block must lie at
+    /// `start_slot`. Skipped slots take the root of the most recent prior block.
+    fn store_historical_block_roots(
+        &self,
+        blocks: &[(Hash256, SignedBeaconBlock<E>)],
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<(), Error> {
+        let chunk_size = <BlockRoots as Field<E>>::chunk_size();
+        let column = <BlockRoots as Field<E>>::column();
+
+        let mut blocks = blocks.iter().peekable();
+        let mut block_root = Hash256::zero();
+        let mut chunk_index = start_slot.as_usize() / chunk_size;
+        let mut chunk = self.load_block_roots_chunk(chunk_index, chunk_size)?;
+
+        for slot in start_slot.as_usize()..end_slot.as_usize() {
+            while let Some((root, _)) = blocks.peek().filter(|(_, b)| b.slot() <= slot as u64) {
+                block_root = *root;
+                blocks.next();
+            }
+
+            if slot / chunk_size != chunk_index {
+                chunk.store(&self.cold_db, column, &chunk_key(chunk_index as u64))?;
+                chunk_index = slot / chunk_size;
+                chunk = self.load_block_roots_chunk(chunk_index, chunk_size)?;
+            }
+
+            chunk.values[slot % chunk_size] = block_root;
+        }
+
+        chunk.store(&self.cold_db, column, &chunk_key(chunk_index as u64))?;
+
+        Ok(())
+    }
+
+    /// Load a chunk of the frozen `block_roots` vector, padding it to `chunk_size` with zeros.
+    fn load_block_roots_chunk(
+        &self,
+        chunk_index: usize,
+        chunk_size: usize,
+    ) -> Result<Chunk<Hash256>, Error> {
+        let mut chunk = Chunk::load(
+            &self.cold_db,
+            <BlockRoots as Field<E>>::column(),
+            &chunk_key(chunk_index as u64),
+        )?
+        .unwrap_or_else(Chunk::default);
+        chunk.values.resize(chunk_size, Hash256::zero());
+        Ok(chunk)
+    }
+
     /// Load the state root of a restore point.
     fn load_restore_point_hash(&self, restore_point_index: u64) -> Result<Hash256, Error> {
         let key = Self::restore_point_key(restore_point_index);
@@ -779,6 +929,42 @@ impl SimpleStoreItem for Split {
     }
 }
 
+/// Describes the oldest block stored in a database that was initialized from a weak subjectivity
+/// checkpoint.
+///
+/// Blocks between `oldest_block_slot` and `anchor_slot` have been filled-in by backfill sync, and
+/// the next block to be filled-in is the block with root `oldest_block_parent`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Encode, Decode)]
+pub struct AnchorInfo {
+    /// The slot of the checkpoint state that the database was initialized from.
+    pub anchor_slot: Slot,
+    /// The slot of the oldest block stored in the database.
+    pub oldest_block_slot: Slot,
+    /// The parent root of the oldest block stored in the database.
+    pub oldest_block_parent: Hash256,
+}
+
+impl AnchorInfo {
+    /// Returns `true` once all blocks back to genesis have been stored.
+    pub fn block_backfill_complete(&self) -> bool {
+        self.oldest_block_slot == 0
+    }
+}
+
+impl SimpleStoreItem for AnchorInfo {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
 /// Struct for summarising a state in the hot database.
 ///
 /// Allows full reconstruction by replaying blocks.
@@ -28,7 +28,7 @@ pub mod iter;
 use std::sync::Arc;
 
 pub use self::config::StoreConfig;
-pub use self::hot_cold_store::{HotColdDB as DiskStore, HotStateSummary};
+pub use self::hot_cold_store::{AnchorInfo, HotColdDB as DiskStore, HotStateSummary};
 pub use self::leveldb_store::LevelDB as SimpleDiskStore;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
@@ -157,6 +157,48 @@ pub trait Store<E: EthSpec>: Sync + Send + Sized + 'static {
         Ok(())
     }
 
+    /// (Optionally) Set the point at which the database is split between hot and cold to the
+    /// given finalized anchor state.
+    ///
+    /// Used when the database is initialized from a weak subjectivity checkpoint rather than from
+    /// genesis, in which case no states prior to the anchor are available.
+    fn initialize_split(
+        &self,
+        _anchor_slot: Slot,
+        _anchor_state_root: Hash256,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Return information about the oldest block in the database, if the database was initialized
+    /// from a weak subjectivity checkpoint.
+    ///
+    /// A return value of `None` indicates that all blocks back to genesis are available.
+    fn get_anchor_info(&self) -> Option<AnchorInfo> {
+        None
+    }
+
+    /// (Optionally) Persist information about the oldest block in the database.
+    fn put_anchor_info(&self, _anchor_info: AnchorInfo) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Store a batch of historic blocks which are older than the current oldest block, and update
+    /// the anchor to `anchor_info`.
+    ///
+    /// The `blocks` must be sorted in ascending slot order. Stores which do not track an anchor
+    /// store the blocks alongside all other blocks.
+    fn put_historical_blocks(
+        &self,
+        blocks: Vec<(Hash256, SignedBeaconBlock<E>)>,
+        _anchor_info: AnchorInfo,
+    ) -> Result<(), Error> {
+        for (block_root, block) in blocks {
+            self.put_block(&block_root, block)?;
+        }
+        Ok(())
+    }
+
     /// Get a forwards (slot-ascending) iterator over the beacon block roots since `start_slot`.
     ///
     /// Will be efficient for frozen portions of the database if using `DiskStore`.
@@ -227,6 +269,20 @@ pub enum DBColumn {
     BeaconHistoricalRoots,
     BeaconRandaoMixes,
     DhtEnrs,
+    /// For the reputations and bans of known peers.
+    PeerReputations,
+    /// For the slasher's own metadata, such as the range of epochs it is tracking.
+    SlasherMeta,
+    /// For the slasher's chunked arrays of minimum and maximum target distances.
+    SlasherMinTargets,
+    SlasherMaxTargets,
+    /// For the mapping from `(validator_index, target_epoch)` to indexed attestation roots.
+    SlasherAttesterRecords,
+    SlasherIndexedAttestations,
+    /// For the list of indexed attestation roots stored for each target epoch.
+    SlasherAttestationRoots,
+    /// For the block headers seen by the slasher at each slot.
+    SlasherProposals,
 }
 
 impl Into<&'static str> for DBColumn {
@@ -247,6 +303,14 @@ impl Into<&'static str> for DBColumn {
             DBColumn::BeaconHistoricalRoots => "bhr",
             DBColumn::BeaconRandaoMixes => "brm",
             DBColumn::DhtEnrs => "dht",
+            DBColumn::PeerReputations => "prp",
+            DBColumn::SlasherMeta => "sme",
+            DBColumn::SlasherMinTargets => "smn",
+            DBColumn::SlasherMaxTargets => "smx",
+            DBColumn::SlasherAttesterRecords => "sar",
+            DBColumn::SlasherIndexedAttestations => "sia",
+            DBColumn::SlasherAttestationRoots => "sat",
+            DBColumn::SlasherProposals => "spr",
         }
     }
 }
@@ -321,6 +321,30 @@ mod tests {
         assert_eq!(wallets, vec![(name, uuid)]);
     }
 
+    #[test]
+    fn set_nextaccount() {
+        let dir = tempdir().unwrap();
+        let base_dir = dir.path();
+        let mgr = WalletManager::open(base_dir).unwrap();
+
+        let mut w = create_wallet(&mgr, 0);
+        let uuid = w.wallet().uuid().clone();
+
+        w.set_nextaccount(42).expect("should set nextaccount");
+        assert_eq!(w.wallet().nextaccount(), 42);
+        assert_eq!(
+            load_wallet_raw(&base_dir, &uuid).nextaccount(),
+            42,
+            "should persist nextaccount"
+        );
+
+        let keystores = w
+            .next_validator(WALLET_PASSWORD, &[1], &[0])
+            .expect("should create validator");
+        assert_eq!(keystores.voting.path(), "m/12381/3600/42/0/0");
+        assert_eq!(load_wallet_raw(&base_dir, &uuid).nextaccount(), 43);
+    }
+
     #[test]
     fn locked_wallet_lockfile() {
         let dir = tempdir().unwrap();
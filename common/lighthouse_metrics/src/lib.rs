@@ -8,6 +8,14 @@
 //! - `IncCounter`: used to represent an ideally ever-growing, never-shrinking integer (e.g.,
 //! number of block processing requests).
 //! - `IntGauge`: used to represent an varying integer (e.g., number of attestations per block).
+//! - `IntCounterVec`: a set of `IntCounter`s partitioned by label values (e.g., number of requests
+//! per HTTP endpoint).
+//! - `IntGaugeVec`: a set of `IntGauge`s partitioned by label values (e.g., balance per validator).
+//! - `HistogramVec`: a set of `Histogram`s partitioned by label values (e.g., request processing
+//! time per RPC protocol).
+//!
+//! The `*_vec` functions degrade in the same way as the rest of the API: if the vector could not
+//! be created, or the label values do not match its label names, they do nothing.
 //!
 //! ## Important
 //!
@@ -56,7 +64,10 @@
 
 use prometheus::{HistogramOpts, HistogramTimer, Opts};
 
-pub use prometheus::{Encoder, Gauge, Histogram, IntCounter, IntGauge, Result, TextEncoder};
+pub use prometheus::{
+    Encoder, Gauge, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    Result, TextEncoder,
+};
 
 /// Collect all the metrics for reporting.
 pub fn gather() -> Vec<prometheus::proto::MetricFamily> {
@@ -72,6 +83,19 @@ pub fn try_create_int_counter(name: &str, help: &str) -> Result<IntCounter> {
     Ok(counter)
 }
 
+/// Attempts to crate an `IntCounterVec`, returning `Err` if the registry does not accept the
+/// counter (potentially due to naming conflict).
+pub fn try_create_int_counter_vec(
+    name: &str,
+    help: &str,
+    label_names: &[&str],
+) -> Result<IntCounterVec> {
+    let opts = Opts::new(name, help);
+    let counter_vec = IntCounterVec::new(opts, label_names)?;
+    prometheus::register(Box::new(counter_vec.clone()))?;
+    Ok(counter_vec)
+}
+
 /// Attempts to crate an `IntGauge`, returning `Err` if the registry does not accept the counter
 /// (potentially due to naming conflict).
 pub fn try_create_int_gauge(name: &str, help: &str) -> Result<IntGauge> {
@@ -81,6 +105,19 @@ pub fn try_create_int_gauge(name: &str, help: &str) -> Result<IntGauge> {
     Ok(gauge)
 }
 
+/// Attempts to crate an `IntGaugeVec`, returning `Err` if the registry does not accept the gauge
+/// (potentially due to naming conflict).
+pub fn try_create_int_gauge_vec(
+    name: &str,
+    help: &str,
+    label_names: &[&str],
+) -> Result<IntGaugeVec> {
+    let opts = Opts::new(name, help);
+    let gauge_vec = IntGaugeVec::new(opts, label_names)?;
+    prometheus::register(Box::new(gauge_vec.clone()))?;
+    Ok(gauge_vec)
+}
+
 /// Attempts to crate a `Gauge`, returning `Err` if the registry does not accept the counter
 /// (potentially due to naming conflict).
 pub fn try_create_float_gauge(name: &str, help: &str) -> Result<Gauge> {
@@ -99,6 +136,53 @@ pub fn try_create_histogram(name: &str, help: &str) -> Result<Histogram> {
     Ok(histogram)
 }
 
+/// Attempts to crate a `Histogram` with the given `buckets`, returning `Err` if the registry does
+/// not accept the counter (potentially due to naming conflict).
+pub fn try_create_histogram_with_buckets(
+    name: &str,
+    help: &str,
+    buckets: Vec<f64>,
+) -> Result<Histogram> {
+    let opts = HistogramOpts::new(name, help).buckets(buckets);
+    let histogram = Histogram::with_opts(opts)?;
+    prometheus::register(Box::new(histogram.clone()))?;
+    Ok(histogram)
+}
+
+/// Attempts to crate a `HistogramVec`, returning `Err` if the registry does not accept the
+/// histogram (potentially due to naming conflict).
+pub fn try_create_histogram_vec(
+    name: &str,
+    help: &str,
+    label_names: &[&str],
+) -> Result<HistogramVec> {
+    let opts = HistogramOpts::new(name, help);
+    let histogram_vec = HistogramVec::new(opts, label_names)?;
+    prometheus::register(Box::new(histogram_vec.clone()))?;
+    Ok(histogram_vec)
+}
+
+/// Returns the `Histogram` with the given `label_values`, if it can be obtained.
+pub fn get_histogram(
+    histogram_vec: &Result<HistogramVec>,
+    label_values: &[&str],
+) -> Option<Histogram> {
+    histogram_vec
+        .as_ref()
+        .ok()?
+        .get_metric_with_label_values(label_values)
+        .ok()
+}
+
+/// Starts a timer for the `Histogram` with the given `label_values`, stopping when it gets
+/// dropped or given to `stop_timer(..)`.
+pub fn start_timer_vec(
+    histogram_vec: &Result<HistogramVec>,
+    label_values: &[&str],
+) -> Option<HistogramTimer> {
+    get_histogram(histogram_vec, label_values).map(|histogram| histogram.start_timer())
+}
+
 /// Starts a timer for the given `Histogram`, stopping when it gets dropped or given to `stop_timer(..)`.
 pub fn start_timer(histogram: &Result<Histogram>) -> Option<HistogramTimer> {
     if let Ok(histogram) = histogram {
@@ -121,6 +205,36 @@ pub fn inc_counter(counter: &Result<IntCounter>) {
     }
 }
 
+/// Returns the `IntCounter` with the given `label_values`, if it can be obtained.
+pub fn get_int_counter(
+    int_counter_vec: &Result<IntCounterVec>,
+    label_values: &[&str],
+) -> Option<IntCounter> {
+    int_counter_vec
+        .as_ref()
+        .ok()?
+        .get_metric_with_label_values(label_values)
+        .ok()
+}
+
+/// Increments the `IntCounter` with the given `label_values` by one.
+pub fn inc_counter_vec(int_counter_vec: &Result<IntCounterVec>, label_values: &[&str]) {
+    if let Some(counter) = get_int_counter(int_counter_vec, label_values) {
+        counter.inc()
+    }
+}
+
+/// Increments the `IntCounter` with the given `label_values` by `value`.
+pub fn inc_counter_vec_by(
+    int_counter_vec: &Result<IntCounterVec>,
+    label_values: &[&str],
+    value: i64,
+) {
+    if let Some(counter) = get_int_counter(int_counter_vec, label_values) {
+        counter.inc_by(value);
+    }
+}
+
 pub fn inc_counter_by(counter: &Result<IntCounter>, value: i64) {
     if let Ok(counter) = counter {
         counter.inc_by(value);
@@ -133,6 +247,39 @@ pub fn set_gauge(gauge: &Result<IntGauge>, value: i64) {
     }
 }
 
+/// Returns the `IntGauge` with the given `label_values`, if it can be obtained.
+pub fn get_int_gauge(
+    int_gauge_vec: &Result<IntGaugeVec>,
+    label_values: &[&str],
+) -> Option<IntGauge> {
+    int_gauge_vec
+        .as_ref()
+        .ok()?
+        .get_metric_with_label_values(label_values)
+        .ok()
+}
+
+/// Sets the `IntGauge` with the given `label_values` to `value`.
+pub fn set_gauge_vec(int_gauge_vec: &Result<IntGaugeVec>, label_values: &[&str], value: i64) {
+    if let Some(gauge) = get_int_gauge(int_gauge_vec, label_values) {
+        gauge.set(value);
+    }
+}
+
+/// Increments the `IntGauge` with the given `label_values` by one.
+pub fn inc_gauge_vec(int_gauge_vec: &Result<IntGaugeVec>, label_values: &[&str]) {
+    if let Some(gauge) = get_int_gauge(int_gauge_vec, label_values) {
+        gauge.inc();
+    }
+}
+
+/// Decrements the `IntGauge` with the given `label_values` by one.
+pub fn dec_gauge_vec(int_gauge_vec: &Result<IntGaugeVec>, label_values: &[&str]) {
+    if let Some(gauge) = get_int_gauge(int_gauge_vec, label_values) {
+        gauge.dec();
+    }
+}
+
 pub fn maybe_set_gauge(gauge: &Result<IntGauge>, value_opt: Option<i64>) {
     if let Some(value) = value_opt {
         set_gauge(gauge, value)
@@ -157,3 +304,10 @@ pub fn observe(histogram: &Result<Histogram>, value: f64) {
         histogram.observe(value);
     }
 }
+
+/// Sets the value of the `Histogram` with the given `label_values` manually.
+pub fn observe_vec(histogram_vec: &Result<HistogramVec>, label_values: &[&str], value: f64) {
+    if let Some(histogram) = get_histogram(histogram_vec, label_values) {
+        histogram.observe(value);
+    }
+}
@@ -4,6 +4,7 @@
 //! Presently, this is only used for testing but it _could_ become a user-facing library.
 
 use eth2_config::Eth2Config;
+use futures::stream::{self, Stream};
 use reqwest::{Client, ClientBuilder, Response, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use ssz::Encode;
@@ -12,16 +13,21 @@ use std::time::Duration;
 use types::{
     Attestation, AttestationData, AttesterSlashing, BeaconBlock, BeaconState, CommitteeIndex,
     Epoch, EthSpec, Fork, Hash256, ProposerSlashing, PublicKey, PublicKeyBytes, Signature,
-    SignedAggregateAndProof, SignedBeaconBlock, Slot,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot, YamlConfig,
 };
 use url::Url;
 
 pub use operation_pool::PersistedOperationPool;
 pub use proto_array_fork_choice::core::ProtoArray;
 pub use rest_types::{
-    CanonicalHeadResponse, Committee, HeadBeaconBlock, Health, IndividualVotesRequest,
-    IndividualVotesResponse, SyncingResponse, ValidatorDutiesRequest, ValidatorDutyBytes,
-    ValidatorRequest, ValidatorResponse, ValidatorSubscription,
+    AttesterData, BanPeerRequest, BannedPeer, BlockHeaderData, BlockId, BlockRewardResponse,
+    CanonicalHeadResponse, ChainHeadData, Committee, CommitteeData, DepositContractData,
+    DialPeerRequest, DisconnectPeerRequest, ErrorMessage, EventKind, EventTopic,
+    FinalityCheckpointsData, GenericResponse, GenesisData, HeadBeaconBlock, Health, IdentityData,
+    IndividualVotesRequest, IndividualVotesResponse, PeerData, ProposerData, RootData, StateId,
+    SyncingData, SyncingResponse, UnbanPeerRequest, ValidatorData, ValidatorDutiesRequest,
+    ValidatorDutyBytes, ValidatorId, ValidatorRequest, ValidatorResponse, ValidatorRewardsResponse,
+    ValidatorSubscription, VersionData,
 };
 
 // Setting a long timeout for debug ensures that crypto-heavy operations can still succeed.
@@ -111,6 +117,14 @@ impl<E: EthSpec> HttpClient<E> {
         Consensus(self.clone())
     }
 
+    pub fn standard(&self) -> Standard<E> {
+        Standard(self.clone())
+    }
+
+    pub fn lighthouse(&self) -> Lighthouse<E> {
+        Lighthouse(self.clone())
+    }
+
     fn url(&self, path: &str) -> Result<Url, Error> {
         self.url.join(path).map_err(|e| e.into())
     }
@@ -669,7 +683,7 @@ impl<E: EthSpec> Consensus<E> {
         &self,
         epoch: Epoch,
         pubkeys: Vec<PublicKeyBytes>,
-    ) -> Result<IndividualVotesResponse, Error> {
+    ) -> Result<Vec<IndividualVotesResponse>, Error> {
         let client = self.0.clone();
         let req_body = IndividualVotesRequest { epoch, pubkeys };
 
@@ -688,6 +702,559 @@ impl<E: EthSpec> Consensus<E> {
     }
 }
 
+/// Provides the functions on the `/lighthouse` endpoint of the node.
+#[derive(Clone)]
+pub struct Lighthouse<E>(HttpClient<E>);
+
+impl<E: EthSpec> Lighthouse<E> {
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.0
+            .url("lighthouse/")
+            .and_then(move |url| url.join(path).map_err(Error::from))
+            .map_err(Into::into)
+    }
+
+    /// Gets the rewards and penalties of every validator for their duties during `epoch`.
+    pub async fn get_validator_rewards(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Vec<ValidatorRewardsResponse>, Error> {
+        let client = self.0.clone();
+        let query_params = vec![("epoch".into(), format!("{}", epoch.as_u64()))];
+        let url = self.url("validator_rewards")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Gets the rewards earned by the proposer of the block with `block_root`, attributed to each
+    /// operation included in the block.
+    pub async fn get_block_rewards(
+        &self,
+        block_root: Hash256,
+    ) -> Result<BlockRewardResponse, Error> {
+        let client = self.0.clone();
+        let query_params = vec![("block_root".into(), root_as_string(block_root))];
+        let url = self.url("block_rewards")?;
+        client.json_get(url, query_params).await
+    }
+
+    /// Perform a POST request of `body` on `path`, discarding the (empty) response.
+    async fn post<T: Serialize>(&self, path: &str, body: T) -> Result<(), Error> {
+        let client = self.0.clone();
+        let url = self.url(path)?;
+        let response = client.json_post::<_>(url, body).await?;
+        error_for_status(response)
+            .await
+            .map(|_| ())
+            .map_err(Error::from)
+    }
+
+    /// Dials a peer at `address`, which may be either an ENR or a multiaddr.
+    pub async fn dial_peer(&self, address: String) -> Result<(), Error> {
+        self.post("peers/dial", DialPeerRequest { address }).await
+    }
+
+    /// Sends a Goodbye with the `reason` code to a connected peer, then disconnects it.
+    pub async fn disconnect_peer(&self, peer_id: String, reason: u64) -> Result<(), Error> {
+        self.post(
+            "peers/disconnect",
+            DisconnectPeerRequest { peer_id, reason },
+        )
+        .await
+    }
+
+    /// Disconnects a peer and bans it for `duration_secs` seconds.
+    pub async fn ban_peer(&self, peer_id: String, duration_secs: u64) -> Result<(), Error> {
+        self.post(
+            "peers/ban",
+            BanPeerRequest {
+                peer_id,
+                duration_secs,
+            },
+        )
+        .await
+    }
+
+    /// Lifts the ban on a peer.
+    pub async fn unban_peer(&self, peer_id: String) -> Result<(), Error> {
+        self.post("peers/unban", UnbanPeerRequest { peer_id }).await
+    }
+
+    /// Gets all banned peers.
+    pub async fn get_banned_peers(&self) -> Result<Vec<BannedPeer>, Error> {
+        let client = self.0.clone();
+        let url = self.url("peers/banned")?;
+        client.json_get(url, vec![]).await
+    }
+}
+
+/// Provides the functions on the standard `/eth/v1` endpoints of the node.
+///
+/// Responses are unwrapped from their `GenericResponse`, so only the `data` is returned.
+#[derive(Clone)]
+pub struct Standard<E>(HttpClient<E>);
+
+impl<E: EthSpec> Standard<E> {
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.0
+            .url("eth/v1/")
+            .and_then(move |url| url.join(path).map_err(Error::from))
+            .map_err(Into::into)
+    }
+
+    /// Perform a GET request on `path`, returning the `data` of the response.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query_pairs: Vec<(String, String)>,
+    ) -> Result<T, Error> {
+        let client = self.0.clone();
+        let url = self.url(path)?;
+        client
+            .json_get::<GenericResponse<T>>(url, query_pairs)
+            .await
+            .map(|response| response.data)
+    }
+
+    /// Perform a POST request of `body` on `path`, mapping the response to a `PublishStatus`.
+    async fn post<T: Serialize>(&self, path: &str, body: T) -> Result<PublishStatus, Error> {
+        let client = self.0.clone();
+        let url = self.url(path)?;
+        let response = client.json_post::<_>(url, body).await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(PublishStatus::Valid),
+            StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
+                response.text().await.map_err(Error::from)?,
+            )),
+            _ => error_for_status(response)
+                .await
+                .map(|_| PublishStatus::Unknown),
+        }
+    }
+
+    /// `GET beacon/genesis`
+    pub async fn get_beacon_genesis(&self) -> Result<GenesisData, Error> {
+        self.get("beacon/genesis", vec![]).await
+    }
+
+    /// `GET beacon/states/{state_id}/root`
+    pub async fn get_beacon_state_root(&self, state_id: StateId) -> Result<Hash256, Error> {
+        self.get::<RootData>(&format!("beacon/states/{}/root", state_id), vec![])
+            .await
+            .map(|data| data.root)
+    }
+
+    /// `GET beacon/states/{state_id}/fork`
+    pub async fn get_beacon_state_fork(&self, state_id: StateId) -> Result<Fork, Error> {
+        self.get(&format!("beacon/states/{}/fork", state_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/states/{state_id}/finality_checkpoints`
+    pub async fn get_beacon_state_finality_checkpoints(
+        &self,
+        state_id: StateId,
+    ) -> Result<FinalityCheckpointsData, Error> {
+        self.get(
+            &format!("beacon/states/{}/finality_checkpoints", state_id),
+            vec![],
+        )
+        .await
+    }
+
+    /// `GET beacon/states/{state_id}/validators`
+    pub async fn get_beacon_state_validators(
+        &self,
+        state_id: StateId,
+    ) -> Result<Vec<ValidatorData>, Error> {
+        self.get(&format!("beacon/states/{}/validators", state_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/states/{state_id}/validators/{validator_id}`
+    pub async fn get_beacon_state_validator(
+        &self,
+        state_id: StateId,
+        validator_id: &ValidatorId,
+    ) -> Result<ValidatorData, Error> {
+        self.get(
+            &format!("beacon/states/{}/validators/{}", state_id, validator_id),
+            vec![],
+        )
+        .await
+    }
+
+    /// `GET beacon/states/{state_id}/committees/{epoch}`
+    ///
+    /// The committees may optionally be filtered by `index` and `slot`.
+    pub async fn get_beacon_state_committees(
+        &self,
+        state_id: StateId,
+        epoch: Epoch,
+        index: Option<CommitteeIndex>,
+        slot: Option<Slot>,
+    ) -> Result<Vec<CommitteeData>, Error> {
+        let mut query_params = vec![];
+        if let Some(index) = index {
+            query_params.push(("index".into(), format!("{}", index)));
+        }
+        if let Some(slot) = slot {
+            query_params.push(("slot".into(), format!("{}", slot.as_u64())));
+        }
+
+        self.get(
+            &format!("beacon/states/{}/committees/{}", state_id, epoch.as_u64()),
+            query_params,
+        )
+        .await
+    }
+
+    /// `GET beacon/headers`
+    ///
+    /// Returns the header of the canonical block at `slot`, or of the head block if `slot` is
+    /// `None`.
+    pub async fn get_beacon_headers(
+        &self,
+        slot: Option<Slot>,
+    ) -> Result<Vec<BlockHeaderData>, Error> {
+        let query_params = if let Some(slot) = slot {
+            vec![("slot".into(), format!("{}", slot.as_u64()))]
+        } else {
+            vec![]
+        };
+
+        self.get("beacon/headers", query_params).await
+    }
+
+    /// `GET beacon/headers/{block_id}`
+    pub async fn get_beacon_header(&self, block_id: BlockId) -> Result<BlockHeaderData, Error> {
+        self.get(&format!("beacon/headers/{}", block_id), vec![])
+            .await
+    }
+
+    /// `POST beacon/blocks`
+    pub async fn post_beacon_block(
+        &self,
+        block: SignedBeaconBlock<E>,
+    ) -> Result<PublishStatus, Error> {
+        self.post("beacon/blocks", block).await
+    }
+
+    /// `GET beacon/blocks/{block_id}`
+    pub async fn get_beacon_block(&self, block_id: BlockId) -> Result<SignedBeaconBlock<E>, Error> {
+        self.get(&format!("beacon/blocks/{}", block_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/blocks/{block_id}/root`
+    pub async fn get_beacon_block_root(&self, block_id: BlockId) -> Result<Hash256, Error> {
+        self.get::<RootData>(&format!("beacon/blocks/{}/root", block_id), vec![])
+            .await
+            .map(|data| data.root)
+    }
+
+    /// `GET beacon/blocks/{block_id}/attestations`
+    pub async fn get_beacon_block_attestations(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Vec<Attestation<E>>, Error> {
+        self.get(&format!("beacon/blocks/{}/attestations", block_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/pool/attestations`
+    pub async fn get_beacon_pool_attestations(&self) -> Result<Vec<Attestation<E>>, Error> {
+        self.get("beacon/pool/attestations", vec![]).await
+    }
+
+    /// `POST beacon/pool/attestations`
+    pub async fn post_beacon_pool_attestations(
+        &self,
+        attestations: Vec<Attestation<E>>,
+    ) -> Result<PublishStatus, Error> {
+        self.post("beacon/pool/attestations", attestations).await
+    }
+
+    /// `GET beacon/pool/attester_slashings`
+    pub async fn get_beacon_pool_attester_slashings(
+        &self,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        self.get("beacon/pool/attester_slashings", vec![]).await
+    }
+
+    /// `POST beacon/pool/attester_slashings`
+    pub async fn post_beacon_pool_attester_slashings(
+        &self,
+        slashing: AttesterSlashing<E>,
+    ) -> Result<PublishStatus, Error> {
+        self.post("beacon/pool/attester_slashings", slashing).await
+    }
+
+    /// `GET beacon/pool/proposer_slashings`
+    pub async fn get_beacon_pool_proposer_slashings(&self) -> Result<Vec<ProposerSlashing>, Error> {
+        self.get("beacon/pool/proposer_slashings", vec![]).await
+    }
+
+    /// `POST beacon/pool/proposer_slashings`
+    pub async fn post_beacon_pool_proposer_slashings(
+        &self,
+        slashing: ProposerSlashing,
+    ) -> Result<PublishStatus, Error> {
+        self.post("beacon/pool/proposer_slashings", slashing).await
+    }
+
+    /// `GET beacon/pool/voluntary_exits`
+    pub async fn get_beacon_pool_voluntary_exits(&self) -> Result<Vec<SignedVoluntaryExit>, Error> {
+        self.get("beacon/pool/voluntary_exits", vec![]).await
+    }
+
+    /// `POST beacon/pool/voluntary_exits`
+    pub async fn post_beacon_pool_voluntary_exits(
+        &self,
+        exit: SignedVoluntaryExit,
+    ) -> Result<PublishStatus, Error> {
+        self.post("beacon/pool/voluntary_exits", exit).await
+    }
+
+    /// `GET validator/duties/attester/{epoch}`
+    pub async fn get_validator_duties_attester(
+        &self,
+        epoch: Epoch,
+        indices: &[u64],
+    ) -> Result<Vec<AttesterData>, Error> {
+        let indices = indices
+            .iter()
+            .map(|index| format!("{}", index))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        self.get(
+            &format!("validator/duties/attester/{}", epoch.as_u64()),
+            vec![("index".into(), indices)],
+        )
+        .await
+    }
+
+    /// `GET validator/duties/proposer/{epoch}`
+    pub async fn get_validator_duties_proposer(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Vec<ProposerData>, Error> {
+        self.get(
+            &format!("validator/duties/proposer/{}", epoch.as_u64()),
+            vec![],
+        )
+        .await
+    }
+
+    /// `GET validator/blocks/{slot}`
+    pub async fn get_validator_blocks(
+        &self,
+        slot: Slot,
+        randao_reveal: &Signature,
+    ) -> Result<BeaconBlock<E>, Error> {
+        self.get(
+            &format!("validator/blocks/{}", slot.as_u64()),
+            vec![("randao_reveal".into(), as_ssz_hex_string(randao_reveal))],
+        )
+        .await
+    }
+
+    /// `GET validator/attestation_data`
+    pub async fn get_validator_attestation_data(
+        &self,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<AttestationData, Error> {
+        self.get(
+            "validator/attestation_data",
+            vec![
+                ("slot".into(), format!("{}", slot.as_u64())),
+                ("committee_index".into(), format!("{}", committee_index)),
+            ],
+        )
+        .await
+    }
+
+    /// `GET validator/aggregate_attestation`
+    pub async fn get_validator_aggregate_attestation(
+        &self,
+        slot: Slot,
+        attestation_data_root: Hash256,
+    ) -> Result<Attestation<E>, Error> {
+        self.get(
+            "validator/aggregate_attestation",
+            vec![
+                ("slot".into(), format!("{}", slot.as_u64())),
+                (
+                    "attestation_data_root".into(),
+                    root_as_string(attestation_data_root),
+                ),
+            ],
+        )
+        .await
+    }
+
+    /// `POST validator/aggregate_and_proofs`
+    pub async fn post_validator_aggregate_and_proofs(
+        &self,
+        signed_aggregate_and_proofs: Vec<SignedAggregateAndProof<E>>,
+    ) -> Result<PublishStatus, Error> {
+        self.post(
+            "validator/aggregate_and_proofs",
+            signed_aggregate_and_proofs,
+        )
+        .await
+    }
+
+    /// `GET node/identity`
+    pub async fn get_node_identity(&self) -> Result<IdentityData, Error> {
+        self.get("node/identity", vec![]).await
+    }
+
+    /// `GET node/peers`
+    pub async fn get_node_peers(&self) -> Result<Vec<PeerData>, Error> {
+        self.get("node/peers", vec![]).await
+    }
+
+    /// `GET node/version`
+    pub async fn get_node_version(&self) -> Result<String, Error> {
+        self.get::<VersionData>("node/version", vec![])
+            .await
+            .map(|data| data.version)
+    }
+
+    /// `GET node/syncing`
+    pub async fn get_node_syncing(&self) -> Result<SyncingData, Error> {
+        self.get("node/syncing", vec![]).await
+    }
+
+    /// `GET node/health`
+    ///
+    /// Returns the status code of the response, which indicates the sync status of the node.
+    pub async fn get_node_health(&self) -> Result<StatusCode, Error> {
+        let client = self.0.clone();
+        let url = self.url("node/health")?;
+        let response = client
+            .client
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        error_for_status(response)
+            .await
+            .map(|response| response.status())
+    }
+
+    /// `GET config/spec`
+    pub async fn get_config_spec(&self) -> Result<YamlConfig, Error> {
+        self.get("config/spec", vec![]).await
+    }
+
+    /// `GET config/fork_schedule`
+    pub async fn get_config_fork_schedule(&self) -> Result<Vec<Fork>, Error> {
+        self.get("config/fork_schedule", vec![]).await
+    }
+
+    /// `GET config/deposit_contract`
+    pub async fn get_config_deposit_contract(&self) -> Result<DepositContractData, Error> {
+        self.get("config/deposit_contract", vec![]).await
+    }
+
+    /// `GET debug/beacon/states/{state_id}`
+    pub async fn get_debug_beacon_state(&self, state_id: StateId) -> Result<BeaconState<E>, Error> {
+        self.get(&format!("debug/beacon/states/{}", state_id), vec![])
+            .await
+    }
+
+    /// `GET debug/beacon/heads`
+    pub async fn get_debug_beacon_heads(&self) -> Result<Vec<ChainHeadData>, Error> {
+        self.get("debug/beacon/heads", vec![]).await
+    }
+
+    /// `GET events?topics`
+    ///
+    /// Returns a stream of the events published on `topics`, which ends when the server closes
+    /// the connection.
+    pub async fn get_events(
+        &self,
+        topics: &[EventTopic],
+    ) -> Result<impl Stream<Item = Result<EventKind<E>, Error>>, Error> {
+        let mut url = self.url("events")?;
+        url.query_pairs_mut().append_pair(
+            "topics",
+            &topics
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+
+        // The timeout of the shared client applies to reading the entire response body, so it
+        // cannot be used for a stream which may never end.
+        let response = Client::new()
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+        let response = error_for_status(response).await?;
+
+        Ok(stream::unfold(
+            (Some(response), vec![]),
+            |(mut response_opt, mut buffer)| async move {
+                loop {
+                    while let Some(message) = take_sse_message(&mut buffer) {
+                        if let Some(data) = sse_data(&message) {
+                            let event = serde_json::from_slice(&data).map_err(Error::from);
+                            return Some((event, (response_opt, buffer)));
+                        }
+                    }
+
+                    let response = response_opt.as_mut()?;
+                    match response.chunk().await {
+                        Ok(Some(chunk)) => buffer.extend_from_slice(&chunk),
+                        Ok(None) => return None,
+                        Err(e) => return Some((Err(Error::from(e)), (None, buffer))),
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// Removes the first complete server-sent event message from `buffer`, if any.
+fn take_sse_message(buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
+    let end = buffer.windows(2).position(|window| window == b"\n\n")?;
+    let mut message = buffer.drain(..end + 2).collect::<Vec<_>>();
+    message.truncate(end);
+    Some(message)
+}
+
+/// Returns the concatenated `data` fields of a server-sent event `message`, or `None` if it has
+/// no data (e.g., it is a comment).
+fn sse_data(message: &[u8]) -> Option<Vec<u8>> {
+    let lines = message
+        .split(|byte| *byte == b'\n')
+        .filter(|line| line.starts_with(b"data:"))
+        .map(|line| {
+            // A single space following the colon is not part of the value.
+            let value = &line[b"data:".len()..];
+            if value.first() == Some(&b' ') {
+                &value[1..]
+            } else {
+                value
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(&b'\n'))
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(bound = "T: EthSpec")]
 pub struct BlockResponse<T: EthSpec> {
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use state_processing::per_epoch_processing::ValidatorStatus;
-use types::{Epoch, PublicKeyBytes};
+use state_processing::per_epoch_processing::{Delta, DeltaBreakdown, ValidatorStatus};
+use state_processing::BlockReward;
+use types::{Epoch, Hash256, PublicKeyBytes, Slot};
 
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
 pub struct IndividualVotesRequest {
@@ -64,3 +65,81 @@ pub struct IndividualVotesResponse {
     /// Voting statistics for the validator, if they voted in the given epoch.
     pub vote: Option<IndividualVote>,
 }
+
+/// The total rewards and penalties applied to a validator's balance for a single duty.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
+pub struct RewardDelta {
+    pub reward_gwei: u64,
+    pub penalty_gwei: u64,
+}
+
+impl Into<RewardDelta> for Delta {
+    fn into(self) -> RewardDelta {
+        RewardDelta {
+            reward_gwei: self.rewards,
+            penalty_gwei: self.penalties,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
+pub struct ValidatorRewardsResponse {
+    /// The index of the validator in state.validators.
+    pub validator_index: u64,
+    /// Attesting to the expected FFG source.
+    pub source: RewardDelta,
+    /// Attesting to the expected FFG target.
+    pub target: RewardDelta,
+    /// Attesting to the expected head.
+    pub head: RewardDelta,
+    /// The speed with which the validator's attestation was included in a block.
+    pub inclusion_delay: RewardDelta,
+    /// Penalties applied whilst the chain is failing to finalize.
+    pub inactivity_penalty: RewardDelta,
+    /// Including the attestations of other validators in a proposed block.
+    pub proposer: RewardDelta,
+}
+
+impl ValidatorRewardsResponse {
+    pub fn from_breakdown(validator_index: usize, breakdown: DeltaBreakdown) -> Self {
+        Self {
+            validator_index: validator_index as u64,
+            source: breakdown.source.into(),
+            target: breakdown.target.into(),
+            head: breakdown.head.into(),
+            inclusion_delay: breakdown.inclusion_delay.into(),
+            inactivity_penalty: breakdown.inactivity_penalty.into(),
+            proposer: breakdown.proposer.into(),
+        }
+    }
+}
+
+/// The rewards earned by the proposer of a block for each operation it included, in Gwei.
+///
+/// Each reward is listed in the same order as the corresponding operation in the block.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
+pub struct BlockRewardResponse {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    pub proposer_index: u64,
+    /// The sum of all the rewards below.
+    pub total: u64,
+    /// An estimate, since attestation rewards are paid during epoch processing.
+    pub attestation_rewards: Vec<u64>,
+    pub proposer_slashing_rewards: Vec<u64>,
+    pub attester_slashing_rewards: Vec<u64>,
+}
+
+impl BlockRewardResponse {
+    pub fn from_block_reward(block_root: Hash256, slot: Slot, reward: BlockReward) -> Self {
+        Self {
+            block_root,
+            slot,
+            proposer_index: reward.proposer_index,
+            total: reward.total(),
+            attestation_rewards: reward.attestation_rewards,
+            proposer_slashing_rewards: reward.proposer_slashing_rewards,
+            attester_slashing_rewards: reward.attester_slashing_rewards,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Types for the server-sent event stream, served at `/eth/v1/events`.
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedVoluntaryExit, Slot};
+
+/// An event emitted by the `BeaconChain`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    bound = "T: EthSpec",
+    rename_all = "snake_case",
+    tag = "event",
+    content = "data"
+)]
+pub enum EventKind<T: EthSpec> {
+    BeaconHeadChanged {
+        reorg: bool,
+        current_head_beacon_block_root: Hash256,
+        previous_head_beacon_block_root: Hash256,
+    },
+    BeaconFinalization {
+        epoch: Epoch,
+        root: Hash256,
+    },
+    BeaconBlockImported {
+        block_root: Hash256,
+        block: Box<SignedBeaconBlock<T>>,
+    },
+    BeaconAttestationImported {
+        attestation: Box<Attestation<T>>,
+    },
+    BeaconVoluntaryExitImported {
+        exit: SignedVoluntaryExit,
+    },
+    ChainReorg {
+        /// The number of slots between the previous head and the common ancestor.
+        depth: u64,
+        common_ancestor_root: Hash256,
+        common_ancestor_slot: Slot,
+        old_head_root: Hash256,
+        old_head_slot: Slot,
+        new_head_root: Hash256,
+        new_head_slot: Slot,
+        /// The roots of the blocks on the previous chain which are no longer canonical, most
+        /// recent first.
+        orphaned_block_roots: Vec<Hash256>,
+    },
+}
+
+impl<T: EthSpec> EventKind<T> {
+    /// Returns the topic under which this event is published to subscribers.
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            EventKind::BeaconHeadChanged { .. } => EventTopic::Head,
+            EventKind::BeaconFinalization { .. } => EventTopic::FinalizedCheckpoint,
+            EventKind::BeaconBlockImported { .. } => EventTopic::Block,
+            EventKind::BeaconAttestationImported { .. } => EventTopic::Attestation,
+            EventKind::BeaconVoluntaryExitImported { .. } => EventTopic::VoluntaryExit,
+            EventKind::ChainReorg { .. } => EventTopic::ChainReorg,
+        }
+    }
+}
+
+/// A topic which may be subscribed to on the event stream.
+///
+/// E.g., `head`, `block` or `finalized_checkpoint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventTopic {
+    Head,
+    Block,
+    Attestation,
+    VoluntaryExit,
+    FinalizedCheckpoint,
+    ChainReorg,
+}
+
+impl EventTopic {
+    /// All topics, in the order they are listed in the API specification.
+    pub fn all() -> Vec<EventTopic> {
+        vec![
+            EventTopic::Head,
+            EventTopic::Block,
+            EventTopic::Attestation,
+            EventTopic::VoluntaryExit,
+            EventTopic::FinalizedCheckpoint,
+            EventTopic::ChainReorg,
+        ]
+    }
+}
+
+impl FromStr for EventTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(EventTopic::Head),
+            "block" => Ok(EventTopic::Block),
+            "attestation" => Ok(EventTopic::Attestation),
+            "voluntary_exit" => Ok(EventTopic::VoluntaryExit),
+            "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
+            "chain_reorg" => Ok(EventTopic::ChainReorg),
+            other => Err(format!("Unknown event topic: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for EventTopic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EventTopic::Head => write!(f, "head"),
+            EventTopic::Block => write!(f, "block"),
+            EventTopic::Attestation => write!(f, "attestation"),
+            EventTopic::VoluntaryExit => write!(f, "voluntary_exit"),
+            EventTopic::FinalizedCheckpoint => write!(f, "finalized_checkpoint"),
+            EventTopic::ChainReorg => write!(f, "chain_reorg"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    #[test]
+    fn topic_round_trip() {
+        for topic in EventTopic::all() {
+            assert_eq!(topic.to_string().parse::<EventTopic>(), Ok(topic));
+        }
+        assert!("blocks".parse::<EventTopic>().is_err());
+    }
+
+    #[test]
+    fn event_json_round_trip() {
+        let event = EventKind::<MainnetEthSpec>::BeaconFinalization {
+            epoch: Epoch::new(3),
+            root: Hash256::from_low_u64_be(7),
+        };
+        let json = serde_json::to_string(&event).expect("should encode");
+
+        assert_eq!(
+            serde_json::from_str::<EventKind<_>>(&json).ok(),
+            Some(event)
+        );
+    }
+}
@@ -4,7 +4,10 @@
 
 mod beacon;
 mod consensus;
+mod events;
+mod network;
 mod node;
+mod standard;
 mod validator;
 
 pub use beacon::{
@@ -16,6 +19,22 @@ pub use validator::{
     ValidatorDutiesRequest, ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription,
 };
 
-pub use consensus::{IndividualVote, IndividualVotesRequest, IndividualVotesResponse};
+pub use events::{EventKind, EventTopic};
+
+pub use consensus::{
+    BlockRewardResponse, IndividualVote, IndividualVotesRequest, IndividualVotesResponse,
+    RewardDelta, ValidatorRewardsResponse,
+};
+
+pub use network::{
+    BanPeerRequest, BannedPeer, DialPeerRequest, DisconnectPeerRequest, UnbanPeerRequest,
+};
 
 pub use node::{Health, SyncingResponse, SyncingStatus};
+
+pub use standard::{
+    AttesterData, BlockHeaderData, BlockId, ChainHeadData, CommitteeData, DepositContractData,
+    ErrorMessage, FinalityCheckpointsData, GenericResponse, GenesisData, IdentityData, PeerData,
+    PeerDirection, PeerState, ProposerData, RootData, StateId, SyncingData, ValidatorData,
+    ValidatorId, ValidatorStatus, VersionData,
+};
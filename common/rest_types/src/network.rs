@@ -0,0 +1,45 @@
+//! Collection of types for administering the peers of the beacon node via the
+//! `/lighthouse/peers/*` HTTP endpoints.
+use serde::{Deserialize, Serialize};
+
+/// The request body for the `/lighthouse/peers/dial` HTTP POST.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DialPeerRequest {
+    /// Either an ENR (starting with `enr:`) or a libp2p multiaddr.
+    pub address: String,
+}
+
+/// The request body for the `/lighthouse/peers/disconnect` HTTP POST.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DisconnectPeerRequest {
+    pub peer_id: String,
+    /// The `GoodbyeReason` code sent to the peer.
+    ///
+    /// `1` is a client shutdown, `2` an irrelevant network, `3` a fault and `129` too many
+    /// peers. Any other value is sent as an unknown reason.
+    pub reason: u64,
+}
+
+/// The request body for the `/lighthouse/peers/ban` HTTP POST.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BanPeerRequest {
+    pub peer_id: String,
+    /// The number of seconds after which the peer is unbanned.
+    pub duration_secs: u64,
+}
+
+/// The request body for the `/lighthouse/peers/unban` HTTP POST.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnbanPeerRequest {
+    pub peer_id: String,
+}
+
+/// A peer returned by the `/lighthouse/peers/banned` HTTP GET.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BannedPeer {
+    pub peer_id: String,
+    /// The number of seconds since the peer was banned.
+    pub banned_secs: u64,
+    /// The number of seconds until the peer is unbanned, if the ban is not permanent.
+    pub unban_in_secs: Option<u64>,
+}
@@ -0,0 +1,408 @@
+//! Collection of types for the standard Eth2 Beacon Node API, served under `/eth/v1`.
+use bls::PublicKeyBytes;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use types::utils::{fork_from_hex_str, fork_to_hex_str};
+use types::{Checkpoint, CommitteeIndex, Epoch, Hash256, SignedBeaconBlockHeader, Slot, Validator};
+
+/// The envelope which wraps every successful response from the standard API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenericResponse<T> {
+    pub data: T,
+}
+
+impl<T> From<T> for GenericResponse<T> {
+    fn from(data: T) -> Self {
+        Self { data }
+    }
+}
+
+/// The body of every unsuccessful response from the standard API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    /// The HTTP status code of the response.
+    pub code: u16,
+    pub message: String,
+    #[serde(default)]
+    pub stacktraces: Vec<String>,
+}
+
+/// Identifies a `BeaconState` in a request path.
+///
+/// E.g., `head`, `genesis`, `finalized`, `justified`, `1234` or a `0x` prefixed state root.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StateId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(Slot),
+    Root(Hash256),
+}
+
+impl FromStr for StateId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(StateId::Head),
+            "genesis" => Ok(StateId::Genesis),
+            "finalized" => Ok(StateId::Finalized),
+            "justified" => Ok(StateId::Justified),
+            other => parse_slot_or_root(other)
+                .map(|id| match id {
+                    SlotOrRoot::Slot(slot) => StateId::Slot(slot),
+                    SlotOrRoot::Root(root) => StateId::Root(root),
+                })
+                .map_err(|e| format!("Invalid state id {}: {}", other, e)),
+        }
+    }
+}
+
+impl fmt::Display for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StateId::Head => write!(f, "head"),
+            StateId::Genesis => write!(f, "genesis"),
+            StateId::Finalized => write!(f, "finalized"),
+            StateId::Justified => write!(f, "justified"),
+            StateId::Slot(slot) => write!(f, "{}", slot),
+            StateId::Root(root) => write!(f, "{}", root_as_hex(root)),
+        }
+    }
+}
+
+/// Identifies a `SignedBeaconBlock` in a request path.
+///
+/// E.g., `head`, `genesis`, `finalized`, `justified`, `1234` or a `0x` prefixed block root.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlockId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(Slot),
+    Root(Hash256),
+}
+
+impl FromStr for BlockId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(BlockId::Head),
+            "genesis" => Ok(BlockId::Genesis),
+            "finalized" => Ok(BlockId::Finalized),
+            "justified" => Ok(BlockId::Justified),
+            other => parse_slot_or_root(other)
+                .map(|id| match id {
+                    SlotOrRoot::Slot(slot) => BlockId::Slot(slot),
+                    SlotOrRoot::Root(root) => BlockId::Root(root),
+                })
+                .map_err(|e| format!("Invalid block id {}: {}", other, e)),
+        }
+    }
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlockId::Head => write!(f, "head"),
+            BlockId::Genesis => write!(f, "genesis"),
+            BlockId::Finalized => write!(f, "finalized"),
+            BlockId::Justified => write!(f, "justified"),
+            BlockId::Slot(slot) => write!(f, "{}", slot),
+            BlockId::Root(root) => write!(f, "{}", root_as_hex(root)),
+        }
+    }
+}
+
+/// Identifies a validator in a request path, either by its index or a `0x` prefixed public key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidatorId {
+    PublicKey(PublicKeyBytes),
+    Index(u64),
+}
+
+impl FromStr for ValidatorId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            hex::decode(s.trim_start_matches("0x"))
+                .map_err(|e| format!("Invalid public key hex {}: {:?}", s, e))
+                .and_then(|bytes| {
+                    PublicKeyBytes::from_bytes(&bytes)
+                        .map_err(|e| format!("Invalid public key {}: {:?}", s, e))
+                })
+                .map(ValidatorId::PublicKey)
+        } else {
+            s.parse::<u64>()
+                .map(ValidatorId::Index)
+                .map_err(|e| format!("Invalid validator index {}: {:?}", s, e))
+        }
+    }
+}
+
+impl fmt::Display for ValidatorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidatorId::PublicKey(pubkey) => write!(f, "0x{}", hex::encode(pubkey.as_slice())),
+            ValidatorId::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+enum SlotOrRoot {
+    Slot(Slot),
+    Root(Hash256),
+}
+
+/// Parse either a decimal slot or a `0x` prefixed 32-byte root.
+fn parse_slot_or_root(s: &str) -> Result<SlotOrRoot, String> {
+    if s.starts_with("0x") {
+        let bytes = hex::decode(s.trim_start_matches("0x"))
+            .map_err(|e| format!("invalid root hex: {:?}", e))?;
+        if bytes.len() == 32 {
+            Ok(SlotOrRoot::Root(Hash256::from_slice(&bytes)))
+        } else {
+            Err(format!("root must be 32 bytes, not {}", bytes.len()))
+        }
+    } else {
+        s.parse::<u64>()
+            .map(|slot| SlotOrRoot::Slot(Slot::new(slot)))
+            .map_err(|e| format!("invalid slot: {:?}", e))
+    }
+}
+
+fn root_as_hex(root: &Hash256) -> String {
+    format!("0x{}", hex::encode(root.as_bytes()))
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenesisData {
+    pub genesis_time: u64,
+    pub genesis_validators_root: Hash256,
+    #[serde(
+        serialize_with = "fork_to_hex_str",
+        deserialize_with = "fork_from_hex_str"
+    )]
+    pub genesis_fork_version: [u8; 4],
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RootData {
+    pub root: Hash256,
+}
+
+impl From<Hash256> for RootData {
+    fn from(root: Hash256) -> Self {
+        Self { root }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FinalityCheckpointsData {
+    pub previous_justified: Checkpoint,
+    pub current_justified: Checkpoint,
+    pub finalized: Checkpoint,
+}
+
+/// The lifecycle status of a validator, as defined by the standard API.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
+impl ValidatorStatus {
+    /// Determine the status of `validator` at `epoch`.
+    pub fn from_validator(validator: &Validator, epoch: Epoch, far_future_epoch: Epoch) -> Self {
+        if epoch < validator.activation_epoch {
+            if validator.activation_eligibility_epoch == far_future_epoch {
+                ValidatorStatus::PendingInitialized
+            } else {
+                ValidatorStatus::PendingQueued
+            }
+        } else if epoch < validator.exit_epoch {
+            if validator.exit_epoch == far_future_epoch {
+                ValidatorStatus::ActiveOngoing
+            } else if validator.slashed {
+                ValidatorStatus::ActiveSlashed
+            } else {
+                ValidatorStatus::ActiveExiting
+            }
+        } else if epoch < validator.withdrawable_epoch {
+            if validator.slashed {
+                ValidatorStatus::ExitedSlashed
+            } else {
+                ValidatorStatus::ExitedUnslashed
+            }
+        } else if validator.effective_balance != 0 {
+            ValidatorStatus::WithdrawalPossible
+        } else {
+            ValidatorStatus::WithdrawalDone
+        }
+    }
+}
+
+impl fmt::Display for ValidatorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match self {
+            ValidatorStatus::PendingInitialized => "pending_initialized",
+            ValidatorStatus::PendingQueued => "pending_queued",
+            ValidatorStatus::ActiveOngoing => "active_ongoing",
+            ValidatorStatus::ActiveExiting => "active_exiting",
+            ValidatorStatus::ActiveSlashed => "active_slashed",
+            ValidatorStatus::ExitedUnslashed => "exited_unslashed",
+            ValidatorStatus::ExitedSlashed => "exited_slashed",
+            ValidatorStatus::WithdrawalPossible => "withdrawal_possible",
+            ValidatorStatus::WithdrawalDone => "withdrawal_done",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorData {
+    pub index: u64,
+    pub balance: u64,
+    pub status: ValidatorStatus,
+    pub validator: Validator,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommitteeData {
+    pub index: CommitteeIndex,
+    pub slot: Slot,
+    pub validators: Vec<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeaderData {
+    pub root: Hash256,
+    /// `true` if the block is in the canonical chain.
+    pub canonical: bool,
+    pub header: SignedBeaconBlockHeader,
+}
+
+/// A block at the head of some chain, as known to fork choice.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainHeadData {
+    pub root: Hash256,
+    pub slot: Slot,
+}
+
+/// The eth1 deposit contract followed by the beacon node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DepositContractData {
+    pub chain_id: u64,
+    pub address: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VersionData {
+    pub version: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SyncingData {
+    pub head_slot: Slot,
+    /// The number of slots between the head and the current slot.
+    pub sync_distance: Slot,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IdentityData {
+    pub peer_id: String,
+    /// The node's ENR, base64 encoded.
+    pub enr: String,
+    pub p2p_addresses: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerState {
+    Connected,
+    Connecting,
+    Disconnected,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerDirection {
+    Inbound,
+    Outbound,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerData {
+    pub peer_id: String,
+    /// The last known listening address of the peer, if any.
+    pub address: Option<String>,
+    pub state: PeerState,
+    /// The direction of the connection, if the peer is connected.
+    pub direction: Option<PeerDirection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttesterData {
+    pub pubkey: PublicKeyBytes,
+    pub validator_index: u64,
+    pub committee_index: CommitteeIndex,
+    pub committee_length: u64,
+    /// The position of the validator within the committee.
+    pub validator_committee_index: u64,
+    pub slot: Slot,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProposerData {
+    pub pubkey: PublicKeyBytes,
+    pub validator_index: u64,
+    pub slot: Slot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_id_round_trip() {
+        let ids = vec![
+            StateId::Head,
+            StateId::Genesis,
+            StateId::Finalized,
+            StateId::Justified,
+            StateId::Slot(Slot::new(42)),
+            StateId::Root(Hash256::from_low_u64_be(42)),
+        ];
+
+        for id in ids {
+            assert_eq!(id.to_string().parse::<StateId>(), Ok(id));
+        }
+    }
+
+    #[test]
+    fn invalid_ids() {
+        assert!("cats".parse::<StateId>().is_err());
+        assert!("0x00".parse::<BlockId>().is_err());
+        assert!("-1".parse::<BlockId>().is_err());
+        assert!("0x42".parse::<ValidatorId>().is_err());
+    }
+
+    #[test]
+    fn validator_id_index() {
+        assert_eq!("7".parse::<ValidatorId>(), Ok(ValidatorId::Index(7)));
+        assert_eq!(ValidatorId::Index(7).to_string(), "7");
+    }
+}
@@ -0,0 +1,190 @@
+//! Provides `LaunchpadDepositData`, the format of the `deposit_data-<timestamp>.json` files which
+//! are produced by the upstream deposit CLI and accepted by the web launchpad.
+//!
+//! Each file contains a JSON list of deposits, where all byte fields are hex-encoded without a
+//! `0x` prefix.
+
+use serde_derive::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::io::{Read, Write};
+use tree_hash::TreeHash;
+use types::{
+    ChainSpec, DepositData, Hash256, PublicKey, PublicKeyBytes, Signature, SignatureBytes,
+    SignedRoot,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    InvalidHex {
+        field: &'static str,
+        error: hex::FromHexError,
+    },
+    InvalidLength {
+        field: &'static str,
+        len: usize,
+        expected: usize,
+    },
+    InvalidPubkey,
+    InvalidSignature,
+    ForkVersionMismatch {
+        expected: [u8; 4],
+        found: [u8; 4],
+    },
+    DepositMessageRootMismatch {
+        expected: Hash256,
+        found: Hash256,
+    },
+    DepositDataRootMismatch {
+        expected: Hash256,
+        found: Hash256,
+    },
+    IncorrectSignature,
+    InvalidJson(String),
+    WriteError(String),
+}
+
+/// Returns the name of the file which the web launchpad expects deposits to be saved in.
+pub fn deposit_data_filename(timestamp: u64) -> String {
+    format!("deposit_data-{}.json", timestamp)
+}
+
+/// A single deposit in a `deposit_data-<timestamp>.json` file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LaunchpadDepositData {
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub amount: u64,
+    pub signature: String,
+    pub deposit_message_root: String,
+    pub deposit_data_root: String,
+    pub fork_version: String,
+}
+
+impl LaunchpadDepositData {
+    /// Instantiates `Self` from `deposit_data`, using the `genesis_fork_version` of `spec`.
+    ///
+    /// The `deposit_data` is not verified, use `Self::verify` to ensure the signature is valid
+    /// for `spec`.
+    pub fn from_deposit_data(deposit_data: &DepositData, spec: &ChainSpec) -> Self {
+        Self {
+            pubkey: hex::encode(deposit_data.pubkey.as_slice()),
+            withdrawal_credentials: hex::encode(deposit_data.withdrawal_credentials.as_bytes()),
+            amount: deposit_data.amount,
+            signature: hex::encode(deposit_data.signature.as_slice()),
+            deposit_message_root: hex::encode(
+                deposit_data
+                    .as_deposit_message()
+                    .tree_hash_root()
+                    .as_bytes(),
+            ),
+            deposit_data_root: hex::encode(deposit_data.tree_hash_root().as_bytes()),
+            fork_version: hex::encode(&spec.genesis_fork_version),
+        }
+    }
+
+    /// Decodes the `DepositData` contained in `self`, without any verification.
+    pub fn deposit_data(&self) -> Result<DepositData, Error> {
+        Ok(DepositData {
+            pubkey: PublicKeyBytes::from_bytes(&decode_hex("pubkey", &self.pubkey)?)
+                .map_err(|_| Error::InvalidPubkey)?,
+            withdrawal_credentials: decode_hash256(
+                "withdrawal_credentials",
+                &self.withdrawal_credentials,
+            )?,
+            amount: self.amount,
+            signature: SignatureBytes::from_bytes(&decode_hex("signature", &self.signature)?)
+                .map_err(|_| Error::InvalidSignature)?,
+        })
+    }
+
+    /// Decodes and verifies the `DepositData` contained in `self`, returning it if it is valid.
+    ///
+    /// ## Errors
+    ///
+    /// - If any of the fields are badly formed.
+    /// - If the `fork_version` is not the `genesis_fork_version` of `spec`.
+    /// - If either of the roots do not match the deposit.
+    /// - If the signature is not valid for the deposit domain of `spec`.
+    pub fn verify(&self, spec: &ChainSpec) -> Result<DepositData, Error> {
+        let deposit_data = self.deposit_data()?;
+
+        let fork_version = decode_hex("fork_version", &self.fork_version)?;
+        if fork_version.len() != 4 {
+            return Err(Error::InvalidLength {
+                field: "fork_version",
+                len: fork_version.len(),
+                expected: 4,
+            });
+        }
+        if fork_version[..] != spec.genesis_fork_version[..] {
+            let mut found = [0; 4];
+            found.copy_from_slice(&fork_version);
+            return Err(Error::ForkVersionMismatch {
+                expected: spec.genesis_fork_version,
+                found,
+            });
+        }
+
+        let deposit_message = deposit_data.as_deposit_message();
+
+        let expected = deposit_message.tree_hash_root();
+        let found = decode_hash256("deposit_message_root", &self.deposit_message_root)?;
+        if found != expected {
+            return Err(Error::DepositMessageRootMismatch { expected, found });
+        }
+
+        let expected = deposit_data.tree_hash_root();
+        let found = decode_hash256("deposit_data_root", &self.deposit_data_root)?;
+        if found != expected {
+            return Err(Error::DepositDataRootMismatch { expected, found });
+        }
+
+        let pubkey: PublicKey = (&deposit_data.pubkey)
+            .try_into()
+            .map_err(|_| Error::InvalidPubkey)?;
+        let signature: Signature = (&deposit_data.signature)
+            .try_into()
+            .map_err(|_| Error::InvalidSignature)?;
+        let message = deposit_message.signing_root(spec.get_deposit_domain());
+
+        if signature.verify(message.as_bytes(), &pubkey) {
+            Ok(deposit_data)
+        } else {
+            Err(Error::IncorrectSignature)
+        }
+    }
+}
+
+/// Writes `deposits` as a JSON list to `writer`.
+pub fn write_deposit_data_json<W: Write>(
+    deposits: &[LaunchpadDepositData],
+    writer: W,
+) -> Result<(), Error> {
+    serde_json::to_writer(writer, deposits).map_err(|e| Error::WriteError(format!("{}", e)))
+}
+
+/// Reads a JSON list of deposits from `reader`.
+pub fn read_deposit_data_json<R: Read>(reader: R) -> Result<Vec<LaunchpadDepositData>, Error> {
+    serde_json::from_reader(reader).map_err(|e| Error::InvalidJson(format!("{}", e)))
+}
+
+/// Decodes the hex string `s`, tolerating an optional `0x` prefix.
+fn decode_hex(field: &'static str, s: &str) -> Result<Vec<u8>, Error> {
+    let s = if s.starts_with("0x") { &s[2..] } else { s };
+
+    hex::decode(s).map_err(|error| Error::InvalidHex { field, error })
+}
+
+fn decode_hash256(field: &'static str, s: &str) -> Result<Hash256, Error> {
+    let bytes = decode_hex(field, s)?;
+
+    if bytes.len() == 32 {
+        Ok(Hash256::from_slice(&bytes))
+    } else {
+        Err(Error::InvalidLength {
+            field,
+            len: bytes.len(),
+            expected: 32,
+        })
+    }
+}
@@ -3,19 +3,25 @@
 //! - `ValidatorDir`: manages a directory containing validator keypairs, deposit info and other
 //! things.
 //! - `Manager`: manages a directory that contains multiple `ValidatorDir`.
+//! - `LaunchpadDepositData`: the deposit data JSON format used by the web launchpad.
 //!
 //! This crate is intended to be used by the account manager to create validators and the validator
 //! client to load those validators.
 
 mod builder;
 pub mod insecure_keys;
+pub mod launchpad;
 mod manager;
 pub mod unencrypted_keys;
 mod validator_dir;
 
-pub use crate::validator_dir::{Error, Eth1DepositData, ValidatorDir, ETH1_DEPOSIT_TX_HASH_FILE};
+pub use crate::validator_dir::{
+    read_eth1_deposit_data, read_eth1_deposit_tx_hash, read_voting_pubkey, validator_is_enabled,
+    Error, Eth1DepositData, ValidatorDir, DISABLED_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
+};
 pub use builder::{
     Builder, Error as BuilderError, ETH1_DEPOSIT_DATA_FILE, VOTING_KEYSTORE_FILE,
     WITHDRAWAL_KEYSTORE_FILE,
 };
+pub use launchpad::{Error as LaunchpadError, LaunchpadDepositData};
 pub use manager::{Error as ManagerError, Manager};
@@ -59,6 +59,14 @@ impl Manager {
             .collect()
     }
 
+    /// Returns the paths of the validator directories in `self.dir`.
+    ///
+    /// The directories are not opened, so they are not locked and may be in use by another
+    /// process (e.g., the validator client).
+    pub fn validator_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        self.iter_dir()
+    }
+
     /// Open a `ValidatorDir` at the given `path`.
     ///
     /// ## Note
@@ -120,10 +128,11 @@ impl Manager {
     ///
     /// Returns an error if a directory is unable to be read.
     pub fn directory_names(&self) -> Result<HashMap<String, PathBuf>, Error> {
-        Ok(HashMap::from_iter(
-            self.iter_dir()?
-                .into_iter()
-                .map(|path| (format!("{:?}", path), path)),
-        ))
+        let names = self.iter_dir()?.into_iter().filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            Some((name, path))
+        });
+
+        Ok(HashMap::from_iter(names))
     }
 }
@@ -8,7 +8,7 @@ use std::fs::{read, remove_file, write, OpenOptions};
 use std::io;
 use std::path::{Path, PathBuf};
 use tree_hash::TreeHash;
-use types::{DepositData, Hash256, Keypair};
+use types::{DepositData, Hash256, Keypair, PublicKey};
 
 /// The file used for indicating if a directory is in-use by another process.
 const LOCK_FILE: &str = ".lock";
@@ -16,6 +16,9 @@ const LOCK_FILE: &str = ".lock";
 /// The file used to save the Eth1 transaction hash from a deposit.
 pub const ETH1_DEPOSIT_TX_HASH_FILE: &str = "eth1-deposit-tx-hash.txt";
 
+/// The file used to indicate that the validator client should not perform duties for a validator.
+pub const DISABLED_FILE: &str = "disabled";
+
 #[derive(Debug)]
 pub enum Error {
     DirectoryDoesNotExist(PathBuf),
@@ -23,6 +26,7 @@ pub enum Error {
     UnableToCreateLockfile(io::Error),
     UnableToOpenKeystore(io::Error),
     UnableToReadKeystore(KeystoreError),
+    InvalidKeystorePubkey(String),
     UnableToOpenPassword(io::Error),
     UnableToReadPassword(PathBuf),
     UnableToDecryptKeypair(KeystoreError),
@@ -37,6 +41,10 @@ pub enum Error {
     UnableToParseDepositData(deposit_contract::DecodeError),
     Eth1TxHashExists(PathBuf),
     UnableToWriteEth1TxHash(io::Error),
+    UnableToReadEth1TxHash(io::Error),
+    Eth1TxHashIsNotUtf8(std::string::FromUtf8Error),
+    UnableToCreateDisabledFile(io::Error),
+    UnableToRemoveDisabledFile(io::Error),
     /// The deposit root in the deposit data file does not match the one generated locally. This is
     /// generally caused by supplying an `amount` at deposit-time that is different to the one used
     /// at generation-time.
@@ -98,6 +106,15 @@ impl ValidatorDir {
         &self.dir
     }
 
+    /// Returns the public key of the voting keystore in `self.dir`, without decrypting it.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a filesystem error or the keystore does not contain a valid public key.
+    pub fn voting_pubkey(&self) -> Result<PublicKey, Error> {
+        read_voting_pubkey(&self.dir)
+    }
+
     /// Attempts to read the keystore in `self.dir` and decrypt the keypair using a password file
     /// in `password_dir`.
     ///
@@ -133,6 +150,16 @@ impl ValidatorDir {
         self.dir.join(ETH1_DEPOSIT_TX_HASH_FILE).exists()
     }
 
+    /// Returns the eth1 deposit transaction hash saved by `Self::save_eth1_deposit_tx_hash`, if
+    /// any.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a file-system error or the file is not valid UTF-8.
+    pub fn eth1_deposit_tx_hash(&self) -> Result<Option<String>, Error> {
+        read_eth1_deposit_tx_hash(&self.dir)
+    }
+
     /// Saves the `tx_hash` to a file in `self.dir`. Artificially requires `mut self` to prevent concurrent
     /// calls.
     ///
@@ -150,6 +177,29 @@ impl ValidatorDir {
         write(path, tx_hash.as_bytes()).map_err(Error::UnableToWriteEth1TxHash)
     }
 
+    /// Returns `false` if the validator has been disabled via `Self::set_enabled`.
+    pub fn is_enabled(&self) -> bool {
+        validator_is_enabled(&self.dir)
+    }
+
+    /// Enables or disables the validator, persisting the choice in `self.dir` so that it survives
+    /// a restart of the validator client.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a file-system error.
+    pub fn set_enabled(&self, enabled: bool) -> Result<(), Error> {
+        let path = self.dir.join(DISABLED_FILE);
+
+        if enabled && path.exists() {
+            remove_file(path).map_err(Error::UnableToRemoveDisabledFile)
+        } else if !enabled && !path.exists() {
+            write(path, &[]).map_err(Error::UnableToCreateDisabledFile)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Attempts to read files in `self.dir` and return an `Eth1DepositData` that can be used for
     /// submitting an Eth1 deposit.
     ///
@@ -158,47 +208,7 @@ impl ValidatorDir {
     /// If there is a file-system error, not all required files exist or the files are
     /// inconsistent.
     pub fn eth1_deposit_data(&self) -> Result<Option<Eth1DepositData>, Error> {
-        // Read and parse `ETH1_DEPOSIT_DATA_FILE`.
-        let path = self.dir.join(ETH1_DEPOSIT_DATA_FILE);
-        if !path.exists() {
-            return Ok(None);
-        }
-        let deposit_data_rlp = read(path)
-            .map_err(Error::UnableToReadDepositData)
-            .and_then(|hex_bytes| {
-                let hex = std::str::from_utf8(&hex_bytes).map_err(|_| Error::DepositDataNotUtf8)?;
-                if hex.starts_with("0x") {
-                    hex::decode(&hex[2..]).map_err(Error::DepositDataInvalidHex)
-                } else {
-                    Err(Error::DepositDataMissing0xPrefix)
-                }
-            })?;
-
-        // Read and parse `ETH1_DEPOSIT_AMOUNT_FILE`.
-        let path = self.dir.join(ETH1_DEPOSIT_AMOUNT_FILE);
-        if !path.exists() {
-            return Err(Error::DepositAmountDoesNotExist(path));
-        }
-        let deposit_amount: u64 =
-            String::from_utf8(read(path).map_err(Error::UnableToReadDepositAmount)?)
-                .map_err(Error::DepositAmountIsNotUtf8)?
-                .parse()
-                .map_err(Error::UnableToParseDepositAmount)?;
-
-        let (deposit_data, root) = decode_eth1_tx_data(&deposit_data_rlp, deposit_amount)
-            .map_err(Error::UnableToParseDepositData)?;
-
-        // This acts as a sanity check to ensure that the amount from `ETH1_DEPOSIT_AMOUNT_FILE`
-        // matches the value that `ETH1_DEPOSIT_DATA_FILE` was created with.
-        if deposit_data.tree_hash_root() != root {
-            return Err(Error::Eth1DepositRootMismatch);
-        }
-
-        Ok(Some(Eth1DepositData {
-            rlp: deposit_data_rlp,
-            deposit_data,
-            root,
-        }))
+        read_eth1_deposit_data(&self.dir)
     }
 }
 
@@ -220,14 +230,7 @@ fn unlock_keypair<P: AsRef<Path>>(
     filename: &str,
     password_dir: P,
 ) -> Result<Keypair, Error> {
-    let keystore = Keystore::from_json_reader(
-        &mut OpenOptions::new()
-            .read(true)
-            .create(false)
-            .open(keystore_dir.clone().join(filename))
-            .map_err(Error::UnableToOpenKeystore)?,
-    )
-    .map_err(Error::UnableToReadKeystore)?;
+    let keystore = read_keystore(keystore_dir, filename)?;
 
     let password_path = password_dir
         .as_ref()
@@ -240,3 +243,115 @@ fn unlock_keypair<P: AsRef<Path>>(
         .decrypt_keypair(password.as_bytes())
         .map_err(Error::UnableToDecryptKeypair)
 }
+
+/// Returns the public key of the voting keystore in the validator directory `dir`, without
+/// decrypting it.
+///
+/// Unlike `ValidatorDir::voting_pubkey`, this does not lock `dir`, so it may be used whilst the
+/// validator is in use by another process (e.g., the validator client).
+///
+/// ## Errors
+///
+/// If there is a filesystem error or the keystore does not contain a valid public key.
+pub fn read_voting_pubkey(dir: &Path) -> Result<PublicKey, Error> {
+    let keystore = read_keystore(dir, VOTING_KEYSTORE_FILE)?;
+
+    hex::decode(keystore.pubkey())
+        .map_err(|e| Error::InvalidKeystorePubkey(format!("{:?}", e)))
+        .and_then(|bytes| {
+            PublicKey::from_bytes(&bytes)
+                .map_err(|e| Error::InvalidKeystorePubkey(format!("{:?}", e)))
+        })
+}
+
+/// Returns the eth1 deposit transaction hash saved in the validator directory `dir`, if any.
+///
+/// Unlike `ValidatorDir::eth1_deposit_tx_hash`, this does not lock `dir`.
+///
+/// ## Errors
+///
+/// If there is a file-system error or the file is not valid UTF-8.
+pub fn read_eth1_deposit_tx_hash(dir: &Path) -> Result<Option<String>, Error> {
+    let path = dir.join(ETH1_DEPOSIT_TX_HASH_FILE);
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    read(path)
+        .map_err(Error::UnableToReadEth1TxHash)
+        .and_then(|bytes| String::from_utf8(bytes).map_err(Error::Eth1TxHashIsNotUtf8))
+        .map(|tx_hash| Some(tx_hash.trim().to_string()))
+}
+
+/// Attempts to read files in the validator directory `dir` and return an `Eth1DepositData` that
+/// can be used for submitting an Eth1 deposit.
+///
+/// Unlike `ValidatorDir::eth1_deposit_data`, this does not lock `dir`.
+///
+/// ## Errors
+///
+/// If there is a file-system error, not all required files exist or the files are
+/// inconsistent.
+pub fn read_eth1_deposit_data(dir: &Path) -> Result<Option<Eth1DepositData>, Error> {
+    // Read and parse `ETH1_DEPOSIT_DATA_FILE`.
+    let path = dir.join(ETH1_DEPOSIT_DATA_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let deposit_data_rlp = read(path)
+        .map_err(Error::UnableToReadDepositData)
+        .and_then(|hex_bytes| {
+            let hex = std::str::from_utf8(&hex_bytes).map_err(|_| Error::DepositDataNotUtf8)?;
+            if hex.starts_with("0x") {
+                hex::decode(&hex[2..]).map_err(Error::DepositDataInvalidHex)
+            } else {
+                Err(Error::DepositDataMissing0xPrefix)
+            }
+        })?;
+
+    // Read and parse `ETH1_DEPOSIT_AMOUNT_FILE`.
+    let path = dir.join(ETH1_DEPOSIT_AMOUNT_FILE);
+    if !path.exists() {
+        return Err(Error::DepositAmountDoesNotExist(path));
+    }
+    let deposit_amount: u64 =
+        String::from_utf8(read(path).map_err(Error::UnableToReadDepositAmount)?)
+            .map_err(Error::DepositAmountIsNotUtf8)?
+            .parse()
+            .map_err(Error::UnableToParseDepositAmount)?;
+
+    let (deposit_data, root) = decode_eth1_tx_data(&deposit_data_rlp, deposit_amount)
+        .map_err(Error::UnableToParseDepositData)?;
+
+    // This acts as a sanity check to ensure that the amount from `ETH1_DEPOSIT_AMOUNT_FILE`
+    // matches the value that `ETH1_DEPOSIT_DATA_FILE` was created with.
+    if deposit_data.tree_hash_root() != root {
+        return Err(Error::Eth1DepositRootMismatch);
+    }
+
+    Ok(Some(Eth1DepositData {
+        rlp: deposit_data_rlp,
+        deposit_data,
+        root,
+    }))
+}
+
+/// Returns `false` if the validator in the validator directory `dir` has been disabled.
+///
+/// Unlike `ValidatorDir::is_enabled`, this does not lock `dir`.
+pub fn validator_is_enabled(dir: &Path) -> bool {
+    !dir.join(DISABLED_FILE).exists()
+}
+
+/// Reads the keystore at `filename` in `keystore_dir`, without decrypting it.
+fn read_keystore(keystore_dir: &Path, filename: &str) -> Result<Keystore, Error> {
+    Keystore::from_json_reader(
+        &mut OpenOptions::new()
+            .read(true)
+            .create(false)
+            .open(keystore_dir.join(filename))
+            .map_err(Error::UnableToOpenKeystore)?,
+    )
+    .map_err(Error::UnableToReadKeystore)
+}
@@ -6,8 +6,10 @@ use std::path::Path;
 use tempfile::{tempdir, TempDir};
 use types::{test_utils::generate_deterministic_keypair, EthSpec, Keypair, MainnetEthSpec};
 use validator_dir::{
-    Builder, BuilderError, ValidatorDir, ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
-    VOTING_KEYSTORE_FILE, WITHDRAWAL_KEYSTORE_FILE,
+    launchpad::{read_deposit_data_json, write_deposit_data_json},
+    Builder, BuilderError, LaunchpadDepositData, LaunchpadError, ValidatorDir, DISABLED_FILE,
+    ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE, VOTING_KEYSTORE_FILE,
+    WITHDRAWAL_KEYSTORE_FILE,
 };
 
 /// A very weak password with which to encrypt the keystores.
@@ -123,6 +125,9 @@ impl Harness {
             assert_eq!(voting_keypair.pk, generate_deterministic_keypair(0).pk)
         }
 
+        // The voting pubkey can be read without decrypting the keystore.
+        assert_eq!(validator.voting_pubkey().unwrap(), voting_keypair.pk);
+
         // Use OR here instead of AND so we *always* check for the withdrawal keystores if random
         // keystores were generated.
         if config.random_withdrawal_keystore || config.store_withdrawal_keystore {
@@ -163,6 +168,7 @@ impl Harness {
 
         // The eth1 deposit file should not exist, yet.
         assert!(!tx_hash_path.exists());
+        assert_eq!(validator.eth1_deposit_tx_hash().unwrap(), None);
 
         let tx = "junk data";
 
@@ -171,6 +177,10 @@ impl Harness {
 
         // Ensure the saved tx hash is correct.
         assert_eq!(fs::read(tx_hash_path).unwrap(), tx.as_bytes().to_vec());
+        assert_eq!(
+            validator.eth1_deposit_tx_hash().unwrap(),
+            Some(tx.to_string())
+        );
 
         // Saving a second tx hash should fail.
         validator.save_eth1_deposit_tx_hash(tx).unwrap_err();
@@ -203,6 +213,32 @@ fn concurrency() {
     ValidatorDir::open(&path).unwrap();
 }
 
+#[test]
+fn enable_and_disable() {
+    let harness = Harness::new();
+
+    let val_dir = harness.create_and_test(&BuildConfig::default());
+    let path = val_dir.dir().clone();
+    assert!(val_dir.is_enabled(), "should be enabled after build");
+
+    val_dir.set_enabled(false).unwrap();
+    assert!(!val_dir.is_enabled());
+    assert!(path.join(DISABLED_FILE).exists());
+
+    // Disabling twice should have no effect.
+    val_dir.set_enabled(false).unwrap();
+    assert!(!val_dir.is_enabled());
+
+    // The choice should persist after re-opening.
+    drop(val_dir);
+    let val_dir = ValidatorDir::open(&path).unwrap();
+    assert!(!val_dir.is_enabled());
+
+    val_dir.set_enabled(true).unwrap();
+    assert!(val_dir.is_enabled());
+    assert!(!path.join(DISABLED_FILE).exists());
+}
+
 #[test]
 fn without_voting_keystore() {
     let harness = Harness::new();
@@ -385,3 +421,48 @@ fn store_withdrawal_keystore_with_eth1_data() {
 
     harness.create_and_test(&config);
 }
+
+#[test]
+fn launchpad_deposit_data() {
+    let harness = Harness::new();
+    let spec = MainnetEthSpec::default_spec();
+
+    let config = BuildConfig {
+        deposit_amount: Some(32000000000),
+        ..BuildConfig::default()
+    };
+
+    let validator = harness.create_and_test(&config);
+    let deposit_data = validator.eth1_deposit_data().unwrap().unwrap().deposit_data;
+
+    let launchpad = LaunchpadDepositData::from_deposit_data(&deposit_data, &spec);
+    assert_eq!(launchpad.verify(&spec).unwrap(), deposit_data);
+
+    // The JSON file should contain a list of deposits.
+    let mut bytes = vec![];
+    write_deposit_data_json(&[launchpad.clone()], &mut bytes).unwrap();
+    assert_eq!(
+        read_deposit_data_json(bytes.as_slice()).unwrap(),
+        vec![launchpad.clone()]
+    );
+
+    // Changing the amount should invalidate the roots.
+    let mut bad_amount = launchpad.clone();
+    bad_amount.amount += 1;
+    match bad_amount.verify(&spec) {
+        Err(LaunchpadError::DepositMessageRootMismatch { .. }) => {}
+        other => panic!("expected deposit message root mismatch, got {:?}", other),
+    }
+
+    // The deposit should not be valid for another network.
+    let mut other_spec = spec.clone();
+    other_spec.genesis_fork_version = [1, 2, 3, 4];
+    match launchpad.verify(&other_spec) {
+        Err(LaunchpadError::ForkVersionMismatch { .. }) => {}
+        other => panic!("expected fork version mismatch, got {:?}", other),
+    }
+    match LaunchpadDepositData::from_deposit_data(&deposit_data, &other_spec).verify(&other_spec) {
+        Err(LaunchpadError::IncorrectSignature) => {}
+        other => panic!("expected incorrect signature, got {:?}", other),
+    }
+}
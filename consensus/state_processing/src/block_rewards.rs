@@ -0,0 +1,193 @@
+//! Attributes the rewards earned by the proposer of a block to the operations it includes.
+//!
+//! Primarily useful for auditing the packing of blocks by the operation pool.
+use crate::common::{get_attesting_indices, get_base_reward};
+use crate::per_block_processing::{
+    process_attester_slashings, process_proposer_slashings, VerifySignatures,
+};
+use crate::{per_slot_processing, BlockProcessingError, SlotProcessingError};
+use safe_arith::{ArithError, SafeArith};
+use std::collections::HashSet;
+use types::*;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The state is at a later slot than the block.
+    StateSlotTooHigh {
+        state_slot: Slot,
+        block_slot: Slot,
+    },
+    SlotProcessingError(SlotProcessingError),
+    BlockProcessingError(BlockProcessingError),
+    BeaconStateError(BeaconStateError),
+    ArithError(ArithError),
+}
+
+/// The rewards earned by the proposer of a block, in Gwei.
+///
+/// Each reward is listed in the same order as the corresponding operation in the block.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BlockReward {
+    pub proposer_index: u64,
+    pub attestation_rewards: Vec<u64>,
+    pub proposer_slashing_rewards: Vec<u64>,
+    pub attester_slashing_rewards: Vec<u64>,
+}
+
+impl BlockReward {
+    /// The sum of all the rewards earned by the proposer.
+    pub fn total(&self) -> u64 {
+        self.attestation_rewards
+            .iter()
+            .chain(self.proposer_slashing_rewards.iter())
+            .chain(self.attester_slashing_rewards.iter())
+            .fold(0, |total, reward| total.saturating_add(*reward))
+    }
+}
+
+/// Computes the rewards earned by the proposer of `block` for each operation it includes.
+///
+/// `state` must be the post-state of the parent of `block`; it is advanced to the slot of `block`
+/// before the operations are applied. Signatures are not verified.
+///
+/// Slashing rewards are paid immediately, so they are measured as the change in the proposer's
+/// balance after applying each slashing with `per_block_processing`. Attestation rewards are only
+/// paid during epoch processing, so they are estimated using the current total active balance.
+/// An attester is only counted for the first attestation which includes it, since later
+/// inclusions earn nothing.
+pub fn compute_block_reward<T: EthSpec>(
+    block: &BeaconBlock<T>,
+    mut state: BeaconState<T>,
+    spec: &ChainSpec,
+) -> Result<BlockReward, Error> {
+    if state.slot > block.slot {
+        return Err(Error::StateSlotTooHigh {
+            state_slot: state.slot,
+            block_slot: block.slot,
+        });
+    }
+
+    while state.slot < block.slot {
+        per_slot_processing(&mut state, None, spec)?;
+    }
+
+    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+    state.build_committee_cache(RelativeEpoch::Current, spec)?;
+
+    let proposer_index = block.proposer_index as usize;
+
+    let mut proposer_slashing_rewards = Vec::with_capacity(block.body.proposer_slashings.len());
+    for proposer_slashing in block.body.proposer_slashings.iter() {
+        let balance_before = get_balance(&state, proposer_index)?;
+        process_proposer_slashings(
+            &mut state,
+            std::slice::from_ref(proposer_slashing),
+            VerifySignatures::False,
+            spec,
+        )?;
+        proposer_slashing_rewards
+            .push(get_balance(&state, proposer_index)?.saturating_sub(balance_before));
+    }
+
+    let mut attester_slashing_rewards = Vec::with_capacity(block.body.attester_slashings.len());
+    for attester_slashing in block.body.attester_slashings.iter() {
+        let balance_before = get_balance(&state, proposer_index)?;
+        process_attester_slashings(
+            &mut state,
+            std::slice::from_ref(attester_slashing),
+            VerifySignatures::False,
+            spec,
+        )?;
+        attester_slashing_rewards
+            .push(get_balance(&state, proposer_index)?.saturating_sub(balance_before));
+    }
+
+    let total_active_balance = state.get_total_balance(
+        state.get_cached_active_validator_indices(RelativeEpoch::Current)?,
+        spec,
+    )?;
+
+    // Attesters which have already been included in an earlier block, keyed by target epoch.
+    let mut included = HashSet::new();
+    for pending_attestation in state
+        .previous_epoch_attestations
+        .iter()
+        .chain(state.current_epoch_attestations.iter())
+    {
+        let committee = state.get_beacon_committee(
+            pending_attestation.data.slot,
+            pending_attestation.data.index,
+        )?;
+        for index in
+            get_attesting_indices::<T>(committee.committee, &pending_attestation.aggregation_bits)?
+        {
+            included.insert((pending_attestation.data.target.epoch, index));
+        }
+    }
+
+    let mut attestation_rewards = Vec::with_capacity(block.body.attestations.len());
+    for attestation in block.body.attestations.iter() {
+        let committee =
+            state.get_beacon_committee(attestation.data.slot, attestation.data.index)?;
+        let mut reward = 0u64;
+
+        for index in get_attesting_indices::<T>(committee.committee, &attestation.aggregation_bits)?
+        {
+            let validator = state
+                .validators
+                .get(index)
+                .ok_or_else(|| BeaconStateError::UnknownValidator(index as u64))?;
+
+            // Slashed validators are not eligible for rewards, so neither is their proposer.
+            if validator.slashed || !included.insert((attestation.data.target.epoch, index)) {
+                continue;
+            }
+
+            reward.safe_add_assign(
+                get_base_reward(&state, index, total_active_balance, spec)?
+                    .safe_div(spec.proposer_reward_quotient)?,
+            )?;
+        }
+
+        attestation_rewards.push(reward);
+    }
+
+    Ok(BlockReward {
+        proposer_index: block.proposer_index,
+        attestation_rewards,
+        proposer_slashing_rewards,
+        attester_slashing_rewards,
+    })
+}
+
+fn get_balance<T: EthSpec>(state: &BeaconState<T>, index: usize) -> Result<u64, BeaconStateError> {
+    state
+        .balances
+        .get(index)
+        .copied()
+        .ok_or_else(|| BeaconStateError::UnknownValidator(index as u64))
+}
+
+impl From<SlotProcessingError> for Error {
+    fn from(e: SlotProcessingError) -> Error {
+        Error::SlotProcessingError(e)
+    }
+}
+
+impl From<BlockProcessingError> for Error {
+    fn from(e: BlockProcessingError) -> Error {
+        Error::BlockProcessingError(e)
+    }
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Error {
+        Error::BeaconStateError(e)
+    }
+}
+
+impl From<ArithError> for Error {
+    fn from(e: ArithError) -> Error {
+        Error::ArithError(e)
+    }
+}
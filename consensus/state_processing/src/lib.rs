@@ -3,13 +3,16 @@
 #[macro_use]
 mod macros;
 
+pub mod block_rewards;
 pub mod common;
 pub mod genesis;
 pub mod per_block_processing;
 pub mod per_epoch_processing;
 pub mod per_slot_processing;
 pub mod test_utils;
+pub mod verify_operation;
 
+pub use block_rewards::{compute_block_reward, BlockReward, Error as BlockRewardError};
 pub use genesis::{
     eth2_genesis_time, initialize_beacon_state_from_eth1, is_valid_genesis_state,
     process_activations,
@@ -20,3 +23,4 @@ pub use per_block_processing::{
 };
 pub use per_epoch_processing::{errors::EpochProcessingError, per_epoch_processing};
 pub use per_slot_processing::{per_slot_processing, Error as SlotProcessingError};
+pub use verify_operation::{SigVerifiedOp, VerifyOperation};
@@ -108,6 +108,47 @@ where
     ))
 }
 
+/// A signature set that is valid if a block was signed by `proposer_pubkey`.
+///
+/// Unlike `block_proposal_signature_set`, no `BeaconState` is required and it is not checked that
+/// `proposer_pubkey` belongs to the expected proposer for the block's slot.
+pub fn block_proposal_signature_set_from_parts<'a, T>(
+    signed_block: &'a SignedBeaconBlock<T>,
+    block_root: Option<Hash256>,
+    proposer_pubkey: Cow<'a, PublicKey>,
+    fork: &Fork,
+    genesis_validators_root: Hash256,
+    spec: &'a ChainSpec,
+) -> SignatureSet
+where
+    T: EthSpec,
+{
+    let block = &signed_block.message;
+
+    let domain = spec.get_domain(
+        block.slot.epoch(T::slots_per_epoch()),
+        Domain::BeaconProposer,
+        fork,
+        genesis_validators_root,
+    );
+
+    let message = if let Some(root) = block_root {
+        SigningRoot {
+            object_root: root,
+            domain,
+        }
+        .tree_hash_root()
+    } else {
+        block.signing_root(domain)
+    };
+
+    SignatureSet::single(
+        &signed_block.signature,
+        proposer_pubkey,
+        message.as_bytes().to_vec(),
+    )
+}
+
 /// A signature set that is valid if the block proposers randao reveal signature is correct.
 pub fn randao_signature_set<'a, T, F>(
     state: &'a BeaconState<T>,
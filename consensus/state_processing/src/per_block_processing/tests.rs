@@ -2,7 +2,8 @@
 
 use super::block_processing_builder::BlockProcessingBuilder;
 use super::errors::*;
-use crate::{per_block_processing, BlockSignatureStrategy};
+use crate::{compute_block_reward, per_block_processing, BlockSignatureStrategy};
+use safe_arith::SafeArith;
 use types::test_utils::{
     AttestationTestTask, AttesterSlashingTestTask, DepositTestTask, ProposerSlashingTestTask,
 };
@@ -884,6 +885,53 @@ fn invalid_proposer_slashing_proposal_epoch_mismatch() {
     );
 }
 
+#[test]
+fn block_reward_proposer_slashing() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec, EPOCH_OFFSET, VALIDATOR_COUNT);
+    let test_task = ProposerSlashingTestTask::Valid;
+    let (block, state) = builder.build_with_proposer_slashing(test_task, 1, None, None, &spec);
+
+    let slashed_index = block.message.body.proposer_slashings[0]
+        .signed_header_1
+        .message
+        .proposer_index as usize;
+    let expected_reward = state.validators[slashed_index]
+        .effective_balance
+        .safe_div(spec.whistleblower_reward_quotient)
+        .unwrap();
+
+    let reward = compute_block_reward(&block.message, state, &spec).unwrap();
+
+    assert_eq!(reward.proposer_slashing_rewards, vec![expected_reward]);
+    assert_eq!(reward.total(), expected_reward);
+}
+
+#[test]
+fn block_reward_duplicate_attestation() {
+    let spec = MainnetEthSpec::default_spec();
+    let builder = get_builder(&spec, EPOCH_OFFSET, VALIDATOR_COUNT);
+    let test_task = AttestationTestTask::Valid;
+    let (mut block, state) =
+        builder.build_with_n_attestations(test_task, NUM_ATTESTATIONS, None, None, &spec);
+
+    let attestation = block.message.body.attestations[0].clone();
+    block
+        .message
+        .body
+        .attestations
+        .push(attestation)
+        .expect("should add duplicate attestation");
+
+    let reward = compute_block_reward(&block.message, state, &spec).unwrap();
+
+    // Only the first inclusion of each attester earns a reward.
+    assert_eq!(reward.attestation_rewards.len(), 2);
+    assert!(reward.attestation_rewards[0] > 0);
+    assert_eq!(reward.attestation_rewards[1], 0);
+    assert_eq!(reward.total(), reward.attestation_rewards[0]);
+}
+
 fn get_builder(
     spec: &ChainSpec,
     epoch_offset: u64,
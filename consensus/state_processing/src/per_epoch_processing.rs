@@ -10,7 +10,9 @@ pub mod registry_updates;
 pub mod tests;
 pub mod validator_statuses;
 
-pub use apply_rewards::process_rewards_and_penalties;
+pub use apply_rewards::{
+    get_delta_breakdowns, process_rewards_and_penalties, Delta, DeltaBreakdown,
+};
 pub use process_slashings::process_slashings;
 pub use registry_updates::process_registry_updates;
 pub use validator_statuses::{TotalBalances, ValidatorStatus, ValidatorStatuses};
@@ -68,6 +70,26 @@ pub fn per_epoch_processing<T: EthSpec>(
     })
 }
 
+/// Compute the rewards and penalties which `per_epoch_processing` would apply to each validator in
+/// `state`, attributed to the duty which caused them.
+///
+/// Justification and finalization are processed on `state` first, since the resulting finality
+/// delay determines whether inactivity penalties apply. The rewards are not applied.
+pub fn get_epoch_delta_breakdowns<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    spec: &ChainSpec,
+) -> Result<Vec<DeltaBreakdown>, Error> {
+    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+    state.build_committee_cache(RelativeEpoch::Current, spec)?;
+
+    let mut validator_statuses = ValidatorStatuses::new(state, spec)?;
+    validator_statuses.process_attestations(&state, spec)?;
+
+    process_justification_and_finalization(state, &validator_statuses.total_balances)?;
+
+    get_delta_breakdowns(state, &validator_statuses, spec)
+}
+
 /// Update the following fields on the `BeaconState`:
 ///
 /// - `justification_bitfield`.
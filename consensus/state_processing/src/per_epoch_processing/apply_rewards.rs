@@ -6,10 +6,10 @@ use safe_arith::SafeArith;
 use types::*;
 
 /// Use to track the changes to a validators balance.
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct Delta {
-    rewards: u64,
-    penalties: u64,
+    pub rewards: u64,
+    pub penalties: u64,
 }
 
 impl Delta {
@@ -32,6 +32,42 @@ impl Delta {
     }
 }
 
+/// The changes to a validators balance during epoch processing, attributed to the duty which
+/// caused them.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct DeltaBreakdown {
+    /// Attesting to the expected FFG source.
+    pub source: Delta,
+    /// Attesting to the expected FFG target.
+    pub target: Delta,
+    /// Attesting to the expected head.
+    pub head: Delta,
+    /// The speed with which an attestation was included in a block.
+    pub inclusion_delay: Delta,
+    /// Penalties applied whilst the chain is failing to finalize.
+    pub inactivity_penalty: Delta,
+    /// Including the attestations of other validators in a proposed block.
+    pub proposer: Delta,
+}
+
+impl DeltaBreakdown {
+    /// Returns the sum of all components.
+    pub fn total(&self) -> Result<Delta, Error> {
+        let mut total = Delta::default();
+        for delta in &[
+            &self.source,
+            &self.target,
+            &self.head,
+            &self.inclusion_delay,
+            &self.inactivity_penalty,
+            &self.proposer,
+        ] {
+            total.combine((*delta).clone())?;
+        }
+        Ok(total)
+    }
+}
+
 /// Apply attester and proposer rewards.
 ///
 /// Spec v0.11.1
@@ -51,15 +87,12 @@ pub fn process_rewards_and_penalties<T: EthSpec>(
         return Err(Error::ValidatorStatusesInconsistent);
     }
 
-    let mut deltas = vec![Delta::default(); state.balances.len()];
-
-    get_attestation_deltas(&mut deltas, state, &validator_statuses, spec)?;
-
-    get_proposer_deltas(&mut deltas, state, validator_statuses, spec)?;
+    let deltas = get_delta_breakdowns(state, validator_statuses, spec)?;
 
     // Apply the deltas, erroring on overflow above but not on overflow below (saturating at 0
     // instead).
-    for (i, delta) in deltas.iter().enumerate() {
+    for (i, breakdown) in deltas.iter().enumerate() {
+        let delta = breakdown.total()?;
         state.balances[i] = state.balances[i].safe_add(delta.rewards)?;
         state.balances[i] = state.balances[i].saturating_sub(delta.penalties);
     }
@@ -67,13 +100,39 @@ pub fn process_rewards_and_penalties<T: EthSpec>(
     Ok(())
 }
 
+/// Compute the attester and proposer rewards for each validator in `state`, without applying
+/// them.
+///
+/// The result is indexed by validator index. No rewards are given in the genesis epoch.
+pub fn get_delta_breakdowns<T: EthSpec>(
+    state: &BeaconState<T>,
+    validator_statuses: &ValidatorStatuses,
+    spec: &ChainSpec,
+) -> Result<Vec<DeltaBreakdown>, Error> {
+    let mut deltas = vec![DeltaBreakdown::default(); state.balances.len()];
+
+    if state.current_epoch() == T::genesis_epoch() {
+        return Ok(deltas);
+    }
+
+    if validator_statuses.statuses.len() != deltas.len() {
+        return Err(Error::ValidatorStatusesInconsistent);
+    }
+
+    get_attestation_deltas(&mut deltas, state, validator_statuses, spec)?;
+
+    get_proposer_deltas(&mut deltas, state, validator_statuses, spec)?;
+
+    Ok(deltas)
+}
+
 /// For each attesting validator, reward the proposer who was first to include their attestation.
 ///
 /// Spec v0.11.1
 fn get_proposer_deltas<T: EthSpec>(
-    deltas: &mut Vec<Delta>,
+    deltas: &mut Vec<DeltaBreakdown>,
     state: &BeaconState<T>,
-    validator_statuses: &mut ValidatorStatuses,
+    validator_statuses: &ValidatorStatuses,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     for (index, validator) in validator_statuses.statuses.iter().enumerate() {
@@ -94,6 +153,7 @@ fn get_proposer_deltas<T: EthSpec>(
             }
 
             deltas[inclusion.proposer_index]
+                .proposer
                 .reward(base_reward.safe_div(spec.proposer_reward_quotient)?)?;
         }
     }
@@ -105,7 +165,7 @@ fn get_proposer_deltas<T: EthSpec>(
 ///
 /// Spec v0.11.1
 fn get_attestation_deltas<T: EthSpec>(
-    deltas: &mut Vec<Delta>,
+    deltas: &mut Vec<DeltaBreakdown>,
     state: &BeaconState<T>,
     validator_statuses: &ValidatorStatuses,
     spec: &ChainSpec,
@@ -120,7 +180,7 @@ fn get_attestation_deltas<T: EthSpec>(
             spec,
         )?;
 
-        let delta = get_attestation_delta::<T>(
+        let breakdown = get_attestation_delta::<T>(
             &validator,
             &validator_statuses.total_balances,
             base_reward,
@@ -128,7 +188,7 @@ fn get_attestation_deltas<T: EthSpec>(
             spec,
         )?;
 
-        deltas[index].combine(delta)?;
+        deltas[index] = breakdown;
     }
 
     Ok(())
@@ -143,8 +203,8 @@ fn get_attestation_delta<T: EthSpec>(
     base_reward: u64,
     finality_delay: u64,
     spec: &ChainSpec,
-) -> Result<Delta, Error> {
-    let mut delta = Delta::default();
+) -> Result<DeltaBreakdown, Error> {
+    let mut delta = DeltaBreakdown::default();
 
     // Is this validator eligible to be rewarded or penalized?
     // Spec: validator index in `eligible_validator_indices`
@@ -177,7 +237,7 @@ fn get_attestation_delta<T: EthSpec>(
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_source_attestations)`
     if validator.is_previous_epoch_attester && !validator.is_slashed {
-        delta.reward(
+        delta.source.reward(
             base_reward
                 .safe_mul(total_attesting_balance_ebi)?
                 .safe_div(total_balance_ebi)?,
@@ -188,47 +248,51 @@ fn get_attestation_delta<T: EthSpec>(
         let inclusion = validator
             .inclusion_info
             .expect("It is a logic error for an attester not to have an inclusion delay.");
-        delta.reward(max_attester_reward.safe_div(inclusion.delay)?)?;
+        delta
+            .inclusion_delay
+            .reward(max_attester_reward.safe_div(inclusion.delay)?)?;
     } else {
-        delta.penalize(base_reward)?;
+        delta.source.penalize(base_reward)?;
     }
 
     // Expected FFG target.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_target_attestations)`
     if validator.is_previous_epoch_target_attester && !validator.is_slashed {
-        delta.reward(
+        delta.target.reward(
             base_reward
                 .safe_mul(matching_target_balance_ebi)?
                 .safe_div(total_balance_ebi)?,
         )?;
     } else {
-        delta.penalize(base_reward)?;
+        delta.target.penalize(base_reward)?;
     }
 
     // Expected head.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_head_attestations)`
     if validator.is_previous_epoch_head_attester && !validator.is_slashed {
-        delta.reward(
+        delta.head.reward(
             base_reward
                 .safe_mul(matching_head_balance_ebi)?
                 .safe_div(total_balance_ebi)?,
         )?;
     } else {
-        delta.penalize(base_reward)?;
+        delta.head.penalize(base_reward)?;
     }
 
     // Inactivity penalty
     if finality_delay > spec.min_epochs_to_inactivity_penalty {
         // All eligible validators are penalized
-        delta.penalize(spec.base_rewards_per_epoch.safe_mul(base_reward)?)?;
+        delta
+            .inactivity_penalty
+            .penalize(spec.base_rewards_per_epoch.safe_mul(base_reward)?)?;
 
         // Additionally, all validators whose FFG target didn't match are penalized extra
         // This condition is equivalent to this condition from the spec:
         // `index not in get_unslashed_attesting_indices(state, matching_target_attestations)`
         if validator.is_slashed || !validator.is_previous_epoch_target_attester {
-            delta.penalize(
+            delta.inactivity_penalty.penalize(
                 validator
                     .current_epoch_effective_balance
                     .safe_mul(finality_delay)?
@@ -1,5 +1,8 @@
 #![cfg(test)]
-use crate::per_epoch_processing::per_epoch_processing;
+use crate::per_epoch_processing::{
+    get_delta_breakdowns, get_epoch_delta_breakdowns, per_epoch_processing,
+    process_rewards_and_penalties, Delta, ValidatorStatuses,
+};
 use env_logger::{Builder, Env};
 use types::test_utils::TestingBeaconStateBuilder;
 use types::*;
@@ -21,3 +24,113 @@ fn runs_without_error() {
 
     per_epoch_processing(&mut state, &spec).unwrap();
 }
+
+#[test]
+fn delta_breakdowns_match_applied_rewards() {
+    let spec = MinimalEthSpec::default_spec();
+
+    let mut builder: TestingBeaconStateBuilder<MinimalEthSpec> =
+        TestingBeaconStateBuilder::from_deterministic_keypairs(8, &spec);
+
+    let target_slot =
+        (MinimalEthSpec::genesis_epoch() + 4).end_slot(MinimalEthSpec::slots_per_epoch());
+    builder.teleport_to_slot(target_slot);
+
+    let (mut state, _keypairs) = builder.build();
+    state
+        .build_committee_cache(RelativeEpoch::Previous, &spec)
+        .unwrap();
+    state
+        .build_committee_cache(RelativeEpoch::Current, &spec)
+        .unwrap();
+
+    let mut validator_statuses = ValidatorStatuses::new(&state, &spec).unwrap();
+    validator_statuses
+        .process_attestations(&state, &spec)
+        .unwrap();
+
+    let breakdowns = get_delta_breakdowns(&state, &validator_statuses, &spec).unwrap();
+    let balances_before = state.balances.clone();
+
+    process_rewards_and_penalties(&mut state, &mut validator_statuses, &spec).unwrap();
+
+    assert_eq!(breakdowns.len(), state.balances.len());
+    for (i, breakdown) in breakdowns.iter().enumerate() {
+        // Without any attestations, every validator misses every duty.
+        assert!(breakdown.source.penalties > 0);
+        assert_eq!(breakdown.source.rewards, 0);
+
+        let total = breakdown.total().unwrap();
+        assert_eq!(
+            state.balances[i],
+            (balances_before[i] + total.rewards).saturating_sub(total.penalties)
+        );
+    }
+}
+
+#[test]
+fn epoch_delta_breakdowns_match_per_epoch_processing() {
+    let spec = MinimalEthSpec::default_spec();
+
+    let mut builder: TestingBeaconStateBuilder<MinimalEthSpec> =
+        TestingBeaconStateBuilder::from_deterministic_keypairs(8, &spec);
+
+    let epoch = MinimalEthSpec::genesis_epoch() + 8;
+    builder.teleport_to_slot(epoch.end_slot(MinimalEthSpec::slots_per_epoch()));
+    builder.insert_attestations(&spec);
+
+    let (mut state, _keypairs) = builder.build();
+
+    // The chain has not finalized for long enough to be in an inactivity leak, but with full
+    // participation this epoch transition finalizes the previous epoch and ends the leak.
+    state.previous_justified_checkpoint.epoch = epoch - 2;
+    state.current_justified_checkpoint.epoch = epoch - 1;
+    state.justification_bits = BitVector::from_bytes(vec![0b0000_0011]).unwrap();
+    state.finalized_checkpoint.epoch = epoch - 6;
+    assert!(
+        (state.previous_epoch() - state.finalized_checkpoint.epoch).as_u64()
+            > spec.min_epochs_to_inactivity_penalty
+    );
+
+    let balances_before = state.balances.clone();
+
+    let mut processed_state = state.clone();
+    per_epoch_processing(&mut processed_state, &spec).unwrap();
+
+    // Computing the breakdown without justification and finalization wrongly applies inactivity
+    // penalties.
+    let mut unjustified_state = state.clone();
+    unjustified_state
+        .build_committee_cache(RelativeEpoch::Previous, &spec)
+        .unwrap();
+    unjustified_state
+        .build_committee_cache(RelativeEpoch::Current, &spec)
+        .unwrap();
+    let mut validator_statuses = ValidatorStatuses::new(&unjustified_state, &spec).unwrap();
+    validator_statuses
+        .process_attestations(&unjustified_state, &spec)
+        .unwrap();
+    assert!(
+        get_delta_breakdowns(&unjustified_state, &validator_statuses, &spec)
+            .unwrap()
+            .iter()
+            .all(|breakdown| breakdown.inactivity_penalty.penalties > 0)
+    );
+
+    let breakdowns = get_epoch_delta_breakdowns(&mut state, &spec).unwrap();
+
+    assert_eq!(
+        state.finalized_checkpoint,
+        processed_state.finalized_checkpoint
+    );
+    assert_eq!(breakdowns.len(), processed_state.balances.len());
+    for (i, breakdown) in breakdowns.iter().enumerate() {
+        assert_eq!(breakdown.inactivity_penalty, Delta::default());
+
+        let total = breakdown.total().unwrap();
+        assert_eq!(
+            processed_state.balances[i],
+            (balances_before[i] + total.rewards).saturating_sub(total.penalties)
+        );
+    }
+}
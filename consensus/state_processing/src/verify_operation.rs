@@ -0,0 +1,81 @@
+//! Provides the `VerifyOperation` trait and the `SigVerifiedOp` wrapper, which allow operations
+//! (exits and slashings) to be verified once and then passed around with a type-level guarantee
+//! that they were valid (including signatures) against some state.
+
+use crate::per_block_processing::{
+    errors::{
+        AttesterSlashingValidationError, ExitValidationError, ProposerSlashingValidationError,
+    },
+    verify_attester_slashing, verify_exit, verify_proposer_slashing, VerifySignatures,
+};
+use types::{
+    AttesterSlashing, BeaconState, ChainSpec, EthSpec, ProposerSlashing, SignedVoluntaryExit,
+};
+
+/// Wrapper around an operation type that acts as proof that its signature has been checked.
+///
+/// The inner field is private, meaning instances of this type can only be constructed
+/// by calling `validate`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SigVerifiedOp<T>(T);
+
+impl<T> SigVerifiedOp<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    pub fn as_inner(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Trait for operations that can be verified and transformed into a `SigVerifiedOp`.
+pub trait VerifyOperation<E: EthSpec>: Sized {
+    type Error;
+
+    /// Verify `self` against `state`, including all signatures.
+    fn validate(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error>;
+}
+
+impl<E: EthSpec> VerifyOperation<E> for SignedVoluntaryExit {
+    type Error = ExitValidationError;
+
+    fn validate(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error> {
+        verify_exit(state, &self, VerifySignatures::True, spec)?;
+        Ok(SigVerifiedOp(self))
+    }
+}
+
+impl<E: EthSpec> VerifyOperation<E> for AttesterSlashing<E> {
+    type Error = AttesterSlashingValidationError;
+
+    fn validate(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error> {
+        verify_attester_slashing(state, &self, VerifySignatures::True, spec)?;
+        Ok(SigVerifiedOp(self))
+    }
+}
+
+impl<E: EthSpec> VerifyOperation<E> for ProposerSlashing {
+    type Error = ProposerSlashingValidationError;
+
+    fn validate(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error> {
+        verify_proposer_slashing(&self, state, VerifySignatures::True, spec)?;
+        Ok(SigVerifiedOp(self))
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     test_utils::TestRandom, BeaconBlock, ChainSpec, Domain, EthSpec, Fork, Hash256, PublicKey,
-    SignedRoot, SigningRoot, Slot,
+    SignedBeaconBlockHeader, SignedRoot, SigningRoot, Slot,
 };
 use bls::Signature;
 use serde_derive::{Deserialize, Serialize};
@@ -102,6 +102,14 @@ impl<E: EthSpec> SignedBeaconBlock<E> {
     pub fn canonical_root(&self) -> Hash256 {
         Hash256::from_slice(&self.message.tree_hash_root()[..])
     }
+
+    /// Returns the signed header of the block, which shares the block's signature.
+    pub fn signed_block_header(&self) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: self.message.block_header(),
+            signature: self.signature.clone(),
+        }
+    }
 }
 
 #[cfg(test)]
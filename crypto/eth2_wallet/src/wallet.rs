@@ -215,6 +215,15 @@ impl Wallet {
         self.json.nextaccount
     }
 
+    /// Sets the value of the JSON wallet `nextaccount` field.
+    ///
+    /// This is intended for restoring a wallet from a mnemonic, where keys up to some index have
+    /// already been generated. Setting `nextaccount` to a value lower than the index of a key that
+    /// is already in use will result in the same key being generated twice.
+    pub fn set_nextaccount(&mut self, nextaccount: u32) {
+        self.json.nextaccount = nextaccount;
+    }
+
     /// Returns the value of the JSON wallet `name` field.
     pub fn name(&self) -> &str {
         &self.json.name
@@ -277,9 +286,23 @@ pub fn recover_validator_secret(
     index: u32,
     key_type: KeyType,
 ) -> Result<(PlainText, ValidatorPath), Error> {
-    let path = ValidatorPath::new(index, key_type);
     let secret = wallet.decrypt_seed(wallet_password)?;
-    let master = DerivedKey::from_seed(secret.as_bytes()).map_err(|()| Error::EmptyPassword)?;
+
+    recover_validator_secret_from_mnemonic(secret.as_bytes(), index, key_type)
+}
+
+/// Returns `(secret, path)` for the `key_type` for the validator at `index`, derived from the
+/// `seed` of a BIP-39 mnemonic rather than an encrypted wallet.
+///
+/// The same caveats as `recover_validator_secret` apply, this function should only be used for
+/// recovering keys which were previously generated.
+pub fn recover_validator_secret_from_mnemonic(
+    seed: &[u8],
+    index: u32,
+    key_type: KeyType,
+) -> Result<(PlainText, ValidatorPath), Error> {
+    let path = ValidatorPath::new(index, key_type);
+    let master = DerivedKey::from_seed(seed).map_err(|()| Error::EmptySeed)?;
 
     let destination = path.iter_nodes().fold(master, |dk, i| dk.child(*i));
 
@@ -0,0 +1,43 @@
+use crate::transition_blocks::load_from_ssz;
+use clap::ArgMatches;
+use state_processing::compute_block_reward;
+use std::path::PathBuf;
+use types::{BeaconState, EthSpec, SignedBeaconBlock};
+
+pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
+    let pre_state_path = matches
+        .value_of("pre-state")
+        .ok_or_else(|| "No pre-state file supplied".to_string())?
+        .parse::<PathBuf>()
+        .map_err(|e| format!("Failed to parse pre-state path: {}", e))?;
+
+    let block_path = matches
+        .value_of("block")
+        .ok_or_else(|| "No block file supplied".to_string())?
+        .parse::<PathBuf>()
+        .map_err(|e| format!("Failed to parse block path: {}", e))?;
+
+    info!("Using {} spec", T::spec_name());
+    info!("Pre-state path: {:?}", pre_state_path);
+    info!("Block path: {:?}", block_path);
+
+    let pre_state: BeaconState<T> = load_from_ssz(pre_state_path)?;
+    let block: SignedBeaconBlock<T> = load_from_ssz(block_path)?;
+
+    let reward = compute_block_reward(&block.message, pre_state, &T::default_spec())
+        .map_err(|e| format!("Unable to compute block reward: {:?}", e))?;
+
+    println!("Proposer index: {}", reward.proposer_index);
+    for (i, gwei) in reward.attestation_rewards.iter().enumerate() {
+        println!("Attestation {}: {} gwei", i, gwei);
+    }
+    for (i, gwei) in reward.proposer_slashing_rewards.iter().enumerate() {
+        println!("Proposer slashing {}: {} gwei", i, gwei);
+    }
+    for (i, gwei) in reward.attester_slashing_rewards.iter().enumerate() {
+        println!("Attester slashing {}: {} gwei", i, gwei);
+    }
+    println!("Total: {} gwei", reward.total());
+
+    Ok(())
+}
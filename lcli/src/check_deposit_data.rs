@@ -1,10 +1,18 @@
 use clap::ArgMatches;
-use clap_utils::{parse_required, parse_ssz_required};
+use clap_utils::{parse_optional, parse_required, parse_ssz_required};
 use deposit_contract::{decode_eth1_tx_data, DEPOSIT_DATA_LEN};
+use eth2_testnet_config::Eth2TestnetConfig;
+use std::fs::File;
+use std::path::PathBuf;
 use tree_hash::TreeHash;
-use types::EthSpec;
+use types::{ChainSpec, EthSpec};
+use validator_dir::launchpad::read_deposit_data_json;
 
 pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
+    if let Some(path) = parse_optional::<PathBuf>(matches, "deposit-data-json")? {
+        return check_deposit_data_json::<T>(matches, path);
+    }
+
     let rlp_bytes = parse_ssz_required::<Vec<u8>>(matches, "deposit-data")?;
     let amount = parse_required(matches, "deposit-amount")?;
 
@@ -29,3 +37,54 @@ pub fn run<T: EthSpec>(matches: &ArgMatches) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Verifies each of the deposits in a launchpad `deposit_data-<timestamp>.json` file against the
+/// spec of the `--testnet-dir` (or the default spec, if no testnet dir is supplied).
+fn check_deposit_data_json<T: EthSpec>(matches: &ArgMatches, path: PathBuf) -> Result<(), String> {
+    let spec = load_spec::<T>(matches)?;
+
+    let deposits = File::open(&path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", path, e))
+        .and_then(|file| {
+            read_deposit_data_json(file).map_err(|e| format!("Unable to parse {:?}: {:?}", path, e))
+        })?;
+
+    if deposits.is_empty() {
+        return Err(format!("{:?} does not contain any deposits", path));
+    }
+
+    let errors = deposits
+        .iter()
+        .enumerate()
+        .filter_map(|(i, deposit)| {
+            deposit
+                .verify(&spec)
+                .err()
+                .map(|e| format!("Deposit {} (0x{}) is invalid: {:?}", i, deposit.pubkey, e))
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join(", "))
+    }
+}
+
+fn load_spec<T: EthSpec>(matches: &ArgMatches) -> Result<ChainSpec, String> {
+    let default_spec = T::default_spec();
+
+    match parse_optional::<PathBuf>(matches, "testnet-dir")? {
+        Some(testnet_dir) => {
+            let testnet_config: Eth2TestnetConfig<T> = Eth2TestnetConfig::load(testnet_dir)?;
+
+            testnet_config
+                .yaml_config
+                .as_ref()
+                .ok_or_else(|| "The testnet directory must contain a spec config".to_string())?
+                .apply_to_chain_spec::<T>(&default_spec)
+                .ok_or_else(|| "The loaded config is not compatible with the spec".to_string())
+        }
+        None => Ok(default_spec),
+    }
+}
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate log;
 
+mod block_rewards;
 mod change_genesis_time;
 mod check_deposit_data;
 mod deploy_deposit_contract;
@@ -131,6 +132,24 @@ fn main() {
                         .help("Path to output a SSZ file."),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("block-rewards")
+                .about("Computes the reward earned by a block proposer for each included operation")
+                .arg(
+                    Arg::with_name("pre-state")
+                        .value_name("BEACON_STATE")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the post-state of the block's parent."),
+                )
+                .arg(
+                    Arg::with_name("block")
+                        .value_name("BEACON_BLOCK")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the block."),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("pretty-hex")
                 .about("Parses SSZ encoded as ASCII 0x-prefixed hex")
@@ -390,7 +409,7 @@ fn main() {
                         .index(1)
                         .value_name("GWEI")
                         .takes_value(true)
-                        .required(true)
+                        .required_unless("deposit-data-json")
                         .help("The amount (in Gwei) that was deposited"),
                 )
                 .arg(
@@ -398,10 +417,20 @@ fn main() {
                         .index(2)
                         .value_name("HEX")
                         .takes_value(true)
-                        .required(true)
+                        .required_unless("deposit-data-json")
                         .help("A 0x-prefixed hex string of the deposit data. Should include the
                             function signature."),
                 )
+                .arg(
+                    Arg::with_name("deposit-data-json")
+                        .long("deposit-data-json")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .conflicts_with_all(&["deposit-amount", "deposit-data"])
+                        .help("The path to a deposit_data-<timestamp>.json file, as produced for
+                            the web launchpad. Each deposit is verified against the spec of the
+                            --testnet-dir."),
+                )
         )
         .subcommand(
             SubCommand::with_name("generate-bootnode-enr")
@@ -549,6 +578,8 @@ fn run<T: EthSpec>(
         ("skip-slots", Some(matches)) => {
             skip_slots::run::<T>(matches).map_err(|e| format!("Failed to skip slots: {}", e))
         }
+        ("block-rewards", Some(matches)) => block_rewards::run::<T>(matches)
+            .map_err(|e| format!("Failed to compute block rewards: {}", e)),
         ("pretty-hex", Some(matches)) => {
             run_parse_hex::<T>(matches).map_err(|e| format!("Failed to pretty print hex: {}", e))
         }
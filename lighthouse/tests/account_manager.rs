@@ -2,14 +2,28 @@
 
 use account_manager::{
     upgrade_legacy_keypairs::{CMD as UPGRADE_CMD, *},
-    validator::{create::*, CMD as VALIDATOR_CMD},
+    validator::{
+        create::*,
+        export_deposit_data::{CMD as EXPORT_DEPOSIT_DATA_CMD, OUTPUT_DIR_FLAG, VALIDATOR_FLAG},
+        import::{
+            CMD as IMPORT_CMD, DIR_FLAG, KEYSTORE_FLAG, PASSWORD_FLAG, SLASHING_PROTECTION_FLAG,
+        },
+        list::{CMD as LIST_VALIDATORS_CMD, JSON_FLAG},
+        recover::{
+            CMD as VALIDATOR_RECOVER_CMD, FIRST_INDEX_FLAG,
+            MNEMONIC_FLAG as VALIDATOR_MNEMONIC_PATH_FLAG,
+        },
+        CMD as VALIDATOR_CMD,
+    },
     wallet::{
         create::{CMD as CREATE_CMD, *},
         list::CMD as LIST_CMD,
+        recover::{CMD as WALLET_RECOVER_CMD, MNEMONIC_FLAG as WALLET_MNEMONIC_PATH_FLAG},
         CMD as WALLET_CMD,
     },
     BASE_DIR_FLAG, CMD as ACCOUNT_CMD, *,
 };
+use eth2_keystore::KeystoreBuilder;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -17,7 +31,7 @@ use std::process::{Command, Output};
 use std::str::from_utf8;
 use tempfile::{tempdir, TempDir};
 use types::Keypair;
-use validator_dir::ValidatorDir;
+use validator_dir::{launchpad::read_deposit_data_json, LaunchpadDepositData, ValidatorDir};
 
 // TODO: create tests for the `lighthouse account validator deposit` command. This involves getting
 // access to an IPC endpoint during testing or adding support for deposit submission via HTTP and
@@ -193,6 +207,79 @@ fn wallet_create_and_list() {
     assert_eq!(list_wallets(wally.base_dir()).len(), 2);
 }
 
+/// Recover a wallet from a mnemonic using the lighthouse CLI.
+fn recover_wallet(
+    name: &str,
+    base_dir: &Path,
+    password: &Path,
+    mnemonic: &Path,
+) -> Result<Output, String> {
+    output_result(
+        wallet_cmd()
+            .arg(format!("--{}", BASE_DIR_FLAG))
+            .arg(base_dir.as_os_str())
+            .arg(WALLET_RECOVER_CMD)
+            .arg(format!("--{}", NAME_FLAG))
+            .arg(&name)
+            .arg(format!("--{}", PASSPHRASE_FLAG))
+            .arg(password.as_os_str())
+            .arg(format!("--{}", WALLET_MNEMONIC_PATH_FLAG))
+            .arg(mnemonic.as_os_str()),
+    )
+}
+
+#[test]
+fn wallet_recover() {
+    let base_temp_dir = tempdir().unwrap();
+    let base_dir: PathBuf = base_temp_dir.path().into();
+
+    let wally = TestWallet::new(&base_dir, "wally");
+    wally.create_expect_success();
+
+    let recovered = TestWallet::new(&base_dir, "wally-recovered");
+    recover_wallet(
+        &recovered.name,
+        &base_dir,
+        &recovered.password_path(),
+        &wally.mnemonic_path(),
+    )
+    .unwrap();
+
+    assert!(recovered.password_path().exists());
+    assert_eq!(list_wallets(&base_dir).len(), 2);
+
+    // Should not recover a wallet with a duplicate name.
+    recover_wallet(
+        &wally.name,
+        &base_dir,
+        &recovered.password_path(),
+        &wally.mnemonic_path(),
+    )
+    .unwrap_err();
+
+    assert_eq!(list_wallets(&base_dir).len(), 2);
+
+    // Both wallets should generate the same validator.
+    let pubkeys = vec![wally, recovered]
+        .into_iter()
+        .map(|wallet| {
+            let validator_dir = tempdir().unwrap();
+            let secrets_dir = tempdir().unwrap();
+            let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+
+            validator
+                .create_expect_success(COUNT_FLAG, 1, false)
+                .pop()
+                .unwrap()
+                .voting_keypair(secrets_dir.path())
+                .unwrap()
+                .pk
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(pubkeys[0], pubkeys[1]);
+}
+
 /// Returns the `lighthouse account wallet` command.
 fn validator_cmd() -> Command {
     let mut cmd = account_cmd();
@@ -366,6 +453,347 @@ fn validator_create() {
     assert_eq!(dir_child_count(validator_dir.path()), 6);
 }
 
+#[test]
+fn validator_recover() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let recovered_validator_dir = tempdir().unwrap();
+    let recovered_secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+    let mnemonic_path = wallet.mnemonic_path();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let created = validator.create(COUNT_FLAG, 3, false).unwrap();
+
+    // Recover the last two validators created by the wallet.
+    let output = output_result(
+        validator_cmd()
+            .arg(VALIDATOR_RECOVER_CMD)
+            .arg(format!("--{}", FIRST_INDEX_FLAG))
+            .arg("1")
+            .arg(format!("--{}", COUNT_FLAG))
+            .arg("2")
+            .arg(format!("--{}", VALIDATOR_MNEMONIC_PATH_FLAG))
+            .arg(mnemonic_path.as_os_str())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(recovered_validator_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(recovered_secrets_dir.path().as_os_str()),
+    )
+    .unwrap();
+
+    let stdout = from_utf8(&output.stdout).expect("stdout is not utf8");
+    let recovered = stdout
+        .lines()
+        .map(|line| {
+            let tab = line.rfind("\t").expect("line must have tab");
+            line[tab + 1..].to_string()
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(recovered, created[1..].to_vec());
+    assert_eq!(dir_child_count(recovered_validator_dir.path()), 2);
+
+    for pubkey in &recovered {
+        let dir = ValidatorDir::open(recovered_validator_dir.path().join(pubkey))
+            .expect("should open validator dir");
+
+        // The voting keystore can be decrypted, but no withdrawal keystore is stored.
+        dir.voting_keypair(recovered_secrets_dir.path()).unwrap();
+        dir.withdrawal_keypair(recovered_secrets_dir.path())
+            .err()
+            .unwrap();
+
+        // Recovered validators are assumed to already be deposited.
+        assert!(dir.eth1_deposit_data().unwrap().is_none());
+    }
+}
+
+/// Import keystores using `lighthouse account validator import`.
+fn import_validators(
+    keystore_flag: &str,
+    keystore_path: &Path,
+    password_path: &Path,
+    validator_dir: &Path,
+    secrets_dir: &Path,
+) -> Result<Output, String> {
+    output_result(
+        validator_cmd()
+            .arg(IMPORT_CMD)
+            .arg(format!("--{}", keystore_flag))
+            .arg(keystore_path.as_os_str())
+            .arg(format!("--{}", PASSWORD_FLAG))
+            .arg(password_path.as_os_str())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.as_os_str()),
+    )
+}
+
+/// Writes a new keystore for a random keypair to `dir`, named like those produced by the
+/// `eth2.0-deposit-cli` for the given derivation `index`.
+fn write_test_keystore(dir: &Path, password: &str, index: usize) -> (Keypair, PathBuf) {
+    let keypair = Keypair::random();
+    let keystore = KeystoreBuilder::new(
+        &keypair,
+        password.as_bytes(),
+        format!("m/12381/3600/{}/0/0", index),
+    )
+    .unwrap()
+    .build()
+    .unwrap();
+
+    let keystore_path = dir.join(format!("keystore-m_12381_3600_{}_0_0.json", index));
+    keystore
+        .to_json_writer(fs::File::create(&keystore_path).unwrap())
+        .unwrap();
+
+    (keypair, keystore_path)
+}
+
+#[test]
+fn validator_import() {
+    let keystores_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let password_dir = tempdir().unwrap();
+    let password = "cats";
+    let password_path = password_dir.path().join("password.txt");
+
+    fs::write(&password_path, format!("{}\n", password)).unwrap();
+
+    let keystores = (0..2)
+        .map(|i| write_test_keystore(keystores_dir.path(), password, i))
+        .collect::<Vec<_>>();
+
+    // Files which are not named like keystores should be ignored.
+    fs::write(keystores_dir.path().join("deposit_data.json"), "[]").unwrap();
+
+    // Should not import with the wrong password.
+    let bad_password_path = password_dir.path().join("bad_password.txt");
+    fs::write(&bad_password_path, "dogs").unwrap();
+    import_validators(
+        DIR_FLAG,
+        keystores_dir.path(),
+        &bad_password_path,
+        validator_dir.path(),
+        secrets_dir.path(),
+    )
+    .unwrap_err();
+
+    assert_eq!(dir_child_count(validator_dir.path()), 0);
+
+    import_validators(
+        DIR_FLAG,
+        keystores_dir.path(),
+        &password_path,
+        validator_dir.path(),
+        secrets_dir.path(),
+    )
+    .unwrap();
+
+    assert_eq!(dir_child_count(validator_dir.path()), 2);
+
+    for (keypair, _) in &keystores {
+        let dir = ValidatorDir::open(validator_dir.path().join(keypair.pk.as_hex_string()))
+            .expect("should open validator dir");
+
+        assert_eq!(
+            dir.voting_keypair(secrets_dir.path()).unwrap().pk,
+            keypair.pk
+        );
+        dir.withdrawal_keypair(secrets_dir.path()).err().unwrap();
+    }
+
+    // Should not import a duplicate keystore.
+    let err = import_validators(
+        KEYSTORE_FLAG,
+        &keystores[0].1,
+        &password_path,
+        validator_dir.path(),
+        secrets_dir.path(),
+    )
+    .unwrap_err();
+
+    assert!(err.contains("already exists"));
+    assert_eq!(dir_child_count(validator_dir.path()), 2);
+}
+
+#[test]
+fn validator_import_bad_slashing_protection() {
+    let keystores_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let password = "cats";
+    let password_path = keystores_dir.path().join("password.txt");
+
+    fs::write(&password_path, password).unwrap();
+
+    let (_, keystore_path) = write_test_keystore(keystores_dir.path(), password, 0);
+
+    let interchange_path = keystores_dir.path().join("interchange.json");
+    fs::write(&interchange_path, "{\"metadata\": {}}").unwrap();
+
+    let err = output_result(
+        validator_cmd()
+            .arg(IMPORT_CMD)
+            .arg(format!("--{}", KEYSTORE_FLAG))
+            .arg(keystore_path.as_os_str())
+            .arg(format!("--{}", PASSWORD_FLAG))
+            .arg(password_path.as_os_str())
+            .arg(format!("--{}", SLASHING_PROTECTION_FLAG))
+            .arg(interchange_path.as_os_str())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str()),
+    )
+    .unwrap_err();
+
+    // Neither the keystore nor any slashing protection data should be imported.
+    assert!(err.contains("Error parsing file for import"));
+    assert_eq!(dir_child_count(validator_dir.path()), 0);
+    assert_eq!(dir_child_count(secrets_dir.path()), 0);
+}
+
+/// Export deposit data using `lighthouse account validator export-deposit-data`.
+fn export_deposit_data(
+    validator: &str,
+    validator_dir: &Path,
+    output_dir: &Path,
+) -> Result<Output, String> {
+    output_result(
+        validator_cmd()
+            .arg(EXPORT_DEPOSIT_DATA_CMD)
+            .arg(format!("--{}", VALIDATOR_FLAG))
+            .arg(validator)
+            .arg(format!("--{}", OUTPUT_DIR_FLAG))
+            .arg(output_dir.as_os_str())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.as_os_str()),
+    )
+}
+
+/// Reads the deposit data file whose path was printed to stdout by `export_deposit_data`.
+fn read_exported_deposit_data(output: &Output, output_dir: &Path) -> Vec<LaunchpadDepositData> {
+    let stdout = from_utf8(&output.stdout).expect("stdout is not utf8");
+    let path = PathBuf::from(stdout.trim());
+    assert_eq!(path.parent(), Some(output_dir));
+    assert_eq!(dir_child_count(output_dir), 1);
+
+    let file = fs::File::open(&path).expect("should open deposit data file");
+    read_deposit_data_json(file).expect("should parse deposit data file")
+}
+
+#[test]
+fn validator_export_deposit_data() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    // The validator directories are left locked, as they would be by a running validator client.
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let dirs = validator.create_expect_success(COUNT_FLAG, 2, false);
+
+    // An imported validator has no deposit data and should be skipped.
+    let keystores_dir = tempdir().unwrap();
+    let password = "cats";
+    let password_path = keystores_dir.path().join("password.txt");
+    fs::write(&password_path, password).unwrap();
+    let (_, keystore_path) = write_test_keystore(keystores_dir.path(), password, 0);
+    import_validators(
+        KEYSTORE_FLAG,
+        &keystore_path,
+        &password_path,
+        validator_dir.path(),
+        secrets_dir.path(),
+    )
+    .unwrap();
+
+    let output_dir = tempdir().unwrap();
+    let output = export_deposit_data("all", validator_dir.path(), output_dir.path()).unwrap();
+
+    let stderr = from_utf8(&output.stderr).expect("stderr is not utf8");
+    assert!(stderr.contains("Skipping validator without deposit data"));
+
+    // Every validator should be exported with the same deposit data it was created with.
+    let deposits = read_exported_deposit_data(&output, output_dir.path());
+    assert_eq!(deposits.len(), dirs.len());
+    for dir in &dirs {
+        let expected = dir.eth1_deposit_data().unwrap().unwrap().deposit_data;
+        assert!(deposits
+            .iter()
+            .any(|deposit| deposit.deposit_data().unwrap() == expected));
+    }
+
+    // A single validator should be exported by the name of its directory.
+    let name = dirs[0].dir().file_name().unwrap().to_str().unwrap();
+    let output_dir = tempdir().unwrap();
+    let output = export_deposit_data(name, validator_dir.path(), output_dir.path()).unwrap();
+
+    let deposits = read_exported_deposit_data(&output, output_dir.path());
+    assert_eq!(deposits.len(), 1);
+    assert_eq!(
+        deposits[0].deposit_data().unwrap(),
+        dirs[0].eth1_deposit_data().unwrap().unwrap().deposit_data
+    );
+
+    export_deposit_data("unknown", validator_dir.path(), output_dir.path()).unwrap_err();
+}
+
+#[test]
+fn validator_list() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let mut created = validator.create(COUNT_FLAG, 2, false).unwrap();
+    created.sort();
+
+    // Listing should not require a lock, so it should succeed whilst a validator is in use.
+    let _locked = ValidatorDir::open(validator_dir.path().join(&created[0]))
+        .expect("should open validator dir");
+
+    let list = |json: bool| {
+        let mut cmd = validator_cmd();
+        cmd.arg(LIST_VALIDATORS_CMD)
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str());
+        if json {
+            cmd.arg(format!("--{}", JSON_FLAG));
+        }
+        let output = output_result(&mut cmd).unwrap();
+        from_utf8(&output.stdout)
+            .expect("stdout is not utf8")
+            .to_string()
+    };
+
+    // Without a beacon node, only the local information about each validator is known.
+    let stdout = list(false);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), created.len());
+    for (line, pubkey) in lines.iter().zip(created.iter()) {
+        assert_eq!(*line, format!("{}\ttrue\t-\t-\t-\t-", pubkey));
+    }
+
+    let stdout = list(true);
+    assert!(stdout.starts_with("["));
+    for pubkey in &created {
+        assert!(stdout.contains(pubkey.as_str()));
+    }
+}
+
 fn write_legacy_keypair<P: AsRef<Path>>(name: &str, dir: P) -> Keypair {
     let keypair = Keypair::random();
 
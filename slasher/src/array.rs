@@ -0,0 +1,196 @@
+//! Min-max span arrays, used to detect surround votes.
+//!
+//! For each validator we maintain two arrays indexed by epoch:
+//!
+//! - `min_targets[e]`: the minimum target epoch of any attestation with a source epoch `> e`.
+//! - `max_targets[e]`: the maximum target epoch of any attestation with a source epoch `< e`.
+//!
+//! A new attestation `(s, t)` surrounds a prior attestation iff `min_targets[s] < t`, and is
+//! surrounded by a prior attestation iff `max_targets[s] > t`.
+//!
+//! Targets are stored as `u16` distances from their index `e` and the arrays are split into
+//! chunks of `Config::chunk_size` epochs, each of which is a separate database entry.
+use crate::{Error, SlasherDB};
+use std::collections::{BTreeMap, BTreeSet};
+use store::DBColumn;
+use types::{Epoch, EthSpec};
+
+/// Distance stored in the min array when no attestation has been seen.
+const MIN_TARGET_EMPTY: u16 = u16::max_value();
+/// Distance stored in the max array when no attestation has been seen.
+const MAX_TARGET_EMPTY: u16 = 0;
+
+/// A conflict between a new attestation and one previously recorded in the span arrays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpanConflict {
+    /// The new attestation surrounds a prior attestation with this target epoch.
+    Surrounds { target_epoch: Epoch },
+    /// The new attestation is surrounded by a prior attestation with this target epoch.
+    SurroundedBy { target_epoch: Epoch },
+}
+
+/// A lazily loaded view of one of a single validator's span arrays.
+struct SpanArray<'a, E: EthSpec> {
+    db: &'a SlasherDB<E>,
+    column: DBColumn,
+    empty_distance: u16,
+    validator_index: u64,
+    chunk_size: u64,
+    chunks: BTreeMap<u64, Vec<u16>>,
+    dirty: BTreeSet<u64>,
+}
+
+impl<'a, E: EthSpec> SpanArray<'a, E> {
+    fn new(
+        db: &'a SlasherDB<E>,
+        column: DBColumn,
+        empty_distance: u16,
+        validator_index: u64,
+        chunk_size: u64,
+    ) -> Self {
+        Self {
+            db,
+            column,
+            empty_distance,
+            validator_index,
+            chunk_size,
+            chunks: BTreeMap::new(),
+            dirty: BTreeSet::new(),
+        }
+    }
+
+    /// Return the chunk containing `epoch`, loading it from disk if necessary.
+    fn chunk(&mut self, epoch: Epoch) -> Result<&mut Vec<u16>, Error> {
+        let chunk_index = epoch.as_u64() / self.chunk_size;
+
+        if !self.chunks.contains_key(&chunk_index) {
+            let chunk = self
+                .db
+                .get_chunk(self.column, self.validator_index, chunk_index)?
+                .unwrap_or_else(|| vec![self.empty_distance; self.chunk_size as usize]);
+            self.chunks.insert(chunk_index, chunk);
+        }
+
+        Ok(self
+            .chunks
+            .get_mut(&chunk_index)
+            .expect("chunk was inserted above"))
+    }
+
+    /// Return the distance stored at `epoch`.
+    fn get(&mut self, epoch: Epoch) -> Result<u16, Error> {
+        let offset = (epoch.as_u64() % self.chunk_size) as usize;
+        Ok(self.chunk(epoch)?[offset])
+    }
+
+    /// Set the distance stored at `epoch`.
+    fn set(&mut self, epoch: Epoch, distance: u16) -> Result<(), Error> {
+        let offset = (epoch.as_u64() % self.chunk_size) as usize;
+        self.chunk(epoch)?[offset] = distance;
+        self.dirty.insert(epoch.as_u64() / self.chunk_size);
+        Ok(())
+    }
+
+    /// Write all modified chunks back to disk.
+    fn flush(&self) -> Result<(), Error> {
+        for chunk_index in &self.dirty {
+            if let Some(chunk) = self.chunks.get(chunk_index) {
+                self.db
+                    .put_chunk(self.column, self.validator_index, *chunk_index, chunk)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The min and max span arrays for a single validator.
+///
+/// Chunks are cached in memory until `Self::flush` is called, so that many attestations from the
+/// same validator may be processed without repeatedly reading from disk.
+pub struct ValidatorSpans<'a, E: EthSpec> {
+    min_targets: SpanArray<'a, E>,
+    max_targets: SpanArray<'a, E>,
+}
+
+impl<'a, E: EthSpec> ValidatorSpans<'a, E> {
+    pub fn new(db: &'a SlasherDB<E>, validator_index: u64, chunk_size: u64) -> Self {
+        Self {
+            min_targets: SpanArray::new(
+                db,
+                DBColumn::SlasherMinTargets,
+                MIN_TARGET_EMPTY,
+                validator_index,
+                chunk_size,
+            ),
+            max_targets: SpanArray::new(
+                db,
+                DBColumn::SlasherMaxTargets,
+                MAX_TARGET_EMPTY,
+                validator_index,
+                chunk_size,
+            ),
+        }
+    }
+
+    /// Check an attestation with `source` and `target` epochs for surround votes, then record it
+    /// in the span arrays.
+    ///
+    /// Epochs prior to `lowest_epoch` have been pruned and are neither checked nor updated. The
+    /// caller must ensure that `lowest_epoch <= source <= target` and that `target - lowest_epoch`
+    /// fits within a `u16`.
+    pub fn check_and_update(
+        &mut self,
+        source: Epoch,
+        target: Epoch,
+        lowest_epoch: Epoch,
+    ) -> Result<Vec<SpanConflict>, Error> {
+        let (source, target, lowest) = (source.as_u64(), target.as_u64(), lowest_epoch.as_u64());
+        let mut conflicts = vec![];
+
+        let min_distance = self.min_targets.get(Epoch::new(source))?;
+        if min_distance != MIN_TARGET_EMPTY && source + u64::from(min_distance) < target {
+            conflicts.push(SpanConflict::Surrounds {
+                target_epoch: Epoch::new(source + u64::from(min_distance)),
+            });
+        }
+
+        let max_distance = self.max_targets.get(Epoch::new(source))?;
+        if source + u64::from(max_distance) > target {
+            conflicts.push(SpanConflict::SurroundedBy {
+                target_epoch: Epoch::new(source + u64::from(max_distance)),
+            });
+        }
+
+        // Every epoch prior to `source` now has a minimum target no greater than `target`. The
+        // minimum targets are non-increasing as the epoch decreases, so we can stop at the first
+        // epoch that already has a lower target.
+        for epoch in (lowest..source).rev() {
+            let existing = self.min_targets.get(Epoch::new(epoch))?;
+            if existing != MIN_TARGET_EMPTY && epoch + u64::from(existing) <= target {
+                break;
+            }
+            self.min_targets
+                .set(Epoch::new(epoch), (target - epoch) as u16)?;
+        }
+
+        // Every epoch after `source` (and before `target`) now has a maximum target no less than
+        // `target`. The maximum targets are non-decreasing as the epoch increases, so we can stop
+        // at the first epoch that already has a greater target.
+        for epoch in source + 1..target {
+            let existing = self.max_targets.get(Epoch::new(epoch))?;
+            if epoch + u64::from(existing) >= target {
+                break;
+            }
+            self.max_targets
+                .set(Epoch::new(epoch), (target - epoch) as u16)?;
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Write all modified chunks back to disk.
+    pub fn flush(&self) -> Result<(), Error> {
+        self.min_targets.flush()?;
+        self.max_targets.flush()
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The number of epochs stored in each chunk of the min-max span arrays.
+pub const DEFAULT_CHUNK_SIZE: u64 = 16;
+/// The number of epochs of history kept by the slasher, approximately 18 days on mainnet.
+pub const DEFAULT_HISTORY_LENGTH: u64 = 4096;
+/// The number of seconds between each batch of queued attestations and blocks being processed.
+pub const DEFAULT_UPDATE_PERIOD: u64 = 12;
+/// The maximum number of attestations (and separately, block headers) held in the queue between
+/// updates. Messages received while the queue is full are dropped.
+pub const DEFAULT_MAX_QUEUE_LENGTH: usize = 262_144;
+
+/// Configuration for the `Slasher`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Directory for the slasher's LevelDB database.
+    pub database_path: PathBuf,
+    /// Number of epochs per chunk of the min-max span arrays.
+    pub chunk_size: u64,
+    /// Number of epochs of attestations and blocks to retain. Slashable messages older than this
+    /// will not be detected.
+    pub history_length: u64,
+    /// Seconds between processing each batch of queued messages.
+    pub update_period: u64,
+    /// Maximum number of messages of each kind to queue between updates.
+    pub max_queue_length: usize,
+}
+
+impl Config {
+    pub fn new(database_path: PathBuf) -> Self {
+        Self {
+            database_path,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            history_length: DEFAULT_HISTORY_LENGTH,
+            update_period: DEFAULT_UPDATE_PERIOD,
+            max_queue_length: DEFAULT_MAX_QUEUE_LENGTH,
+        }
+    }
+
+    /// Check that the config values are usable.
+    ///
+    /// Target distances are stored as `u16`, with `u16::max_value()` reserved to mean "no
+    /// attestation", so the history length must be strictly less than that value.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.chunk_size == 0
+            || self.history_length == 0
+            || self.update_period == 0
+            || self.max_queue_length == 0
+        {
+            Err(Error::ConfigInvalidZeroParameter {
+                config: self.clone(),
+            })
+        } else if self.history_length >= u64::from(u16::max_value()) {
+            Err(Error::ConfigInvalidHistoryLength {
+                history_length: self.history_length,
+                max_history_length: u64::from(u16::max_value()) - 1,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{Config, Error};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::fs;
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, SimpleDiskStore, SimpleStoreItem, Store};
+use types::{Epoch, EthSpec, Hash256, IndexedAttestation, SignedBeaconBlockHeader, Slot};
+
+/// 32-byte key for accessing the `SlasherMetadata`.
+pub const METADATA_DB_KEY: &str = "SLASHERMETADATASLASHERMETADATASL";
+
+/// Information about the range of data held by the slasher's database.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct SlasherMetadata {
+    /// One greater than the highest validator index seen by the slasher.
+    pub validator_count: u64,
+    /// The lowest epoch for which data is retained. All data prior to this epoch has been pruned.
+    pub lowest_epoch: Epoch,
+}
+
+impl SimpleStoreItem for SlasherMetadata {
+    fn db_column() -> DBColumn {
+        DBColumn::SlasherMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+/// Build a key from a validator index followed by an epoch, slot or chunk index.
+///
+/// Big-endian encoding keeps the keys for each validator adjacent and in order on disk.
+fn validator_key(validator_index: u64, index: u64) -> Vec<u8> {
+    let mut key = validator_index.to_be_bytes().to_vec();
+    key.extend_from_slice(&index.to_be_bytes());
+    key
+}
+
+/// Build a key for an indexed attestation, prefixed by its target epoch.
+fn indexed_attestation_key(target_epoch: Epoch, root: Hash256) -> Vec<u8> {
+    let mut key = target_epoch.as_u64().to_be_bytes().to_vec();
+    key.extend_from_slice(root.as_bytes());
+    key
+}
+
+/// The slasher's on-disk storage.
+///
+/// Attestations are recorded per validator and target epoch, pointing at a single copy of the
+/// `IndexedAttestation` that they were seen in. Block headers are recorded per slot.
+pub struct SlasherDB<E: EthSpec> {
+    db: SimpleDiskStore<E>,
+}
+
+impl<E: EthSpec> SlasherDB<E> {
+    /// Open the database at `config.database_path`, creating it if it does not already exist.
+    pub fn open(config: Arc<Config>) -> Result<Self, Error> {
+        fs::create_dir_all(&config.database_path)?;
+        let db = SimpleDiskStore::open(&config.database_path)?;
+        Ok(Self { db })
+    }
+
+    pub fn load_metadata(&self) -> Result<Option<SlasherMetadata>, Error> {
+        let key = Hash256::from_slice(METADATA_DB_KEY.as_bytes());
+        Ok(self.db.get(&key)?)
+    }
+
+    pub fn store_metadata(&self, metadata: &SlasherMetadata) -> Result<(), Error> {
+        let key = Hash256::from_slice(METADATA_DB_KEY.as_bytes());
+        Ok(self.db.put(&key, metadata)?)
+    }
+
+    /// Load a chunk of one of the min-max span arrays.
+    pub fn get_chunk(
+        &self,
+        column: DBColumn,
+        validator_index: u64,
+        chunk_index: u64,
+    ) -> Result<Option<Vec<u16>>, Error> {
+        self.db
+            .get_bytes(column.into(), &validator_key(validator_index, chunk_index))?
+            .map(|bytes| Vec::from_ssz_bytes(&bytes))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    pub fn put_chunk(
+        &self,
+        column: DBColumn,
+        validator_index: u64,
+        chunk_index: u64,
+        chunk: &[u16],
+    ) -> Result<(), Error> {
+        Ok(self.db.put_bytes(
+            column.into(),
+            &validator_key(validator_index, chunk_index),
+            &chunk.to_vec().as_ssz_bytes(),
+        )?)
+    }
+
+    /// Return the root of the indexed attestation that `validator_index` attested in for
+    /// `target_epoch`, if any.
+    pub fn get_attester_record(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+    ) -> Result<Option<Hash256>, Error> {
+        Ok(self
+            .db
+            .get_bytes(
+                DBColumn::SlasherAttesterRecords.into(),
+                &validator_key(validator_index, target_epoch.as_u64()),
+            )?
+            .map(|bytes| Hash256::from_slice(&bytes)))
+    }
+
+    pub fn put_attester_record(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+        indexed_attestation_root: Hash256,
+    ) -> Result<(), Error> {
+        Ok(self.db.put_bytes(
+            DBColumn::SlasherAttesterRecords.into(),
+            &validator_key(validator_index, target_epoch.as_u64()),
+            indexed_attestation_root.as_bytes(),
+        )?)
+    }
+
+    pub fn get_indexed_attestation(
+        &self,
+        target_epoch: Epoch,
+        root: Hash256,
+    ) -> Result<IndexedAttestation<E>, Error> {
+        let bytes = self
+            .db
+            .get_bytes(
+                DBColumn::SlasherIndexedAttestations.into(),
+                &indexed_attestation_key(target_epoch, root),
+            )?
+            .ok_or_else(|| Error::MissingIndexedAttestation { root })?;
+        Ok(IndexedAttestation::from_ssz_bytes(&bytes)?)
+    }
+
+    /// Store `indexed_attestation` under `root`, recording the root against its target epoch so
+    /// that it may be pruned later.
+    pub fn put_indexed_attestation(
+        &self,
+        root: Hash256,
+        indexed_attestation: &IndexedAttestation<E>,
+    ) -> Result<(), Error> {
+        let target_epoch = indexed_attestation.data.target.epoch;
+        let mut roots = self.get_attestation_roots(target_epoch)?;
+
+        if roots.contains(&root) {
+            return Ok(());
+        }
+
+        self.db.put_bytes(
+            DBColumn::SlasherIndexedAttestations.into(),
+            &indexed_attestation_key(target_epoch, root),
+            &indexed_attestation.as_ssz_bytes(),
+        )?;
+
+        roots.push(root);
+        self.db.put_bytes(
+            DBColumn::SlasherAttestationRoots.into(),
+            &target_epoch.as_u64().to_be_bytes(),
+            &roots.as_ssz_bytes(),
+        )?;
+
+        Ok(())
+    }
+
+    fn get_attestation_roots(&self, target_epoch: Epoch) -> Result<Vec<Hash256>, Error> {
+        Ok(self
+            .db
+            .get_bytes(
+                DBColumn::SlasherAttestationRoots.into(),
+                &target_epoch.as_u64().to_be_bytes(),
+            )?
+            .map(|bytes| Vec::from_ssz_bytes(&bytes))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Return all of the block headers seen at `slot`.
+    pub fn get_proposals(&self, slot: Slot) -> Result<Vec<SignedBeaconBlockHeader>, Error> {
+        Ok(self
+            .db
+            .get_bytes(
+                DBColumn::SlasherProposals.into(),
+                &slot.as_u64().to_be_bytes(),
+            )?
+            .map(|bytes| Vec::from_ssz_bytes(&bytes))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    pub fn put_proposals(
+        &self,
+        slot: Slot,
+        headers: &[SignedBeaconBlockHeader],
+    ) -> Result<(), Error> {
+        Ok(self.db.put_bytes(
+            DBColumn::SlasherProposals.into(),
+            &slot.as_u64().to_be_bytes(),
+            &headers.to_vec().as_ssz_bytes(),
+        )?)
+    }
+
+    /// Delete all data for the epochs in `start_epoch..end_epoch`.
+    ///
+    /// Span chunks are only deleted once every epoch they cover is below `end_epoch`.
+    pub fn prune(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        validator_count: u64,
+        chunk_size: u64,
+    ) -> Result<(), Error> {
+        for epoch in (start_epoch.as_u64()..end_epoch.as_u64()).map(Epoch::new) {
+            for validator_index in 0..validator_count {
+                self.db.key_delete(
+                    DBColumn::SlasherAttesterRecords.into(),
+                    &validator_key(validator_index, epoch.as_u64()),
+                )?;
+            }
+
+            for root in self.get_attestation_roots(epoch)? {
+                self.db.key_delete(
+                    DBColumn::SlasherIndexedAttestations.into(),
+                    &indexed_attestation_key(epoch, root),
+                )?;
+            }
+            self.db.key_delete(
+                DBColumn::SlasherAttestationRoots.into(),
+                &epoch.as_u64().to_be_bytes(),
+            )?;
+
+            for slot in epoch.slot_iter(E::slots_per_epoch()) {
+                self.db.key_delete(
+                    DBColumn::SlasherProposals.into(),
+                    &slot.as_u64().to_be_bytes(),
+                )?;
+            }
+        }
+
+        let start_chunk = start_epoch.as_u64() / chunk_size;
+        let end_chunk = end_epoch.as_u64() / chunk_size;
+        for chunk_index in start_chunk..end_chunk {
+            for validator_index in 0..validator_count {
+                for column in &[DBColumn::SlasherMinTargets, DBColumn::SlasherMaxTargets] {
+                    self.db.key_delete(
+                        (*column).into(),
+                        &validator_key(validator_index, chunk_index),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::Config;
+use std::io;
+use types::{Epoch, Hash256};
+
+#[derive(Debug)]
+pub enum Error {
+    DatabaseError(store::Error),
+    DatabaseIOError(io::Error),
+    SszDecodeError(ssz::DecodeError),
+    ConfigInvalidZeroParameter {
+        config: Config,
+    },
+    ConfigInvalidHistoryLength {
+        history_length: u64,
+        max_history_length: u64,
+    },
+    /// The span arrays referred to an attestation which has no attester record.
+    MissingAttesterRecord {
+        validator_index: u64,
+        target_epoch: Epoch,
+    },
+    /// An attester record referred to an indexed attestation which is not in the database.
+    MissingIndexedAttestation {
+        root: Hash256,
+    },
+}
+
+impl From<store::Error> for Error {
+    fn from(e: store::Error) -> Self {
+        Error::DatabaseError(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::DatabaseIOError(e)
+    }
+}
+
+impl From<ssz::DecodeError> for Error {
+    fn from(e: ssz::DecodeError) -> Self {
+        Error::SszDecodeError(e)
+    }
+}
@@ -0,0 +1,526 @@
+//! Detects slashable behaviour by other validators, producing `AttesterSlashing` and
+//! `ProposerSlashing` objects which may be included in blocks.
+//!
+//! Attestations and block headers are queued as they are verified on gossip and processed in
+//! batches by `Slasher::process_queued`, which is expected to be called periodically.
+//!
+//! - Double votes are detected by recording the attestation for each validator and target epoch.
+//! - Surround votes are detected using min-max span arrays (see the `array` module).
+//! - Double proposals are detected by recording the block headers seen at each slot.
+mod array;
+mod config;
+mod database;
+mod error;
+
+pub use crate::config::Config;
+pub use crate::database::SlasherDB;
+pub use crate::error::Error;
+
+use crate::array::{SpanConflict, ValidatorSpans};
+use crate::database::SlasherMetadata;
+use parking_lot::Mutex;
+use slog::{debug, info, warn, Logger};
+use std::collections::BTreeMap;
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::{
+    AttesterSlashing, Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing,
+    SignedBeaconBlockHeader,
+};
+
+/// Detects slashable attestations and block proposals.
+pub struct Slasher<E: EthSpec> {
+    db: SlasherDB<E>,
+    config: Arc<Config>,
+    attestation_queue: Mutex<Vec<IndexedAttestation<E>>>,
+    block_queue: Mutex<Vec<SignedBeaconBlockHeader>>,
+    attester_slashings: Mutex<Vec<AttesterSlashing<E>>>,
+    proposer_slashings: Mutex<Vec<ProposerSlashing>>,
+    /// Number of messages dropped due to a full queue since the last call to `process_queued`.
+    num_dropped: AtomicUsize,
+    log: Logger,
+}
+
+impl<E: EthSpec> Slasher<E> {
+    /// Open the slasher's database, creating it if it does not already exist.
+    pub fn open(config: Config, log: Logger) -> Result<Self, Error> {
+        config.validate()?;
+        let config = Arc::new(config);
+        let db = SlasherDB::open(config.clone())?;
+
+        Ok(Self {
+            db,
+            config,
+            attestation_queue: Mutex::new(vec![]),
+            block_queue: Mutex::new(vec![]),
+            attester_slashings: Mutex::new(vec![]),
+            proposer_slashings: Mutex::new(vec![]),
+            num_dropped: AtomicUsize::new(0),
+            log,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Queue an attestation which has been verified (including its signature) for processing.
+    ///
+    /// The attestation is dropped if the queue already holds `max_queue_length` attestations.
+    pub fn accept_attestation(&self, attestation: IndexedAttestation<E>) {
+        let mut queue = self.attestation_queue.lock();
+        if queue.len() < self.config.max_queue_length {
+            queue.push(attestation);
+        } else {
+            self.num_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Queue a block header which has been verified (including its signature) for processing.
+    ///
+    /// The header is dropped if the queue already holds `max_queue_length` headers.
+    pub fn accept_block_header(&self, header: SignedBeaconBlockHeader) {
+        let mut queue = self.block_queue.lock();
+        if queue.len() < self.config.max_queue_length {
+            queue.push(header);
+        } else {
+            self.num_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Take all of the attester slashings found since the last call to this function.
+    pub fn get_attester_slashings(&self) -> Vec<AttesterSlashing<E>> {
+        mem::replace(&mut *self.attester_slashings.lock(), vec![])
+    }
+
+    /// Take all of the proposer slashings found since the last call to this function.
+    pub fn get_proposer_slashings(&self) -> Vec<ProposerSlashing> {
+        mem::replace(&mut *self.proposer_slashings.lock(), vec![])
+    }
+
+    /// Process all queued attestations and block headers, then prune data which has fallen out of
+    /// the history window ending at `current_epoch`.
+    ///
+    /// This function is not safe to call concurrently with itself.
+    pub fn process_queued(&self, current_epoch: Epoch) -> Result<(), Error> {
+        let attestations = mem::replace(&mut *self.attestation_queue.lock(), vec![]);
+        let blocks = mem::replace(&mut *self.block_queue.lock(), vec![]);
+        let num_attestations = attestations.len();
+        let num_blocks = blocks.len();
+
+        let num_dropped = self.num_dropped.swap(0, Ordering::Relaxed);
+        if num_dropped > 0 {
+            warn!(
+                self.log,
+                "Slasher queue full, messages dropped";
+                "dropped" => num_dropped,
+                "max_queue_length" => self.config.max_queue_length,
+                "msg" => "consider decreasing the update period",
+            );
+        }
+
+        let lowest_epoch = (current_epoch + 1).saturating_sub(self.config.history_length);
+        let mut metadata = self.db.load_metadata()?.unwrap_or_else(|| SlasherMetadata {
+            validator_count: 0,
+            lowest_epoch,
+        });
+
+        let proposer_slashings = self.process_blocks(blocks, lowest_epoch)?;
+        let attester_slashings =
+            self.process_attestations(attestations, current_epoch, lowest_epoch, &mut metadata)?;
+
+        if metadata.lowest_epoch < lowest_epoch {
+            // Data can only have been stored for one history length prior to the new window.
+            let prune_from = std::cmp::max(
+                metadata.lowest_epoch,
+                lowest_epoch.saturating_sub(self.config.history_length),
+            );
+            self.db.prune(
+                prune_from,
+                lowest_epoch,
+                metadata.validator_count,
+                self.config.chunk_size,
+            )?;
+            metadata.lowest_epoch = lowest_epoch;
+        }
+
+        self.db.store_metadata(&metadata)?;
+
+        debug!(
+            self.log,
+            "Processed slasher queue";
+            "attestations" => num_attestations,
+            "blocks" => num_blocks,
+            "current_epoch" => current_epoch,
+        );
+
+        if attester_slashings > 0 || proposer_slashings > 0 {
+            info!(
+                self.log,
+                "Found slashable offences";
+                "attester_slashings" => attester_slashings,
+                "proposer_slashings" => proposer_slashings,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Check each block header against those previously seen at the same slot, returning the
+    /// number of new proposer slashings found.
+    fn process_blocks(
+        &self,
+        headers: Vec<SignedBeaconBlockHeader>,
+        lowest_epoch: Epoch,
+    ) -> Result<usize, Error> {
+        let mut slashings = vec![];
+
+        for header in headers {
+            let slot = header.message.slot;
+            if slot.epoch(E::slots_per_epoch()) < lowest_epoch {
+                continue;
+            }
+
+            let mut proposals = self.db.get_proposals(slot)?;
+            let existing = proposals
+                .iter()
+                .find(|existing| existing.message.proposer_index == header.message.proposer_index);
+
+            match existing {
+                Some(existing) if existing.message != header.message => {
+                    slashings.push(ProposerSlashing {
+                        signed_header_1: existing.clone(),
+                        signed_header_2: header,
+                    });
+                }
+                Some(_) => (),
+                None => {
+                    proposals.push(header);
+                    self.db.put_proposals(slot, &proposals)?;
+                }
+            }
+        }
+
+        let mut proposer_slashings = self.proposer_slashings.lock();
+        let num_new = slashings.len();
+        proposer_slashings.extend(slashings);
+        Ok(num_new)
+    }
+
+    /// Check each attestation for double and surround votes by each of its attesters, returning
+    /// the number of new attester slashings found.
+    fn process_attestations(
+        &self,
+        attestations: Vec<IndexedAttestation<E>>,
+        current_epoch: Epoch,
+        lowest_epoch: Epoch,
+        metadata: &mut SlasherMetadata,
+    ) -> Result<usize, Error> {
+        // Group the attestations by validator so that each validator's span arrays only need to
+        // be loaded once.
+        let mut by_validator = BTreeMap::<u64, Vec<(Hash256, Arc<IndexedAttestation<E>>)>>::new();
+
+        for attestation in attestations {
+            let source = attestation.data.source.epoch;
+            let target = attestation.data.target.epoch;
+
+            if source > target || source < lowest_epoch || target > current_epoch + 1 {
+                debug!(
+                    self.log,
+                    "Slasher ignoring attestation";
+                    "source" => source,
+                    "target" => target,
+                    "lowest_epoch" => lowest_epoch,
+                );
+                continue;
+            }
+
+            let root = attestation.tree_hash_root();
+            self.db.put_indexed_attestation(root, &attestation)?;
+
+            let attestation = Arc::new(attestation);
+            for &validator_index in attestation.attesting_indices.iter() {
+                by_validator
+                    .entry(validator_index)
+                    .or_insert_with(Vec::new)
+                    .push((root, attestation.clone()));
+            }
+        }
+
+        let mut slashings = vec![];
+
+        for (validator_index, attestations) in by_validator {
+            metadata.validator_count = std::cmp::max(metadata.validator_count, validator_index + 1);
+
+            let mut spans = ValidatorSpans::new(&self.db, validator_index, self.config.chunk_size);
+
+            for (root, attestation) in attestations {
+                let source = attestation.data.source.epoch;
+                let target = attestation.data.target.epoch;
+
+                if let Some(existing_root) = self.db.get_attester_record(validator_index, target)? {
+                    if existing_root != root {
+                        let existing = self.db.get_indexed_attestation(target, existing_root)?;
+                        if existing.data != attestation.data {
+                            slashings.push(AttesterSlashing {
+                                attestation_1: existing,
+                                attestation_2: (*attestation).clone(),
+                            });
+                        }
+                    }
+                    continue;
+                }
+
+                self.db.put_attester_record(validator_index, target, root)?;
+
+                for conflict in spans.check_and_update(source, target, lowest_epoch)? {
+                    let slashing = match conflict {
+                        SpanConflict::Surrounds { target_epoch } => AttesterSlashing {
+                            attestation_1: (*attestation).clone(),
+                            attestation_2: self.get_attestation(validator_index, target_epoch)?,
+                        },
+                        SpanConflict::SurroundedBy { target_epoch } => AttesterSlashing {
+                            attestation_1: self.get_attestation(validator_index, target_epoch)?,
+                            attestation_2: (*attestation).clone(),
+                        },
+                    };
+                    slashings.push(slashing);
+                }
+            }
+
+            spans.flush()?;
+        }
+
+        // Many validators may be slashed by the same pair of attestations.
+        let mut attester_slashings = self.attester_slashings.lock();
+        let mut num_new = 0;
+        for slashing in slashings {
+            if !attester_slashings.contains(&slashing) {
+                attester_slashings.push(slashing);
+                num_new += 1;
+            }
+        }
+        Ok(num_new)
+    }
+
+    /// Load the attestation made by `validator_index` for `target_epoch`.
+    fn get_attestation(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+    ) -> Result<IndexedAttestation<E>, Error> {
+        let root = self
+            .db
+            .get_attester_record(validator_index, target_epoch)?
+            .ok_or_else(|| Error::MissingAttesterRecord {
+                validator_index,
+                target_epoch,
+            })?;
+        self.db.get_indexed_attestation(target_epoch, root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use types::{
+        AggregateSignature, AttestationData, BeaconBlockHeader, Checkpoint, MainnetEthSpec,
+        Signature, Slot, VariableList,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn slasher(dir: &tempfile::TempDir) -> Slasher<E> {
+        let config = Config::new(dir.path().join("slasher_db"));
+        let log = Logger::root(slog::Discard, slog::o!());
+        Slasher::open(config, log).expect("should open slasher")
+    }
+
+    fn attestation(
+        indices: &[u64],
+        source: u64,
+        target: u64,
+        target_root: u64,
+    ) -> IndexedAttestation<E> {
+        IndexedAttestation {
+            attesting_indices: VariableList::new(indices.to_vec()).unwrap(),
+            data: AttestationData {
+                slot: Epoch::new(target).start_slot(E::slots_per_epoch()),
+                index: 0,
+                beacon_block_root: Hash256::zero(),
+                source: Checkpoint {
+                    epoch: Epoch::new(source),
+                    root: Hash256::zero(),
+                },
+                target: Checkpoint {
+                    epoch: Epoch::new(target),
+                    root: Hash256::from_low_u64_be(target_root),
+                },
+            },
+            signature: AggregateSignature::new(),
+        }
+    }
+
+    fn block_header(proposer_index: u64, slot: u64, state_root: u64) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: Slot::new(slot),
+                proposer_index,
+                parent_root: Hash256::zero(),
+                state_root: Hash256::from_low_u64_be(state_root),
+                body_root: Hash256::zero(),
+            },
+            signature: Signature::empty_signature(),
+        }
+    }
+
+    /// Process each batch of attestations in turn, returning the slashings found.
+    fn process(
+        slasher: &Slasher<E>,
+        batches: Vec<Vec<IndexedAttestation<E>>>,
+        current_epoch: u64,
+    ) -> Vec<AttesterSlashing<E>> {
+        for batch in batches {
+            for attestation in batch {
+                slasher.accept_attestation(attestation);
+            }
+            slasher.process_queued(Epoch::new(current_epoch)).unwrap();
+        }
+        slasher.get_attester_slashings()
+    }
+
+    #[test]
+    fn no_slashing_for_valid_attestations() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let attestations = (1..10)
+            .map(|epoch| vec![attestation(&[0, 1], epoch - 1, epoch, epoch)])
+            .collect();
+        assert!(process(&slasher, attestations, 10).is_empty());
+    }
+
+    #[test]
+    fn duplicate_attestation_not_slashable() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let att = attestation(&[0], 0, 1, 1);
+        let aggregate = attestation(&[0, 1, 2], 0, 1, 1);
+        assert!(process(&slasher, vec![vec![att.clone()], vec![att, aggregate]], 1).is_empty());
+    }
+
+    #[test]
+    fn double_vote() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let att1 = attestation(&[0, 1], 0, 1, 1);
+        let att2 = attestation(&[1, 2], 0, 1, 2);
+        let slashings = process(&slasher, vec![vec![att1.clone()], vec![att2.clone()]], 1);
+        assert_eq!(
+            slashings,
+            vec![AttesterSlashing {
+                attestation_1: att1,
+                attestation_2: att2,
+            }]
+        );
+    }
+
+    #[test]
+    fn surrounding_vote() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let inner = attestation(&[3], 2, 3, 3);
+        let outer = attestation(&[3], 1, 4, 4);
+        let slashings = process(&slasher, vec![vec![inner.clone()], vec![outer.clone()]], 4);
+        assert_eq!(slashings.len(), 1);
+        assert_eq!(slashings[0].attestation_1, outer);
+        assert_eq!(slashings[0].attestation_2, inner);
+        assert!(slashings[0]
+            .attestation_1
+            .is_surround_vote(&slashings[0].attestation_2));
+    }
+
+    #[test]
+    fn surrounded_vote() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let outer = attestation(&[7], 1, 6, 6);
+        let inner = attestation(&[7], 3, 4, 4);
+        let slashings = process(&slasher, vec![vec![outer.clone()], vec![inner.clone()]], 6);
+        assert_eq!(slashings.len(), 1);
+        assert_eq!(slashings[0].attestation_1, outer);
+        assert_eq!(slashings[0].attestation_2, inner);
+    }
+
+    #[test]
+    fn surround_within_single_batch() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let inner = attestation(&[0], 2, 3, 3);
+        let outer = attestation(&[0], 1, 4, 4);
+        let slashings = process(&slasher, vec![vec![inner, outer]], 4);
+        assert_eq!(slashings.len(), 1);
+    }
+
+    #[test]
+    fn double_proposal() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let header1 = block_header(5, 10, 1);
+        let header2 = block_header(5, 10, 2);
+
+        slasher.accept_block_header(header1.clone());
+        slasher.accept_block_header(header1.clone());
+        slasher.accept_block_header(block_header(6, 10, 2));
+        slasher.process_queued(Epoch::new(0)).unwrap();
+        assert!(slasher.get_proposer_slashings().is_empty());
+
+        slasher.accept_block_header(header2.clone());
+        slasher.process_queued(Epoch::new(0)).unwrap();
+        assert_eq!(
+            slasher.get_proposer_slashings(),
+            vec![ProposerSlashing {
+                signed_header_1: header1,
+                signed_header_2: header2,
+            }]
+        );
+    }
+
+    #[test]
+    fn attestations_outside_history_ignored() {
+        let dir = tempdir().unwrap();
+        let slasher = slasher(&dir);
+        let history_length = slasher.config().history_length;
+
+        let inner = attestation(&[0], 2, 3, 3);
+        assert!(process(&slasher, vec![vec![inner]], 3).is_empty());
+
+        // Advance the window past the first attestation, then attempt a double vote which is
+        // too old to be processed.
+        let current_epoch = history_length + 3;
+        let double = attestation(&[0], 2, 3, 4);
+        assert!(process(&slasher, vec![vec![double]], current_epoch).is_empty());
+    }
+
+    #[test]
+    fn full_queue_drops_messages() {
+        let dir = tempdir().unwrap();
+        let mut config = Config::new(dir.path().join("slasher_db"));
+        config.max_queue_length = 1;
+        let log = Logger::root(slog::Discard, slog::o!());
+        let slasher = Slasher::<E>::open(config, log).unwrap();
+
+        // The conflicting attestation is dropped, so no slashing is found.
+        slasher.accept_attestation(attestation(&[0], 0, 1, 1));
+        slasher.accept_attestation(attestation(&[0], 0, 1, 2));
+        slasher.process_queued(Epoch::new(1)).unwrap();
+        assert!(slasher.get_attester_slashings().is_empty());
+
+        // Once the queue has been processed there is room for it again.
+        slasher.accept_attestation(attestation(&[0], 0, 1, 2));
+        slasher.process_queued(Epoch::new(1)).unwrap();
+        assert_eq!(slasher.get_attester_slashings().len(), 1);
+    }
+}
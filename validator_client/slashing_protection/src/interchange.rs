@@ -0,0 +1,191 @@
+//! Types for the slashing protection interchange format defined in EIP-3076.
+//!
+//! Both the "complete" format (the full signing history of each validator) and the "minimal"
+//! format (only the most recent block and attestation of each validator) are supported.
+//!
+//! https://eips.ethereum.org/EIPS/eip-3076
+
+use serde_derive::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use types::{Epoch, Hash256, PublicKey, Slot};
+
+/// The only version of the interchange format supported by this implementation.
+pub const SUPPORTED_INTERCHANGE_FORMAT_VERSION: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InterchangeFormat {
+    Complete,
+    Minimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterchangeMetadata {
+    pub interchange_format: InterchangeFormat,
+    #[serde(with = "quoted_u64")]
+    pub interchange_format_version: u64,
+    pub genesis_validators_root: Hash256,
+}
+
+/// The full signing history of a single validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompleteInterchangeData {
+    pub pubkey: PublicKey,
+    pub signed_blocks: Vec<InterchangeBlock>,
+    pub signed_attestations: Vec<InterchangeAttestation>,
+}
+
+/// The most recent block and attestation signed by a single validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MinimalInterchangeData {
+    pub pubkey: PublicKey,
+    #[serde(
+        default,
+        with = "quoted_u64_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_signed_block_slot: Option<Slot>,
+    #[serde(
+        default,
+        with = "quoted_u64_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_signed_attestation_source_epoch: Option<Epoch>,
+    #[serde(
+        default,
+        with = "quoted_u64_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_signed_attestation_target_epoch: Option<Epoch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterchangeBlock {
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+    /// The signing root is optional; without it, a block at the same slot cannot be recognised
+    /// as a re-broadcast and will be refused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<Hash256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterchangeAttestation {
+    #[serde(with = "quoted_u64")]
+    pub source_epoch: Epoch,
+    #[serde(with = "quoted_u64")]
+    pub target_epoch: Epoch,
+    /// The signing root is optional, see `InterchangeBlock::signing_root`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<Hash256>,
+}
+
+/// The data in an interchange file, which must agree with `InterchangeMetadata::interchange_format`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InterchangeData {
+    Complete(Vec<CompleteInterchangeData>),
+    Minimal(Vec<MinimalInterchangeData>),
+}
+
+impl InterchangeData {
+    /// The format that `self` is encoded in.
+    pub fn format(&self) -> InterchangeFormat {
+        match self {
+            InterchangeData::Complete(_) => InterchangeFormat::Complete,
+            InterchangeData::Minimal(_) => InterchangeFormat::Minimal,
+        }
+    }
+
+    /// The number of validators included in `self`.
+    pub fn len(&self) -> usize {
+        match self {
+            InterchangeData::Complete(data) => data.len(),
+            InterchangeData::Minimal(data) => data.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Interchange {
+    pub metadata: InterchangeMetadata,
+    pub data: InterchangeData,
+}
+
+impl Interchange {
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn from_json_reader(reader: impl Read) -> Result<Self, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    pub fn write_to(&self, writer: impl Write) -> Result<(), serde_json::Error> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+}
+
+/// Serialize slots and epochs as decimal strings, as required by the interchange format.
+mod quoted_u64 {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Copy + Into<u64>,
+        S: Serializer,
+    {
+        serializer.serialize_str(&(*value).into().to_string())
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<u64>,
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse::<u64>()
+            .map(T::from)
+            .map_err(|e| serde::de::Error::custom(format!("invalid integer {}: {:?}", s, e)))
+    }
+}
+
+/// As per `quoted_u64`, for optional values.
+mod quoted_u64_opt {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Copy + Into<u64>,
+        S: Serializer,
+    {
+        match value {
+            Some(value) => super::quoted_u64::serialize(value, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: From<u64>,
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| {
+                s.parse::<u64>().map(T::from).map_err(|e| {
+                    serde::de::Error::custom(format!("invalid integer {}: {:?}", s, e))
+                })
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,412 @@
+#![cfg(test)]
+
+use crate::attestation_tests::attestation_data_builder;
+use crate::block_tests::block;
+use crate::interchange::*;
+use crate::test_utils::*;
+use crate::*;
+use tempfile::{tempdir, TempDir};
+use types::{Epoch, Hash256, Slot};
+
+const GENESIS_VALIDATORS_ROOT: Hash256 = Hash256::zero();
+
+fn new_db() -> (TempDir, SlashingDatabase) {
+    let dir = tempdir().unwrap();
+    let slashing_db = SlashingDatabase::create(&dir.path().join("slashing_protection.sqlite"))
+        .expect("should create database");
+    (dir, slashing_db)
+}
+
+fn minimal_interchange(block_slot: u64, source_epoch: u64, target_epoch: u64) -> Interchange {
+    Interchange {
+        metadata: InterchangeMetadata {
+            interchange_format: InterchangeFormat::Minimal,
+            interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root: GENESIS_VALIDATORS_ROOT,
+        },
+        data: InterchangeData::Minimal(vec![MinimalInterchangeData {
+            pubkey: pubkey(0),
+            last_signed_block_slot: Some(Slot::new(block_slot)),
+            last_signed_attestation_source_epoch: Some(Epoch::new(source_epoch)),
+            last_signed_attestation_target_epoch: Some(Epoch::new(target_epoch)),
+        }]),
+    }
+}
+
+#[test]
+fn complete_round_trip() {
+    let (_dir, slashing_db) = new_db();
+    let pubkeys = vec![pubkey(0), pubkey(1)];
+    slashing_db.register_validators(pubkeys.iter()).unwrap();
+
+    for pk in &pubkeys {
+        for slot in &[1, 4, 9] {
+            slashing_db
+                .check_and_insert_block_proposal(pk, &block(*slot), DEFAULT_DOMAIN)
+                .unwrap();
+        }
+        for (source, target) in &[(0, 1), (1, 2), (2, 5)] {
+            slashing_db
+                .check_and_insert_attestation(
+                    pk,
+                    &attestation_data_builder(*source, *target),
+                    DEFAULT_DOMAIN,
+                )
+                .unwrap();
+        }
+    }
+
+    let exported = slashing_db
+        .export_interchange_info(GENESIS_VALIDATORS_ROOT, InterchangeFormat::Complete)
+        .unwrap();
+    assert_eq!(exported.data.len(), pubkeys.len());
+
+    // The interchange should survive serialization.
+    let mut json = vec![];
+    exported.write_to(&mut json).unwrap();
+    let parsed = Interchange::from_json_reader(json.as_slice()).unwrap();
+    assert_eq!(parsed, exported);
+
+    let (_dir2, new_slashing_db) = new_db();
+    new_slashing_db
+        .import_interchange_info(&parsed, GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    // The complete history of each validator is imported.
+    let reexported = new_slashing_db
+        .export_interchange_info(GENESIS_VALIDATORS_ROOT, InterchangeFormat::Complete)
+        .unwrap();
+    assert_eq!(reexported, exported);
+
+    // Imported history should prevent slashable messages.
+    assert_eq!(
+        new_slashing_db.check_and_insert_attestation(
+            &pubkeys[0],
+            &attestation_data_builder(3, 4),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::PrevSurroundsNew {
+                prev: SignedAttestation::from_attestation(
+                    &attestation_data_builder(2, 5),
+                    DEFAULT_DOMAIN
+                )
+            }
+        ))
+    );
+}
+
+#[test]
+fn export_single_validator() {
+    let (_dir, slashing_db) = new_db();
+    let pubkeys = vec![pubkey(0), pubkey(1)];
+    slashing_db.register_validators(pubkeys.iter()).unwrap();
+
+    for (i, pk) in pubkeys.iter().enumerate() {
+        slashing_db
+            .check_and_insert_block_proposal(pk, &block(i as u64 + 1), DEFAULT_DOMAIN)
+            .unwrap();
+    }
+
+    let exported = slashing_db
+        .export_validator_interchange_info(GENESIS_VALIDATORS_ROOT, &pubkeys[1])
+        .unwrap();
+
+    match exported.data {
+        InterchangeData::Complete(data) => {
+            assert_eq!(data.len(), 1);
+            assert_eq!(data[0].pubkey, pubkeys[1]);
+            assert_eq!(data[0].signed_blocks.len(), 1);
+            assert_eq!(data[0].signed_blocks[0].slot, Slot::new(2));
+        }
+        InterchangeData::Minimal(_) => panic!("should export in the complete format"),
+    }
+
+    let unknown = slashing_db
+        .export_validator_interchange_info(GENESIS_VALIDATORS_ROOT, &pubkey(2))
+        .unwrap();
+    assert!(unknown.data.is_empty());
+}
+
+#[test]
+fn minimal_import_enforces_lower_bounds() {
+    let (_dir, slashing_db) = new_db();
+    slashing_db
+        .import_interchange_info(&minimal_interchange(10, 4, 5), GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    let pk = pubkey(0);
+
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(9), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(
+            InvalidBlock::SlotViolatesLowerBound {
+                block_slot: Slot::new(9),
+                bound_slot: Slot::new(10),
+            }
+        ))
+    );
+    assert!(matches!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(10), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(_)))
+    ));
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(11), DEFAULT_DOMAIN),
+        Ok(Safe::Valid)
+    );
+
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(3, 6),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::NewSurroundsPrev {
+                prev: SignedAttestation {
+                    source_epoch: Epoch::new(4),
+                    target_epoch: Epoch::new(5),
+                    signing_root: Hash256::zero(),
+                }
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(4, 4),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::TargetLessThanOrEqLowerBound {
+                target_epoch: Epoch::new(4),
+                bound_epoch: Epoch::new(5),
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(5, 6),
+            DEFAULT_DOMAIN
+        ),
+        Ok(Safe::Valid)
+    );
+}
+
+#[test]
+fn import_over_older_history_enforces_lower_bounds() {
+    let (_dir, slashing_db) = new_db();
+    let pk = pubkey(0);
+    slashing_db.register_validator(&pk).unwrap();
+
+    // Local history which is older than the imported history.
+    slashing_db
+        .check_and_insert_block_proposal(&pk, &block(2), DEFAULT_DOMAIN)
+        .unwrap();
+    slashing_db
+        .check_and_insert_attestation(&pk, &attestation_data_builder(1, 2), DEFAULT_DOMAIN)
+        .unwrap();
+
+    slashing_db
+        .import_interchange_info(&minimal_interchange(10, 4, 5), GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    // The bounds are set by the imported history, not the older local history.
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(5), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(
+            InvalidBlock::SlotViolatesLowerBound {
+                block_slot: Slot::new(5),
+                bound_slot: Slot::new(10),
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(2, 3),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::SourceLessThanLowerBound {
+                source_epoch: Epoch::new(2),
+                bound_epoch: Epoch::new(4),
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(4, 4),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::TargetLessThanOrEqLowerBound {
+                target_epoch: Epoch::new(4),
+                bound_epoch: Epoch::new(5),
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(11), DEFAULT_DOMAIN),
+        Ok(Safe::Valid)
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(5, 6),
+            DEFAULT_DOMAIN
+        ),
+        Ok(Safe::Valid)
+    );
+}
+
+#[test]
+fn import_keeps_surrounded_local_history() {
+    let (_dir, slashing_db) = new_db();
+    let pk = pubkey(0);
+    slashing_db.register_validator(&pk).unwrap();
+
+    let local = attestation_data_builder(5, 6);
+    slashing_db
+        .check_and_insert_attestation(&pk, &local, DEFAULT_DOMAIN)
+        .unwrap();
+
+    let interchange = Interchange {
+        metadata: InterchangeMetadata {
+            interchange_format: InterchangeFormat::Complete,
+            interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root: GENESIS_VALIDATORS_ROOT,
+        },
+        data: InterchangeData::Complete(vec![CompleteInterchangeData {
+            pubkey: pk.clone(),
+            signed_blocks: vec![],
+            signed_attestations: vec![InterchangeAttestation {
+                source_epoch: Epoch::new(1),
+                target_epoch: Epoch::new(8),
+                signing_root: None,
+            }],
+        }]),
+    };
+    slashing_db
+        .import_interchange_info(&interchange, GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    // The new attestation is above the imported bounds, but surrounds the local attestation which
+    // must not have been discarded by the import.
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(3, 9),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::NewSurroundsPrev {
+                prev: SignedAttestation::from_attestation(&local, DEFAULT_DOMAIN)
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(5, 9),
+            DEFAULT_DOMAIN
+        ),
+        Ok(Safe::Valid)
+    );
+}
+
+#[test]
+fn import_existing_data_takes_precedence() {
+    let (_dir, slashing_db) = new_db();
+    let pk = pubkey(0);
+    slashing_db.register_validator(&pk).unwrap();
+
+    let existing = block(10);
+    slashing_db
+        .check_and_insert_block_proposal(&pk, &existing, DEFAULT_DOMAIN)
+        .unwrap();
+
+    slashing_db
+        .import_interchange_info(&minimal_interchange(10, 0, 1), GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    // The existing block must still be recognised as the same data.
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &existing, DEFAULT_DOMAIN),
+        Ok(Safe::SameData)
+    );
+}
+
+#[test]
+fn import_invalid_metadata() {
+    let (_dir, slashing_db) = new_db();
+
+    let mut interchange = minimal_interchange(1, 0, 1);
+    interchange.metadata.interchange_format_version = 3;
+    assert!(matches!(
+        slashing_db.import_interchange_info(&interchange, GENESIS_VALIDATORS_ROOT),
+        Err(InterchangeError::UnsupportedVersion(3))
+    ));
+
+    let interchange = minimal_interchange(1, 0, 1);
+    assert!(matches!(
+        slashing_db.import_interchange_info(&interchange, Hash256::repeat_byte(0x42)),
+        Err(InterchangeError::GenesisValidatorsRootMismatch { .. })
+    ));
+
+    let mut interchange = minimal_interchange(1, 0, 1);
+    interchange.metadata.interchange_format = InterchangeFormat::Complete;
+    assert!(matches!(
+        slashing_db.import_interchange_info(&interchange, GENESIS_VALIDATORS_ROOT),
+        Err(InterchangeError::FormatMismatch { .. })
+    ));
+
+    // Nothing should have been imported.
+    assert_eq!(
+        slashing_db
+            .export_interchange_info(GENESIS_VALIDATORS_ROOT, InterchangeFormat::Minimal)
+            .unwrap()
+            .data,
+        InterchangeData::Minimal(vec![])
+    );
+}
+
+#[test]
+fn parse_minimal_json() {
+    let json = format!(
+        r#"{{
+            "metadata": {{
+                "interchange_format": "minimal",
+                "interchange_format_version": "4",
+                "genesis_validators_root": "{:?}"
+            }},
+            "data": [
+                {{
+                    "pubkey": "{}",
+                    "last_signed_block_slot": "81952",
+                    "last_signed_attestation_source_epoch": "2290",
+                    "last_signed_attestation_target_epoch": "3007"
+                }}
+            ]
+        }}"#,
+        GENESIS_VALIDATORS_ROOT,
+        pubkey(0).as_hex_string()
+    );
+
+    let interchange = Interchange::from_json_str(&json).unwrap();
+    assert_eq!(
+        interchange.metadata.interchange_format,
+        InterchangeFormat::Minimal
+    );
+    assert_eq!(
+        interchange.data,
+        InterchangeData::Minimal(vec![MinimalInterchangeData {
+            pubkey: pubkey(0),
+            last_signed_block_slot: Some(Slot::new(81952)),
+            last_signed_attestation_source_epoch: Some(Epoch::new(2290)),
+            last_signed_attestation_target_epoch: Some(Epoch::new(3007)),
+        }])
+    );
+}
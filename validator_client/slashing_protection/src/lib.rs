@@ -1,5 +1,7 @@
 mod attestation_tests;
 mod block_tests;
+pub mod interchange;
+mod interchange_tests;
 mod parallel_tests;
 mod signed_attestation;
 mod signed_block;
@@ -8,7 +10,7 @@ mod test_utils;
 
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
-pub use crate::slashing_database::SlashingDatabase;
+pub use crate::slashing_database::{InterchangeError, SlashingDatabase};
 use rusqlite::Error as SQLError;
 use std::io::{Error as IOError, ErrorKind};
 use std::string::ToString;
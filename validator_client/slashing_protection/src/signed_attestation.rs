@@ -20,6 +20,19 @@ pub enum InvalidAttestation {
     PrevSurroundsNew { prev: SignedAttestation },
     /// The attestation is invalid because its source epoch is greater than its target epoch.
     SourceExceedsTarget,
+    /// The attestation's source epoch is less than the minimum source epoch in the database,
+    /// which may have been imported from another client without the full history of prior
+    /// attestations.
+    SourceLessThanLowerBound {
+        source_epoch: Epoch,
+        bound_epoch: Epoch,
+    },
+    /// The attestation's target epoch is less than or equal to the minimum target epoch in the
+    /// database, for the same reason as `SourceLessThanLowerBound`.
+    TargetLessThanOrEqLowerBound {
+        target_epoch: Epoch,
+        bound_epoch: Epoch,
+    },
 }
 
 impl SignedAttestation {
@@ -12,6 +12,12 @@ pub struct SignedBlock {
 #[derive(PartialEq, Debug)]
 pub enum InvalidBlock {
     DoubleBlockProposal(SignedBlock),
+    /// The block's slot is prior to the earliest block in the database, which may have been
+    /// imported from another client without the full history of prior blocks.
+    SlotViolatesLowerBound {
+        block_slot: Slot,
+        bound_slot: Slot,
+    },
 }
 
 impl SignedBlock {
@@ -1,3 +1,8 @@
+use crate::interchange::{
+    CompleteInterchangeData, Interchange, InterchangeAttestation, InterchangeBlock,
+    InterchangeData, InterchangeFormat, InterchangeMetadata, MinimalInterchangeData,
+    SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+};
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
 use crate::{NotSafe, Safe, SignedAttestation, SignedBlock};
@@ -6,7 +11,7 @@ use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
 use std::fs::{File, OpenOptions};
 use std::path::Path;
 use std::time::Duration;
-use types::{AttestationData, BeaconBlockHeader, Hash256, PublicKey, SignedRoot};
+use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKey, SignedRoot, Slot};
 
 type Pool = r2d2::Pool<SqliteConnectionManager>;
 
@@ -80,15 +85,41 @@ impl SlashingDatabase {
             params![],
         )?;
 
+        Self::create_lower_bounds_table(&conn)?;
+
         Ok(Self { conn_pool })
     }
 
     /// Open an existing `SlashingDatabase` from disk.
     pub fn open(path: &Path) -> Result<Self, NotSafe> {
         let conn_pool = Self::open_conn_pool(&path)?;
+
+        // Databases created before interchange imports were supported lack this table.
+        Self::create_lower_bounds_table(&conn_pool.get()?)?;
+
         Ok(Self { conn_pool })
     }
 
+    /// Create the table which holds the lower bounds set by imported slashing protection data, if
+    /// it does not already exist.
+    ///
+    /// Each bound is the maximum value seen in any import for the validator, so that messages
+    /// signed by another client but absent from an interchange file (e.g., one in the minimal
+    /// format) can never be surrounded or double-signed.
+    fn create_lower_bounds_table(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS lower_bounds (
+                validator_id INTEGER PRIMARY KEY,
+                block_slot INTEGER,
+                source_epoch INTEGER,
+                target_epoch INTEGER,
+                FOREIGN KEY(validator_id) REFERENCES validators(id)
+            )",
+            params![],
+        )?;
+        Ok(())
+    }
+
     /// Open a new connection pool with all of the necessary settings and tweaks.
     fn open_conn_pool(path: &Path) -> Result<Pool, NotSafe> {
         let manager = SqliteConnectionManager::file(path)
@@ -170,6 +201,41 @@ impl SlashingDatabase {
         .ok_or_else(|| NotSafe::UnregisteredValidator(public_key.clone()))
     }
 
+    /// Get the database-internal ID for a validator, registering it if it is not yet known.
+    fn get_or_register_validator_id(
+        txn: &Transaction,
+        public_key: &PublicKey,
+    ) -> Result<i64, NotSafe> {
+        match Self::get_validator_id(txn, public_key) {
+            Err(NotSafe::UnregisteredValidator(_)) => {
+                txn.execute(
+                    "INSERT INTO validators (public_key) VALUES (?1)",
+                    params![&public_key.as_hex_string()],
+                )?;
+                Ok(txn.last_insert_rowid())
+            }
+            other => other,
+        }
+    }
+
+    /// Get the lower bounds set by imports for a validator, as `(block_slot, source_epoch,
+    /// target_epoch)`. Each bound is `None` if no data of that kind has been imported.
+    fn get_lower_bounds(
+        txn: &Transaction,
+        validator_id: i64,
+    ) -> Result<(Option<Slot>, Option<Epoch>, Option<Epoch>), NotSafe> {
+        Ok(txn
+            .query_row(
+                "SELECT block_slot, source_epoch, target_epoch
+                 FROM lower_bounds
+                 WHERE validator_id = ?1",
+                params![validator_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?
+            .unwrap_or((None, None, None)))
+    }
+
     /// Check a block proposal from `validator_pubkey` for slash safety.
     fn check_block_proposal(
         &self,
@@ -203,7 +269,19 @@ impl SlashingDatabase {
                 )))
             }
         } else {
-            Ok(Safe::Valid)
+            // Refuse to sign any block at or prior to the latest imported block, since the imported
+            // history may not include every block signed by the other client.
+            let (bound_slot, _, _) = Self::get_lower_bounds(txn, validator_id)?;
+
+            match bound_slot {
+                Some(bound_slot) if block_header.slot <= bound_slot => Err(NotSafe::InvalidBlock(
+                    InvalidBlock::SlotViolatesLowerBound {
+                        block_slot: block_header.slot,
+                        bound_slot,
+                    },
+                )),
+                _ => Ok(Safe::Valid),
+            }
         }
     }
 
@@ -299,6 +377,34 @@ impl SlashingDatabase {
             ));
         }
 
+        // 4. Check that the attestation is not prior to the latest imported source and target.
+        // The imported history may not include every attestation signed by the other client, but
+        // an attestation with a source no lower than any imported source cannot surround one, and
+        // an attestation with a greater target cannot double vote.
+        let (_, min_source, min_target) = Self::get_lower_bounds(txn, validator_id)?;
+
+        if let Some(bound_epoch) = min_source {
+            if att_source_epoch < bound_epoch {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::SourceLessThanLowerBound {
+                        source_epoch: att_source_epoch,
+                        bound_epoch,
+                    },
+                ));
+            }
+        }
+
+        if let Some(bound_epoch) = min_target {
+            if att_target_epoch <= bound_epoch {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::TargetLessThanOrEqLowerBound {
+                        target_epoch: att_target_epoch,
+                        bound_epoch,
+                    },
+                ));
+            }
+        }
+
         // Everything has been checked, return Valid
         Ok(Safe::Valid)
     }
@@ -403,6 +509,381 @@ impl SlashingDatabase {
         txn.commit()?;
         Ok(safe)
     }
+
+    /// Import slashing protection data in the EIP-3076 interchange format, registering any
+    /// validators that are not yet known.
+    ///
+    /// All imported blocks and attestations are stored alongside the existing history. The latest
+    /// imported block slot, source epoch and target epoch of each validator are also recorded as
+    /// lower bounds on the messages which may be signed, which protects the validator against
+    /// messages signed by the other client but absent from the interchange (see
+    /// `check_attestation`).
+    ///
+    /// Existing data takes precedence, so if a block or attestation conflicts with one already in
+    /// the database (same slot or target epoch), the imported one is ignored. Either way the
+    /// validator will refuse to sign a conflicting message.
+    ///
+    /// The import is atomic: if an error occurs, no data is imported.
+    pub fn import_interchange_info(
+        &self,
+        interchange: &Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<(), InterchangeError> {
+        let metadata = &interchange.metadata;
+
+        if metadata.interchange_format_version != SUPPORTED_INTERCHANGE_FORMAT_VERSION {
+            return Err(InterchangeError::UnsupportedVersion(
+                metadata.interchange_format_version,
+            ));
+        }
+
+        if metadata.genesis_validators_root != genesis_validators_root {
+            return Err(InterchangeError::GenesisValidatorsRootMismatch {
+                interchange_file: metadata.genesis_validators_root,
+                client: genesis_validators_root,
+            });
+        }
+
+        // An empty list of data is valid in either format.
+        if !interchange.data.is_empty() && interchange.data.format() != metadata.interchange_format
+        {
+            return Err(InterchangeError::FormatMismatch {
+                metadata: metadata.interchange_format,
+                data: interchange.data.format(),
+            });
+        }
+
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        match &interchange.data {
+            InterchangeData::Complete(records) => {
+                for record in records {
+                    let validator_id = Self::get_or_register_validator_id(&txn, &record.pubkey)?;
+
+                    for block in &record.signed_blocks {
+                        Self::import_block(&txn, validator_id, block.slot, block.signing_root)?;
+                    }
+
+                    for attestation in &record.signed_attestations {
+                        Self::import_attestation(
+                            &txn,
+                            validator_id,
+                            attestation.source_epoch,
+                            attestation.target_epoch,
+                            attestation.signing_root,
+                        )?;
+                    }
+
+                    Self::import_lower_bounds(
+                        &txn,
+                        validator_id,
+                        record.signed_blocks.iter().map(|b| b.slot).max(),
+                        record
+                            .signed_attestations
+                            .iter()
+                            .map(|a| a.source_epoch)
+                            .max(),
+                        record
+                            .signed_attestations
+                            .iter()
+                            .map(|a| a.target_epoch)
+                            .max(),
+                    )?;
+                }
+            }
+            InterchangeData::Minimal(records) => {
+                for record in records {
+                    let validator_id = Self::get_or_register_validator_id(&txn, &record.pubkey)?;
+
+                    if let Some(slot) = record.last_signed_block_slot {
+                        Self::import_block(&txn, validator_id, slot, None)?;
+                    }
+
+                    if let (Some(source_epoch), Some(target_epoch)) = (
+                        record.last_signed_attestation_source_epoch,
+                        record.last_signed_attestation_target_epoch,
+                    ) {
+                        Self::import_attestation(
+                            &txn,
+                            validator_id,
+                            source_epoch,
+                            target_epoch,
+                            None,
+                        )?;
+                    }
+
+                    Self::import_lower_bounds(
+                        &txn,
+                        validator_id,
+                        record.last_signed_block_slot,
+                        record.last_signed_attestation_source_epoch,
+                        record.last_signed_attestation_target_epoch,
+                    )?;
+                }
+            }
+        }
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Insert an imported block, ignoring it if a block already exists at the same slot.
+    ///
+    /// Blocks without a signing root are given a zero root so that they can never be confused with
+    /// a real block.
+    fn import_block(
+        txn: &Transaction,
+        validator_id: i64,
+        slot: Slot,
+        signing_root: Option<Hash256>,
+    ) -> Result<(), NotSafe> {
+        txn.execute(
+            "INSERT OR IGNORE INTO signed_blocks (validator_id, slot, signing_root)
+             VALUES (?1, ?2, ?3)",
+            params![
+                validator_id,
+                slot,
+                signing_root.unwrap_or_else(Hash256::zero).as_bytes()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Insert an imported attestation, ignoring it if an attestation already exists with the
+    /// same target epoch.
+    ///
+    /// Attestations without a signing root are given a zero root, as per `import_block`.
+    fn import_attestation(
+        txn: &Transaction,
+        validator_id: i64,
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        signing_root: Option<Hash256>,
+    ) -> Result<(), NotSafe> {
+        txn.execute(
+            "INSERT OR IGNORE INTO signed_attestations
+                (validator_id, source_epoch, target_epoch, signing_root)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                validator_id,
+                source_epoch,
+                target_epoch,
+                signing_root.unwrap_or_else(Hash256::zero).as_bytes()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Raise the lower bounds of a validator to the latest imported block slot, source epoch and
+    /// target epoch. A bound is never lowered, and is left unchanged if the value is `None`.
+    fn import_lower_bounds(
+        txn: &Transaction,
+        validator_id: i64,
+        block_slot: Option<Slot>,
+        source_epoch: Option<Epoch>,
+        target_epoch: Option<Epoch>,
+    ) -> Result<(), NotSafe> {
+        let (prev_slot, prev_source, prev_target) = Self::get_lower_bounds(txn, validator_id)?;
+
+        txn.execute(
+            "INSERT OR REPLACE INTO lower_bounds
+                (validator_id, block_slot, source_epoch, target_epoch)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                validator_id,
+                std::cmp::max(prev_slot, block_slot),
+                std::cmp::max(prev_source, source_epoch),
+                std::cmp::max(prev_target, target_epoch)
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Export the slashing protection data for all registered validators in the EIP-3076
+    /// interchange format.
+    pub fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        format: InterchangeFormat,
+    ) -> Result<Interchange, InterchangeError> {
+        self.export_interchange_info_for(genesis_validators_root, format, None)
+    }
+
+    /// Export the complete signing history of a single validator in the EIP-3076 interchange
+    /// format, e.g. so that it may be imported into another client after the validator is removed.
+    ///
+    /// If the validator is not registered the returned interchange contains no data.
+    pub fn export_validator_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        public_key: &PublicKey,
+    ) -> Result<Interchange, InterchangeError> {
+        self.export_interchange_info_for(
+            genesis_validators_root,
+            InterchangeFormat::Complete,
+            Some(public_key),
+        )
+    }
+
+    /// Export the data for `public_key`, or for all validators if `public_key` is `None`.
+    fn export_interchange_info_for(
+        &self,
+        genesis_validators_root: Hash256,
+        format: InterchangeFormat,
+        public_key: Option<&PublicKey>,
+    ) -> Result<Interchange, InterchangeError> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        // A validator may have been registered more than once, in which case only the first
+        // registration is used (see `get_validator_id`).
+        let validators = txn
+            .prepare(
+                "SELECT MIN(id), public_key
+                 FROM validators
+                 WHERE ?1 IS NULL OR public_key = ?1
+                 GROUP BY public_key
+                 ORDER BY MIN(id) ASC",
+            )?
+            .query_map(params![public_key.map(PublicKey::as_hex_string)], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let data = match format {
+            InterchangeFormat::Complete => InterchangeData::Complete(
+                validators
+                    .into_iter()
+                    .map(|(validator_id, pubkey)| {
+                        let signed_blocks = txn
+                            .prepare(
+                                "SELECT slot, signing_root
+                                 FROM signed_blocks
+                                 WHERE validator_id = ?1
+                                 ORDER BY slot ASC",
+                            )?
+                            .query_map(params![validator_id], SignedBlock::from_row)?
+                            .map(|block| {
+                                block.map(|block| InterchangeBlock {
+                                    slot: block.slot,
+                                    signing_root: Some(block.signing_root),
+                                })
+                            })
+                            .collect::<Result<_, _>>()?;
+
+                        let signed_attestations = txn
+                            .prepare(
+                                "SELECT source_epoch, target_epoch, signing_root
+                                 FROM signed_attestations
+                                 WHERE validator_id = ?1
+                                 ORDER BY target_epoch ASC",
+                            )?
+                            .query_map(params![validator_id], SignedAttestation::from_row)?
+                            .map(|attestation| {
+                                attestation.map(|attestation| InterchangeAttestation {
+                                    source_epoch: attestation.source_epoch,
+                                    target_epoch: attestation.target_epoch,
+                                    signing_root: Some(attestation.signing_root),
+                                })
+                            })
+                            .collect::<Result<_, _>>()?;
+
+                        Ok(CompleteInterchangeData {
+                            pubkey: parse_pubkey(&pubkey)?,
+                            signed_blocks,
+                            signed_attestations,
+                        })
+                    })
+                    .collect::<Result<_, InterchangeError>>()?,
+            ),
+            InterchangeFormat::Minimal => InterchangeData::Minimal(
+                validators
+                    .into_iter()
+                    .map(|(validator_id, pubkey)| {
+                        let last_signed_block_slot = txn
+                            .prepare("SELECT MAX(slot) FROM signed_blocks WHERE validator_id = ?1")?
+                            .query_row(params![validator_id], |row| row.get(0))?;
+
+                        let (source_epoch, target_epoch) = txn
+                            .prepare(
+                                "SELECT MAX(source_epoch), MAX(target_epoch)
+                                 FROM signed_attestations
+                                 WHERE validator_id = ?1",
+                            )?
+                            .query_row(params![validator_id], |row| {
+                                Ok((row.get(0)?, row.get(1)?))
+                            })?;
+
+                        Ok(MinimalInterchangeData {
+                            pubkey: parse_pubkey(&pubkey)?,
+                            last_signed_block_slot,
+                            last_signed_attestation_source_epoch: source_epoch,
+                            last_signed_attestation_target_epoch: target_epoch,
+                        })
+                    })
+                    .collect::<Result<_, InterchangeError>>()?,
+            ),
+        };
+
+        Ok(Interchange {
+            metadata: InterchangeMetadata {
+                interchange_format: format,
+                interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+                genesis_validators_root,
+            },
+            data,
+        })
+    }
+}
+
+/// Parse a public key stored in the `validators` table, as per `PublicKey::as_hex_string`.
+fn parse_pubkey(pubkey: &str) -> Result<PublicKey, InterchangeError> {
+    if !pubkey.starts_with("0x") {
+        return Err(InterchangeError::InvalidPubkey(pubkey.to_string()));
+    }
+
+    hex::decode(&pubkey[2..])
+        .ok()
+        .and_then(|bytes| PublicKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| InterchangeError::InvalidPubkey(pubkey.to_string()))
+}
+
+#[derive(Debug)]
+pub enum InterchangeError {
+    /// The interchange file uses a version of the format that is not supported.
+    UnsupportedVersion(u64),
+    /// The interchange file is for a different network.
+    GenesisValidatorsRootMismatch {
+        interchange_file: Hash256,
+        client: Hash256,
+    },
+    /// The format declared in the metadata does not match the format of the data.
+    FormatMismatch {
+        metadata: InterchangeFormat,
+        data: InterchangeFormat,
+    },
+    /// A public key in the database could not be parsed.
+    InvalidPubkey(String),
+    NotSafe(NotSafe),
+}
+
+impl From<NotSafe> for InterchangeError {
+    fn from(error: NotSafe) -> Self {
+        InterchangeError::NotSafe(error)
+    }
+}
+
+impl From<rusqlite::Error> for InterchangeError {
+    fn from(error: rusqlite::Error) -> Self {
+        NotSafe::from(error).into()
+    }
+}
+
+impl From<r2d2::Error> for InterchangeError {
+    fn from(error: r2d2::Error) -> Self {
+        NotSafe::from(error).into()
+    }
 }
 
 #[cfg(test)]
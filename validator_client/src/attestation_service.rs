@@ -1,11 +1,14 @@
 use crate::{
+    beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
     duties_service::{DutiesService, DutyAndProof},
+    metrics,
     validator_store::ValidatorStore,
 };
 use environment::RuntimeContext;
 use exit_future::Signal;
+use futures::future::join_all;
 use futures::{FutureExt, StreamExt};
-use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
+use remote_beacon_node::PublishStatus;
 use slog::{crit, debug, info, trace};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
@@ -19,7 +22,7 @@ pub struct AttestationServiceBuilder<T, E: EthSpec> {
     duties_service: Option<DutiesService<T, E>>,
     validator_store: Option<ValidatorStore<T, E>>,
     slot_clock: Option<T>,
-    beacon_node: Option<RemoteBeaconNode<E>>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
 }
 
@@ -29,7 +32,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             duties_service: None,
             validator_store: None,
             slot_clock: None,
-            beacon_node: None,
+            beacon_nodes: None,
             context: None,
         }
     }
@@ -49,8 +52,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
-    pub fn beacon_node(mut self, beacon_node: RemoteBeaconNode<E>) -> Self {
-        self.beacon_node = Some(beacon_node);
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
         self
     }
 
@@ -71,9 +74,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or_else(|| "Cannot build AttestationService without slot_clock")?,
-                beacon_node: self
-                    .beacon_node
-                    .ok_or_else(|| "Cannot build AttestationService without beacon_node")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build AttestationService without beacon_nodes")?,
                 context: self
                     .context
                     .ok_or_else(|| "Cannot build AttestationService without runtime_context")?,
@@ -87,7 +90,7 @@ pub struct Inner<T, E: EthSpec> {
     duties_service: DutiesService<T, E>,
     validator_store: ValidatorStore<T, E>,
     slot_clock: T,
-    beacon_node: RemoteBeaconNode<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
 }
 
@@ -327,60 +330,68 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .epoch(E::slots_per_epoch());
 
         let attestation = self
-            .beacon_node
-            .http
-            .validator()
-            .produce_attestation(slot, committee_index)
+            .beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node
+                    .http
+                    .validator()
+                    .produce_attestation(slot, committee_index)
+                    .await
+            })
             .await
-            .map_err(|e| format!("Failed to produce attestation: {:?}", e))?;
+            .map_err(|e| format!("Failed to produce attestation: {}", e))?;
 
         // For each validator in `validator_duties`, clone the `attestation` and add
         // their signature.
         //
         // If any validator is unable to sign, they are simply skipped.
-        let signed_attestations = validator_duties
-            .iter()
-            .filter_map(|duty| {
-                // Ensure that all required fields are present in the validator duty.
-                let (duty_slot, duty_committee_index, validator_committee_position, _) =
-                    if let Some(tuple) = duty.attestation_duties() {
-                        tuple
-                    } else {
-                        crit!(
-                            log,
-                            "Missing validator duties when signing";
-                            "duties" => format!("{:?}", duty)
-                        );
-                        return None;
-                    };
-
-                // Ensure that the attestation matches the duties.
-                if duty_slot != attestation.data.slot
-                    || duty_committee_index != attestation.data.index
-                {
+        let attestation = &attestation;
+        let signing_futures = validator_duties.iter().map(|duty| async move {
+            // Ensure that all required fields are present in the validator duty.
+            let (duty_slot, duty_committee_index, validator_committee_position, _) =
+                if let Some(tuple) = duty.attestation_duties() {
+                    tuple
+                } else {
                     crit!(
                         log,
-                        "Inconsistent validator duties during signing";
-                        "validator" => format!("{:?}", duty.validator_pubkey()),
-                        "duty_slot" => duty_slot,
-                        "attestation_slot" => attestation.data.slot,
-                        "duty_index" => duty_committee_index,
-                        "attestation_index" => attestation.data.index,
+                        "Missing validator duties when signing";
+                        "duties" => format!("{:?}", duty)
                     );
                     return None;
-                }
+                };
+
+            // Ensure that the attestation matches the duties.
+            if duty_slot != attestation.data.slot || duty_committee_index != attestation.data.index
+            {
+                crit!(
+                    log,
+                    "Inconsistent validator duties during signing";
+                    "validator" => format!("{:?}", duty.validator_pubkey()),
+                    "duty_slot" => duty_slot,
+                    "attestation_slot" => attestation.data.slot,
+                    "duty_index" => duty_committee_index,
+                    "attestation_index" => attestation.data.index,
+                );
+                return None;
+            }
 
-                let mut attestation = attestation.clone();
+            let mut attestation = attestation.clone();
 
-                self.validator_store
-                    .sign_attestation(
-                        duty.validator_pubkey(),
-                        validator_committee_position,
-                        &mut attestation,
-                        current_epoch,
-                    )
-                    .map(|_| attestation)
-            })
+            self.validator_store
+                .sign_attestation(
+                    duty.validator_pubkey(),
+                    validator_committee_position,
+                    &mut attestation,
+                    current_epoch,
+                )
+                .await
+                .map(|_| attestation)
+        });
+
+        let signed_attestations = join_all(signing_futures)
+            .await
+            .into_iter()
+            .flatten()
             .collect::<Vec<_>>();
 
         // If there are any signed attestations, publish them to the BN. Otherwise,
@@ -389,32 +400,52 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             let num_attestations = signed_attestations.len();
             let beacon_block_root = attestation.data.beacon_block_root;
 
-            self.beacon_node
-                .http
-                .validator()
-                .publish_attestations(signed_attestations)
+            let signed_attestations_ref = &signed_attestations;
+            self.beacon_nodes
+                .first_success(RequireSynced::No, |beacon_node| async move {
+                    beacon_node
+                        .http
+                        .validator()
+                        .publish_attestations(signed_attestations_ref.clone())
+                        .await
+                })
                 .await
-                .map_err(|e| format!("Failed to publish attestation: {:?}", e))
-                .map(move |publish_status| match publish_status {
-                    PublishStatus::Valid => info!(
-                        log,
-                        "Successfully published attestations";
-                        "count" => num_attestations,
-                        "head_block" => format!("{:?}", beacon_block_root),
-                        "committee_index" => committee_index,
-                        "slot" => slot.as_u64(),
-                        "type" => "unaggregated",
-                    ),
-                    PublishStatus::Invalid(msg) => crit!(
-                        log,
-                        "Published attestation was invalid";
-                        "message" => msg,
-                        "committee_index" => committee_index,
-                        "slot" => slot.as_u64(),
-                        "type" => "unaggregated",
-                    ),
-                    PublishStatus::Unknown => {
-                        crit!(log, "Unknown condition when publishing unagg. attestation")
+                .map_err(|e| {
+                    metrics::inc_counter_vec_by(
+                        &metrics::PUBLISHED_ATTESTATIONS_TOTAL,
+                        &[metrics::FAILED],
+                        num_attestations as i64,
+                    );
+                    format!("Failed to publish attestation: {}", e)
+                })
+                .map(move |publish_status| {
+                    metrics::inc_counter_vec_by(
+                        &metrics::PUBLISHED_ATTESTATIONS_TOTAL,
+                        &[metrics::publish_status_label(&publish_status)],
+                        num_attestations as i64,
+                    );
+
+                    match publish_status {
+                        PublishStatus::Valid => info!(
+                            log,
+                            "Successfully published attestations";
+                            "count" => num_attestations,
+                            "head_block" => format!("{:?}", beacon_block_root),
+                            "committee_index" => committee_index,
+                            "slot" => slot.as_u64(),
+                            "type" => "unaggregated",
+                        ),
+                        PublishStatus::Invalid(msg) => crit!(
+                            log,
+                            "Published attestation was invalid";
+                            "message" => msg,
+                            "committee_index" => committee_index,
+                            "slot" => slot.as_u64(),
+                            "type" => "unaggregated",
+                        ),
+                        PublishStatus::Unknown => {
+                            crit!(log, "Unknown condition when publishing unagg. attestation")
+                        }
                     }
                 })
                 .map(|()| Some(attestation))
@@ -450,66 +481,96 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     ) -> Result<(), String> {
         let log = &self.context.log;
 
+        let attestation_data_ref = &attestation.data;
         let aggregated_attestation = self
-            .beacon_node
-            .http
-            .validator()
-            .produce_aggregate_attestation(&attestation.data)
+            .beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node
+                    .http
+                    .validator()
+                    .produce_aggregate_attestation(attestation_data_ref)
+                    .await
+            })
             .await
-            .map_err(|e| format!("Failed to produce an aggregate attestation: {:?}", e))?;
+            .map_err(|e| format!("Failed to produce an aggregate attestation: {}", e))?;
 
         // For each validator, clone the `aggregated_attestation` and convert it into
         // a `SignedAggregateAndProof`
-        let signed_aggregate_and_proofs = validator_duties
-            .iter()
-            .filter_map(|duty_and_proof| {
-                // Do not produce a signed aggregator for validators that are not
-                // subscribed aggregators.
-                let selection_proof = duty_and_proof.selection_proof.as_ref()?.clone();
-
-                let (duty_slot, duty_committee_index, _, validator_index) =
-                    duty_and_proof.attestation_duties().or_else(|| {
-                        crit!(log, "Missing duties when signing aggregate");
-                        None
-                    })?;
-
-                let pubkey = &duty_and_proof.duty.validator_pubkey;
-                let slot = attestation.data.slot;
-                let committee_index = attestation.data.index;
-
-                if duty_slot != slot || duty_committee_index != committee_index {
-                    crit!(log, "Inconsistent validator duties during signing");
-                    return None;
-                }
-
-                if let Some(signed_aggregate_and_proof) =
-                    self.validator_store.produce_signed_aggregate_and_proof(
-                        pubkey,
-                        validator_index,
-                        aggregated_attestation.clone(),
-                        selection_proof,
-                    )
-                {
-                    Some(signed_aggregate_and_proof)
-                } else {
-                    crit!(log, "Failed to sign attestation");
+        let aggregated_attestation = &aggregated_attestation;
+        let attestation = &attestation;
+        let signing_futures = validator_duties.iter().map(|duty_and_proof| async move {
+            // Do not produce a signed aggregator for validators that are not
+            // subscribed aggregators.
+            let selection_proof = duty_and_proof.selection_proof.as_ref()?.clone();
+
+            let (duty_slot, duty_committee_index, _, validator_index) =
+                duty_and_proof.attestation_duties().or_else(|| {
+                    crit!(log, "Missing duties when signing aggregate");
                     None
-                }
-            })
+                })?;
+
+            let pubkey = &duty_and_proof.duty.validator_pubkey;
+            let slot = attestation.data.slot;
+            let committee_index = attestation.data.index;
+
+            if duty_slot != slot || duty_committee_index != committee_index {
+                crit!(log, "Inconsistent validator duties during signing");
+                return None;
+            }
+
+            if let Some(signed_aggregate_and_proof) = self
+                .validator_store
+                .produce_signed_aggregate_and_proof(
+                    pubkey,
+                    validator_index,
+                    aggregated_attestation.clone(),
+                    selection_proof,
+                )
+                .await
+            {
+                Some(signed_aggregate_and_proof)
+            } else {
+                crit!(log, "Failed to sign attestation");
+                None
+            }
+        });
+
+        let signed_aggregate_and_proofs = join_all(signing_futures)
+            .await
+            .into_iter()
+            .flatten()
             .collect::<Vec<_>>();
 
         // If there any signed aggregates and proofs were produced, publish them to the
         // BN.
         if let Some(first) = signed_aggregate_and_proofs.first().cloned() {
             let attestation = first.message.aggregate;
+            let num_aggregates = signed_aggregate_and_proofs.len();
 
+            let signed_aggregate_and_proofs_ref = &signed_aggregate_and_proofs;
             let publish_status = self
-                .beacon_node
-                .http
-                .validator()
-                .publish_aggregate_and_proof(signed_aggregate_and_proofs)
+                .beacon_nodes
+                .first_success(RequireSynced::No, |beacon_node| async move {
+                    beacon_node
+                        .http
+                        .validator()
+                        .publish_aggregate_and_proof(signed_aggregate_and_proofs_ref.clone())
+                        .await
+                })
                 .await
-                .map_err(|e| format!("Failed to publish aggregate and proofs: {:?}", e))?;
+                .map_err(|e| {
+                    metrics::inc_counter_vec_by(
+                        &metrics::PUBLISHED_AGGREGATES_TOTAL,
+                        &[metrics::FAILED],
+                        num_aggregates as i64,
+                    );
+                    format!("Failed to publish aggregate and proofs: {}", e)
+                })?;
+            metrics::inc_counter_vec_by(
+                &metrics::PUBLISHED_AGGREGATES_TOTAL,
+                &[metrics::publish_status_label(&publish_status)],
+                num_aggregates as i64,
+            );
             match publish_status {
                 PublishStatus::Valid => info!(
                     log,
@@ -0,0 +1,446 @@
+//! Allows for a list of `RemoteBeaconNode` to appear as a single entity which will exhibit
+//! "fallback" behaviour; it will try a request on each of the nodes, in order, until one of them
+//! succeeds or they have all failed.
+
+use crate::is_synced::is_synced;
+use crate::metrics;
+use environment::RuntimeContext;
+use eth2_config::Eth2Config;
+use exit_future::Signal;
+use futures::{FutureExt, StreamExt};
+use parking_lot::RwLock;
+use remote_beacon_node::RemoteBeaconNode;
+use slog::{debug, error, info, o, warn, Logger};
+use slot_clock::SlotClock;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::time::{interval_at, Duration, Instant};
+use types::EthSpec;
+
+/// Indicates if a beacon node must be synced before some action is performed on it.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum RequireSynced {
+    Yes,
+    No,
+}
+
+/// The reason why a request was not served by a particular beacon node.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The node was unavailable and we didn't attempt to contact it.
+    Unavailable(CandidateError),
+    /// We attempted to contact the node but it failed.
+    RequestFailed(E),
+}
+
+/// The list of errors encountered whilst attempting to perform a query, one per endpoint.
+#[derive(Debug)]
+pub struct AllErrored<E>(pub Vec<(String, Error<E>)>);
+
+impl<E: fmt::Debug> fmt::Display for AllErrored<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "All endpoints failed")?;
+        for (i, (endpoint, error)) in self.0.iter().enumerate() {
+            let comma = if i + 1 < self.0.len() { "," } else { "" };
+
+            write!(f, " {} => {:?}{}", endpoint, error, comma)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reasons why a candidate might not be ready.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandidateError {
+    Uninitialized,
+    Offline,
+    Incompatible,
+    NotSynced,
+}
+
+impl CandidateError {
+    /// Returns a short, static description of `self`, suitable for use as a metric label.
+    fn as_str(self) -> &'static str {
+        match self {
+            CandidateError::Uninitialized => "uninitialized",
+            CandidateError::Offline => "offline",
+            CandidateError::Incompatible => "incompatible",
+            CandidateError::NotSynced => "not_synced",
+        }
+    }
+}
+
+/// Represents a `RemoteBeaconNode` that may or may not be "ready" to receive requests.
+pub struct CandidateBeaconNode<E: EthSpec> {
+    endpoint: String,
+    beacon_node: RemoteBeaconNode<E>,
+    status: RwLock<Result<(), CandidateError>>,
+}
+
+impl<E: EthSpec> CandidateBeaconNode<E> {
+    /// Instantiate a new node. Its status is `Uninitialized` until it is first checked.
+    pub fn new(endpoint: String, beacon_node: RemoteBeaconNode<E>) -> Self {
+        Self {
+            endpoint,
+            beacon_node,
+            status: RwLock::new(Err(CandidateError::Uninitialized)),
+        }
+    }
+
+    /// Returns the URL of the beacon node, as supplied by the user.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the status of `self`.
+    ///
+    /// If `RequireSynced::No`, any `NotSynced` node will be ignored and mapped to `Ok(())`.
+    pub fn status(&self, synced: RequireSynced) -> Result<(), CandidateError> {
+        match *self.status.read() {
+            Err(CandidateError::NotSynced) if synced == RequireSynced::No => Ok(()),
+            other => other,
+        }
+    }
+
+    /// Perform some queries against the node to determine if it is a good candidate, updating
+    /// `self.status` and returning that result.
+    pub async fn refresh_status<T: SlotClock>(
+        &self,
+        slot_clock: Option<&T>,
+        eth2_config: Option<&Eth2Config>,
+        log: &Logger,
+    ) -> Result<(), CandidateError> {
+        let log = log.new(o!("endpoint" => self.endpoint.clone()));
+
+        let new_status = self.check_health(slot_clock, eth2_config, &log).await;
+
+        *self.status.write() = new_status;
+
+        if let Err(e) = new_status {
+            metrics::inc_counter_vec(
+                &metrics::BEACON_NODE_HEALTH_CHECK_FAILURES,
+                &[&self.endpoint, e.as_str()],
+            );
+        }
+
+        new_status
+    }
+
+    /// Checks that the node is online, is using the same spec as us and, if a `slot_clock` is
+    /// supplied, that it is synced.
+    async fn check_health<T: SlotClock>(
+        &self,
+        slot_clock: Option<&T>,
+        eth2_config: Option<&Eth2Config>,
+        log: &Logger,
+    ) -> Result<(), CandidateError> {
+        let version = self
+            .beacon_node
+            .http
+            .node()
+            .get_version()
+            .await
+            .map_err(|e| {
+                warn!(
+                    log,
+                    "Beacon node is offline";
+                    "error" => format!("{:?}", e),
+                );
+                CandidateError::Offline
+            })?;
+
+        if let Some(expected) = eth2_config {
+            let remote = self
+                .beacon_node
+                .http
+                .spec()
+                .get_eth2_config()
+                .await
+                .map_err(|e| {
+                    warn!(
+                        log,
+                        "Unable to read eth2 config from beacon node";
+                        "error" => format!("{:?}", e),
+                    );
+                    CandidateError::Offline
+                })?;
+
+            if remote.spec_constants != expected.spec_constants || remote.spec != expected.spec {
+                error!(
+                    log,
+                    "Beacon node is using an incompatible spec";
+                    "expected" => &expected.spec_constants,
+                    "got" => &remote.spec_constants,
+                );
+                return Err(CandidateError::Incompatible);
+            }
+        }
+
+        if let Some(slot_clock) = slot_clock {
+            if !is_synced(&self.beacon_node, slot_clock, Some(&log)).await {
+                return Err(CandidateError::NotSynced);
+            }
+        }
+
+        debug!(
+            log,
+            "Beacon node is available";
+            "version" => version,
+        );
+
+        Ok(())
+    }
+}
+
+/// A collection of `CandidateBeaconNode` that can be used to perform requests with "fallback"
+/// behaviour, where the failure of one candidate results in the next candidate receiving an
+/// identical query.
+pub struct BeaconNodeFallback<T, E: EthSpec> {
+    candidates: Vec<CandidateBeaconNode<E>>,
+    slot_clock: Option<T>,
+    eth2_config: Option<Eth2Config>,
+    log: Logger,
+}
+
+impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
+    /// Instantiate a new fallback, where `candidates` are tried in the order given.
+    pub fn new(candidates: Vec<CandidateBeaconNode<E>>, log: Logger) -> Self {
+        Self {
+            candidates,
+            slot_clock: None,
+            eth2_config: None,
+            log,
+        }
+    }
+
+    /// Used to update the slot clock post-instantiation.
+    ///
+    /// This is the result of a chicken-and-egg issue where `Self` needs a slot clock for some
+    /// operations, but `Self` is required to obtain the slot clock since we need the genesis time
+    /// from a beacon node.
+    ///
+    /// Until this is set, candidates are not checked for sync status.
+    pub fn set_slot_clock(&mut self, slot_clock: T) {
+        self.slot_clock = Some(slot_clock);
+    }
+
+    /// Used to set the config that all candidates must match post-instantiation, for the same
+    /// reason as `Self::set_slot_clock`.
+    ///
+    /// Until this is set, candidates are not checked for compatibility.
+    pub fn set_eth2_config(&mut self, eth2_config: Eth2Config) {
+        self.eth2_config = Some(eth2_config);
+    }
+
+    /// The total number of beacon nodes.
+    pub fn num_total(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// The number of beacon nodes which were synced at their last health check.
+    pub fn num_synced(&self) -> usize {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.status(RequireSynced::Yes).is_ok())
+            .count()
+    }
+
+    /// The number of beacon nodes which were online and compatible at their last health check,
+    /// regardless of their sync status.
+    pub fn num_available(&self) -> usize {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.status(RequireSynced::No).is_ok())
+            .count()
+    }
+
+    /// Perform a health check on all candidates, concurrently.
+    pub async fn update_all_candidates(&self) {
+        let futures = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                candidate.refresh_status(
+                    self.slot_clock.as_ref(),
+                    self.eth2_config.as_ref(),
+                    &self.log,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Run all updates concurrently and ignore results; the statuses are stored in each
+        // candidate.
+        let _ = futures::future::join_all(futures).await;
+    }
+
+    /// Run `func` against each candidate in `self`, returning immediately if a result is found.
+    /// Otherwise, return all the errors encountered along the way.
+    ///
+    /// First this function will try all candidates which were synced at their last health check.
+    /// If `require_synced == RequireSynced::No`, it will then try unsynced candidates. Finally,
+    /// it will re-check the health of any candidates that were unavailable and try them if they
+    /// have recovered.
+    pub async fn first_success<'a, F, O, Err, R>(
+        &'a self,
+        require_synced: RequireSynced,
+        func: F,
+    ) -> Result<O, AllErrored<Err>>
+    where
+        F: Fn(&'a RemoteBeaconNode<E>) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: fmt::Debug,
+    {
+        let mut errors = vec![];
+        let mut to_retry = vec![];
+        let mut retry_unsynced = vec![];
+
+        // Run `func` using a `candidate`, returning the value or capturing errors.
+        //
+        // We use a macro instead of a closure here since it is not trivial to move `func` into a
+        // closure.
+        macro_rules! try_func {
+            ($candidate: ident) => {{
+                metrics::inc_counter_vec(&metrics::BEACON_NODE_REQUESTS, &[$candidate.endpoint()]);
+
+                let timer = metrics::start_timer_vec(
+                    &metrics::BEACON_NODE_REQUEST_TIMES,
+                    &[$candidate.endpoint()],
+                );
+
+                // There exists a race condition where `func` may be called when the candidate is
+                // actually not ready. We deem this an acceptable inefficiency.
+                let result = func(&$candidate.beacon_node).await;
+                metrics::stop_timer(timer);
+
+                match result {
+                    Ok(val) => {
+                        debug!(
+                            self.log,
+                            "Beacon node request succeeded";
+                            "endpoint" => $candidate.endpoint(),
+                            "prior_failures" => errors.len(),
+                        );
+                        return Ok(val);
+                    }
+                    Err(e) => {
+                        debug!(
+                            self.log,
+                            "Beacon node request failed";
+                            "endpoint" => $candidate.endpoint(),
+                            "error" => format!("{:?}", e),
+                        );
+                        metrics::inc_counter_vec(
+                            &metrics::BEACON_NODE_REQUEST_ERRORS,
+                            &[$candidate.endpoint()],
+                        );
+                        errors.push(($candidate.endpoint.clone(), Error::RequestFailed(e)));
+                    }
+                }
+            }};
+        }
+
+        // First pass: try `func` on all synced and ready candidates.
+        //
+        // This ensures that we always choose a synced node if it is available.
+        for candidate in &self.candidates {
+            match candidate.status(RequireSynced::Yes) {
+                Err(e @ CandidateError::NotSynced) if require_synced == RequireSynced::No => {
+                    // This client is unsynced, we will try it after trying all synced clients.
+                    retry_unsynced.push(candidate);
+                    errors.push((candidate.endpoint.clone(), Error::Unavailable(e)));
+                }
+                Err(e) => {
+                    // This client was not ready on the first pass, we might try it again later.
+                    to_retry.push(candidate);
+                    errors.push((candidate.endpoint.clone(), Error::Unavailable(e)));
+                }
+                Ok(()) => try_func!(candidate),
+            }
+        }
+
+        // Second pass: try `func` on ready unsynced candidates. This only runs if we permit
+        // unsynced candidates.
+        //
+        // Due to async race-conditions, it is possible that we will send a request to a candidate
+        // that has been set to an offline/unready status. This is acceptable.
+        if require_synced == RequireSynced::No {
+            for candidate in retry_unsynced {
+                try_func!(candidate);
+            }
+        }
+
+        // Third pass: try again, attempting to make non-ready clients become ready.
+        for candidate in to_retry {
+            let new_status = candidate
+                .refresh_status(
+                    self.slot_clock.as_ref(),
+                    self.eth2_config.as_ref(),
+                    &self.log,
+                )
+                .await;
+
+            let is_ready = match new_status {
+                Ok(()) => true,
+                Err(CandidateError::NotSynced) => require_synced == RequireSynced::No,
+                Err(_) => false,
+            };
+
+            // If the candidate hasn't become ready then skip it; its error was already recorded
+            // during the first pass.
+            if is_ready {
+                try_func!(candidate);
+            }
+        }
+
+        // There were no candidates already ready and we were unable to make any of them ready.
+        Err(AllErrored(errors))
+    }
+}
+
+/// Spawns a service which checks the health of every beacon node once per slot, so that requests
+/// are routed to healthy nodes without having to wait for a failure.
+pub fn start_fallback_updater_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+) -> Result<Signal, String> {
+    let log = context.log.clone();
+    let runtime_handle = context.runtime_handle.clone();
+
+    let slot_duration = Duration::from_millis(context.eth2_config.spec.milliseconds_per_slot);
+    let duration_to_next_slot = beacon_nodes
+        .slot_clock
+        .as_ref()
+        .ok_or_else(|| "Cannot start fallback updater without slot clock")?
+        .duration_to_next_slot()
+        .ok_or_else(|| "Unable to determine duration to next slot")?;
+
+    info!(
+        log,
+        "Beacon node health checks started";
+        "beacon_nodes" => beacon_nodes.num_total(),
+    );
+
+    // Run the health checks half way through each slot, away from the start of the slot where
+    // blocks are produced.
+    let mut interval = interval_at(
+        Instant::now() + duration_to_next_slot + (slot_duration / 2),
+        slot_duration,
+    );
+
+    let interval_fut = async move {
+        while interval.next().await.is_some() {
+            beacon_nodes.update_all_candidates().await;
+        }
+    };
+
+    let (exit_signal, exit_fut) = exit_future::signal();
+
+    let future = futures::future::select(
+        Box::pin(interval_fut),
+        exit_fut.map(move |_| info!(log, "Shutdown complete")),
+    );
+    runtime_handle.spawn(future);
+
+    Ok(exit_signal)
+}
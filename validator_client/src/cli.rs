@@ -1,4 +1,3 @@
-use crate::config::DEFAULT_HTTP_SERVER;
 use clap::{App, Arg};
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -8,13 +7,25 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             "When connected to a beacon node, performs the duties of a staked \
                 validator (e.g., proposing blocks and attestations).",
         )
+        .arg(
+            Arg::with_name("beacon-nodes")
+                .long("beacon-nodes")
+                .value_name("NETWORK_ADDRESSES")
+                .help(
+                    "Comma-separated addresses to one or more beacon node HTTP APIs. \
+                    Requests are sent to the first synced node in the list, falling back to \
+                    the next node on failure. Default is http://localhost:5052.",
+                )
+                .takes_value(true),
+        )
+        // This argument is deprecated, use `--beacon-nodes` instead.
         .arg(
             Arg::with_name("server")
                 .long("server")
                 .value_name("NETWORK_ADDRESS")
-                .help("Address to connect to BeaconNode.")
-                .default_value(&DEFAULT_HTTP_SERVER)
-                .takes_value(true),
+                .help("Deprecated. Use --beacon-nodes.")
+                .takes_value(true)
+                .conflicts_with("beacon-nodes"),
         )
         .arg(
             Arg::with_name("secrets-dir")
@@ -44,4 +55,105 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                       node is not synced.",
                 ),
         )
+        .arg(
+            Arg::with_name("remote-signer-url")
+                .long("remote-signer-url")
+                .value_name("URL")
+                .help(
+                    "The HTTP endpoint of a remote signer. Signing requests for the validators \
+                    listed in --remote-signer-keys are sent to this endpoint instead of using \
+                    local keystores. Slashing protection is still performed locally.",
+                )
+                .requires("remote-signer-keys")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("remote-signer-keys")
+                .long("remote-signer-keys")
+                .value_name("PUBLIC_KEYS")
+                .help(
+                    "Comma-separated, 0x-prefixed voting public keys of the validators whose \
+                    secret keys are held by the remote signer.",
+                )
+                .requires("remote-signer-url")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("enable-doppelganger-protection")
+                .long("enable-doppelganger-protection")
+                .help(
+                    "If present, the validator client will not sign any messages for a validator \
+                    until it has observed the network for --doppelganger-epochs and seen no \
+                    attestations from that validator. If attestations are seen, the validator \
+                    will not perform any duties until the validator client is restarted. This \
+                    helps to prevent slashing when the same keys are accidentally run on \
+                    multiple hosts, at the cost of missing some attestations at startup.",
+                ),
+        )
+        .arg(
+            Arg::with_name("doppelganger-epochs")
+                .long("doppelganger-epochs")
+                .value_name("EPOCHS")
+                .help(
+                    "The number of epochs for which each validator is checked for activity \
+                    before signing is enabled. Only used with --enable-doppelganger-protection.",
+                )
+                .default_value("2")
+                .takes_value(true),
+        )
+        /* HTTP API related arguments */
+        .arg(
+            Arg::with_name("http")
+                .long("http")
+                .help(
+                    "Enable the HTTP API, which allows validators to be listed, imported, \
+                    enabled, disabled and deleted whilst the validator client is running. \
+                    Requests must include the bearer token stored in the api-token.txt file \
+                    in the --datadir. Disabled by default.",
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-address")
+                .long("http-address")
+                .value_name("ADDRESS")
+                .help("Set the listen address for the HTTP API server.")
+                .default_value("127.0.0.1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-port")
+                .long("http-port")
+                .value_name("PORT")
+                .help("Set the listen TCP port for the HTTP API server.")
+                .default_value("5062")
+                .takes_value(true),
+        )
+        /* Prometheus metrics HTTP server related arguments */
+        .arg(
+            Arg::with_name("metrics")
+                .long("metrics")
+                .help(
+                    "Enable the Prometheus metrics HTTP server, which reports duties, signing, \
+                    publishing and beacon node request statistics at /metrics. Disabled by \
+                    default.",
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("metrics-address")
+                .long("metrics-address")
+                .value_name("ADDRESS")
+                .help("Set the listen address for the Prometheus metrics HTTP server.")
+                .default_value("127.0.0.1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics-port")
+                .long("metrics-port")
+                .value_name("PORT")
+                .help("Set the listen TCP port for the Prometheus metrics HTTP server.")
+                .default_value("5064")
+                .takes_value(true),
+        )
 }
@@ -1,7 +1,10 @@
+use crate::doppelganger_service::DEFAULT_DOPPELGANGER_EPOCHS;
+use crate::{http_api, http_metrics};
 use clap::ArgMatches;
 use clap_utils::{parse_optional, parse_path_with_default_in_home_dir};
 use serde_derive::{Deserialize, Serialize};
 use std::path::PathBuf;
+use types::PublicKey;
 
 pub const DEFAULT_HTTP_SERVER: &str = "http://localhost:5052/";
 pub const DEFAULT_DATA_DIR: &str = ".lighthouse/validators";
@@ -16,15 +19,29 @@ pub struct Config {
     pub data_dir: PathBuf,
     /// The directory containing the passwords to unlock validator keystores.
     pub secrets_dir: PathBuf,
-    /// The http endpoint of the beacon node API.
+    /// The http endpoints of the beacon node APIs, in order of preference.
     ///
-    /// Should be similar to `http://localhost:8080`
-    pub http_server: String,
+    /// Each should be similar to `http://localhost:8080`.
+    pub beacon_nodes: Vec<String>,
     /// If true, the validator client will still poll for duties and produce blocks even if the
     /// beacon node is not synced at startup.
     pub allow_unsynced_beacon_node: bool,
     /// If true, register new validator keys with the slashing protection database.
     pub auto_register: bool,
+    /// If true, refuse to sign messages for each validator until it has been observed to be
+    /// inactive on the network for `doppelganger_epochs`.
+    pub doppelganger_protection: bool,
+    /// The number of epochs for which each validator is checked for doppelgangers.
+    pub doppelganger_epochs: u64,
+    /// The HTTP endpoint of a remote signer which holds the secret keys for
+    /// `remote_signer_keys`.
+    pub remote_signer_url: Option<String>,
+    /// The validators which should be signed for by the remote signer.
+    pub remote_signer_keys: Vec<PublicKey>,
+    /// Configuration for the HTTP API used to manage validators at runtime.
+    pub http_api: http_api::Config,
+    /// Configuration for the HTTP server which exposes Prometheus metrics.
+    pub http_metrics: http_metrics::Config,
 }
 
 impl Default for Config {
@@ -39,9 +56,15 @@ impl Default for Config {
         Self {
             data_dir,
             secrets_dir,
-            http_server: DEFAULT_HTTP_SERVER.to_string(),
+            beacon_nodes: vec![DEFAULT_HTTP_SERVER.to_string()],
             allow_unsynced_beacon_node: false,
             auto_register: false,
+            doppelganger_protection: false,
+            doppelganger_epochs: DEFAULT_DOPPELGANGER_EPOCHS,
+            remote_signer_url: None,
+            remote_signer_keys: vec![],
+            http_api: <_>::default(),
+            http_metrics: <_>::default(),
         }
     }
 }
@@ -65,12 +88,73 @@ impl Config {
             ));
         }
 
-        if let Some(server) = parse_optional(cli_args, "server")? {
-            config.http_server = server;
+        if let Some(beacon_nodes) = parse_optional::<String>(cli_args, "beacon-nodes")? {
+            config.beacon_nodes = beacon_nodes
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+        } else if let Some(server) = parse_optional(cli_args, "server")? {
+            // The `--server` flag is deprecated in favour of `--beacon-nodes`.
+            config.beacon_nodes = vec![server];
+        }
+
+        if config.beacon_nodes.is_empty() {
+            return Err("At least one beacon node must be supplied (--beacon-nodes)".to_string());
         }
 
         config.allow_unsynced_beacon_node = cli_args.is_present("allow-unsynced");
         config.auto_register = cli_args.is_present("auto-register");
+        config.doppelganger_protection = cli_args.is_present("enable-doppelganger-protection");
+
+        if let Some(doppelganger_epochs) = parse_optional(cli_args, "doppelganger-epochs")? {
+            config.doppelganger_epochs = doppelganger_epochs;
+        }
+
+        config.remote_signer_url = parse_optional(cli_args, "remote-signer-url")?;
+
+        if let Some(remote_signer_keys) = parse_optional::<String>(cli_args, "remote-signer-keys")?
+        {
+            config.remote_signer_keys = remote_signer_keys
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(parse_public_key)
+                .collect::<Result<_, _>>()?;
+        }
+
+        if cli_args.is_present("http") {
+            config.http_api.enabled = true;
+        }
+
+        if let Some(address) = cli_args.value_of("http-address") {
+            config.http_api.listen_addr = address
+                .parse()
+                .map_err(|_| "http-address is not a valid IPv4 address.")?;
+        }
+
+        if let Some(port) = cli_args.value_of("http-port") {
+            config.http_api.listen_port = port
+                .parse::<u16>()
+                .map_err(|_| "http-port is not a valid u16.")?;
+        }
+
+        if cli_args.is_present("metrics") {
+            config.http_metrics.enabled = true;
+        }
+
+        if let Some(address) = cli_args.value_of("metrics-address") {
+            config.http_metrics.listen_addr = address
+                .parse()
+                .map_err(|_| "metrics-address is not a valid IPv4 address.")?;
+        }
+
+        if let Some(port) = cli_args.value_of("metrics-port") {
+            config.http_metrics.listen_port = port
+                .parse::<u16>()
+                .map_err(|_| "metrics-port is not a valid u16.")?;
+        }
 
         if let Some(secrets_dir) = parse_optional(cli_args, "secrets-dir")? {
             config.secrets_dir = secrets_dir;
@@ -86,3 +170,14 @@ impl Config {
         Ok(config)
     }
 }
+
+/// Parse a 0x-prefixed, hex-encoded BLS public key.
+fn parse_public_key(s: &str) -> Result<PublicKey, String> {
+    if !s.starts_with("0x") {
+        return Err(format!("Public key must have a 0x prefix: {}", s));
+    }
+
+    let bytes = hex::decode(&s[2..]).map_err(|e| format!("Invalid hex {}: {:?}", s, e))?;
+
+    PublicKey::from_bytes(&bytes).map_err(|e| format!("Invalid public key {}: {:?}", s, e))
+}
@@ -0,0 +1,326 @@
+//! Provides the `DoppelgangerService`, which prevents the validator client from signing messages
+//! for a validator until it has observed the network for some number of epochs and seen no
+//! evidence that the same validator is being run elsewhere.
+//!
+//! ## Detection
+//!
+//! Signing is disabled for each validator as it is registered with the service. Starting from
+//! the epoch *after* registration, the service uses the `/consensus/individual_votes` endpoint to
+//! check whether the validator had an attestation included on-chain for each epoch. Since this
+//! validator client refuses to sign during that time, any such attestation must have been
+//! produced by another validator client (a "doppelganger").
+//!
+//! Attestations for epoch `n` may be included up until the end of epoch `n + 1`, so an epoch can
+//! only be checked once epoch `n + 2` has started. This means that signing is enabled no earlier
+//! than `detection_epochs + 2` epochs after registration.
+//!
+//! If a doppelganger is detected, signing remains disabled for that validator until the
+//! validator client is restarted.
+
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use environment::RuntimeContext;
+use exit_future::Signal;
+use futures::{FutureExt, StreamExt};
+use parking_lot::RwLock;
+use slog::{crit, debug, error, info};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+use tokio::time::{interval_at, Duration, Instant};
+use types::{ChainSpec, Epoch, EthSpec, PublicKey, PublicKeyBytes};
+
+/// The default number of epochs for which each validator is checked before signing is enabled.
+pub const DEFAULT_DOPPELGANGER_EPOCHS: u64 = 2;
+
+/// The state of doppelganger detection for a single validator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DoppelgangerState {
+    /// The validator is still being checked, signing is disabled.
+    Checking {
+        /// The next epoch which will be checked for attestations.
+        next_check_epoch: Epoch,
+        /// The number of epochs which must still be checked before signing is enabled.
+        remaining_epochs: u64,
+    },
+    /// No doppelganger was detected, signing is enabled.
+    Safe,
+    /// The validator was seen attesting whilst signing was disabled, signing will never be
+    /// enabled.
+    Detected,
+}
+
+/// Builds a `DoppelgangerService`.
+pub struct DoppelgangerServiceBuilder<T, E: EthSpec> {
+    slot_clock: Option<T>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    context: Option<RuntimeContext<E>>,
+    detection_epochs: u64,
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> DoppelgangerServiceBuilder<T, E> {
+    pub fn new() -> Self {
+        Self {
+            slot_clock: None,
+            beacon_nodes: None,
+            context: None,
+            detection_epochs: DEFAULT_DOPPELGANGER_EPOCHS,
+        }
+    }
+
+    pub fn slot_clock(mut self, slot_clock: T) -> Self {
+        self.slot_clock = Some(slot_clock);
+        self
+    }
+
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
+        self
+    }
+
+    pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    pub fn detection_epochs(mut self, detection_epochs: u64) -> Self {
+        self.detection_epochs = detection_epochs;
+        self
+    }
+
+    pub fn build(self) -> Result<DoppelgangerService<T, E>, String> {
+        Ok(DoppelgangerService {
+            inner: Arc::new(Inner {
+                states: RwLock::new(HashMap::new()),
+                slot_clock: self
+                    .slot_clock
+                    .ok_or_else(|| "Cannot build DoppelgangerService without slot_clock")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build DoppelgangerService without beacon_nodes")?,
+                context: self
+                    .context
+                    .ok_or_else(|| "Cannot build DoppelgangerService without runtime_context")?,
+                detection_epochs: self.detection_epochs,
+            }),
+        })
+    }
+}
+
+/// Helper to minimise `Arc` usage.
+pub struct Inner<T, E: EthSpec> {
+    states: RwLock<HashMap<PublicKey, DoppelgangerState>>,
+    slot_clock: T,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    context: RuntimeContext<E>,
+    detection_epochs: u64,
+}
+
+/// Checks the network for other instances of our validators before allowing them to sign.
+pub struct DoppelgangerService<T, E: EthSpec> {
+    inner: Arc<Inner<T, E>>,
+}
+
+impl<T, E: EthSpec> Clone for DoppelgangerService<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, E: EthSpec> Deref for DoppelgangerService<T, E> {
+    type Target = Inner<T, E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> DoppelgangerService<T, E> {
+    /// Start checking `pubkey` for doppelgangers, disabling signing until the checks have passed.
+    ///
+    /// Has no effect if `pubkey` is already known to the service.
+    pub fn register_new_validator(&self, pubkey: PublicKey) {
+        let state = match self.slot_clock.now() {
+            // If no epochs are to be checked there is nothing to wait for.
+            _ if self.detection_epochs == 0 => DoppelgangerState::Safe,
+            Some(slot) => DoppelgangerState::Checking {
+                next_check_epoch: slot.epoch(E::slots_per_epoch()) + 1,
+                remaining_epochs: self.detection_epochs,
+            },
+            // Prior to genesis no other validator client can have been signing messages.
+            None => DoppelgangerState::Safe,
+        };
+
+        self.states.write().entry(pubkey).or_insert(state);
+    }
+
+    /// Returns `true` if `pubkey` has passed all doppelganger checks and may be used for signing.
+    ///
+    /// Returns `false` for any validator which has not been registered with the service.
+    pub fn signing_enabled(&self, pubkey: &PublicKey) -> bool {
+        self.states.read().get(pubkey) == Some(&DoppelgangerState::Safe)
+    }
+
+    /// Starts the service which periodically checks for doppelgangers.
+    pub fn start_update_service(self, spec: &ChainSpec) -> Result<Signal, String> {
+        let log = self.context.log.clone();
+
+        let slot_duration = Duration::from_millis(spec.milliseconds_per_slot);
+        let duration_to_next_slot = self
+            .slot_clock
+            .duration_to_next_slot()
+            .ok_or_else(|| "Unable to determine duration to next slot".to_string())?;
+
+        info!(
+            log,
+            "Doppelganger protection enabled";
+            "detection_epochs" => self.detection_epochs,
+        );
+
+        // Run the checks half way through each slot, away from the start of the slot where
+        // blocks are produced.
+        let mut interval = interval_at(
+            Instant::now() + duration_to_next_slot + (slot_duration / 2),
+            slot_duration,
+        );
+
+        let (exit_signal, exit_fut) = exit_future::signal();
+        let runtime_handle = self.inner.context.runtime_handle.clone();
+
+        let interval_fut = async move {
+            while interval.next().await.is_some() {
+                self.clone().do_update().await.ok();
+            }
+        };
+
+        let future = futures::future::select(
+            Box::pin(interval_fut),
+            exit_fut.map(move |_| info!(log, "Shutdown complete")),
+        );
+        runtime_handle.spawn(future);
+
+        Ok(exit_signal)
+    }
+
+    /// Check each validator for doppelgangers in the earliest epoch that is both due to be
+    /// checked and which can no longer receive attestations.
+    async fn do_update(self) -> Result<(), ()> {
+        let log = &self.context.log;
+
+        let current_epoch = self
+            .slot_clock
+            .now()
+            .ok_or_else(|| error!(log, "Unable to read slot clock"))?
+            .epoch(E::slots_per_epoch());
+
+        // Group the validators by the epoch that they are next due to be checked at.
+        let mut to_check: HashMap<Epoch, Vec<PublicKey>> = HashMap::new();
+        for (pubkey, state) in self.states.read().iter() {
+            if let DoppelgangerState::Checking {
+                next_check_epoch, ..
+            } = state
+            {
+                if *next_check_epoch + 1 < current_epoch {
+                    to_check
+                        .entry(*next_check_epoch)
+                        .or_insert_with(Vec::new)
+                        .push(pubkey.clone());
+                }
+            }
+        }
+
+        for (check_epoch, pubkeys) in to_check {
+            // Requesting the votes for the following epoch returns the attestations for
+            // `check_epoch` as "previous epoch" attestations, including those that were included
+            // late.
+            let request_epoch = check_epoch + 1;
+            let pubkey_bytes = pubkeys
+                .iter()
+                .map(|pubkey| PublicKeyBytes::from(pubkey.clone()))
+                .collect::<Vec<_>>();
+            let pubkey_bytes_ref = &pubkey_bytes;
+
+            let responses = self
+                .beacon_nodes
+                .first_success(RequireSynced::Yes, |beacon_node| async move {
+                    beacon_node
+                        .http
+                        .consensus()
+                        .get_individual_votes(request_epoch, pubkey_bytes_ref.clone())
+                        .await
+                })
+                .await
+                .map_err(|e| {
+                    error!(
+                        log,
+                        "Unable to check for doppelgangers";
+                        "error" => format!("{}", e),
+                        "epoch" => check_epoch,
+                    )
+                })?;
+
+            let mut states = self.states.write();
+
+            for (pubkey, pubkey_bytes) in pubkeys.iter().zip(pubkey_bytes.iter()) {
+                let attested = responses
+                    .iter()
+                    .find(|response| response.pubkey == *pubkey_bytes)
+                    .and_then(|response| response.vote.as_ref())
+                    .map_or(false, |vote| vote.is_previous_epoch_attester);
+
+                let state = if let Some(state) = states.get_mut(pubkey) {
+                    state
+                } else {
+                    continue;
+                };
+
+                let remaining_epochs = match *state {
+                    DoppelgangerState::Checking {
+                        next_check_epoch,
+                        remaining_epochs,
+                    } if next_check_epoch == check_epoch => remaining_epochs,
+                    // The state was changed whilst we were waiting for the beacon node.
+                    _ => continue,
+                };
+
+                if attested {
+                    crit!(
+                        log,
+                        "Doppelganger detected";
+                        "msg" => "another instance of this validator is active, signing has been \
+                        disabled. Ensure the validator is not running elsewhere before restarting.",
+                        "epoch" => check_epoch,
+                        "validator" => format!("{:?}", pubkey),
+                    );
+                    *state = DoppelgangerState::Detected;
+                } else if remaining_epochs <= 1 {
+                    info!(
+                        log,
+                        "Doppelganger protection complete";
+                        "msg" => "starting validator duties",
+                        "validator" => format!("{:?}", pubkey),
+                    );
+                    *state = DoppelgangerState::Safe;
+                } else {
+                    debug!(
+                        log,
+                        "No doppelganger found";
+                        "remaining_epochs" => remaining_epochs - 1,
+                        "epoch" => check_epoch,
+                        "validator" => format!("{:?}", pubkey),
+                    );
+                    *state = DoppelgangerState::Checking {
+                        next_check_epoch: check_epoch + 1,
+                        remaining_epochs: remaining_epochs - 1,
+                    };
+                }
+            }
+        }
+
+        // Returning an error will stop the interval. This is not desired, a single failure
+        // should not stop all future attempts.
+        Ok(())
+    }
+}
@@ -1,9 +1,13 @@
-use crate::{is_synced::is_synced, validator_store::ValidatorStore};
+use crate::{
+    beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
+    metrics,
+    validator_store::ValidatorStore,
+};
 use environment::RuntimeContext;
 use exit_future::Signal;
 use futures::{FutureExt, StreamExt};
 use parking_lot::RwLock;
-use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
+use remote_beacon_node::PublishStatus;
 use rest_types::{ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription};
 use slog::{debug, error, info, trace, warn};
 use slot_clock::SlotClock;
@@ -39,7 +43,7 @@ impl DutyAndProof {
     ///
     /// - `self.validator_pubkey` is not known in `validator_store`.
     /// - There's an arith error during computation.
-    pub fn compute_selection_proof<T: SlotClock + 'static, E: EthSpec>(
+    pub async fn compute_selection_proof<T: SlotClock + 'static, E: EthSpec>(
         &mut self,
         validator_store: &ValidatorStore<T, E>,
     ) -> Result<(), String> {
@@ -56,6 +60,7 @@ impl DutyAndProof {
 
         let selection_proof = validator_store
             .produce_selection_proof(&self.duty.validator_pubkey, slot)
+            .await
             .ok_or_else(|| "Failed to produce selection proof".to_string())?;
 
         self.selection_proof = selection_proof
@@ -154,6 +159,17 @@ impl InsertOutcome {
             InsertOutcome::Invalid => false,
         }
     }
+
+    /// Returns the label used to record the outcome in the `DUTIES_FETCHED` metric.
+    pub fn metrics_label(&self) -> &'static str {
+        match self {
+            InsertOutcome::NewValidator => "new_validator",
+            InsertOutcome::NewEpoch => "new_epoch",
+            InsertOutcome::Identical => "identical",
+            InsertOutcome::Replaced { .. } => "replaced",
+            InsertOutcome::Invalid => "invalid",
+        }
+    }
 }
 
 #[derive(Default)]
@@ -242,17 +258,24 @@ impl DutiesStore {
         )
     }
 
-    fn insert<T: SlotClock + 'static, E: EthSpec>(
-        &self,
-        epoch: Epoch,
-        mut duties: DutyAndProof,
-        slots_per_epoch: u64,
-        validator_store: &ValidatorStore<T, E>,
-    ) -> Result<InsertOutcome, String> {
+    /// Returns `true` if `duty` is identical to the duties already stored for `epoch`.
+    fn is_known(&self, epoch: Epoch, duty: &ValidatorDuty) -> bool {
+        self.store
+            .read()
+            .get(&duty.validator_pubkey)
+            .and_then(|validator_map| validator_map.get(&epoch))
+            .map_or(false, |known_duties| known_duties.duty == *duty)
+    }
+
+    /// Insert `duties` into the store.
+    ///
+    /// The selection proof must already have been computed for `duties`, unless they are
+    /// identical to some already-known duties.
+    fn insert(&self, epoch: Epoch, duties: DutyAndProof, slots_per_epoch: u64) -> InsertOutcome {
         let mut store = self.store.write();
 
         if !duties_match_epoch(&duties.duty, epoch, slots_per_epoch) {
-            return Ok(InsertOutcome::Invalid);
+            return InsertOutcome::Invalid;
         }
 
         // TODO: refactor with Entry.
@@ -260,31 +283,22 @@ impl DutiesStore {
         if let Some(validator_map) = store.get_mut(&duties.duty.validator_pubkey) {
             if let Some(known_duties) = validator_map.get_mut(&epoch) {
                 if known_duties.duty == duties.duty {
-                    Ok(InsertOutcome::Identical)
+                    InsertOutcome::Identical
                 } else {
-                    // Compute the selection proof.
-                    duties.compute_selection_proof(validator_store)?;
-
                     // Determine if a re-subscription is required.
                     let should_resubscribe = duties.subscription_eq(known_duties);
 
                     // Replace the existing duties.
                     *known_duties = duties;
 
-                    Ok(InsertOutcome::Replaced { should_resubscribe })
+                    InsertOutcome::Replaced { should_resubscribe }
                 }
             } else {
-                // Compute the selection proof.
-                duties.compute_selection_proof(validator_store)?;
-
                 validator_map.insert(epoch, duties);
 
-                Ok(InsertOutcome::NewEpoch)
+                InsertOutcome::NewEpoch
             }
         } else {
-            // Compute the selection proof.
-            duties.compute_selection_proof(validator_store)?;
-
             let validator_pubkey = duties.duty.validator_pubkey.clone();
 
             let mut validator_map = HashMap::new();
@@ -292,7 +306,7 @@ impl DutiesStore {
 
             store.insert(validator_pubkey, validator_map);
 
-            Ok(InsertOutcome::NewValidator)
+            InsertOutcome::NewValidator
         }
     }
 
@@ -309,7 +323,7 @@ impl DutiesStore {
 pub struct DutiesServiceBuilder<T, E: EthSpec> {
     validator_store: Option<ValidatorStore<T, E>>,
     slot_clock: Option<T>,
-    beacon_node: Option<RemoteBeaconNode<E>>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
     allow_unsynced_beacon_node: bool,
 }
@@ -319,7 +333,7 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
         Self {
             validator_store: None,
             slot_clock: None,
-            beacon_node: None,
+            beacon_nodes: None,
             context: None,
             allow_unsynced_beacon_node: false,
         }
@@ -335,8 +349,8 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
         self
     }
 
-    pub fn beacon_node(mut self, beacon_node: RemoteBeaconNode<E>) -> Self {
-        self.beacon_node = Some(beacon_node);
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
         self
     }
 
@@ -361,9 +375,9 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or_else(|| "Cannot build DutiesService without slot_clock")?,
-                beacon_node: self
-                    .beacon_node
-                    .ok_or_else(|| "Cannot build DutiesService without beacon_node")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build DutiesService without beacon_nodes")?,
                 context: self
                     .context
                     .ok_or_else(|| "Cannot build DutiesService without runtime_context")?,
@@ -378,7 +392,7 @@ pub struct Inner<T, E: EthSpec> {
     store: Arc<DutiesStore>,
     validator_store: ValidatorStore<T, E>,
     pub(crate) slot_clock: T,
-    pub(crate) beacon_node: RemoteBeaconNode<E>,
+    pub(crate) beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
     /// If true, the duties service will poll for duties from the beacon node even if it is not
     /// synced.
@@ -415,6 +429,16 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
         self.validator_store.num_voting_validators()
     }
 
+    /// Returns `RequireSynced::No` if the user has permitted duties to be obtained from an
+    /// unsynced beacon node.
+    fn require_synced(&self) -> RequireSynced {
+        if self.allow_unsynced_beacon_node {
+            RequireSynced::No
+        } else {
+            RequireSynced::Yes
+        }
+    }
+
     /// Returns the total number of validators that should propose in the given epoch.
     pub fn proposer_count(&self, epoch: Epoch) -> usize {
         self.store.proposer_count(epoch)
@@ -485,9 +509,7 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
     async fn do_update(self) -> Result<(), ()> {
         let log = &self.context.log;
 
-        if !is_synced(&self.beacon_node, &self.slot_clock, None).await
-            && !self.allow_unsynced_beacon_node
-        {
+        if self.beacon_nodes.num_synced() == 0 && !self.allow_unsynced_beacon_node {
             return Ok(());
         }
 
@@ -542,13 +564,18 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
     /// Attempt to download the duties of all managed validators for the given `epoch`.
     async fn update_epoch(self, epoch: Epoch) -> Result<(), String> {
         let pubkeys = self.validator_store.voting_pubkeys();
+        let pubkeys_ref = &pubkeys;
         let all_duties = self
-            .beacon_node
-            .http
-            .validator()
-            .get_duties(epoch, pubkeys.as_slice())
+            .beacon_nodes
+            .first_success(self.require_synced(), |beacon_node| async move {
+                beacon_node
+                    .http
+                    .validator()
+                    .get_duties(epoch, pubkeys_ref.as_slice())
+                    .await
+            })
             .await
-            .map_err(move |e| format!("Failed to get duties for epoch {}: {:?}", epoch, e))?;
+            .map_err(move |e| format!("Failed to get duties for epoch {}: {}", epoch, e))?;
 
         let log = self.context.log.clone();
 
@@ -560,70 +587,82 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
 
         // For each of the duties, attempt to insert them into our local store and build a
         // list of new or changed selections proofs for any aggregating validators.
-        let validator_subscriptions = all_duties
-            .into_iter()
-            .filter_map(|remote_duties| {
-                // Convert the remote duties into our local representation.
-                let duties: DutyAndProof = remote_duties
-                    .clone()
-                    .try_into()
-                    .map_err(|e| {
-                        error!(
-                            log,
-                            "Unable to convert remote duties";
-                            "error" => e
-                        )
-                    })
-                    .ok()?;
-
-                let validator_pubkey = duties.duty.validator_pubkey.clone();
-
-                // Attempt to update our local store.
-                let outcome = self
-                    .store
-                    .insert(epoch, duties, E::slots_per_epoch(), &self.validator_store)
-                    .map_err(|e| {
-                        error!(
-                            log,
-                            "Unable to store duties";
-                            "error" => e
-                        )
-                    })
-                    .ok()?;
+        let mut validator_subscriptions = vec![];
+
+        for remote_duties in all_duties {
+            // Convert the remote duties into our local representation.
+            let mut duties: DutyAndProof = match remote_duties.clone().try_into() {
+                Ok(duties) => duties,
+                Err(e) => {
+                    error!(
+                        log,
+                        "Unable to convert remote duties";
+                        "error" => e
+                    );
+                    continue;
+                }
+            };
 
-                match &outcome {
-                    InsertOutcome::NewValidator => {
-                        debug!(
-                            log,
-                            "First duty assignment for validator";
-                            "proposal_slots" => format!("{:?}", &remote_duties.block_proposal_slots),
-                            "attestation_slot" => format!("{:?}", &remote_duties.attestation_slot),
-                            "validator" => format!("{:?}", &remote_duties.validator_pubkey)
-                        );
-                        new_validator += 1;
-                    }
-                    InsertOutcome::NewEpoch => new_epoch += 1,
-                    InsertOutcome::Identical => identical += 1,
-                    InsertOutcome::Replaced { .. } => replaced += 1,
-                    InsertOutcome::Invalid => invalid += 1,
-                };
+            let validator_pubkey = duties.duty.validator_pubkey.clone();
 
-                // The selection proof is computed on `store.insert`, so it's necessary to check
-                // with the store that the validator is an aggregator.
-                let is_aggregator = self.store.is_aggregator(&validator_pubkey, &epoch)?;
+            // Computing the selection proof may require a request to a remote signer, so only do
+            // so if the duties are not already known.
+            if !self.store.is_known(epoch, &duties.duty) {
+                if let Err(e) = duties.compute_selection_proof(&self.validator_store).await {
+                    error!(
+                        log,
+                        "Unable to store duties";
+                        "error" => e
+                    );
+                    continue;
+                }
+            }
 
-                if outcome.is_subscription_candidate() {
-                    Some(ValidatorSubscription {
-                        validator_index: remote_duties.validator_index?,
-                        attestation_committee_index: remote_duties.attestation_committee_index?,
-                        slot: remote_duties.attestation_slot?,
-                        is_aggregator,
-                    })
+            // Attempt to update our local store.
+            let outcome = self.store.insert(epoch, duties, E::slots_per_epoch());
+
+            match &outcome {
+                InsertOutcome::NewValidator => {
+                    debug!(
+                        log,
+                        "First duty assignment for validator";
+                        "proposal_slots" => format!("{:?}", &remote_duties.block_proposal_slots),
+                        "attestation_slot" => format!("{:?}", &remote_duties.attestation_slot),
+                        "validator" => format!("{:?}", &remote_duties.validator_pubkey)
+                    );
+                    new_validator += 1;
+                }
+                InsertOutcome::NewEpoch => new_epoch += 1,
+                InsertOutcome::Identical => identical += 1,
+                InsertOutcome::Replaced { .. } => replaced += 1,
+                InsertOutcome::Invalid => invalid += 1,
+            };
+            metrics::inc_counter_vec(&metrics::DUTIES_FETCHED, &[outcome.metrics_label()]);
+
+            // The selection proof is only computed for new or changed duties, so it's necessary
+            // to check with the store that the validator is an aggregator.
+            let is_aggregator =
+                if let Some(is_aggregator) = self.store.is_aggregator(&validator_pubkey, &epoch) {
+                    is_aggregator
                 } else {
-                    None
+                    continue;
+                };
+
+            if outcome.is_subscription_candidate() {
+                if let (Some(validator_index), Some(attestation_committee_index), Some(slot)) = (
+                    remote_duties.validator_index,
+                    remote_duties.attestation_committee_index,
+                    remote_duties.attestation_slot,
+                ) {
+                    validator_subscriptions.push(ValidatorSubscription {
+                        validator_index,
+                        attestation_committee_index,
+                        slot,
+                        is_aggregator,
+                    });
                 }
-            })
-            .collect::<Vec<_>>();
+            }
+        }
 
         if invalid > 0 {
             error!(
@@ -660,12 +699,17 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
 
             Ok(())
         } else {
-            self.beacon_node
-                .http
-                .validator()
-                .subscribe(validator_subscriptions)
+            let validator_subscriptions_ref = &validator_subscriptions;
+            self.beacon_nodes
+                .first_success(self.require_synced(), |beacon_node| async move {
+                    beacon_node
+                        .http
+                        .validator()
+                        .subscribe(validator_subscriptions_ref.clone())
+                        .await
+                })
                 .await
-                .map_err(|e| format!("Failed to subscribe validators: {:?}", e))
+                .map_err(|e| format!("Failed to subscribe validators: {}", e))
                 .map(move |status| {
                     match status {
                         PublishStatus::Valid => debug!(
@@ -1,8 +1,8 @@
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
 use environment::RuntimeContext;
 use exit_future::Signal;
 use futures::{FutureExt, StreamExt};
 use parking_lot::RwLock;
-use remote_beacon_node::RemoteBeaconNode;
 use slog::{debug, info, trace};
 use slot_clock::SlotClock;
 use std::ops::Deref;
@@ -17,7 +17,7 @@ const TIME_DELAY_FROM_SLOT: Duration = Duration::from_millis(80);
 pub struct ForkServiceBuilder<T, E: EthSpec> {
     fork: Option<Fork>,
     slot_clock: Option<T>,
-    beacon_node: Option<RemoteBeaconNode<E>>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
 }
 
@@ -26,7 +26,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ForkServiceBuilder<T, E> {
         Self {
             fork: None,
             slot_clock: None,
-            beacon_node: None,
+            beacon_nodes: None,
             context: None,
         }
     }
@@ -36,8 +36,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ForkServiceBuilder<T, E> {
         self
     }
 
-    pub fn beacon_node(mut self, beacon_node: RemoteBeaconNode<E>) -> Self {
-        self.beacon_node = Some(beacon_node);
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
         self
     }
 
@@ -53,9 +53,9 @@ impl<T: SlotClock + 'static, E: EthSpec> ForkServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or_else(|| "Cannot build ForkService without slot_clock")?,
-                beacon_node: self
-                    .beacon_node
-                    .ok_or_else(|| "Cannot build ForkService without beacon_node")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build ForkService without beacon_nodes")?,
                 context: self
                     .context
                     .ok_or_else(|| "Cannot build ForkService without runtime_context")?,
@@ -67,7 +67,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ForkServiceBuilder<T, E> {
 /// Helper to minimise `Arc` usage.
 pub struct Inner<T, E: EthSpec> {
     fork: RwLock<Option<Fork>>,
-    beacon_node: RemoteBeaconNode<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
     slot_clock: T,
 }
@@ -148,16 +148,16 @@ impl<T: SlotClock + 'static, E: EthSpec> ForkService<T, E> {
 
         let fork = self
             .inner
-            .beacon_node
-            .http
-            .beacon()
-            .get_fork()
+            .beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node.http.beacon().get_fork().await
+            })
             .await
             .map_err(|e| {
                 trace!(
                     log,
                     "Fork update failed";
-                    "error" => format!("Error retrieving fork: {:?}", e)
+                    "error" => format!("Error retrieving fork: {}", e)
                 )
             })?;
 
@@ -0,0 +1,113 @@
+use rand::RngCore;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+/// The name of the file in the validator client data directory which contains the API token.
+pub const API_TOKEN_FILENAME: &str = "api-token.txt";
+
+/// All API tokens begin with this prefix, making them easy to recognise in configuration files.
+pub const API_TOKEN_PREFIX: &str = "api-token-";
+
+/// The number of random bytes in an API token.
+const API_TOKEN_BYTES: usize = 32;
+
+/// The secret which must be supplied as a bearer token in the `Authorization` header of every
+/// request to the validator client HTTP API.
+///
+/// The token is stored in plain text in the validator client data directory, which should only be
+/// readable by the user running the validator client.
+pub struct ApiSecret {
+    token: String,
+}
+
+impl ApiSecret {
+    /// Read the token from `API_TOKEN_FILENAME` in `dir`, or generate a new random token and
+    /// save it to that file if it does not yet exist.
+    pub fn create_or_open<P: AsRef<Path>>(dir: P) -> Result<Self, String> {
+        let path = dir.as_ref().join(API_TOKEN_FILENAME);
+
+        if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+            let token = contents.trim().to_string();
+
+            if !token.starts_with(API_TOKEN_PREFIX) || token.len() == API_TOKEN_PREFIX.len() {
+                return Err(format!(
+                    "Invalid API token in {:?}, delete the file to generate a new token",
+                    path
+                ));
+            }
+
+            Ok(Self { token })
+        } else {
+            let mut bytes = [0; API_TOKEN_BYTES];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            let token = format!("{}{}", API_TOKEN_PREFIX, hex::encode(&bytes));
+
+            // Only the owner may read or modify the token (600 permissions).
+            OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path)
+                .and_then(|mut file| file.write_all(token.as_bytes()))
+                .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))?;
+
+            Ok(Self { token })
+        }
+    }
+
+    /// Returns the token which must be supplied to the API.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Returns the expected value of the `Authorization` header.
+    pub fn auth_header_value(&self) -> String {
+        format!("Bearer {}", self.token)
+    }
+
+    /// Returns `true` if `header_value` is a valid `Authorization` header for this secret.
+    pub fn is_authorized(&self, header_value: &str) -> bool {
+        let expected = self.auth_header_value();
+
+        // Compare every byte so that the time taken does not depend on how much of the token was
+        // guessed correctly.
+        expected.len() == header_value.len()
+            && expected
+                .bytes()
+                .zip(header_value.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    #[test]
+    fn create_then_open() {
+        let dir = TempDir::new("api_secret").unwrap();
+
+        let created = ApiSecret::create_or_open(dir.path()).unwrap();
+        assert!(created.token().starts_with(API_TOKEN_PREFIX));
+
+        let opened = ApiSecret::create_or_open(dir.path()).unwrap();
+        assert_eq!(created.token(), opened.token());
+    }
+
+    #[test]
+    fn authorization() {
+        let dir = TempDir::new("api_secret").unwrap();
+        let secret = ApiSecret::create_or_open(dir.path()).unwrap();
+
+        assert!(secret.is_authorized(&secret.auth_header_value()));
+        assert!(!secret.is_authorized(secret.token()));
+        assert!(!secret.is_authorized(""));
+        assert!(!secret.is_authorized(&format!("Bearer {}0", secret.token())));
+    }
+}
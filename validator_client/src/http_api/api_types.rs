@@ -0,0 +1,38 @@
+//! Types which are sent to and received from the validator client HTTP API.
+
+use eth2_keystore::Keystore;
+use serde_derive::{Deserialize, Serialize};
+use slashing_protection::interchange::Interchange;
+use types::PublicKey;
+
+/// A validator known to the validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorData {
+    pub enabled: bool,
+    pub voting_pubkey: PublicKey,
+}
+
+/// The body of `POST /lighthouse/validators/keystore`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeystoreValidatorsPostRequest {
+    /// The password which decrypts `keystore`.
+    pub password: String,
+    /// If `false`, the validator is imported but does not perform duties until it is enabled.
+    pub enable: bool,
+    /// An EIP-2335 voting keystore.
+    pub keystore: Keystore,
+}
+
+/// The body of `PATCH /lighthouse/validators/{voting_pubkey}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorPatchRequest {
+    pub enabled: bool,
+}
+
+/// The response to `DELETE /lighthouse/validators/{voting_pubkey}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeleteValidatorResponse {
+    /// The slashing protection data for the deleted validator, which should be imported into any
+    /// other client which will use the same key.
+    pub slashing_protection: Interchange,
+}
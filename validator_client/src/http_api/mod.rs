@@ -0,0 +1,621 @@
+//! An HTTP API which allows the validators managed by the validator client to be listed, imported,
+//! enabled, disabled and deleted whilst the validator client is running.
+//!
+//! Every request must include an `Authorization: Bearer <token>` header, where the token is read
+//! from `API_TOKEN_FILENAME` in the validator client data directory (see `ApiSecret`).
+//!
+//! ## Endpoints
+//!
+//! - `GET /lighthouse/validators`: list all validators.
+//! - `GET /lighthouse/validators/{voting_pubkey}`: get a single validator.
+//! - `POST /lighthouse/validators/keystore`: import an EIP-2335 keystore.
+//! - `PATCH /lighthouse/validators/{voting_pubkey}`: enable or disable a validator.
+//! - `DELETE /lighthouse/validators/{voting_pubkey}`: delete a validator, returning its slashing
+//!   protection data.
+
+mod api_secret;
+pub mod api_types;
+
+use crate::validator_store::ValidatorStore;
+use api_types::{
+    DeleteValidatorResponse, KeystoreValidatorsPostRequest, ValidatorData, ValidatorPatchRequest,
+};
+use exit_future::Signal;
+use futures::{FutureExt, TryFutureExt};
+use hyper::header::AUTHORIZATION;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_derive::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use types::{EthSpec, PublicKey};
+use validator_dir::Builder as ValidatorDirBuilder;
+
+pub use api_secret::{ApiSecret, API_TOKEN_FILENAME};
+
+/// The path prefix shared by all validator endpoints.
+const VALIDATORS_PATH: &str = "/lighthouse/validators";
+
+/// Configuration for the validator client HTTP API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// Enable the HTTP API server.
+    pub enabled: bool,
+    /// The IPv4 address the HTTP API server will listen on.
+    pub listen_addr: Ipv4Addr,
+    /// The port the HTTP API server will listen on.
+    pub listen_port: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: Ipv4Addr::new(127, 0, 0, 1),
+            listen_port: 5062,
+        }
+    }
+}
+
+/// The state shared between all requests to the HTTP API.
+pub struct Context<T, E: EthSpec> {
+    pub api_secret: ApiSecret,
+    pub validator_store: ValidatorStore<T, E>,
+    /// The directory in which new validator directories are created.
+    pub validator_dir: PathBuf,
+    /// The directory in which the passwords for new validator keystores are stored.
+    pub secrets_dir: PathBuf,
+    pub runtime_handle: Handle,
+    pub log: Logger,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ApiError {
+    Unauthorized(String),
+    BadRequest(String),
+    NotFound(String),
+    MethodNotAllowed(String),
+    ServerError(String),
+}
+
+pub type ApiResult = Result<Response<Body>, ApiError>;
+
+impl ApiError {
+    pub fn status_code(self) -> (StatusCode, String) {
+        match self {
+            ApiError::Unauthorized(desc) => (StatusCode::UNAUTHORIZED, desc),
+            ApiError::BadRequest(desc) => (StatusCode::BAD_REQUEST, desc),
+            ApiError::NotFound(desc) => (StatusCode::NOT_FOUND, desc),
+            ApiError::MethodNotAllowed(desc) => (StatusCode::METHOD_NOT_ALLOWED, desc),
+            ApiError::ServerError(desc) => (StatusCode::INTERNAL_SERVER_ERROR, desc),
+        }
+    }
+}
+
+impl Into<Response<Body>> for ApiError {
+    fn into(self) -> Response<Body> {
+        let (status_code, desc) = self.status_code();
+        Response::builder()
+            .status(status_code)
+            .header("content-type", "text/plain; charset=utf-8")
+            .body(Body::from(desc))
+            .expect("Response should always be created.")
+    }
+}
+
+/// Start the HTTP API server, returning a signal which shuts it down and the address it is
+/// listening on.
+///
+/// Must be called from within the context of a tokio runtime.
+pub fn start_server<T: SlotClock + 'static, E: EthSpec>(
+    config: &Config,
+    context: Arc<Context<T, E>>,
+) -> Result<(Signal, SocketAddr), String> {
+    let log = context.log.clone();
+    let runtime_handle = context.runtime_handle.clone();
+
+    let make_service = make_service_fn(move |_| {
+        let context = context.clone();
+
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                route(req, context.clone())
+            }))
+        }
+    });
+
+    let bind_addr = (config.listen_addr, config.listen_port).into();
+    let server = Server::try_bind(&bind_addr)
+        .map_err(|e| format!("Unable to bind HTTP API to {}: {:?}", bind_addr, e))?
+        .serve(make_service);
+
+    // This may be different to `bind_addr` if the port was 0 (which allows the OS to choose a
+    // free port).
+    let listen_addr = server.local_addr();
+
+    let (exit_signal, exit) = exit_future::signal();
+    let exit_log = log.clone();
+    let error_log = log.clone();
+    let server_future = server
+        .with_graceful_shutdown(exit.map(move |_| info!(exit_log, "HTTP API shutdown")))
+        .map_err(move |e| warn!(error_log, "HTTP API failed"; "error" => format!("{:?}", e)));
+
+    info!(
+        log,
+        "HTTP API started";
+        "address" => format!("{}", listen_addr.ip()),
+        "port" => listen_addr.port(),
+    );
+
+    runtime_handle.spawn(server_future);
+
+    Ok((exit_signal, listen_addr))
+}
+
+async fn route<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    context: Arc<Context<T, E>>,
+) -> Result<Response<Body>, hyper::Error> {
+    let path = req.uri().path().to_string();
+    let method = req.method().clone();
+
+    let result = match check_authorization(&req, &context.api_secret) {
+        Ok(()) => match (&method, path.as_ref()) {
+            (&Method::GET, VALIDATORS_PATH) => get_validators(&context),
+            (&Method::POST, "/lighthouse/validators/keystore") => {
+                post_validators_keystore(req, &context).await
+            }
+            (_, path) if path.starts_with(VALIDATORS_PATH) => match parse_validator_path(path) {
+                Ok(pubkey) => match method {
+                    Method::GET => get_validator(&pubkey, &context),
+                    Method::PATCH => patch_validator(req, &pubkey, &context).await,
+                    Method::DELETE => delete_validator(&pubkey, &context),
+                    _ => Err(ApiError::MethodNotAllowed(path.to_string())),
+                },
+                Err(e) => Err(e),
+            },
+            _ => Err(ApiError::NotFound(
+                "Request path and/or method not found.".to_string(),
+            )),
+        },
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(response) => Ok(response),
+        Err(e) => {
+            if let ApiError::ServerError(desc) = &e {
+                warn!(
+                    context.log,
+                    "HTTP API request failed";
+                    "path" => path,
+                    "method" => format!("{}", method),
+                    "error" => desc,
+                );
+            }
+            Ok(e.into())
+        }
+    }
+}
+
+/// Ensure the request contains a bearer token matching `api_secret`.
+fn check_authorization(req: &Request<Body>, api_secret: &ApiSecret) -> Result<(), ApiError> {
+    let header = req
+        .headers()
+        .get(AUTHORIZATION)
+        .ok_or_else(|| ApiError::Unauthorized("Missing Authorization header".to_string()))?
+        .to_str()
+        .map_err(|_| ApiError::Unauthorized("Invalid Authorization header".to_string()))?;
+
+    if api_secret.is_authorized(header) {
+        Ok(())
+    } else {
+        Err(ApiError::Unauthorized("Invalid API token".to_string()))
+    }
+}
+
+/// Parse the public key from a path of the form `/lighthouse/validators/{voting_pubkey}`.
+fn parse_validator_path(path: &str) -> Result<PublicKey, ApiError> {
+    let prefix = format!("{}/", VALIDATORS_PATH);
+    if !path.starts_with(&prefix) {
+        return Err(ApiError::NotFound(
+            "Request path and/or method not found.".to_string(),
+        ));
+    }
+
+    let pubkey = &path[prefix.len()..];
+    if !pubkey.starts_with("0x") {
+        return Err(ApiError::BadRequest(format!(
+            "Public key must have a 0x prefix: {}",
+            pubkey
+        )));
+    }
+
+    hex::decode(&pubkey[2..])
+        .map_err(|e| ApiError::BadRequest(format!("Invalid hex {}: {:?}", pubkey, e)))
+        .and_then(|bytes| {
+            PublicKey::from_bytes(&bytes).map_err(|e| {
+                ApiError::BadRequest(format!("Invalid public key {}: {:?}", pubkey, e))
+            })
+        })
+}
+
+async fn parse_json_body<T: DeserializeOwned>(req: Request<Body>) -> Result<T, ApiError> {
+    let bytes = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Unable to get request body: {:?}", e)))?;
+
+    serde_json::from_slice(&bytes)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse JSON body: {:?}", e)))
+}
+
+fn json_response<T: Serialize>(item: &T) -> ApiResult {
+    let body = serde_json::to_vec(item).map_err(|e| {
+        ApiError::ServerError(format!("Unable to serialize response body: {:?}", e))
+    })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+}
+
+/// `GET /lighthouse/validators`
+fn get_validators<T: SlotClock + 'static, E: EthSpec>(context: &Context<T, E>) -> ApiResult {
+    let validators = context
+        .validator_store
+        .validator_statuses()
+        .into_iter()
+        .map(|(voting_pubkey, enabled)| ValidatorData {
+            enabled,
+            voting_pubkey,
+        })
+        .collect::<Vec<_>>();
+
+    json_response(&validators)
+}
+
+/// `GET /lighthouse/validators/{voting_pubkey}`
+fn get_validator<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: &PublicKey,
+    context: &Context<T, E>,
+) -> ApiResult {
+    let enabled = context
+        .validator_store
+        .validator_status(pubkey)
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown validator: {:?}", pubkey)))?;
+
+    json_response(&ValidatorData {
+        enabled,
+        voting_pubkey: pubkey.clone(),
+    })
+}
+
+/// `POST /lighthouse/validators/keystore`
+async fn post_validators_keystore<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    context: &Context<T, E>,
+) -> ApiResult {
+    let body: KeystoreValidatorsPostRequest = parse_json_body(req).await?;
+    let keystore = body.keystore;
+    let password = body.password;
+
+    // Decrypting a keystore is deliberately expensive, avoid blocking the executor.
+    let (keystore, password, voting_keypair) = context
+        .runtime_handle
+        .spawn_blocking(move || {
+            keystore
+                .decrypt_keypair(password.as_bytes())
+                .map(|keypair| (keystore, password, keypair))
+        })
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Keystore decryption failed: {:?}", e)))?
+        .map_err(|e| ApiError::BadRequest(format!("Unable to decrypt keystore: {:?}", e)))?;
+
+    if context
+        .validator_store
+        .validator_status(&voting_keypair.pk)
+        .is_some()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Validator {:?} is already known",
+            voting_keypair.pk
+        )));
+    }
+
+    let validator_dir =
+        ValidatorDirBuilder::new(context.validator_dir.clone(), context.secrets_dir.clone())
+            .voting_keystore(keystore, password.as_bytes())
+            .store_withdrawal_keystore(false)
+            .build()
+            .map_err(|e| {
+                ApiError::ServerError(format!("Unable to create validator directory: {:?}", e))
+            })?;
+
+    validator_dir.set_enabled(body.enable).map_err(|e| {
+        ApiError::ServerError(format!("Unable to update validator directory: {:?}", e))
+    })?;
+
+    let voting_pubkey = voting_keypair.pk.clone();
+
+    context
+        .validator_store
+        .add_validator_keystore(validator_dir, voting_keypair)
+        .map_err(ApiError::ServerError)?;
+
+    json_response(&ValidatorData {
+        enabled: body.enable,
+        voting_pubkey,
+    })
+}
+
+/// `PATCH /lighthouse/validators/{voting_pubkey}`
+async fn patch_validator<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    pubkey: &PublicKey,
+    context: &Context<T, E>,
+) -> ApiResult {
+    let body: ValidatorPatchRequest = parse_json_body(req).await?;
+
+    let known = context
+        .validator_store
+        .set_validator_enabled(pubkey, body.enabled)
+        .map_err(ApiError::ServerError)?;
+
+    if !known {
+        return Err(ApiError::NotFound(format!(
+            "Unknown validator: {:?}",
+            pubkey
+        )));
+    }
+
+    json_response(&ValidatorData {
+        enabled: body.enabled,
+        voting_pubkey: pubkey.clone(),
+    })
+}
+
+/// `DELETE /lighthouse/validators/{voting_pubkey}`
+fn delete_validator<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: &PublicKey,
+    context: &Context<T, E>,
+) -> ApiResult {
+    let slashing_protection = context
+        .validator_store
+        .remove_validator(pubkey)
+        .map_err(ApiError::ServerError)?
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown validator: {:?}", pubkey)))?;
+
+    json_response(&DeleteValidatorResponse {
+        slashing_protection,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config as ValidatorClientConfig;
+    use eth2_keystore::KeystoreBuilder;
+    use slashing_protection::interchange::{CompleteInterchangeData, InterchangeData};
+    use slot_clock::TestingSlotClock;
+    use std::fs::create_dir_all;
+    use tempdir::TempDir;
+    use types::{test_utils::generate_deterministic_keypair, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    const PASSWORD: &str = "cats";
+
+    /// Calls `route` directly against a validator client with no validators.
+    struct ApiTester {
+        context: Arc<Context<TestingSlotClock, E>>,
+        _dir: TempDir,
+    }
+
+    impl ApiTester {
+        fn new() -> Self {
+            let dir = TempDir::new("http_api").unwrap();
+            let config = ValidatorClientConfig {
+                data_dir: dir.path().join("validators"),
+                secrets_dir: dir.path().join("secrets"),
+                ..ValidatorClientConfig::default()
+            };
+            create_dir_all(&config.data_dir).unwrap();
+            create_dir_all(&config.secrets_dir).unwrap();
+
+            let log = environment::null_logger().unwrap();
+
+            let context = Arc::new(Context {
+                api_secret: ApiSecret::create_or_open(&config.data_dir).unwrap(),
+                validator_store: ValidatorStore::for_testing(&config, log.clone()),
+                validator_dir: config.data_dir.clone(),
+                secrets_dir: config.secrets_dir.clone(),
+                runtime_handle: Handle::current(),
+                log,
+            });
+
+            Self { context, _dir: dir }
+        }
+
+        async fn request(
+            &self,
+            method: Method,
+            path: &str,
+            auth: Option<String>,
+            body: Body,
+        ) -> Response<Body> {
+            let mut builder = Request::builder().method(method).uri(path);
+            if let Some(auth) = auth {
+                builder = builder.header(AUTHORIZATION, auth);
+            }
+
+            route(builder.body(body).unwrap(), self.context.clone())
+                .await
+                .unwrap()
+        }
+
+        async fn authorized_request<T: Serialize>(
+            &self,
+            method: Method,
+            path: &str,
+            body: Option<&T>,
+        ) -> Response<Body> {
+            let body = body
+                .map(|body| Body::from(serde_json::to_vec(body).unwrap()))
+                .unwrap_or_else(Body::empty);
+            let auth = self.context.api_secret.auth_header_value();
+
+            self.request(method, path, Some(auth), body).await
+        }
+
+        async fn list_validators(&self) -> Vec<ValidatorData> {
+            let response = self
+                .authorized_request::<()>(Method::GET, VALIDATORS_PATH, None)
+                .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            response_json(response).await
+        }
+    }
+
+    async fn response_json<T: DeserializeOwned>(response: Response<Body>) -> T {
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn validator_path(pubkey: &PublicKey) -> String {
+        format!("{}/{}", VALIDATORS_PATH, pubkey.as_hex_string())
+    }
+
+    #[tokio::test]
+    async fn requests_without_valid_token_are_rejected() {
+        let tester = ApiTester::new();
+
+        let response = tester
+            .request(Method::GET, VALIDATORS_PATH, None, Body::empty())
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = tester
+            .request(
+                Method::GET,
+                VALIDATORS_PATH,
+                Some("Bearer not-the-token".to_string()),
+                Body::empty(),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let pubkey = generate_deterministic_keypair(0).pk;
+        let response = tester
+            .request(
+                Method::DELETE,
+                &validator_path(&pubkey),
+                None,
+                Body::empty(),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn add_list_and_remove_validator() {
+        let tester = ApiTester::new();
+        assert!(tester.list_validators().await.is_empty());
+
+        let keypair = generate_deterministic_keypair(0);
+        let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), "".into())
+            .unwrap()
+            .build()
+            .unwrap();
+        let request = KeystoreValidatorsPostRequest {
+            password: PASSWORD.to_string(),
+            enable: true,
+            keystore,
+        };
+
+        let response = tester
+            .authorized_request(
+                Method::POST,
+                "/lighthouse/validators/keystore",
+                Some(&request),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let expected = ValidatorData {
+            enabled: true,
+            voting_pubkey: keypair.pk.clone(),
+        };
+        assert_eq!(response_json::<ValidatorData>(response).await, expected);
+        assert_eq!(tester.list_validators().await, vec![expected]);
+
+        let validator_dir = tester
+            .context
+            .validator_dir
+            .join(keypair.pk.as_hex_string());
+        let password_path = tester.context.secrets_dir.join(keypair.pk.as_hex_string());
+        assert!(validator_dir.exists());
+        assert!(password_path.exists());
+
+        // Adding the same keystore twice is rejected.
+        let response = tester
+            .authorized_request(
+                Method::POST,
+                "/lighthouse/validators/keystore",
+                Some(&request),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = tester
+            .authorized_request::<()>(Method::DELETE, &validator_path(&keypair.pk), None)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response: DeleteValidatorResponse = response_json(response).await;
+        assert_eq!(
+            response.slashing_protection.data,
+            InterchangeData::Complete(vec![CompleteInterchangeData {
+                pubkey: keypair.pk.clone(),
+                signed_blocks: vec![],
+                signed_attestations: vec![],
+            }])
+        );
+
+        assert!(tester.list_validators().await.is_empty());
+        assert!(!validator_dir.exists());
+        assert!(!password_path.exists());
+
+        let response = tester
+            .authorized_request::<()>(Method::DELETE, &validator_path(&keypair.pk), None)
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn parse_validator_paths() {
+        let pubkey = types::test_utils::generate_deterministic_keypair(0).pk;
+
+        assert_eq!(
+            parse_validator_path(&format!("{}/{}", VALIDATORS_PATH, pubkey.as_hex_string())),
+            Ok(pubkey)
+        );
+        assert!(matches!(
+            parse_validator_path(&format!("{}/0x1234", VALIDATORS_PATH)),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            parse_validator_path(&format!("{}/1234", VALIDATORS_PATH)),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            parse_validator_path(&format!("{}extra", VALIDATORS_PATH)),
+            Err(ApiError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+//! An optional HTTP server which exposes the Prometheus metrics of the validator client at
+//! `GET /metrics`.
+//!
+//! Unlike the `http_api`, the metrics server does not require an API token since it does not
+//! expose any sensitive information or allow any changes to be made.
+
+use crate::metrics::{self, Encoder, TextEncoder};
+use crate::validator_store::ValidatorStore;
+use exit_future::Signal;
+use futures::{FutureExt, TryFutureExt};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use rest_types::Health;
+use serde_derive::{Deserialize, Serialize};
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use types::EthSpec;
+
+/// Configuration for the validator client metrics server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// Enable the metrics server.
+    pub enabled: bool,
+    /// The IPv4 address the metrics server will listen on.
+    pub listen_addr: Ipv4Addr,
+    /// The port the metrics server will listen on.
+    pub listen_port: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: Ipv4Addr::new(127, 0, 0, 1),
+            listen_port: 5064,
+        }
+    }
+}
+
+/// The state shared between all requests to the metrics server.
+pub struct Context<T, E: EthSpec> {
+    pub validator_store: ValidatorStore<T, E>,
+    pub runtime_handle: Handle,
+    pub log: Logger,
+}
+
+/// Start the metrics server, returning a signal which shuts it down and the address it is
+/// listening on.
+///
+/// Must be called from within the context of a tokio runtime.
+pub fn start_server<T: SlotClock + 'static, E: EthSpec>(
+    config: &Config,
+    context: Arc<Context<T, E>>,
+) -> Result<(Signal, SocketAddr), String> {
+    let log = context.log.clone();
+    let runtime_handle = context.runtime_handle.clone();
+
+    let make_service = make_service_fn(move |_| {
+        let context = context.clone();
+
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                let context = context.clone();
+                async move { Ok::<_, hyper::Error>(route(req, &context)) }
+            }))
+        }
+    });
+
+    let bind_addr = (config.listen_addr, config.listen_port).into();
+    let server = Server::try_bind(&bind_addr)
+        .map_err(|e| format!("Unable to bind metrics server to {}: {:?}", bind_addr, e))?
+        .serve(make_service);
+
+    // This may be different to `bind_addr` if the port was 0 (which allows the OS to choose a
+    // free port).
+    let listen_addr = server.local_addr();
+
+    let (exit_signal, exit) = exit_future::signal();
+    let exit_log = log.clone();
+    let error_log = log.clone();
+    let server_future = server
+        .with_graceful_shutdown(exit.map(move |_| info!(exit_log, "Metrics server shutdown")))
+        .map_err(move |e| {
+            warn!(
+                error_log,
+                "Metrics server failed";
+                "error" => format!("{:?}", e)
+            )
+        });
+
+    info!(
+        log,
+        "Metrics server started";
+        "address" => format!("{}", listen_addr.ip()),
+        "port" => listen_addr.port(),
+    );
+
+    runtime_handle.spawn(server_future);
+
+    Ok((exit_signal, listen_addr))
+}
+
+fn route<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    context: &Context<T, E>,
+) -> Response<Body> {
+    let result = match (req.method(), req.uri().path()) {
+        (&Method::GET, "/metrics") => get_prometheus(context),
+        _ => Err((
+            StatusCode::NOT_FOUND,
+            "Request path and/or method not found.".to_string(),
+        )),
+    };
+
+    let (status_code, content_type, body) = match result {
+        Ok(body) => (StatusCode::OK, "text/plain; version=0.0.4", body),
+        Err((status_code, desc)) => {
+            if status_code == StatusCode::INTERNAL_SERVER_ERROR {
+                warn!(
+                    context.log,
+                    "Metrics request failed";
+                    "error" => &desc,
+                );
+            }
+            (status_code, "text/plain; charset=utf-8", desc)
+        }
+    };
+
+    Response::builder()
+        .status(status_code)
+        .header("content-type", content_type)
+        .body(Body::from(body))
+        .expect("Response should always be created.")
+}
+
+/// Returns the full set of Prometheus metrics for the validator client.
+///
+/// Most metrics are updated as events occur, however the validator counts and process metrics
+/// are only updated at the time of the scrape.
+fn get_prometheus<T: SlotClock + 'static, E: EthSpec>(
+    context: &Context<T, E>,
+) -> Result<String, (StatusCode, String)> {
+    let statuses = context.validator_store.validator_statuses();
+    metrics::set_gauge(
+        &metrics::ENABLED_VALIDATORS_COUNT,
+        statuses.iter().filter(|(_, enabled)| *enabled).count() as i64,
+    );
+    metrics::set_gauge(&metrics::TOTAL_VALIDATORS_COUNT, statuses.len() as i64);
+
+    if let Ok(health) = Health::observe() {
+        metrics::set_gauge(&metrics::PROCESS_NUM_THREADS, health.pid_num_threads as i64);
+        metrics::set_gauge(
+            &metrics::PROCESS_RES_MEM,
+            health.pid_mem_resident_set_size as i64,
+        );
+        metrics::set_gauge(
+            &metrics::PROCESS_VIRT_MEM,
+            health.pid_mem_virtual_memory_size as i64,
+        );
+    }
+
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&metrics::gather(), &mut buffer)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Unable to encode metrics: {:?}", e),
+            )
+        })?;
+
+    String::from_utf8(buffer).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Unable to encode metrics: {:?}", e),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config as ValidatorClientConfig;
+    use hyper::Client;
+    use slot_clock::TestingSlotClock;
+    use std::fs::create_dir_all;
+    use tempdir::TempDir;
+    use types::MainnetEthSpec;
+
+    async fn get(addr: SocketAddr, path: &str) -> (StatusCode, String) {
+        let uri = format!("http://{}{}", addr, path).parse().unwrap();
+        let response = Client::new().get(uri).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn serves_metrics() {
+        let dir = TempDir::new("http_metrics").unwrap();
+        let validator_config = ValidatorClientConfig {
+            data_dir: dir.path().join("validators"),
+            secrets_dir: dir.path().join("secrets"),
+            ..ValidatorClientConfig::default()
+        };
+        create_dir_all(&validator_config.data_dir).unwrap();
+
+        let log = environment::null_logger().unwrap();
+        let context = Arc::new(Context {
+            validator_store: ValidatorStore::<TestingSlotClock, MainnetEthSpec>::for_testing(
+                &validator_config,
+                log.clone(),
+            ),
+            runtime_handle: Handle::current(),
+            log,
+        });
+
+        let config = Config {
+            enabled: true,
+            listen_addr: Ipv4Addr::new(127, 0, 0, 1),
+            listen_port: 0,
+        };
+        let (_exit, listen_addr) = start_server(&config, context).unwrap();
+
+        let (status, body) = get(listen_addr, "/metrics").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("vc_validators_enabled_count"));
+        assert!(body.contains("vc_validators_total_count"));
+
+        let (status, _) = get(listen_addr, "/").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get(listen_addr, "/lighthouse/validators").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,29 +1,43 @@
 mod attestation_service;
+mod beacon_node_fallback;
 mod block_service;
 mod cli;
 mod config;
+mod doppelganger_service;
 mod duties_service;
 mod fork_service;
+pub mod http_api;
+pub mod http_metrics;
 mod is_synced;
+mod metrics;
 mod notifier;
+mod signing_method;
 mod validator_store;
 
 pub use cli::cli_app;
-pub use config::Config;
+pub use config::{Config, SLASHING_PROTECTION_FILENAME};
+
+#[macro_use]
+extern crate lazy_static;
 
 use attestation_service::{AttestationService, AttestationServiceBuilder};
+use beacon_node_fallback::{
+    start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode, RequireSynced,
+};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
-use config::SLASHING_PROTECTION_FILENAME;
+use doppelganger_service::{DoppelgangerService, DoppelgangerServiceBuilder};
 use duties_service::{DutiesService, DutiesServiceBuilder};
 use environment::RuntimeContext;
 use exit_future::Signal;
 use fork_service::{ForkService, ForkServiceBuilder};
+use http_api::ApiSecret;
 use notifier::spawn_notifier;
 use remote_beacon_node::RemoteBeaconNode;
 use slog::{error, info, warn, Logger};
 use slot_clock::SlotClock;
 use slot_clock::SystemTimeSlotClock;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{delay_for, Duration};
 use types::EthSpec;
@@ -41,6 +55,9 @@ pub struct ProductionValidatorClient<T: EthSpec> {
     fork_service: ForkService<SystemTimeSlotClock, T>,
     block_service: BlockService<SystemTimeSlotClock, T>,
     attestation_service: AttestationService<SystemTimeSlotClock, T>,
+    beacon_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, T>>,
+    doppelganger_service: Option<DoppelgangerService<SystemTimeSlotClock, T>>,
+    validator_store: ValidatorStore<SystemTimeSlotClock, T>,
     exit_signals: Vec<Signal>,
     config: Config,
 }
@@ -68,7 +85,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         info!(
             log_1,
             "Starting validator client";
-            "beacon_node" => &config.http_server,
+            "beacon_nodes" => format!("{:?}", &config.beacon_nodes),
             "datadir" => format!("{:?}", config.data_dir),
         );
 
@@ -80,24 +97,33 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             );
         }
 
-        let beacon_node =
-            RemoteBeaconNode::new_with_timeout(config.http_server.clone(), HTTP_TIMEOUT)
-                .map_err(|e| format!("Unable to init beacon node http client: {}", e))?;
+        let candidates = config
+            .beacon_nodes
+            .iter()
+            .map(|endpoint| {
+                RemoteBeaconNode::new_with_timeout(endpoint.clone(), HTTP_TIMEOUT)
+                    .map(|beacon_node| CandidateBeaconNode::new(endpoint.clone(), beacon_node))
+                    .map_err(|e| format!("Unable to init beacon node http client: {}", e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut beacon_nodes: BeaconNodeFallback<SystemTimeSlotClock, T> =
+            BeaconNodeFallback::new(candidates, context.log.clone());
 
         // TODO: check if all logs in wait_for_node are produed while awaiting
-        let beacon_node = wait_for_node(beacon_node, log_2).await?;
-        let eth2_config = beacon_node
-            .http
-            .spec()
-            .get_eth2_config()
+        wait_for_node(&beacon_nodes, log_2).await?;
+        let eth2_config = beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node.http.spec().get_eth2_config().await
+            })
             .await
-            .map_err(|e| format!("Unable to read eth2 config from beacon node: {:?}", e))?;
-        let genesis_time = beacon_node
-            .http
-            .beacon()
-            .get_genesis_time()
+            .map_err(|e| format!("Unable to read eth2 config from beacon node: {}", e))?;
+        let genesis_time = beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node.http.beacon().get_genesis_time().await
+            })
             .await
-            .map_err(|e| format!("Unable to read genesis time from beacon node: {:?}", e))?;
+            .map_err(|e| format!("Unable to read genesis time from beacon node: {}", e))?;
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map_err(|e| format!("Unable to read system time: {:?}", e))?;
@@ -124,14 +150,18 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 "seconds_ago" => (now - genesis).as_secs()
             );
         }
-        let genesis_validators_root = beacon_node
-            .http
-            .beacon()
-            .get_genesis_validators_root()
+        let genesis_validators_root = beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node
+                    .http
+                    .beacon()
+                    .get_genesis_validators_root()
+                    .await
+            })
             .await
             .map_err(|e| {
                 format!(
-                    "Unable to read genesis validators root from beacon node: {:?}",
+                    "Unable to read genesis validators root from beacon node: {}",
                     e
                 )
             })?;
@@ -160,18 +190,40 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             Duration::from_millis(context.eth2_config.spec.milliseconds_per_slot),
         );
 
+        beacon_nodes.set_slot_clock(slot_clock.clone());
+        beacon_nodes.set_eth2_config(context.eth2_config.clone());
+        let beacon_nodes = Arc::new(beacon_nodes);
+
+        // Perform an initial health check so the services begin with an accurate view of which
+        // beacon nodes are synced.
+        beacon_nodes.update_all_candidates().await;
+
         let fork_service = ForkServiceBuilder::new()
             .slot_clock(slot_clock.clone())
-            .beacon_node(beacon_node.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("fork".into()))
             .build()?;
 
+        let doppelganger_service = if config.doppelganger_protection {
+            Some(
+                DoppelgangerServiceBuilder::new()
+                    .slot_clock(slot_clock.clone())
+                    .beacon_nodes(beacon_nodes.clone())
+                    .runtime_context(context.service_context("doppelganger".into()))
+                    .detection_epochs(config.doppelganger_epochs)
+                    .build()?,
+            )
+        } else {
+            None
+        };
+
         let validator_store: ValidatorStore<SystemTimeSlotClock, T> =
             ValidatorStore::load_from_disk(
                 &config,
                 genesis_validators_root,
                 context.eth2_config.spec.clone(),
                 fork_service.clone(),
+                doppelganger_service.clone(),
                 log.clone(),
             )?;
 
@@ -190,7 +242,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let duties_service = DutiesServiceBuilder::new()
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
-            .beacon_node(beacon_node.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("duties".into()))
             .allow_unsynced_beacon_node(config.allow_unsynced_beacon_node)
             .build()?;
@@ -199,15 +251,15 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .duties_service(duties_service.clone())
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
-            .beacon_node(beacon_node.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("block".into()))
             .build()?;
 
         let attestation_service = AttestationServiceBuilder::new()
             .duties_service(duties_service.clone())
             .slot_clock(slot_clock)
-            .validator_store(validator_store)
-            .beacon_node(beacon_node)
+            .validator_store(validator_store.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
             .build()?;
 
@@ -217,6 +269,9 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             fork_service,
             block_service,
             attestation_service,
+            beacon_nodes,
+            doppelganger_service,
+            validator_store,
             exit_signals: vec![],
             config,
         })
@@ -247,6 +302,12 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .start_update_service(&self.context.eth2_config.spec)
             .map_err(|e| format!("Unable to start attestation service: {}", e))?;
 
+        let fallback_exit = start_fallback_updater_service(
+            self.context.service_context("fallback".into()),
+            self.beacon_nodes.clone(),
+        )
+        .map_err(|e| format!("Unable to start beacon node fallback service: {}", e))?;
+
         let notifier_exit =
             spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
@@ -255,29 +316,73 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             fork_exit,
             block_exit,
             attestation_exit,
+            fallback_exit,
             notifier_exit,
         ];
 
+        if let Some(doppelganger_service) = &self.doppelganger_service {
+            let doppelganger_exit = doppelganger_service
+                .clone()
+                .start_update_service(&self.context.eth2_config.spec)
+                .map_err(|e| format!("Unable to start doppelganger service: {}", e))?;
+
+            self.exit_signals.push(doppelganger_exit);
+        }
+
+        if self.config.http_api.enabled {
+            let api_secret = ApiSecret::create_or_open(&self.config.data_dir)?;
+            let context = self.context.service_context("http_api".into());
+
+            let http_api_context = Arc::new(http_api::Context {
+                api_secret,
+                validator_store: self.validator_store.clone(),
+                validator_dir: self.config.data_dir.clone(),
+                secrets_dir: self.config.secrets_dir.clone(),
+                runtime_handle: context.runtime_handle.clone(),
+                log: context.log.clone(),
+            });
+
+            let (http_api_exit, _listen_addr) =
+                http_api::start_server(&self.config.http_api, http_api_context)
+                    .map_err(|e| format!("Unable to start HTTP API: {}", e))?;
+
+            self.exit_signals.push(http_api_exit);
+        }
+
+        if self.config.http_metrics.enabled {
+            let context = self.context.service_context("http_metrics".into());
+
+            let http_metrics_context = Arc::new(http_metrics::Context {
+                validator_store: self.validator_store.clone(),
+                runtime_handle: context.runtime_handle.clone(),
+                log: context.log.clone(),
+            });
+
+            let (http_metrics_exit, _listen_addr) =
+                http_metrics::start_server(&self.config.http_metrics, http_metrics_context)
+                    .map_err(|e| format!("Unable to start metrics server: {}", e))?;
+
+            self.exit_signals.push(http_metrics_exit);
+        }
+
         Ok(())
     }
 }
 
-/// Request the version from the node, looping back and trying again on failure. Exit once the node
-/// has been contacted.
-async fn wait_for_node<E: EthSpec>(
-    beacon_node: RemoteBeaconNode<E>,
+/// Request the version from the beacon nodes, looping back and trying again on failure. Exit once
+/// any of the nodes has been contacted.
+async fn wait_for_node<T: SlotClock, E: EthSpec>(
+    beacon_nodes: &BeaconNodeFallback<T, E>,
     log: Logger,
-) -> Result<RemoteBeaconNode<E>, String> {
-    // Try to get the version string from the node, looping until success is returned.
+) -> Result<(), String> {
+    // Try to get the version string from a node, looping until success is returned.
     loop {
         let log = log.clone();
-        let result = beacon_node
-            .clone()
-            .http
-            .node()
-            .get_version()
-            .await
-            .map_err(|e| format!("{:?}", e));
+        let result = beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node.http.node().get_version().await
+            })
+            .await;
 
         match result {
             Ok(version) => {
@@ -287,13 +392,13 @@ async fn wait_for_node<E: EthSpec>(
                     "version" => version,
                 );
 
-                return Ok(beacon_node);
+                return Ok(());
             }
             Err(e) => {
                 error!(
                     log,
-                    "Unable to connect to beacon node";
-                    "error" => format!("{:?}", e),
+                    "Unable to connect to a beacon node";
+                    "error" => format!("{}", e),
                 );
                 delay_for(RETRY_DELAY).await;
             }
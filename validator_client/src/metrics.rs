@@ -0,0 +1,127 @@
+use remote_beacon_node::PublishStatus;
+
+pub use lighthouse_metrics::*;
+
+/*
+ * Label values used to record the outcome of signing and publishing.
+ */
+pub const SUCCESS: &str = "success";
+pub const SAME_DATA: &str = "same_data";
+pub const UNREGISTERED: &str = "unregistered";
+pub const SLASHABLE: &str = "slashable";
+pub const VALID: &str = "valid";
+pub const INVALID: &str = "invalid";
+pub const UNKNOWN: &str = "unknown";
+pub const FAILED: &str = "failed";
+
+lazy_static! {
+    /*
+     * Beacon node fallback
+     */
+    pub static ref BEACON_NODE_REQUESTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_beacon_node_requests_total",
+        "Count of requests sent to each beacon node",
+        &["endpoint"]
+    );
+    pub static ref BEACON_NODE_REQUEST_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_beacon_node_request_errors_total",
+        "Count of requests to each beacon node which returned an error",
+        &["endpoint"]
+    );
+    pub static ref BEACON_NODE_HEALTH_CHECK_FAILURES: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "vc_beacon_node_health_check_failures_total",
+            "Count of health checks which found a beacon node unavailable, by reason",
+            &["endpoint", "reason"]
+        );
+    pub static ref BEACON_NODE_REQUEST_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_beacon_node_request_seconds",
+        "Time taken for each beacon node to respond to a request, including failures",
+        &["endpoint"]
+    );
+
+    /*
+     * Duties
+     */
+    pub static ref DUTIES_FETCHED: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_duties_fetched_total",
+        "Count of validator duties downloaded from a beacon node, by how they changed the store",
+        &["outcome"]
+    );
+
+    /*
+     * Signing
+     */
+    pub static ref SIGNED_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signed_beacon_blocks_total",
+        "Count of blocks checked by slashing protection, by status",
+        &["status"]
+    );
+    pub static ref SIGNED_ATTESTATIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signed_attestations_total",
+        "Count of attestations checked by slashing protection, by status",
+        &["status"]
+    );
+    pub static ref SIGNED_AGGREGATES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signed_aggregates_total",
+        "Count of aggregates signed, by status",
+        &["status"]
+    );
+    pub static ref SIGNING_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_signing_seconds",
+        "Time taken to obtain a signature from a signing method, by domain",
+        &["domain"]
+    );
+
+    /*
+     * Publishing
+     */
+    pub static ref PUBLISHED_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_published_beacon_blocks_total",
+        "Count of blocks published to a beacon node, by status",
+        &["status"]
+    );
+    pub static ref PUBLISHED_ATTESTATIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_published_attestations_total",
+        "Count of attestations published to a beacon node, by status",
+        &["status"]
+    );
+    pub static ref PUBLISHED_AGGREGATES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_published_aggregates_total",
+        "Count of aggregates published to a beacon node, by status",
+        &["status"]
+    );
+
+    /*
+     * Scraped when the metrics endpoint is queried
+     */
+    pub static ref ENABLED_VALIDATORS_COUNT: Result<IntGauge> = try_create_int_gauge(
+        "vc_validators_enabled_count",
+        "Number of enabled validators"
+    );
+    pub static ref TOTAL_VALIDATORS_COUNT: Result<IntGauge> = try_create_int_gauge(
+        "vc_validators_total_count",
+        "Number of validators, enabled or not"
+    );
+    pub static ref PROCESS_NUM_THREADS: Result<IntGauge> = try_create_int_gauge(
+        "process_num_threads",
+        "Number of threads used by the current process"
+    );
+    pub static ref PROCESS_RES_MEM: Result<IntGauge> = try_create_int_gauge(
+        "process_resident_memory_bytes",
+        "Resident memory used by the current process"
+    );
+    pub static ref PROCESS_VIRT_MEM: Result<IntGauge> = try_create_int_gauge(
+        "process_virtual_memory_bytes",
+        "Virtual memory used by the current process"
+    );
+}
+
+/// Returns the label used to record `status` in the `PUBLISHED_*` metrics.
+pub fn publish_status_label(status: &PublishStatus) -> &'static str {
+    match status {
+        PublishStatus::Valid => VALID,
+        PublishStatus::Invalid(_) => INVALID,
+        PublishStatus::Unknown => UNKNOWN,
+    }
+}
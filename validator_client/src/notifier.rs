@@ -1,7 +1,7 @@
-use crate::{is_synced::is_synced, ProductionValidatorClient};
+use crate::ProductionValidatorClient;
 use exit_future::Signal;
 use futures::{FutureExt, StreamExt};
-use slog::{error, info};
+use slog::{error, info, warn};
 use slot_clock::SlotClock;
 use tokio::time::{interval_at, Duration, Instant};
 use types::EthSpec;
@@ -28,15 +28,38 @@ pub fn spawn_notifier<T: EthSpec>(client: &ProductionValidatorClient<T>) -> Resu
         let log = &context.log;
 
         while interval.next().await.is_some() {
-            if !is_synced(
-                &duties_service.beacon_node,
-                &duties_service.slot_clock,
-                Some(&log),
-            )
-            .await
-                && !allow_unsynced_beacon_node
-            {
-                continue;
+            let num_available = duties_service.beacon_nodes.num_available();
+            let num_synced = duties_service.beacon_nodes.num_synced();
+            let num_total = duties_service.beacon_nodes.num_total();
+
+            if num_synced == 0 {
+                error!(
+                    log,
+                    "No synced beacon nodes";
+                    "total" => num_total,
+                    "available" => num_available,
+                    "synced" => num_synced,
+                );
+
+                if !allow_unsynced_beacon_node {
+                    continue;
+                }
+            } else if num_available < num_total {
+                warn!(
+                    log,
+                    "Some beacon nodes unavailable";
+                    "total" => num_total,
+                    "available" => num_available,
+                    "synced" => num_synced,
+                );
+            } else {
+                info!(
+                    log,
+                    "Connected to beacon nodes";
+                    "total" => num_total,
+                    "available" => num_available,
+                    "synced" => num_synced,
+                );
             }
 
             if let Some(slot) = duties_service.slot_clock.now() {
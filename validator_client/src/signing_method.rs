@@ -0,0 +1,319 @@
+//! Provides the `SigningMethod` trait which allows the `ValidatorStore` to obtain signatures
+//! without knowing where the secret key for a validator is held.
+//!
+//! There are two implementations:
+//!
+//! - `LocalKeystore`: signs with a `Keypair` that was decrypted from a keystore on this host.
+//! - `RemoteSigner`: sends the signing root (along with the fork and domain information used to
+//!   compute it) to an external HTTP signer, allowing keys to be held on a separate host.
+//!
+//! Slashing protection is *not* the responsibility of a `SigningMethod`; the `ValidatorStore`
+//! must check each message with the local slashing protection database before requesting a
+//! signature.
+
+use futures::future::{self, BoxFuture, FutureExt};
+use reqwest::{Client, StatusCode};
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+use types::{ChainSpec, Domain, Epoch, Fork, Hash256, Keypair, PublicKey, Signature};
+use url::Url;
+
+/// The timeout applied to every request to a remote signer.
+pub const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(12);
+
+#[derive(Debug)]
+pub enum Error {
+    /// The remote signer URL could not be parsed or joined with a request path.
+    InvalidUrl(String),
+    /// The request to the remote signer could not be sent or its response could not be read.
+    RequestFailed(String),
+    /// The remote signer responded with a non-success status code.
+    DidNotSucceed { status: StatusCode, body: String },
+}
+
+/// The information required to compute the domain for a message.
+///
+/// This is sent to remote signers alongside the signing root so that they may perform their own
+/// checks before signing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningContext {
+    pub domain: Domain,
+    pub epoch: Epoch,
+    pub fork: Fork,
+    pub genesis_validators_root: Hash256,
+}
+
+impl SigningContext {
+    /// Returns the `Hash256` used as the domain when computing the signing root.
+    pub fn domain_hash(&self, spec: &ChainSpec) -> Hash256 {
+        spec.get_domain(
+            self.epoch,
+            self.domain,
+            &self.fork,
+            self.genesis_validators_root,
+        )
+    }
+}
+
+/// A backend capable of producing signatures for a single validator.
+pub trait SigningMethod: Send + Sync {
+    /// The public key of the validator for which `self` produces signatures.
+    fn voting_public_key(&self) -> &PublicKey;
+
+    /// Sign `signing_root`, which must have been computed using `signing_context`.
+    fn get_signature(
+        &self,
+        signing_root: Hash256,
+        signing_context: SigningContext,
+        spec: &ChainSpec,
+    ) -> BoxFuture<'_, Result<Signature, Error>>;
+}
+
+/// Signs messages using a keypair held in memory.
+pub struct LocalKeystore {
+    voting_keypair: Keypair,
+}
+
+impl LocalKeystore {
+    pub fn new(voting_keypair: Keypair) -> Self {
+        Self { voting_keypair }
+    }
+}
+
+impl SigningMethod for LocalKeystore {
+    fn voting_public_key(&self) -> &PublicKey {
+        &self.voting_keypair.pk
+    }
+
+    fn get_signature(
+        &self,
+        signing_root: Hash256,
+        _signing_context: SigningContext,
+        _spec: &ChainSpec,
+    ) -> BoxFuture<'_, Result<Signature, Error>> {
+        let signature = Signature::new(signing_root.as_bytes(), &self.voting_keypair.sk);
+        future::ready(Ok(signature)).boxed()
+    }
+}
+
+/// The body of a request to `POST /sign/{pubkey}` on a remote signer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSignerRequest {
+    /// The root which should be signed.
+    pub signing_root: Hash256,
+    /// The domain used when computing `signing_root`.
+    pub domain: Hash256,
+    /// The epoch used to compute `domain`.
+    pub epoch: Epoch,
+    /// The fork used to compute `domain`.
+    pub fork: Fork,
+    pub genesis_validators_root: Hash256,
+}
+
+/// The body of a successful response from `POST /sign/{pubkey}` on a remote signer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSignerResponse {
+    pub signature: Signature,
+}
+
+/// Signs messages by sending requests to an external HTTP signer which holds the secret key.
+///
+/// Requests are sent to `POST {url}/sign/{pubkey}` where `pubkey` is the 0x-prefixed hex
+/// encoding of the voting public key.
+pub struct RemoteSigner {
+    url: Url,
+    client: Client,
+    voting_public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    pub fn new(url: &str, voting_public_key: PublicKey) -> Result<Self, Error> {
+        let client = Client::builder()
+            .timeout(REMOTE_SIGNER_TIMEOUT)
+            .build()
+            .map_err(|e| Error::RequestFailed(format!("{:?}", e)))?;
+
+        // Ensure the URL ends in a slash so that any path it contains is retained when joined
+        // with the request path.
+        let url = if url.ends_with('/') {
+            Url::parse(url)
+        } else {
+            Url::parse(&format!("{}/", url))
+        }
+        .map_err(|e| Error::InvalidUrl(format!("{:?}", e)))?;
+
+        Ok(Self {
+            url,
+            client,
+            voting_public_key,
+        })
+    }
+
+    /// Returns the URL of the remote signer.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    async fn request_signature(&self, request: RemoteSignerRequest) -> Result<Signature, Error> {
+        let url = self
+            .url
+            .join(&format!("sign/{}", self.voting_public_key.as_hex_string()))
+            .map_err(|e| Error::InvalidUrl(format!("{:?}", e)))?;
+
+        let response = self
+            .client
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| Error::RequestFailed(format!("{:?}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .map_err(|e| Error::RequestFailed(format!("{:?}", e)))?;
+            return Err(Error::DidNotSucceed { status, body });
+        }
+
+        response
+            .json::<RemoteSignerResponse>()
+            .await
+            .map(|response| response.signature)
+            .map_err(|e| Error::RequestFailed(format!("{:?}", e)))
+    }
+}
+
+impl SigningMethod for RemoteSigner {
+    fn voting_public_key(&self) -> &PublicKey {
+        &self.voting_public_key
+    }
+
+    fn get_signature(
+        &self,
+        signing_root: Hash256,
+        signing_context: SigningContext,
+        spec: &ChainSpec,
+    ) -> BoxFuture<'_, Result<Signature, Error>> {
+        let request = RemoteSignerRequest {
+            signing_root,
+            domain: signing_context.domain_hash(spec),
+            epoch: signing_context.epoch,
+            fork: signing_context.fork,
+            genesis_validators_root: signing_context.genesis_validators_root,
+        };
+
+        self.request_signature(request).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server,
+    };
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use types::{EthSpec, MainnetEthSpec, SecretKey, SignedRoot, Slot};
+
+    type E = MainnetEthSpec;
+
+    /// Starts a mock remote signer which signs any request with `keypair`, returning the address
+    /// it is listening on.
+    fn spawn_mock_signer(keypair: Keypair) -> SocketAddr {
+        let make_service = make_service_fn(move |_| {
+            let keypair = keypair.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let keypair = keypair.clone();
+                    async move {
+                        let expected_path = format!("/sign/{}", keypair.pk.as_hex_string());
+                        if req.uri().path() != expected_path {
+                            return Ok::<_, Infallible>(
+                                Response::builder()
+                                    .status(404)
+                                    .body(Body::from("unknown key"))
+                                    .unwrap(),
+                            );
+                        }
+
+                        let bytes = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                        let request: RemoteSignerRequest = serde_json::from_slice(&bytes).unwrap();
+                        let response = RemoteSignerResponse {
+                            signature: Signature::new(request.signing_root.as_bytes(), &keypair.sk),
+                        };
+
+                        Ok(Response::new(Body::from(
+                            serde_json::to_vec(&response).unwrap(),
+                        )))
+                    }
+                }))
+            }
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn signing_context(spec: &ChainSpec) -> SigningContext {
+        SigningContext {
+            domain: Domain::SelectionProof,
+            epoch: Epoch::new(1),
+            fork: Fork {
+                previous_version: spec.genesis_fork_version,
+                current_version: spec.genesis_fork_version,
+                epoch: Epoch::new(0),
+            },
+            genesis_validators_root: Hash256::repeat_byte(42),
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_signer_matches_local_keystore() {
+        let spec = E::default_spec();
+        let keypair = Keypair::random();
+        let addr = spawn_mock_signer(keypair.clone());
+
+        let local = LocalKeystore::new(keypair.clone());
+        let remote = RemoteSigner::new(&format!("http://{}", addr), keypair.pk.clone()).unwrap();
+        assert_eq!(local.voting_public_key(), remote.voting_public_key());
+
+        let context = signing_context(&spec);
+        let signing_root = Slot::new(E::slots_per_epoch()).signing_root(context.domain_hash(&spec));
+
+        let local_signature = local
+            .get_signature(signing_root, context.clone(), &spec)
+            .await
+            .unwrap();
+        let remote_signature = remote
+            .get_signature(signing_root, context, &spec)
+            .await
+            .unwrap();
+
+        assert_eq!(local_signature, remote_signature);
+        assert!(remote_signature.verify(signing_root.as_bytes(), &keypair.pk));
+    }
+
+    #[tokio::test]
+    async fn remote_signer_unknown_key() {
+        let spec = E::default_spec();
+        let addr = spawn_mock_signer(Keypair::random());
+
+        let other_pk = PublicKey::from_secret_key(&SecretKey::random());
+        let remote = RemoteSigner::new(&format!("http://{}", addr), other_pk).unwrap();
+
+        let result = remote
+            .get_signature(Hash256::zero(), signing_context(&spec), &spec)
+            .await;
+
+        assert!(
+            matches!(result, Err(Error::DidNotSucceed { status, .. }) if status == 404),
+            "should fail for a key unknown to the signer"
+        );
+    }
+}
@@ -1,26 +1,36 @@
 use crate::config::SLASHING_PROTECTION_FILENAME;
-use crate::{config::Config, fork_service::ForkService};
+use crate::signing_method::{LocalKeystore, RemoteSigner, SigningContext, SigningMethod};
+use crate::{
+    config::Config, doppelganger_service::DoppelgangerService, fork_service::ForkService, metrics,
+};
 use parking_lot::RwLock;
-use slashing_protection::{NotSafe, Safe, SlashingDatabase};
-use slog::{crit, error, warn, Logger};
+use slashing_protection::{interchange::Interchange, NotSafe, Safe, SlashingDatabase};
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
+use std::fs::{remove_dir_all, remove_file};
 use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tempdir::TempDir;
 use types::{
-    Attestation, BeaconBlock, ChainSpec, Domain, Epoch, EthSpec, Fork, Hash256, Keypair, PublicKey,
-    SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot, Slot,
+    AggregateAndProof, Attestation, BeaconBlock, ChainSpec, Domain, Epoch, EthSpec, Fork, Hash256,
+    Keypair, PublicKey, SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedRoot, Slot,
 };
 use validator_dir::{Manager as ValidatorManager, ValidatorDir};
 
 struct LocalValidator {
-    validator_dir: ValidatorDir,
-    voting_keypair: Keypair,
+    /// The directory containing the voting keystore, if the secret key is held on this host.
+    validator_dir: Option<ValidatorDir>,
+    signing_method: Arc<dyn SigningMethod>,
+    /// Disabled validators are retained in the store but do not perform any duties.
+    enabled: bool,
 }
 
-/// We derive our own `PartialEq` to avoid doing equality checks between secret keys.
+/// We derive our own `PartialEq` to avoid doing equality checks between signing methods (and
+/// therefore secret keys).
 ///
 /// It's nice to avoid secret key comparisons from a security perspective, but it's also a little
 /// risky when it comes to `HashMap` integrity (that's why we need `PartialEq`).
@@ -36,7 +46,8 @@ struct LocalValidator {
 impl PartialEq for LocalValidator {
     fn eq(&self, other: &Self) -> bool {
         self.validator_dir == other.validator_dir
-            && self.voting_keypair.pk == other.voting_keypair.pk
+            && self.signing_method.voting_public_key() == other.signing_method.voting_public_key()
+            && self.enabled == other.enabled
     }
 }
 
@@ -44,11 +55,14 @@ impl PartialEq for LocalValidator {
 pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<HashMap<PublicKey, LocalValidator>>>,
     slashing_protection: SlashingDatabase,
+    /// The directory containing the passwords for the keystores of local validators.
+    secrets_dir: PathBuf,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
     temp_dir: Option<Arc<TempDir>>,
     fork_service: ForkService<T, E>,
+    doppelganger_service: Option<DoppelgangerService<T, E>>,
     _phantom: PhantomData<E>,
 }
 
@@ -58,6 +72,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         fork_service: ForkService<T, E>,
+        doppelganger_service: Option<DoppelgangerService<T, E>>,
         log: Logger,
     ) -> Result<Self, String> {
         let slashing_db_path = config.data_dir.join(SLASHING_PROTECTION_FILENAME);
@@ -69,29 +84,66 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 )
             })?;
 
-        let validator_key_values = ValidatorManager::open(&config.data_dir)
+        let mut validator_key_values = ValidatorManager::open(&config.data_dir)
             .map_err(|e| format!("unable to read data_dir: {:?}", e))?
             .decrypt_all_validators(config.secrets_dir.clone(), Some(&log))
             .map_err(|e| format!("unable to decrypt all validator directories: {:?}", e))?
             .into_iter()
             .map(|(kp, dir)| {
+                let signing_method: Arc<dyn SigningMethod> = Arc::new(LocalKeystore::new(kp));
                 (
-                    kp.pk.clone(),
+                    signing_method.voting_public_key().clone(),
                     LocalValidator {
-                        validator_dir: dir,
-                        voting_keypair: kp,
+                        enabled: dir.is_enabled(),
+                        validator_dir: Some(dir),
+                        signing_method,
                     },
                 )
-            });
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(remote_signer_url) = &config.remote_signer_url {
+            for pubkey in &config.remote_signer_keys {
+                if validator_key_values
+                    .iter()
+                    .any(|(known, _)| known == pubkey)
+                {
+                    return Err(format!(
+                        "Validator {:?} has both a local keystore and a remote signer",
+                        pubkey
+                    ));
+                }
+
+                let remote_signer = RemoteSigner::new(remote_signer_url, pubkey.clone())
+                    .map_err(|e| format!("Unable to initialize remote signer: {:?}", e))?;
+
+                validator_key_values.push((
+                    pubkey.clone(),
+                    LocalValidator {
+                        validator_dir: None,
+                        signing_method: Arc::new(remote_signer),
+                        enabled: true,
+                    },
+                ));
+            }
+        }
+
+        if let Some(doppelganger_service) = &doppelganger_service {
+            for (pubkey, _) in &validator_key_values {
+                doppelganger_service.register_new_validator(pubkey.clone());
+            }
+        }
 
         Ok(Self {
             validators: Arc::new(RwLock::new(HashMap::from_iter(validator_key_values))),
             slashing_protection,
+            secrets_dir: config.secrets_dir.clone(),
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
             temp_dir: None,
             fork_service,
+            doppelganger_service,
             _phantom: PhantomData,
         })
     }
@@ -106,16 +158,216 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map_err(|e| format!("Error while registering validators: {:?}", e))
     }
 
+    /// Returns the public keys of all enabled validators.
     pub fn voting_pubkeys(&self) -> Vec<PublicKey> {
         self.validators
             .read()
             .iter()
-            .map(|(pubkey, _dir)| pubkey.clone())
+            .filter(|(_pubkey, validator)| validator.enabled)
+            .map(|(pubkey, _validator)| pubkey.clone())
             .collect()
     }
 
+    /// Returns the number of enabled validators.
     pub fn num_voting_validators(&self) -> usize {
-        self.validators.read().len()
+        self.validators
+            .read()
+            .values()
+            .filter(|validator| validator.enabled)
+            .count()
+    }
+
+    /// Returns the public key of every validator in the store, along with whether or not it is
+    /// enabled.
+    pub fn validator_statuses(&self) -> Vec<(PublicKey, bool)> {
+        self.validators
+            .read()
+            .iter()
+            .map(|(pubkey, validator)| (pubkey.clone(), validator.enabled))
+            .collect()
+    }
+
+    /// Returns `Some(enabled)` if `validator_pubkey` is known to the store.
+    pub fn validator_status(&self, validator_pubkey: &PublicKey) -> Option<bool> {
+        self.validators
+            .read()
+            .get(validator_pubkey)
+            .map(|validator| validator.enabled)
+    }
+
+    /// Add a validator with a keystore on this host to the store whilst the validator client is
+    /// running.
+    ///
+    /// Since the validator has been explicitly added, it is registered with the slashing
+    /// protection database regardless of `--auto-register`.
+    pub fn add_validator_keystore(
+        &self,
+        validator_dir: ValidatorDir,
+        voting_keypair: Keypair,
+    ) -> Result<(), String> {
+        let pubkey = voting_keypair.pk.clone();
+
+        if self.validators.read().contains_key(&pubkey) {
+            return Err(format!("Validator {:?} is already known", pubkey));
+        }
+
+        self.slashing_protection
+            .register_validator(&pubkey)
+            .map_err(|e| format!("Error while registering validator: {:?}", e))?;
+
+        if let Some(doppelganger_service) = &self.doppelganger_service {
+            doppelganger_service.register_new_validator(pubkey.clone());
+        }
+
+        let validator = LocalValidator {
+            enabled: validator_dir.is_enabled(),
+            validator_dir: Some(validator_dir),
+            signing_method: Arc::new(LocalKeystore::new(voting_keypair)),
+        };
+
+        info!(
+            self.log,
+            "Added validator";
+            "enabled" => validator.enabled,
+            "public_key" => format!("{:?}", pubkey),
+        );
+
+        self.validators.write().insert(pubkey, validator);
+
+        Ok(())
+    }
+
+    /// Enable or disable `validator_pubkey`, returning `Ok(false)` if it is not known.
+    ///
+    /// For validators with a local keystore the choice is persisted to the validator directory.
+    /// Validators using a remote signer are enabled again when the validator client restarts.
+    pub fn set_validator_enabled(
+        &self,
+        validator_pubkey: &PublicKey,
+        enabled: bool,
+    ) -> Result<bool, String> {
+        let mut validators = self.validators.write();
+
+        let validator = if let Some(validator) = validators.get_mut(validator_pubkey) {
+            validator
+        } else {
+            return Ok(false);
+        };
+
+        if let Some(validator_dir) = &validator.validator_dir {
+            validator_dir
+                .set_enabled(enabled)
+                .map_err(|e| format!("Unable to update validator directory: {:?}", e))?;
+        }
+
+        if validator.enabled != enabled {
+            info!(
+                self.log,
+                "Updated validator";
+                "enabled" => enabled,
+                "public_key" => format!("{:?}", validator_pubkey),
+            );
+        }
+
+        validator.enabled = enabled;
+
+        Ok(true)
+    }
+
+    /// Remove `validator_pubkey` from the store, returning its slashing protection data or
+    /// `Ok(None)` if it is not known.
+    ///
+    /// The validator directory and keystore password are deleted from disk, however the
+    /// validator's history is retained in the slashing protection database so that it remains
+    /// protected should it be added again. Validators using a remote signer are only removed
+    /// until the validator client restarts.
+    ///
+    /// If the validator directory cannot be deleted the validator is kept in the store.
+    pub fn remove_validator(
+        &self,
+        validator_pubkey: &PublicKey,
+    ) -> Result<Option<Interchange>, String> {
+        // Hold the lock for the entire removal so the validator cannot be re-added whilst its
+        // directory is being deleted.
+        let mut validators = self.validators.write();
+
+        let mut validator = if let Some(validator) = validators.remove(validator_pubkey) {
+            validator
+        } else {
+            return Ok(None);
+        };
+
+        if let Some(validator_dir) = validator.validator_dir.take() {
+            let dir = validator_dir.dir().clone();
+            // Dropping the `ValidatorDir` removes its lockfile.
+            drop(validator_dir);
+
+            if let Err(e) = remove_dir_all(&dir) {
+                // Some of the directory may remain and be loaded again on restart, so keep the
+                // validator in the store rather than hiding it until then.
+                validator.validator_dir = ValidatorDir::open(&dir).ok();
+                validators.insert(validator_pubkey.clone(), validator);
+
+                return Err(format!(
+                    "Unable to delete validator directory {:?}: {:?}",
+                    dir, e
+                ));
+            }
+
+            let password_path = self.secrets_dir.join(validator_pubkey.as_hex_string());
+            if password_path.exists() {
+                // The keystore is already gone, so a leftover password is not worth failing for.
+                if let Err(e) = remove_file(&password_path) {
+                    warn!(
+                        self.log,
+                        "Unable to delete validator password";
+                        "error" => format!("{:?}", e),
+                        "path" => format!("{:?}", password_path),
+                    );
+                }
+            }
+        }
+
+        drop(validators);
+
+        info!(
+            self.log,
+            "Removed validator";
+            "public_key" => format!("{:?}", validator_pubkey),
+        );
+
+        self.slashing_protection
+            .export_validator_interchange_info(self.genesis_validators_root, validator_pubkey)
+            .map(Some)
+            .map_err(|e| format!("Unable to export slashing protection data: {:?}", e))
+    }
+
+    /// Returns `true` if `validator_pubkey` is enabled and has passed any doppelganger checks.
+    ///
+    /// This should be checked before slashing protection, so that the slashing protection
+    /// database is not updated for messages which will not be signed.
+    fn signing_enabled(&self, validator_pubkey: &PublicKey) -> bool {
+        self.validator_status(validator_pubkey) == Some(true)
+            && self.doppelganger_checks_passed(validator_pubkey)
+    }
+
+    /// Returns `true` if doppelganger protection is disabled, or if `validator_pubkey` has passed
+    /// the doppelganger checks.
+    fn doppelganger_checks_passed(&self, validator_pubkey: &PublicKey) -> bool {
+        let passed = self
+            .doppelganger_service
+            .as_ref()
+            .map_or(true, |service| service.signing_enabled(validator_pubkey));
+
+        if !passed {
+            debug!(
+                self.log,
+                "Not signing whilst doppelganger protection is active";
+                "public_key" => format!("{:?}", validator_pubkey)
+            );
+        }
+
+        passed
     }
 
     fn fork(&self) -> Option<Fork> {
@@ -128,26 +380,70 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.fork_service.fork()
     }
 
-    pub fn randao_reveal(&self, validator_pubkey: &PublicKey, epoch: Epoch) -> Option<Signature> {
-        // TODO: check this against the slot clock to make sure it's not an early reveal?
-        self.validators
+    /// Returns the information required to compute the domain for a message, or `None` if the
+    /// `Fork` is not yet known.
+    fn signing_context(&self, domain: Domain, epoch: Epoch) -> Option<SigningContext> {
+        Some(SigningContext {
+            domain,
+            epoch,
+            fork: self.fork()?,
+            genesis_validators_root: self.genesis_validators_root,
+        })
+    }
+
+    /// Obtain a signature over `signing_root` from the signing method for `validator_pubkey`.
+    ///
+    /// Any slashing protection checks must be performed *before* calling this function.
+    async fn sign(
+        &self,
+        validator_pubkey: &PublicKey,
+        signing_root: Hash256,
+        signing_context: SigningContext,
+    ) -> Option<Signature> {
+        let signing_method = self
+            .validators
             .read()
             .get(validator_pubkey)
-            .and_then(|local_validator| {
-                let voting_keypair = &local_validator.voting_keypair;
-                let domain = self.spec.get_domain(
-                    epoch,
-                    Domain::Randao,
-                    &self.fork()?,
-                    self.genesis_validators_root,
-                );
-                let message = epoch.signing_root(domain);
+            .filter(|validator| validator.enabled)
+            .map(|validator| validator.signing_method.clone())?;
 
-                Some(Signature::new(message.as_bytes(), &voting_keypair.sk))
+        let _timer = metrics::start_timer_vec(
+            &metrics::SIGNING_TIMES,
+            &[domain_metrics_label(signing_context.domain)],
+        );
+
+        signing_method
+            .get_signature(signing_root, signing_context, &self.spec)
+            .await
+            .map_err(|e| {
+                error!(
+                    self.log,
+                    "Unable to obtain signature";
+                    "public_key" => format!("{:?}", validator_pubkey),
+                    "error" => format!("{:?}", e)
+                )
             })
+            .ok()
+    }
+
+    pub async fn randao_reveal(
+        &self,
+        validator_pubkey: &PublicKey,
+        epoch: Epoch,
+    ) -> Option<Signature> {
+        // TODO: check this against the slot clock to make sure it's not an early reveal?
+        if !self.signing_enabled(validator_pubkey) {
+            return None;
+        }
+
+        let signing_context = self.signing_context(Domain::Randao, epoch)?;
+        let signing_root = epoch.signing_root(signing_context.domain_hash(&self.spec));
+
+        self.sign(validator_pubkey, signing_root, signing_context)
+            .await
     }
 
-    pub fn sign_block(
+    pub async fn sign_block(
         &self,
         validator_pubkey: &PublicKey,
         block: BeaconBlock<E>,
@@ -164,14 +460,13 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             return None;
         }
 
+        if !self.signing_enabled(validator_pubkey) {
+            return None;
+        }
+
         // Check for slashing conditions.
-        let fork = self.fork()?;
-        let domain = self.spec.get_domain(
-            block.epoch(),
-            Domain::BeaconProposer,
-            &fork,
-            self.genesis_validators_root,
-        );
+        let signing_context = self.signing_context(Domain::BeaconProposer, block.epoch())?;
+        let domain = signing_context.domain_hash(&self.spec);
 
         let slashing_status = self.slashing_protection.check_and_insert_block_proposal(
             validator_pubkey,
@@ -182,18 +477,25 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         match slashing_status {
             // We can safely sign this block.
             Ok(Safe::Valid) => {
-                let validators = self.validators.read();
-                let validator = validators.get(validator_pubkey)?;
-                let voting_keypair = &validator.voting_keypair;
-
-                Some(block.sign(
-                    &voting_keypair.sk,
-                    &fork,
-                    self.genesis_validators_root,
-                    &self.spec,
-                ))
+                let signing_root = block.signing_root(domain);
+                let signature = self
+                    .sign(validator_pubkey, signing_root, signing_context)
+                    .await;
+
+                let status = if signature.is_some() {
+                    metrics::SUCCESS
+                } else {
+                    metrics::FAILED
+                };
+                metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[status]);
+
+                Some(SignedBeaconBlock {
+                    message: block,
+                    signature: signature?,
+                })
             }
             Ok(Safe::SameData) => {
+                metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::SAME_DATA]);
                 warn!(
                     self.log,
                     "Skipping signing of previously signed block";
@@ -201,6 +503,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 None
             }
             Err(NotSafe::UnregisteredValidator(pk)) => {
+                metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::UNREGISTERED]);
                 warn!(
                     self.log,
                     "Not signing block for unregistered validator";
@@ -210,6 +513,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 None
             }
             Err(e) => {
+                metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::SLASHABLE]);
                 crit!(
                     self.log,
                     "Not signing slashable block";
@@ -220,7 +524,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
-    pub fn sign_attestation(
+    pub async fn sign_attestation(
         &self,
         validator_pubkey: &PublicKey,
         validator_committee_position: usize,
@@ -232,15 +536,15 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             return None;
         }
 
+        if !self.signing_enabled(validator_pubkey) {
+            return None;
+        }
+
         // Checking for slashing conditions.
-        let fork = self.fork()?;
+        let signing_context =
+            self.signing_context(Domain::BeaconAttester, attestation.data.target.epoch)?;
+        let domain = signing_context.domain_hash(&self.spec);
 
-        let domain = self.spec.get_domain(
-            attestation.data.target.epoch,
-            Domain::BeaconAttester,
-            &fork,
-            self.genesis_validators_root,
-        );
         let slashing_status = self.slashing_protection.check_and_insert_attestation(
             validator_pubkey,
             &attestation.data,
@@ -250,30 +554,37 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         match slashing_status {
             // We can safely sign this attestation.
             Ok(Safe::Valid) => {
-                let validators = self.validators.read();
-                let validator = validators.get(validator_pubkey)?;
-                let voting_keypair = &validator.voting_keypair;
-
-                attestation
-                    .sign(
-                        &voting_keypair.sk,
-                        validator_committee_position,
-                        &fork,
-                        self.genesis_validators_root,
-                        &self.spec,
-                    )
-                    .map_err(|e| {
-                        error!(
-                            self.log,
-                            "Error whilst signing attestation";
-                            "error" => format!("{:?}", e)
-                        )
-                    })
-                    .ok()?;
-
-                Some(())
+                let signing_root = attestation.data.signing_root(domain);
+                let result = self
+                    .sign(validator_pubkey, signing_root, signing_context)
+                    .await
+                    .and_then(|signature| {
+                        attestation
+                            .add_signature(&signature, validator_committee_position)
+                            .map_err(|e| {
+                                error!(
+                                    self.log,
+                                    "Error whilst signing attestation";
+                                    "error" => format!("{:?}", e)
+                                )
+                            })
+                            .ok()
+                    });
+
+                let status = if result.is_some() {
+                    metrics::SUCCESS
+                } else {
+                    metrics::FAILED
+                };
+                metrics::inc_counter_vec(&metrics::SIGNED_ATTESTATIONS_TOTAL, &[status]);
+
+                result
             }
             Ok(Safe::SameData) => {
+                metrics::inc_counter_vec(
+                    &metrics::SIGNED_ATTESTATIONS_TOTAL,
+                    &[metrics::SAME_DATA],
+                );
                 warn!(
                     self.log,
                     "Skipping signing of previously signed attestation"
@@ -281,6 +592,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 None
             }
             Err(NotSafe::UnregisteredValidator(pk)) => {
+                metrics::inc_counter_vec(
+                    &metrics::SIGNED_ATTESTATIONS_TOTAL,
+                    &[metrics::UNREGISTERED],
+                );
                 warn!(
                     self.log,
                     "Not signing attestation for unregistered validator";
@@ -290,6 +605,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 None
             }
             Err(e) => {
+                metrics::inc_counter_vec(
+                    &metrics::SIGNED_ATTESTATIONS_TOTAL,
+                    &[metrics::SLASHABLE],
+                );
                 crit!(
                     self.log,
                     "Not signing slashable attestation";
@@ -305,43 +624,107 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     ///
     /// The resulting `SignedAggregateAndProof` is sent on the aggregation channel and cannot be
     /// modified by actors other than the signing validator.
-    pub fn produce_signed_aggregate_and_proof(
+    pub async fn produce_signed_aggregate_and_proof(
         &self,
         validator_pubkey: &PublicKey,
         validator_index: u64,
         aggregate: Attestation<E>,
         selection_proof: SelectionProof,
     ) -> Option<SignedAggregateAndProof<E>> {
-        let validators = self.validators.read();
-        let voting_keypair = &validators.get(validator_pubkey)?.voting_keypair;
+        if !self.signing_enabled(validator_pubkey) {
+            return None;
+        }
+
+        let signing_context = self.signing_context(
+            Domain::AggregateAndProof,
+            aggregate.data.slot.epoch(E::slots_per_epoch()),
+        )?;
 
-        Some(SignedAggregateAndProof::from_aggregate(
-            validator_index,
+        let message = AggregateAndProof {
+            aggregator_index: validator_index,
             aggregate,
-            Some(selection_proof),
-            &voting_keypair.sk,
-            &self.fork()?,
-            self.genesis_validators_root,
-            &self.spec,
-        ))
+            selection_proof: selection_proof.into(),
+        };
+        let signing_root = message.signing_root(signing_context.domain_hash(&self.spec));
+
+        let signature = self
+            .sign(validator_pubkey, signing_root, signing_context)
+            .await;
+
+        let status = if signature.is_some() {
+            metrics::SUCCESS
+        } else {
+            metrics::FAILED
+        };
+        metrics::inc_counter_vec(&metrics::SIGNED_AGGREGATES_TOTAL, &[status]);
+
+        Some(SignedAggregateAndProof {
+            message,
+            signature: signature?,
+        })
     }
 
     /// Produces a `SelectionProof` for the `slot`, signed by with corresponding secret key to
     /// `validator_pubkey`.
-    pub fn produce_selection_proof(
+    pub async fn produce_selection_proof(
         &self,
         validator_pubkey: &PublicKey,
         slot: Slot,
     ) -> Option<SelectionProof> {
-        let validators = self.validators.read();
-        let voting_keypair = &validators.get(validator_pubkey)?.voting_keypair;
-
-        Some(SelectionProof::new::<E>(
-            slot,
-            &voting_keypair.sk,
-            &self.fork()?,
-            self.genesis_validators_root,
-            &self.spec,
-        ))
+        let signing_context =
+            self.signing_context(Domain::SelectionProof, slot.epoch(E::slots_per_epoch()))?;
+        let signing_root = slot.signing_root(signing_context.domain_hash(&self.spec));
+
+        self.sign(validator_pubkey, signing_root, signing_context)
+            .await
+            .map(SelectionProof::from)
+    }
+}
+
+#[cfg(test)]
+impl<E: EthSpec> ValidatorStore<slot_clock::TestingSlotClock, E> {
+    /// Load the validators in `config.data_dir` without connecting to a beacon node.
+    ///
+    /// Must be called from within the context of a tokio runtime.
+    pub fn for_testing(config: &Config, log: Logger) -> Self {
+        use crate::beacon_node_fallback::BeaconNodeFallback;
+        use crate::fork_service::ForkServiceBuilder;
+        use environment::RuntimeContext;
+        use std::time::Duration;
+
+        let spec = E::default_spec();
+        let slot_clock = slot_clock::TestingSlotClock::new(
+            Slot::new(0),
+            Duration::from_secs(0),
+            Duration::from_millis(spec.milliseconds_per_slot),
+        );
+        let context = RuntimeContext {
+            runtime_handle: tokio::runtime::Handle::current(),
+            log: log.clone(),
+            eth_spec_instance: E::default(),
+            eth2_config: eth2_config::Eth2Config::mainnet(),
+        };
+        let fork_service = ForkServiceBuilder::new()
+            .slot_clock(slot_clock)
+            .beacon_nodes(Arc::new(BeaconNodeFallback::new(vec![], log.clone())))
+            .runtime_context(context)
+            .build()
+            .expect("should build fork service");
+
+        Self::load_from_disk(config, Hash256::zero(), spec, fork_service, None, log)
+            .expect("should load validator store")
+    }
+}
+
+/// Returns the label used to record signing times for `domain` in the `SIGNING_TIMES` metric.
+fn domain_metrics_label(domain: Domain) -> &'static str {
+    match domain {
+        Domain::BeaconProposer => "beacon_proposer",
+        Domain::BeaconAttester => "beacon_attester",
+        Domain::Randao => "randao",
+        Domain::Deposit => "deposit",
+        Domain::VoluntaryExit => "voluntary_exit",
+        Domain::SelectionProof => "selection_proof",
+        Domain::AggregateAndProof => "aggregate_and_proof",
     }
 }